@@ -114,6 +114,32 @@ impl DiscreteCDF<u64, f64> for Bernoulli {
     fn sf(&self, x: u64) -> f64 {
         self.b.sf(x)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// bernoulli distribution at `p`, i.e. the smallest `x` such that
+    /// `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if p <= 1 - q { 0 } else { 1 }
+    /// ```
+    ///
+    /// where `q` is the probability of success
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if self.cdf(0) >= p {
+            0
+        } else {
+            1
+        }
+    }
 }
 
 impl Min<u64> for Bernoulli {
@@ -342,4 +368,15 @@ mod testing {
         test_almost(0.3, 0.3, 1e-15, sf(0));
         test_almost(0.7, 0.7, 1e-15, sf(0));
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Bernoulli| x.inverse_cdf(arg);
+        test_case(0.3, 0, inverse_cdf(0.0));
+        test_case(0.3, 0, inverse_cdf(0.5));
+        test_case(0.3, 1, inverse_cdf(0.8));
+        test_case(0.3, 1, inverse_cdf(1.0));
+        test_case(0.0, 0, inverse_cdf(1.0));
+        test_case(1.0, 1, inverse_cdf(0.5));
+    }
 }
@@ -1,9 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::{beta, gamma};
+use crate::distribution::{internal, Continuous, ContinuousCDF, Fit};
+use crate::function::{beta, float::cast, gamma, SpecialFloat};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
-use core::f64::INFINITY as INF;
+use nalgebra::DMatrix;
 use rand::Rng;
 
 /// Implements the [Beta](https://en.wikipedia.org/wiki/Beta_distribution)
@@ -21,12 +21,12 @@ use rand::Rng;
 /// assert!(prec::almost_eq(n.pdf(0.5), 1.5, 1e-14));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Beta {
-    shape_a: f64,
-    shape_b: f64,
+pub struct Beta<T = f64> {
+    shape_a: T,
+    shape_b: T,
 }
 
-impl Beta {
+impl<T: SpecialFloat> Beta<T> {
     /// Constructs a new beta distribution with shapeA (α) of `shape_a`
     /// and shapeB (β) of `shape_b`
     ///
@@ -46,12 +46,12 @@ impl Beta {
     /// result = Beta::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(shape_a: f64, shape_b: f64) -> Result<Beta> {
+    pub fn new(shape_a: T, shape_b: T) -> Result<Beta<T>> {
         if shape_a.is_nan()
             || shape_b.is_nan()
             || shape_a.is_infinite() && shape_b.is_infinite()
-            || shape_a <= 0.0
-            || shape_b <= 0.0
+            || shape_a <= T::zero()
+            || shape_b <= T::zero()
         {
             return Err(StatsError::BadParams);
         };
@@ -68,7 +68,7 @@ impl Beta {
     /// let n = Beta::new(2.0, 2.0).unwrap();
     /// assert_eq!(n.shape_a(), 2.0);
     /// ```
-    pub fn shape_a(&self) -> f64 {
+    pub fn shape_a(&self) -> T {
         self.shape_a
     }
 
@@ -82,21 +82,21 @@ impl Beta {
     /// let n = Beta::new(2.0, 2.0).unwrap();
     /// assert_eq!(n.shape_b(), 2.0);
     /// ```
-    pub fn shape_b(&self) -> f64 {
+    pub fn shape_b(&self) -> T {
         self.shape_b
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Beta {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Beta<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         // Generated by sampling two gamma distributions and normalizing.
-        let x = super::gamma::sample_unchecked(rng, self.shape_a, 1.0);
-        let y = super::gamma::sample_unchecked(rng, self.shape_b, 1.0);
+        let x = super::gamma::sample_unchecked(rng, self.shape_a, T::one());
+        let y = super::gamma::sample_unchecked(rng, self.shape_b, T::one());
         x / (x + y)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Beta {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Beta<T> {
     /// Calculates the cumulative distribution function for the beta
     /// distribution
     /// at `x`
@@ -109,20 +109,20 @@ impl ContinuousCDF<f64, f64> for Beta {
     ///
     /// where `α` is shapeA, `β` is shapeB, and `I_x` is the regularized
     /// lower incomplete beta function
-    fn cdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
-        } else if x >= 1.0 {
-            1.0
+    fn cdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
+        } else if x >= T::one() {
+            T::one()
         } else if self.shape_a.is_infinite() {
-            if x < 1.0 {
-                0.0
+            if x < T::one() {
+                T::zero()
             } else {
-                1.0
+                T::one()
             }
         } else if self.shape_b.is_infinite() {
-            1.0
-        } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
+            T::one()
+        } else if ulps_eq!(self.shape_a, T::one()) && ulps_eq!(self.shape_b, T::one()) {
             x
         } else {
             beta::beta_reg(self.shape_a, self.shape_b, x)
@@ -140,28 +140,58 @@ impl ContinuousCDF<f64, f64> for Beta {
     ///
     /// where `α` is shapeA, `β` is shapeB, and `I_x` is the regularized
     /// lower incomplete beta function
-    fn sf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            1.0
-        } else if x >= 1.0 {
-            0.0
+    fn sf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::one()
+        } else if x >= T::one() {
+            T::zero()
         } else if self.shape_a.is_infinite() {
-            if x < 1.0 {
-                1.0
+            if x < T::one() {
+                T::one()
             } else {
-                0.0
+                T::zero()
             }
         } else if self.shape_b.is_infinite() {
-            0.0
-        } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
-            1. - x
+            T::zero()
+        } else if ulps_eq!(self.shape_a, T::one()) && ulps_eq!(self.shape_b, T::one()) {
+            T::one() - x
         } else {
-            beta::beta_reg(self.shape_b, self.shape_a, 1.0 - x) 
+            beta::beta_reg(self.shape_b, self.shape_a, T::one() - x) 
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// beta distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// I^-1(α, β, p)
+    /// ```
+    ///
+    /// where `α` is shapeA, `β` is shapeB and `I^-1` is the inverse of the
+    /// regularized lower incomplete beta function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if self.shape_a.is_infinite() {
+            T::one()
+        } else if self.shape_b.is_infinite() {
+            T::zero()
+        } else if ulps_eq!(self.shape_a, T::one()) && ulps_eq!(self.shape_b, T::one()) {
+            p
+        } else {
+            beta::inv_beta_reg(self.shape_a, self.shape_b, p)
         }
     }
 }
 
-impl Min<f64> for Beta {
+impl<T: SpecialFloat> Min<T> for Beta<T> {
     /// Returns the minimum value in the domain of the
     /// beta distribution representable by a double precision
     /// float
@@ -171,12 +201,12 @@ impl Min<f64> for Beta {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for Beta {
+impl<T: SpecialFloat> Max<T> for Beta<T> {
     /// Returns the maximum value in the domain of the
     /// beta distribution representable by a double precision
     /// float
@@ -186,12 +216,12 @@ impl Max<f64> for Beta {
     /// ```ignore
     /// 1
     /// ```
-    fn max(&self) -> f64 {
-        1.0
+    fn max(&self) -> T {
+        T::one()
     }
 }
 
-impl Distribution<f64> for Beta {
+impl<T: SpecialFloat> Distribution<T> for Beta<T> {
     /// Returns the mean of the beta distribution
     ///
     /// # Formula
@@ -201,9 +231,9 @@ impl Distribution<f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA and `β` is shapeB
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         let mean = if self.shape_a.is_infinite() {
-            1.0
+            T::one()
         } else {
             self.shape_a / (self.shape_a + self.shape_b)
         };
@@ -220,14 +250,14 @@ impl Distribution<f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA and `β` is shapeB
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         let var = if self.shape_a.is_infinite() || self.shape_b.is_infinite() {
-            0.0
+            T::zero()
         } else {
             self.shape_a * self.shape_b
                 / ((self.shape_a + self.shape_b)
                     * (self.shape_a + self.shape_b)
-                    * (self.shape_a + self.shape_b + 1.0))
+                    * (self.shape_a + self.shape_b + T::one()))
         };
         Some(var)
     }
@@ -240,15 +270,15 @@ impl Distribution<f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA, `β` is shapeB and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         let entr = if self.shape_a.is_infinite() || self.shape_b.is_infinite() {
             // unsupported limit
             return None;
         } else {
             beta::ln_beta(self.shape_a, self.shape_b)
-                - (self.shape_a - 1.0) * gamma::digamma(self.shape_a)
-                - (self.shape_b - 1.0) * gamma::digamma(self.shape_b)
-                + (self.shape_a + self.shape_b - 2.0) * gamma::digamma(self.shape_a + self.shape_b)
+                - (self.shape_a - T::one()) * gamma::digamma(self.shape_a)
+                - (self.shape_b - T::one()) * gamma::digamma(self.shape_b)
+                + (self.shape_a + self.shape_b - cast(2.0)) * gamma::digamma(self.shape_a + self.shape_b)
         };
         Some(entr)
     }
@@ -261,20 +291,20 @@ impl Distribution<f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA and `β` is shapeB
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         let skew = if self.shape_a.is_infinite() {
-            -2.0
+            -cast::<T>(2.0)
         } else if self.shape_b.is_infinite() {
-            2.0
+            cast(2.0)
         } else {
-            2.0 * (self.shape_b - self.shape_a) * (self.shape_a + self.shape_b + 1.0).sqrt()
-                / ((self.shape_a + self.shape_b + 2.0) * (self.shape_a * self.shape_b).sqrt())
+            cast::<T>(2.0) * (self.shape_b - self.shape_a) * (self.shape_a + self.shape_b + T::one()).sqrt()
+                / ((self.shape_a + self.shape_b + cast(2.0)) * (self.shape_a * self.shape_b).sqrt())
         };
         Some(skew)
     }
 }
 
-impl Mode<Option<f64>> for Beta {
+impl<T: SpecialFloat> Mode<Option<T>> for Beta<T> {
     /// Returns the mode of the Beta distribution.
     ///
     /// # Remarks
@@ -295,20 +325,20 @@ impl Mode<Option<f64>> for Beta {
     /// ```
     ///
     /// where `α` is shapeA and `β` is shapeB
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         // TODO: perhaps relax constraint in order to allow calculation
         // of 'anti-mode;
-        if self.shape_a <= 1.0 || self.shape_b <= 1.0 {
+        if self.shape_a <= T::one() || self.shape_b <= T::one() {
             None
         } else if self.shape_a.is_infinite() {
-            Some(1.0)
+            Some(T::one())
         } else {
-            Some((self.shape_a - 1.0) / (self.shape_a + self.shape_b - 2.0))
+            Some((self.shape_a - T::one()) / (self.shape_a + self.shape_b - cast(2.0)))
         }
     }
 }
 
-impl Continuous<f64, f64> for Beta {
+impl<T: SpecialFloat> Continuous<T, T> for Beta<T> {
     /// Calculates the probability density function for the beta distribution
     /// at `x`.
     ///
@@ -321,29 +351,31 @@ impl Continuous<f64, f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA, `β` is shapeB, and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
-        if !(0.0..=1.0).contains(&x) {
-            0.0
+    fn pdf(&self, x: T) -> T {
+        if !(T::zero()..=T::one()).contains(&x) {
+            T::zero()
         } else if self.shape_a.is_infinite() {
-            if ulps_eq!(x, 1.0) {
-                INF
+            if ulps_eq!(x, T::one()) {
+                T::infinity()
             } else {
-                0.0
+                T::zero()
             }
         } else if self.shape_b.is_infinite() {
             if is_zero(x) {
-                INF
+                T::infinity()
             } else {
-                0.0
+                T::zero()
             }
-        } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
-            1.0
-        } else if self.shape_a > 80.0 || self.shape_b > 80.0 {
+        } else if ulps_eq!(self.shape_a, T::one()) && ulps_eq!(self.shape_b, T::one()) {
+            T::one()
+        } else if self.shape_a > internal::gamma_overflow_threshold(80.0, 15.0)
+            || self.shape_b > internal::gamma_overflow_threshold(80.0, 15.0)
+        {
             self.ln_pdf(x).exp()
         } else {
             let bb = gamma::gamma(self.shape_a + self.shape_b)
                 / (gamma::gamma(self.shape_a) * gamma::gamma(self.shape_b));
-            bb * x.powf(self.shape_a - 1.0) * (1.0 - x).powf(self.shape_b - 1.0)
+            bb * x.powf(self.shape_a - T::one()) * (T::one() - x).powf(self.shape_b - T::one())
         }
     }
 
@@ -359,50 +391,171 @@ impl Continuous<f64, f64> for Beta {
     /// ```
     ///
     /// where `α` is shapeA, `β` is shapeB, and `Γ` is the gamma function
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if !(0.0..=1.0).contains(&x) {
-            -INF
+    fn ln_pdf(&self, x: T) -> T {
+        if !(T::zero()..=T::one()).contains(&x) {
+            -T::infinity()
         } else if self.shape_a.is_infinite() {
-            if ulps_eq!(x, 1.0) {
-                INF
+            if ulps_eq!(x, T::one()) {
+                T::infinity()
             } else {
-                -INF
+                -T::infinity()
             }
         } else if self.shape_b.is_infinite() {
             if is_zero(x) {
-                INF
+                T::infinity()
             } else {
-                -INF
+                -T::infinity()
             }
-        } else if ulps_eq!(self.shape_a, 1.0) && ulps_eq!(self.shape_b, 1.0) {
-            0.0
+        } else if ulps_eq!(self.shape_a, T::one()) && ulps_eq!(self.shape_b, T::one()) {
+            T::zero()
         } else {
             let aa = gamma::ln_gamma(self.shape_a + self.shape_b)
                 - gamma::ln_gamma(self.shape_a)
                 - gamma::ln_gamma(self.shape_b);
-            let bb = if ulps_eq!(self.shape_a, 1.0) && is_zero(x) {
-                0.0
+            let bb = if ulps_eq!(self.shape_a, T::one()) && is_zero(x) {
+                T::zero()
             } else if is_zero(x) {
-                -INF
+                -T::infinity()
             } else {
-                (self.shape_a - 1.0) * x.ln()
+                (self.shape_a - T::one()) * x.ln()
             };
-            let cc = if ulps_eq!(self.shape_b, 1.0) && ulps_eq!(x, 1.0) {
-                0.0
-            } else if ulps_eq!(x, 1.0) {
-                -INF
+            let cc = if ulps_eq!(self.shape_b, T::one()) && ulps_eq!(x, T::one()) {
+                T::zero()
+            } else if ulps_eq!(x, T::one()) {
+                -T::infinity()
             } else {
-                (self.shape_b - 1.0) * (1.0 - x).ln()
+                (self.shape_b - T::one()) * (T::one() - x).ln()
             };
             aa + bb + cc
         }
     }
 }
 
+impl Fit<f64> for Beta {
+    /// Returns the maximum likelihood estimate of the beta distribution for
+    /// the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is not in `(0, 1)`, if all
+    /// observations are equal or if the likelihood equations fail to
+    /// converge
+    ///
+    /// # Remarks
+    ///
+    /// The likelihood equations are solved by Newton's method starting from
+    /// the method of moments estimate. As the log-likelihood is concave in
+    /// `(α, β)` the steps are damped until it does not decrease.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ψ(α) - ψ(α + β) = (1 / n) * Σ ln(x_i)
+    /// ψ(β) - ψ(α + β) = (1 / n) * Σ ln(1 - x_i)
+    /// ```
+    ///
+    /// where `ψ` is the digamma function
+    fn fit_mle(data: &[f64]) -> Result<Beta> {
+        if data.iter().any(|&x| x.is_nan() || x <= 0.0 || x >= 1.0) {
+            return Err(StatsError::BadParams);
+        }
+        let g_a = data.iter().map(|x| x.ln()).mean();
+        let g_b = data.iter().map(|x| (-x).ln_1p()).mean();
+        let ln_likelihood = |a: f64, b: f64| {
+            gamma::ln_gamma(a + b) - gamma::ln_gamma(a) - gamma::ln_gamma(b)
+                + (a - 1.0) * g_a
+                + (b - 1.0) * g_b
+        };
+
+        let start = Beta::fit_mom(data)?;
+        let (mut a, mut b) = (start.shape_a, start.shape_b);
+        for _ in 0..100 {
+            let d_ab = gamma::digamma(a + b);
+            let f_a = gamma::digamma(a) - d_ab - g_a;
+            let f_b = gamma::digamma(b) - d_ab - g_b;
+            let t_ab = gamma::trigamma(a + b);
+            let j_aa = gamma::trigamma(a) - t_ab;
+            let j_bb = gamma::trigamma(b) - t_ab;
+            let det = j_aa * j_bb - t_ab * t_ab;
+            let step_a = (j_bb * f_a + t_ab * f_b) / det;
+            let step_b = (j_aa * f_b + t_ab * f_a) / det;
+
+            if step_a.abs() <= 1e-12 * a && step_b.abs() <= 1e-12 * b {
+                return Beta::new(a - step_a, b - step_b);
+            }
+
+            let current = ln_likelihood(a, b);
+            let mut t = 1.0;
+            while a - t * step_a <= 0.0
+                || b - t * step_b <= 0.0
+                || ln_likelihood(a - t * step_a, b - t * step_b) < current
+            {
+                t /= 2.0;
+                if t < 1e-10 {
+                    return Err(StatsError::ComputationFailedToConverge);
+                }
+            }
+            a -= t * step_a;
+            b -= t * step_b;
+        }
+        Err(StatsError::ComputationFailedToConverge)
+    }
+
+    /// Returns the method of moments estimate of the beta distribution for
+    /// the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// c = m * (1 - m) / s^2 - 1
+    /// α = m * c
+    /// β = (1 - m) * c
+    /// ```
+    ///
+    /// where `m` and `s^2` are the mean and the population variance of the
+    /// observations
+    fn fit_mom(data: &[f64]) -> Result<Beta> {
+        let mean = data.mean();
+        let c = mean * (1.0 - mean) / data.population_variance() - 1.0;
+        if !c.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        Beta::new(mean * c, (1.0 - mean) * c)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(α, β)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n * [ ψ'(α) - ψ'(α + β), -ψ'(α + β)         ]
+    ///     [ -ψ'(α + β),        ψ'(β) - ψ'(α + β) ]
+    /// ```
+    ///
+    /// where `ψ'` is the trigamma function
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let n = data.len() as f64;
+        let t_ab = gamma::trigamma(self.shape_a + self.shape_b);
+        Some(DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                n * (gamma::trigamma(self.shape_a) - t_ab),
+                -n * t_ab,
+                -n * t_ab,
+                n * (gamma::trigamma(self.shape_b) - t_ab),
+            ],
+        ))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use super::*;
+    use crate::distribution::Fit;
+    use core::f64::INFINITY as INF;
     use crate::consts::ACC;
     use super::super::internal::*;
     use crate::statistics::*;
@@ -679,4 +832,50 @@ mod tests {
         test::check_continuous_distribution(&try_create((1.2, 3.4)), 0.0, 1.0);
         test::check_continuous_distribution(&try_create((4.5, 6.7)), 0.0, 1.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let f = |arg: f64| move |x: Beta| x.inverse_cdf(arg);
+        let test = [
+            ((2.0, 3.0), 0.1, 0.14255931671003072354),
+            ((2.0, 3.0), 0.5, 0.38572756813238954828),
+            ((2.0, 3.0), 0.9, 0.67953941627818170137),
+            ((0.5, 0.5), 0.1, 0.024471741852423216636),
+            ((0.5, 0.5), 0.5, 0.5),
+            ((0.5, 0.5), 0.9, 0.97552825814757679684),
+            ((5.0, 1.0), 0.1, 0.63095734448019325644),
+            ((5.0, 1.0), 0.5, 0.87055056329612413914),
+            ((5.0, 1.0), 0.9, 0.97914836236097679447),
+            ((0.1, 10.0), 0.1, 6.3563428471165108555e-12),
+            ((0.1, 10.0), 0.5, 0.00006210520703091707154),
+            ((0.1, 10.0), 0.9, 0.027469622296097830083),
+        ];
+        for &(arg, p, res) in test.iter() {
+            test_case(arg, res, f(p));
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Beta::<f32>::new(2.5, 3.0).unwrap();
+        let d64 = Beta::new(2.5, 3.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.125, 0.25, 0.5, 0.875], 1e-4);
+        let d32 = Beta::<f32>::new(20.0, 30.0).unwrap();
+        let d64 = Beta::new(20.0, 30.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 0.375, 0.5], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.12, 0.45, 0.67, 0.23, 0.81, 0.34, 0.56, 0.29, 0.74, 0.41];
+        let n = Beta::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.shape_a(), 2.1723958775648798, 1e-12);
+        assert_almost_eq!(n.shape_b(), 2.5268280905618092, 1e-12);
+        test::check_observed_information(n.observed_information(&data), &[3.4488412882761692, -2.3703523528620188, -2.3703523528620188, 2.4706519744093664], 1e-12);
+        let n = Beta::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.shape_a(), 1.9845841145389467, 1e-13);
+        assert_almost_eq!(n.shape_b(), 2.3110524970172149, 1e-13);
+        assert!(Beta::fit_mle(&[0.5, 1.0]).is_err());
+        assert!(Beta::fit_mle(&[0.5, 0.5]).is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF};
 use crate::function::{beta, factorial};
 use crate::is_zero;
 use crate::statistics::*;
@@ -85,6 +85,54 @@ impl Binomial {
     pub fn n(&self) -> u64 {
         self.n
     }
+
+    /// Returns the maximum likelihood estimate of the binomial distribution
+    /// with `n` trials for the observed numbers of successes in `data`. As
+    /// the number of trials is known, this is an inherent method rather than
+    /// an implementation of `Fit`. The estimate coincides with the method of
+    /// moments estimate.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or any observation exceeds `n`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// p = Σ x_i / (n * m)
+    /// ```
+    ///
+    /// where `m` is the number of observations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Binomial;
+    ///
+    /// let b = Binomial::fit_mle(10, &[2, 3, 7]).unwrap();
+    /// assert_eq!(b.p(), 0.4);
+    /// ```
+    pub fn fit_mle(n: u64, data: &[u64]) -> Result<Binomial> {
+        if data.is_empty() || data.iter().any(|&x| x > n) {
+            return Err(StatsError::BadParams);
+        }
+        let sum = data.iter().map(|&x| x as f64).sum::<f64>();
+        Binomial::new(sum / (n as f64 * data.len() as f64), n)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to `p`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ x_i / p^2 + Σ (n - x_i) / (1 - p)^2
+    /// ```
+    pub fn observed_information(&self, data: &[u64]) -> f64 {
+        let (successes, failures) = data.iter().fold((0.0, 0.0), |(s, f), &x| {
+            (s + x as f64, f + self.n.saturating_sub(x) as f64)
+        });
+        successes / (self.p * self.p) + failures / ((1.0 - self.p) * (1.0 - self.p))
+    }
 }
 
 impl ::rand::distributions::Distribution<f64> for Binomial {
@@ -138,6 +186,31 @@ impl DiscreteCDF<u64, f64> for Binomial {
             beta::beta_reg(k as f64 + 1.0, (self.n - k) as f64, self.p)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// binomial distribution at `p`, i.e. the smallest `x` such that `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The Cornish-Fisher expansion of the quantile is used as the initial
+    /// guess of a local search over `[0, n]`
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let std_dev = self.std_dev().unwrap();
+        let guess = internal::cornish_fisher_quantile(
+            p,
+            self.mean().unwrap(),
+            std_dev,
+            (1.0 - 2.0 * self.p) / std_dev,
+        );
+        internal::discrete_inverse_cdf(self, p, guess)
+    }
 }
 
 impl Min<u64> for Binomial {
@@ -570,4 +643,44 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.3, 5), 5);
         test::check_discrete_distribution(&try_create(0.7, 10), 10);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Binomial| x.inverse_cdf(arg);
+        test_case(0.3, 10, 0, inverse_cdf(0.01));
+        test_case(0.3, 10, 2, inverse_cdf(0.3));
+        test_case(0.3, 10, 3, inverse_cdf(0.5));
+        test_case(0.3, 10, 5, inverse_cdf(0.9));
+        test_case(0.3, 10, 8, inverse_cdf(0.999));
+        test_case(0.5, 100, 38, inverse_cdf(0.01));
+        test_case(0.5, 100, 47, inverse_cdf(0.3));
+        test_case(0.5, 100, 50, inverse_cdf(0.5));
+        test_case(0.5, 100, 56, inverse_cdf(0.9));
+        test_case(0.5, 100, 65, inverse_cdf(0.999));
+        test_case(0.01, 1000, 3, inverse_cdf(0.01));
+        test_case(0.01, 1000, 8, inverse_cdf(0.3));
+        test_case(0.01, 1000, 10, inverse_cdf(0.5));
+        test_case(0.01, 1000, 14, inverse_cdf(0.9));
+        test_case(0.01, 1000, 21, inverse_cdf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        test::check_discrete_inverse_cdf(&try_create(0.3, 10));
+        test::check_discrete_inverse_cdf(&try_create(0.5, 100));
+        test::check_discrete_inverse_cdf(&try_create(0.01, 1000));
+        test::check_discrete_inverse_cdf(&try_create(0.0, 10));
+        test::check_discrete_inverse_cdf(&try_create(1.0, 10));
+        test::check_discrete_inverse_cdf(&try_create(0.5, 1_000_000));
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0, 3, 1, 7, 2, 0, 5, 1, 9, 2, 4, 0];
+        let n = Binomial::fit_mle(10, &data).unwrap();
+        assert_almost_eq!(n.p(), 0.28333333333333333, 1e-15);
+        assert_almost_eq!(n.observed_information(&data), 590.97127222982216, 1e-11);
+        assert!(Binomial::fit_mle(10, &[]).is_err());
+        assert!(Binomial::fit_mle(5, &data).is_err());
+    }
 }
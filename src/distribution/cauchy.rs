@@ -1,8 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Cauchy](https://en.wikipedia.org/wiki/Cauchy_distribution)
 /// distribution, also known as the Lorentz distribution.
@@ -18,12 +19,12 @@ use std::f64;
 /// assert_eq!(n.pdf(1.0), 0.1591549430918953357689);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Cauchy {
-    location: f64,
-    scale: f64,
+pub struct Cauchy<T = f64> {
+    location: T,
+    scale: T,
 }
 
-impl Cauchy {
+impl<T: SpecialFloat> Cauchy<T> {
     /// Constructs a new cauchy distribution with the given
     /// location and scale.
     ///
@@ -42,8 +43,8 @@ impl Cauchy {
     /// result = Cauchy::new(0.0, -1.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(location: f64, scale: f64) -> Result<Cauchy> {
-        if location.is_nan() || scale.is_nan() || scale <= 0.0 {
+    pub fn new(location: T, scale: T) -> Result<Cauchy<T>> {
+        if location.is_nan() || scale.is_nan() || scale <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Cauchy { location, scale })
@@ -60,7 +61,7 @@ impl Cauchy {
     /// let n = Cauchy::new(0.0, 1.0).unwrap();
     /// assert_eq!(n.location(), 0.0);
     /// ```
-    pub fn location(&self) -> f64 {
+    pub fn location(&self) -> T {
         self.location
     }
 
@@ -74,18 +75,18 @@ impl Cauchy {
     /// let n = Cauchy::new(0.0, 1.0).unwrap();
     /// assert_eq!(n.scale(), 1.0);
     /// ```
-    pub fn scale(&self) -> f64 {
+    pub fn scale(&self) -> T {
         self.scale
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Cauchy {
-    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
-        self.location + self.scale * (f64::consts::PI * (r.gen::<f64>() - 0.5)).tan()
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Cauchy<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
+        self.location + self.scale * (T::PI() * (cast::<T>(r.gen::<f64>() - 0.5))).tan()
     }
 }
 
-impl ContinuousCDF<f64, f64> for Cauchy {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Cauchy<T> {
     /// Calculates the cumulative distribution function for the
     /// cauchy distribution at `x`
     ///
@@ -96,8 +97,8 @@ impl ContinuousCDF<f64, f64> for Cauchy {
     /// ```
     ///
     /// where `x_0` is the location and `γ` is the scale
-    fn cdf(&self, x: f64) -> f64 {
-        (1.0 / f64::consts::PI) * ((x - self.location) / self.scale).atan() + 0.5
+    fn cdf(&self, x: T) -> T {
+        (T::one() / T::PI()) * ((x - self.location) / self.scale).atan() + cast(0.5)
     }
 
     /// Calculates the survival function for the
@@ -112,12 +113,39 @@ impl ContinuousCDF<f64, f64> for Cauchy {
     /// where `x_0` is the location and `γ` is the scale.
     /// note that this is identical to the cdf except for
     /// the negative argument to the arctan function
-    fn sf(&self, x: f64) -> f64 {
-        (1.0 / f64::consts::PI) * ((self.location - x) / self.scale).atan() + 0.5
+    fn sf(&self, x: T) -> T {
+        (T::one() / T::PI()) * ((self.location - x) / self.scale).atan() + cast(0.5)
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// cauchy distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x_0 + γ * tan(π * (p - 1 / 2))
+    /// ```
+    ///
+    /// where `x_0` is the location and `γ` is the scale
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == T::zero() {
+            T::neg_infinity()
+        } else if p == T::one() {
+            T::infinity()
+        } else {
+            self.location + self.scale * (T::PI() * (p - cast(0.5))).tan()
+        }
     }
 }
 
-impl Min<f64> for Cauchy {
+impl<T: SpecialFloat> Min<T> for Cauchy<T> {
     /// Returns the minimum value in the domain of the cauchy
     /// distribution representable by a double precision float
     ///
@@ -126,12 +154,12 @@ impl Min<f64> for Cauchy {
     /// ```ignore
     /// NEG_INF
     /// ```
-    fn min(&self) -> f64 {
-        f64::NEG_INFINITY
+    fn min(&self) -> T {
+        T::neg_infinity()
     }
 }
 
-impl Max<f64> for Cauchy {
+impl<T: SpecialFloat> Max<T> for Cauchy<T> {
     /// Returns the maximum value in the domain of the cauchy
     /// distribution representable by a double precision float
     ///
@@ -140,12 +168,12 @@ impl Max<f64> for Cauchy {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Cauchy {
+impl<T: SpecialFloat> Distribution<T> for Cauchy<T> {
     /// Returns the entropy of the cauchy distribution
     ///
     /// # Formula
@@ -155,12 +183,12 @@ impl Distribution<f64> for Cauchy {
     /// ```
     ///
     /// where `γ` is the scale
-    fn entropy(&self) -> Option<f64> {
-        Some((4.0 * f64::consts::PI * self.scale).ln())
+    fn entropy(&self) -> Option<T> {
+        Some((cast::<T>(4.0) * T::PI() * self.scale).ln())
     }
 }
 
-impl Median<f64> for Cauchy {
+impl<T: SpecialFloat> Median<T> for Cauchy<T> {
     /// Returns the median of the cauchy distribution
     ///
     /// # Formula
@@ -170,12 +198,12 @@ impl Median<f64> for Cauchy {
     /// ```
     ///
     /// where `x_0` is the location
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.location
     }
 }
 
-impl Mode<Option<f64>> for Cauchy {
+impl<T: SpecialFloat> Mode<Option<T>> for Cauchy<T> {
     /// Returns the mode of the cauchy distribution
     ///
     /// # Formula
@@ -185,12 +213,12 @@ impl Mode<Option<f64>> for Cauchy {
     /// ```
     ///
     /// where `x_0` is the location
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.location)
     }
 }
 
-impl Continuous<f64, f64> for Cauchy {
+impl<T: SpecialFloat> Continuous<T, T> for Cauchy<T> {
     /// Calculates the probability density function for the cauchy
     /// distribution at `x`
     ///
@@ -201,10 +229,10 @@ impl Continuous<f64, f64> for Cauchy {
     /// ```
     ///
     /// where `x_0` is the location and `γ` is the scale
-    fn pdf(&self, x: f64) -> f64 {
-        1.0 / (f64::consts::PI
+    fn pdf(&self, x: T) -> T {
+        T::one() / (T::PI()
             * self.scale
-            * (1.0 + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)))
+            * (T::one() + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)))
     }
 
     /// Calculates the log probability density function for the cauchy
@@ -217,18 +245,148 @@ impl Continuous<f64, f64> for Cauchy {
     /// ```
     ///
     /// where `x_0` is the location and `γ` is the scale
-    fn ln_pdf(&self, x: f64) -> f64 {
-        -(f64::consts::PI
+    fn ln_pdf(&self, x: T) -> T {
+        -(T::PI()
             * self.scale
-            * (1.0 + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)))
+            * (T::one() + ((x - self.location) / self.scale) * ((x - self.location) / self.scale)))
             .ln()
     }
 }
 
+impl Fit<f64> for Cauchy {
+    /// Returns the maximum likelihood estimate of the cauchy distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, if all observations are equal or
+    /// if the likelihood equations fail to converge
+    ///
+    /// # Remarks
+    ///
+    /// Starting from the median and half the interquartile range, the
+    /// log-likelihood is maximized by Newton's method where the Hessian is
+    /// negative definite and by Fisher scoring elsewhere, halving the steps
+    /// until the log-likelihood does not decrease. The likelihood of the
+    /// location may have several local maxima, the one found is the one
+    /// closest to the median.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ (x_i - x_0) / (γ^2 + (x_i - x_0)^2) = 0
+    /// Σ γ^2 / (γ^2 + (x_i - x_0)^2) = n / 2
+    /// ```
+    ///
+    /// where `x_0` is the location and `γ` the scale
+    fn fit_mle(data: &[f64]) -> Result<Cauchy> {
+        if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        let n = data.len() as f64;
+        let mut sorted = Data::new(data.to_vec());
+        let mut location = OrderStatistics::median(&mut sorted);
+        let mut scale = (sorted.upper_quartile() - sorted.lower_quartile()) / 2.0;
+        if scale <= 0.0 {
+            scale = data.iter().map(|x| (x - location).abs()).mean();
+        }
+        if scale <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let ln_likelihood = |location: f64, scale: f64| {
+            n * scale.ln()
+                - data
+                    .iter()
+                    .map(|x| (scale * scale + (x - location) * (x - location)).ln())
+                    .sum::<f64>()
+        };
+        for _ in 0..500 {
+            let (g, h) = score_and_hessian(data, location, scale);
+            let det = h[(0, 0)] * h[(1, 1)] - h[(0, 1)] * h[(0, 1)];
+            let (step_location, step_scale) = if h[(0, 0)] < 0.0 && det > 0.0 {
+                (
+                    (h[(0, 1)] * g[1] - h[(1, 1)] * g[0]) / det,
+                    (h[(0, 1)] * g[0] - h[(0, 0)] * g[1]) / det,
+                )
+            } else {
+                let w = 2.0 * scale * scale / n;
+                (w * g[0], w * g[1])
+            };
+            if step_location.abs() <= 1e-12 * scale && step_scale.abs() <= 1e-12 * scale {
+                return Cauchy::new(location + step_location, scale + step_scale);
+            }
+
+            let current = ln_likelihood(location, scale);
+            let mut t = 1.0;
+            while scale + t * step_scale <= 0.0
+                || ln_likelihood(location + t * step_location, scale + t * step_scale) < current
+            {
+                t /= 2.0;
+                if t < 1e-10 {
+                    return Err(StatsError::ComputationFailedToConverge);
+                }
+            }
+            location += t * step_location;
+            scale += t * step_scale;
+        }
+        Err(StatsError::ComputationFailedToConverge)
+    }
+
+    /// The cauchy distribution has no moments, so the method of moments is
+    /// not applicable
+    ///
+    /// # Errors
+    ///
+    /// Always returns `StatsError::SpecialCase`
+    fn fit_mom(_data: &[f64]) -> Result<Cauchy> {
+        Err(StatsError::SpecialCase(
+            "the cauchy distribution has no moments",
+        ))
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(x_0, γ)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// [ Σ 2(γ^2 - d_i^2) / q_i^2, Σ 4γd_i / q_i^2                       ]
+    /// [ Σ 4γd_i / q_i^2,          n / γ^2 + Σ 2(d_i^2 - γ^2) / q_i^2 ]
+    /// ```
+    ///
+    /// where `d_i = x_i - x_0` and `q_i = γ^2 + d_i^2`
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let (_, h) = score_and_hessian(data, self.location, self.scale);
+        Some(-h)
+    }
+}
+
+/// Returns the gradient and the Hessian of the log-likelihood of `data` with
+/// respect to the location and the scale of a cauchy distribution
+fn score_and_hessian(data: &[f64], location: f64, scale: f64) -> ([f64; 2], DMatrix<f64>) {
+    let n = data.len() as f64;
+    let scale_2 = scale * scale;
+    let mut g = [0.0, n / scale];
+    let (mut h_ll, mut h_ls, mut h_ss) = (0.0, 0.0, -n / scale_2);
+    for x in data {
+        let d = x - location;
+        let q = scale_2 + d * d;
+        let q_2 = q * q;
+        g[0] += 2.0 * d / q;
+        g[1] -= 2.0 * scale / q;
+        h_ll += 2.0 * (d * d - scale_2) / q_2;
+        h_ls -= 4.0 * d * scale / q_2;
+        h_ss -= 2.0 * (d * d - scale_2) / q_2;
+    }
+    (g, DMatrix::from_row_slice(2, 2, &[h_ll, h_ls, h_ls, h_ss]))
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
+    use crate::distribution::Fit;
     use crate::distribution::{ContinuousCDF, Continuous, Cauchy};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
@@ -466,4 +624,41 @@ mod tests {
         test::check_continuous_distribution(&try_create(-1.2, 3.4), -1500.0, 1500.0);
         test::check_continuous_distribution(&try_create(-4.5, 6.7), -5000.0, 5000.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Cauchy| x.inverse_cdf(arg);
+        test_almost(0.0, 1.0, -3.0776835371752532199, 1e-14, inverse_cdf(0.1));
+        test_almost(0.0, 1.0, 0.0, 1e-15, inverse_cdf(0.5));
+        test_almost(0.0, 1.0, 3.0776835371752541331, 1e-14, inverse_cdf(0.9));
+        test_almost(-5.0, 2.0, -11.15536707435050644, 1e-13, inverse_cdf(0.1));
+        test_almost(-5.0, 2.0, -5.0, 1e-14, inverse_cdf(0.5));
+        test_almost(-5.0, 2.0, 1.1553670743505082662, 1e-14, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_inverse_cdf_bounds() {
+        let inverse_cdf = |arg: f64| move |x: Cauchy| x.inverse_cdf(arg);
+        test_case(0.0, 1.0, f64::NEG_INFINITY, inverse_cdf(0.0));
+        test_case(0.0, 1.0, f64::INFINITY, inverse_cdf(1.0));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Cauchy::<f32>::new(1.0, 0.5).unwrap();
+        let d64 = Cauchy::new(1.0, 0.5).unwrap();
+        // tan(π(p - 1/2)) is ill-conditioned in the tails, which costs f32 a few digits
+        test::check_f32_matches_f64(&d32, &d64, &[-2.0, 0.0, 1.0, 1.5, 5.0], 1e-3);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+        let n = Cauchy::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.location(), 0.11543802270929453, 1e-12);
+        assert_almost_eq!(n.scale(), 1.0582673843630112, 1e-12);
+        test::check_observed_information(n.observed_information(&data), &[4.0586868531289219, -0.5039249937617208, -0.5039249937617208, 4.8704437877086365], 1e-11);
+        assert!(Cauchy::fit_mom(&data).is_err());
+        assert!(Cauchy::fit_mle(&[1.0, 1.0]).is_err());
+    }
 }
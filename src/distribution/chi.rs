@@ -1,9 +1,8 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::distribution::{internal, Continuous, ContinuousCDF};
+use crate::function::{float::cast, gamma, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Chi](https://en.wikipedia.org/wiki/Chi_distribution)
 /// distribution
@@ -20,11 +19,11 @@ use std::f64;
 /// assert!(prec::almost_eq(n.pdf(1.0), 0.60653065971263342360, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Chi {
-    freedom: f64,
+pub struct Chi<T = f64> {
+    freedom: T,
 }
 
-impl Chi {
+impl<T: SpecialFloat> Chi<T> {
     /// Constructs a new chi distribution
     /// with `freedom` degrees of freedom
     ///
@@ -44,8 +43,8 @@ impl Chi {
     /// result = Chi::new(0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(freedom: f64) -> Result<Chi> {
-        if freedom.is_nan() || freedom <= 0.0 {
+    pub fn new(freedom: T) -> Result<Chi<T>> {
+        if freedom.is_nan() || freedom <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Chi { freedom })
@@ -63,22 +62,22 @@ impl Chi {
     /// let n = Chi::new(2.0).unwrap();
     /// assert_eq!(n.freedom(), 2.0);
     /// ```
-    pub fn freedom(&self) -> f64 {
+    pub fn freedom(&self) -> T {
         self.freedom
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Chi {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        (0..self.freedom as i64)
-            .fold(0.0, |acc, _| {
-                acc + super::normal::sample_unchecked(rng, 0.0, 1.0).powf(2.0)
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Chi<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        (0..self.freedom.to_f64().unwrap() as i64)
+            .fold(T::zero(), |acc, _| {
+                acc + super::normal::sample_unchecked(rng, T::zero(), T::one()).powf(cast(2.0))
             })
             .sqrt()
     }
 }
 
-impl ContinuousCDF<f64, f64> for Chi {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Chi<T> {
     /// Calculates the cumulative distribution function for the chi
     /// distribution at `x`.
     ///
@@ -90,13 +89,13 @@ impl ContinuousCDF<f64, f64> for Chi {
     ///
     /// where `k` is the degrees of freedom and `P` is
     /// the regularized lower incomplete Gamma function
-    fn cdf(&self, x: f64) -> f64 {
-        if self.freedom == f64::INFINITY || x == f64::INFINITY {
-            1.0
-        } else if x <= 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if self.freedom == T::infinity() || x == T::infinity() {
+            T::one()
+        } else if x <= T::zero() {
+            T::zero()
         } else {
-            gamma::gamma_lr(self.freedom / 2.0, x * x / 2.0)
+            gamma::gamma_lr(self.freedom / cast(2.0), x * x / cast(2.0))
         }
     }
 
@@ -111,18 +110,44 @@ impl ContinuousCDF<f64, f64> for Chi {
     ///
     /// where `k` is the degrees of freedom and `P` is
     /// the regularized upper incomplete Gamma function
-    fn sf(&self, x: f64) -> f64 {
-        if self.freedom == f64::INFINITY || x == f64::INFINITY {
-            0.0
-        } else if x <= 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if self.freedom == T::infinity() || x == T::infinity() {
+            T::zero()
+        } else if x <= T::zero() {
+            T::one()
         } else {
-            gamma::gamma_ur(self.freedom / 2.0, x * x / 2.0)
+            gamma::gamma_ur(self.freedom / cast(2.0), x * x / cast(2.0))
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// chi distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt(2 * P^-1(k / 2, p))
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom and `P^-1` is the inverse of the
+    /// lower regularized incomplete gamma function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if self.freedom == T::infinity() {
+            T::infinity()
+        } else {
+            (cast::<T>(2.0) * gamma::inv_gamma_lr(self.freedom / cast(2.0), p)).sqrt()
         }
     }
 }
 
-impl Min<f64> for Chi {
+impl<T: SpecialFloat> Min<T> for Chi<T> {
     /// Returns the minimum value in the domain of the chi distribution
     /// representable by a double precision float
     ///
@@ -131,12 +156,12 @@ impl Min<f64> for Chi {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for Chi {
+impl<T: SpecialFloat> Max<T> for Chi<T> {
     /// Returns the maximum value in the domain of the chi distribution
     /// representable by a double precision float
     ///
@@ -145,12 +170,12 @@ impl Max<f64> for Chi {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Chi {
+impl<T: SpecialFloat> Distribution<T> for Chi<T> {
     /// Returns the mean of the chi distribution
     ///
     /// # Remarks
@@ -164,24 +189,23 @@ impl Distribution<f64> for Chi {
     /// ```
     ///
     /// where `k` is degrees of freedom and `Γ` is the gamma function
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         if self.freedom.is_infinite() {
             None
-        } else if self.freedom > 300.0 {
+        } else if self.freedom > internal::gamma_overflow_threshold(300.0, 60.0) {
             // Large n approximation based on the Stirling series approximation to the Gamma function
             // This avoids call the Gamma function with large arguments and returning NaN
             //
             // Relative accuracy follows O(1/n^4) and at 300 d.o.f. is better than 1e-12
-            // For a f32 impl the threshold should be changed to 150
             Some(
                 self.freedom.sqrt()
-                    / ((1.0 + 0.25 / self.freedom)
-                        * (1.0 + 0.03125 / (self.freedom * self.freedom))
-                        * (1.0 - 0.046875 / (self.freedom * self.freedom * self.freedom))),
+                    / ((T::one() + cast::<T>(0.25) / self.freedom)
+                        * (T::one() + cast::<T>(0.03125) / (self.freedom * self.freedom))
+                        * (T::one() - cast::<T>(0.046875) / (self.freedom * self.freedom * self.freedom))),
             )
         } else {
-            let mean = f64::consts::SQRT_2 * gamma::gamma((self.freedom + 1.0) / 2.0)
-                / gamma::gamma(self.freedom / 2.0);
+            let mean = T::SQRT_2() * gamma::gamma((self.freedom + T::one()) / cast(2.0))
+                / gamma::gamma(self.freedom / cast(2.0));
             Some(mean)
         }
     }
@@ -199,7 +223,7 @@ impl Distribution<f64> for Chi {
     ///
     /// where `k` is degrees of freedom and `μ` is the mean
     /// of the distribution
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         let mean = self.mean()?;
         Some(self.freedom - mean * mean)
     }
@@ -217,15 +241,15 @@ impl Distribution<f64> for Chi {
     ///
     /// where `k` is degrees of freedom, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         if self.freedom.is_infinite() {
             return None;
         }
-        let entr = gamma::ln_gamma(self.freedom / 2.0)
+        let entr = gamma::ln_gamma(self.freedom / cast(2.0))
             + (self.freedom
-                - (2.0f64).ln()
-                - (self.freedom - 1.0) * gamma::digamma(self.freedom / 2.0))
-                / 2.0;
+                - T::LN_2()
+                - (self.freedom - T::one()) * gamma::digamma(self.freedom / cast(2.0)))
+                / cast(2.0);
         Some(entr)
     }
     /// Returns the skewness of the chi distribution
@@ -241,14 +265,14 @@ impl Distribution<f64> for Chi {
     /// ```
     /// where `μ` is the mean and `σ` the standard deviation
     /// of the distribution
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         let sigma = self.std_dev()?;
-        let skew = self.mean()? * (1.0 - 2.0 * sigma * sigma) / (sigma * sigma * sigma);
+        let skew = self.mean()? * (T::one() - cast::<T>(2.0) * sigma * sigma) / (sigma * sigma * sigma);
         Some(skew)
     }
 }
 
-impl Mode<Option<f64>> for Chi {
+impl<T: SpecialFloat> Mode<Option<T>> for Chi<T> {
     /// Returns the mode for the chi distribution
     ///
     /// # Panics
@@ -262,15 +286,15 @@ impl Mode<Option<f64>> for Chi {
     /// ```
     ///
     /// where `k` is the degrees of freedom
-    fn mode(&self) -> Option<f64> {
-        if self.freedom - 1.0 < 0.0 {
+    fn mode(&self) -> Option<T> {
+        if self.freedom - T::one() < T::zero() {
             return None;
         }
-        Some((self.freedom - 1.0).sqrt())
+        Some((self.freedom - T::one()).sqrt())
     }
 }
 
-impl Continuous<f64, f64> for Chi {
+impl<T: SpecialFloat> Continuous<T, T> for Chi<T> {
     /// Calculates the probability density function for the chi
     /// distribution at `x`
     ///
@@ -281,16 +305,16 @@ impl Continuous<f64, f64> for Chi {
     /// ```
     ///
     /// where `k` is the degrees of freedom and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
-        if self.freedom == f64::INFINITY || x == f64::INFINITY || x <= 0.0 {
-            0.0
-        } else if self.freedom > 160.0 {
+    fn pdf(&self, x: T) -> T {
+        if self.freedom == T::infinity() || x == T::infinity() || x <= T::zero() {
+            T::zero()
+        } else if self.freedom > internal::gamma_overflow_threshold(160.0, 60.0) {
             self.ln_pdf(x).exp()
         } else {
-            (2.0f64).powf(1.0 - self.freedom / 2.0)
-                * x.powf(self.freedom - 1.0)
-                * (-x * x / 2.0).exp()
-                / gamma::gamma(self.freedom / 2.0)
+            cast::<T>(2.0).powf(T::one() - self.freedom / cast(2.0))
+                * x.powf(self.freedom - T::one())
+                * (-x * x / cast(2.0)).exp()
+                / gamma::gamma(self.freedom / cast(2.0))
         }
     }
 
@@ -302,13 +326,13 @@ impl Continuous<f64, f64> for Chi {
     /// ```ignore
     /// ln((2^(1 - (k / 2)) * x^(k - 1) * e^(-x^2 / 2)) / Γ(k / 2))
     /// ```
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if self.freedom == f64::INFINITY || x == f64::INFINITY || x <= 0.0 {
-            f64::NEG_INFINITY
+    fn ln_pdf(&self, x: T) -> T {
+        if self.freedom == T::infinity() || x == T::infinity() || x <= T::zero() {
+            T::neg_infinity()
         } else {
-            (1.0 - self.freedom / 2.0) * (2.0f64).ln() + ((self.freedom - 1.0) * x.ln())
-                - x * x / 2.0
-                - gamma::ln_gamma(self.freedom / 2.0)
+            (T::one() - self.freedom / cast(2.0)) * T::LN_2() + ((self.freedom - T::one()) * x.ln())
+                - x * x / cast(2.0)
+                - gamma::ln_gamma(self.freedom / cast(2.0))
         }
     }
 }
@@ -616,4 +640,28 @@ mod tests {
         test::check_continuous_distribution(&try_create(2.0), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(5.0), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Chi| x.inverse_cdf(arg);
+        test_almost(1.0, 0.12566134685507404122, 1e-15, inverse_cdf(0.1));
+        test_almost(1.0, 0.6744897501960817432, 1e-15, inverse_cdf(0.5));
+        test_almost(1.0, 1.6448536269514728225, 1e-14, inverse_cdf(0.9));
+        test_almost(2.0, 0.45904360502642079045, 1e-15, inverse_cdf(0.1));
+        test_almost(2.0, 1.177410022515474691, 1e-14, inverse_cdf(0.5));
+        test_almost(2.0, 2.1459660262893473431, 1e-14, inverse_cdf(0.9));
+        test_almost(5.0, 1.2689791121063904425, 1e-14, inverse_cdf(0.1));
+        test_almost(5.0, 2.0860153861118875547, 1e-14, inverse_cdf(0.5));
+        test_almost(5.0, 3.0391375256445896897, 1e-14, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Chi::<f32>::new(3.0).unwrap();
+        let d64 = Chi::new(3.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.5, 1.0, 2.0, 3.0], 1e-4);
+        let d32 = Chi::<f32>::new(100.0).unwrap();
+        let d64 = Chi::new(100.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[9.0, 10.0, 11.0], 1e-4);
+    }
 }
@@ -1,8 +1,8 @@
 use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::Result;
 use rand::Rng;
-use std::f64;
 
 /// Implements the
 /// [Chi-squared](https://en.wikipedia.org/wiki/Chi-squared_distribution)
@@ -22,12 +22,12 @@ use std::f64;
 /// assert!(prec::almost_eq(n.pdf(4.0), 0.107981933026376103901, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct ChiSquared {
-    freedom: f64,
-    g: Gamma,
+pub struct ChiSquared<T = f64> {
+    freedom: T,
+    g: Gamma<T>,
 }
 
-impl ChiSquared {
+impl<T: SpecialFloat> ChiSquared<T> {
     /// Constructs a new chi-squared distribution with `freedom`
     /// degrees of freedom. This is equivalent to a Gamma distribution
     /// with a shape of `freedom / 2.0` and a rate of `0.5`.
@@ -48,8 +48,8 @@ impl ChiSquared {
     /// result = ChiSquared::new(0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(freedom: f64) -> Result<ChiSquared> {
-        Gamma::new(freedom / 2.0, 0.5).map(|g| ChiSquared { freedom, g })
+    pub fn new(freedom: T) -> Result<ChiSquared<T>> {
+        Gamma::new(freedom / cast(2.0), cast(0.5)).map(|g| ChiSquared { freedom, g })
     }
 
     /// Returns the degrees of freedom of the chi-squared
@@ -63,7 +63,7 @@ impl ChiSquared {
     /// let n = ChiSquared::new(3.0).unwrap();
     /// assert_eq!(n.freedom(), 3.0);
     /// ```
-    pub fn freedom(&self) -> f64 {
+    pub fn freedom(&self) -> T {
         self.freedom
     }
 
@@ -77,7 +77,7 @@ impl ChiSquared {
     /// let n = ChiSquared::new(3.0).unwrap();
     /// assert_eq!(n.shape(), 3.0 / 2.0);
     /// ```
-    pub fn shape(&self) -> f64 {
+    pub fn shape(&self) -> T {
         self.g.shape()
     }
 
@@ -91,18 +91,18 @@ impl ChiSquared {
     /// let n = ChiSquared::new(3.0).unwrap();
     /// assert_eq!(n.rate(), 0.5);
     /// ```
-    pub fn rate(&self) -> f64 {
+    pub fn rate(&self) -> T {
         self.g.rate()
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for ChiSquared {
-    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for ChiSquared<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
         ::rand::distributions::Distribution::sample(&self.g, r)
     }
 }
 
-impl ContinuousCDF<f64, f64> for ChiSquared {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for ChiSquared<T> {
     /// Calculates the cumulative distribution function for the
     /// chi-squared distribution at `x`
     ///
@@ -114,7 +114,7 @@ impl ContinuousCDF<f64, f64> for ChiSquared {
     ///
     /// where `k` is the degrees of freedom, `Γ` is the gamma function,
     /// and `γ` is the lower incomplete gamma function
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         self.g.cdf(x)
     }
 
@@ -129,12 +129,31 @@ impl ContinuousCDF<f64, f64> for ChiSquared {
     ///
     /// where `k` is the degrees of freedom, `Γ` is the gamma function,
     /// and `γ` is the upper incomplete gamma function
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         self.g.sf(x)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// chi-squared distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2 * P^-1(k / 2, p)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom and `P^-1` is the inverse of the
+    /// lower regularized incomplete gamma function
+    fn inverse_cdf(&self, p: T) -> T {
+        self.g.inverse_cdf(p)
+    }
 }
 
-impl Min<f64> for ChiSquared {
+impl<T: SpecialFloat> Min<T> for ChiSquared<T> {
     /// Returns the minimum value in the domain of the
     /// chi-squared distribution representable by a double precision
     /// float
@@ -144,12 +163,12 @@ impl Min<f64> for ChiSquared {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for ChiSquared {
+impl<T: SpecialFloat> Max<T> for ChiSquared<T> {
     /// Returns the maximum value in the domain of the
     /// chi-squared distribution representable by a double precision
     /// float
@@ -159,12 +178,12 @@ impl Max<f64> for ChiSquared {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for ChiSquared {
+impl<T: SpecialFloat> Distribution<T> for ChiSquared<T> {
     /// Returns the mean of the chi-squared distribution
     ///
     /// # Formula
@@ -174,7 +193,7 @@ impl Distribution<f64> for ChiSquared {
     /// ```
     ///
     /// where `k` is the degrees of freedom
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         self.g.mean()
     }
     /// Returns the variance of the chi-squared distribution
@@ -186,7 +205,7 @@ impl Distribution<f64> for ChiSquared {
     /// ```
     ///
     /// where `k` is the degrees of freedom
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         self.g.variance()
     }
     /// Returns the entropy of the chi-squared distribution
@@ -199,7 +218,7 @@ impl Distribution<f64> for ChiSquared {
     ///
     /// where `k` is the degrees of freedom, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         self.g.entropy()
     }
     /// Returns the skewness of the chi-squared distribution
@@ -211,12 +230,12 @@ impl Distribution<f64> for ChiSquared {
     /// ```
     ///
     /// where `k` is the degrees of freedom
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         self.g.skewness()
     }
 }
 
-impl Median<f64> for ChiSquared {
+impl<T: SpecialFloat> Median<T> for ChiSquared<T> {
     /// Returns the median  of the chi-squared distribution
     ///
     /// # Formula
@@ -224,19 +243,19 @@ impl Median<f64> for ChiSquared {
     /// ```ignore
     /// k * (1 - (2 / 9k))^3
     /// ```
-    fn median(&self) -> f64 {
-        if self.freedom < 1.0 {
+    fn median(&self) -> T {
+        if self.freedom < T::one() {
             // if k is small, calculate using expansion of formula
-            self.freedom - 2.0 / 3.0 + 12.0 / (81.0 * self.freedom)
-                - 8.0 / (729.0 * self.freedom * self.freedom)
+            self.freedom - cast::<T>(2.0) / cast(3.0) + cast::<T>(12.0) / (cast::<T>(81.0) * self.freedom)
+                - cast::<T>(8.0) / (cast::<T>(729.0) * self.freedom * self.freedom)
         } else {
             // if k is large enough, median heads toward k - 2/3
-            self.freedom - 2.0 / 3.0
+            self.freedom - cast::<T>(2.0) / cast(3.0)
         }
     }
 }
 
-impl Mode<Option<f64>> for ChiSquared {
+impl<T: SpecialFloat> Mode<Option<T>> for ChiSquared<T> {
     /// Returns the mode of the chi-squared distribution
     ///
     /// # Formula
@@ -246,12 +265,12 @@ impl Mode<Option<f64>> for ChiSquared {
     /// ```
     ///
     /// where `k` is the degrees of freedom
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         self.g.mode()
     }
 }
 
-impl Continuous<f64, f64> for ChiSquared {
+impl<T: SpecialFloat> Continuous<T, T> for ChiSquared<T> {
     /// Calculates the probability density function for the chi-squared
     /// distribution at `x`
     ///
@@ -262,7 +281,7 @@ impl Continuous<f64, f64> for ChiSquared {
     /// ```
     ///
     /// where `k` is the degrees of freedom and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         self.g.pdf(x)
     }
 
@@ -274,7 +293,7 @@ impl Continuous<f64, f64> for ChiSquared {
     /// ```ignore
     /// ln(1 / (2^(k / 2) * Γ(k / 2)) * x^((k / 2) - 1) * e^(-x / 2))
     /// ```
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         self.g.ln_pdf(x)
     }
 }
@@ -283,7 +302,7 @@ impl Continuous<f64, f64> for ChiSquared {
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::Median;
-    use crate::distribution::ChiSquared;
+    use crate::distribution::{ChiSquared, ContinuousCDF};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -326,4 +345,28 @@ mod tests {
         test::check_continuous_distribution(&try_create(2.0), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(5.0), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: ChiSquared| x.inverse_cdf(arg);
+        test_almost(1.0, 0.0039321400000195231684, 1e-16, inverse_cdf(0.05));
+        test_almost(1.0, 0.45493642311957275194, 1e-14, inverse_cdf(0.5));
+        test_almost(1.0, 3.8414588206941244691, 1e-13, inverse_cdf(0.95));
+        test_almost(2.0, 0.1025865887751010727, 1e-14, inverse_cdf(0.05));
+        test_almost(2.0, 1.3862943611198906188, 1e-13, inverse_cdf(0.5));
+        test_almost(2.0, 5.9914645471079802105, 1e-13, inverse_cdf(0.95));
+        test_almost(10.0, 3.9402991361190600947, 1e-13, inverse_cdf(0.05));
+        test_almost(10.0, 9.3418177655919674406, 1e-13, inverse_cdf(0.5));
+        test_almost(10.0, 18.307038053275144003, 1e-12, inverse_cdf(0.95));
+        test_almost(100.0, 77.929465165017259806, 1e-12, inverse_cdf(0.05));
+        test_almost(100.0, 99.334129235988455754, 1e-11, inverse_cdf(0.5));
+        test_almost(100.0, 124.34211340400407469, 1e-11, inverse_cdf(0.95));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = ChiSquared::<f32>::new(4.5).unwrap();
+        let d64 = ChiSquared::new(4.5).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.5, 2.0, 5.0, 10.0], 1e-4);
+    }
 }
@@ -1,4 +1,5 @@
 use crate::distribution::{Continuous, ContinuousCDF};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
@@ -16,9 +17,9 @@ use rand::Rng;
 /// assert_eq!(n.mean().unwrap(), 3.0);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Dirac(f64);
+pub struct Dirac<T = f64>(T);
 
-impl Dirac {
+impl<T: SpecialFloat> Dirac<T> {
     ///  Constructs a new dirac distribution function at value `v`.
     ///
     /// # Errors
@@ -36,7 +37,7 @@ impl Dirac {
     /// result = Dirac::new(f64::NAN);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(v: f64) -> Result<Self> {
+    pub fn new(v: T) -> Result<Self> {
         if v.is_nan() {
             Err(StatsError::BadParams)
         } else {
@@ -45,23 +46,23 @@ impl Dirac {
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Dirac {
-    fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Dirac<T> {
+    fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> T {
         self.0
     }
 }
 
-impl ContinuousCDF<f64, f64> for Dirac {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Dirac<T> {
     /// Calculates the cumulative distribution function for the
     /// dirac distribution at `x`
     ///
     /// Where the value is 1 if x > `v`, 0 otherwise.
     ///
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         if x < self.0 {
-            0.0
+            T::zero()
         } else {
-            1.0
+            T::one()
         }
     }
 
@@ -70,16 +71,16 @@ impl ContinuousCDF<f64, f64> for Dirac {
     ///
     /// Where the value is 0 if x > `v`, 1 otherwise.
     ///
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         if x < self.0 {
-            1.0
+            T::one()
         } else {
-            0.0
+            T::zero()
         }
     }
 }
 
-impl Min<f64> for Dirac {
+impl<T: SpecialFloat> Min<T> for Dirac<T> {
     /// Returns the minimum value in the domain of the
     /// dirac distribution representable by a double precision float
     ///
@@ -88,12 +89,12 @@ impl Min<f64> for Dirac {
     /// ```ignore
     /// v
     /// ```
-    fn min(&self) -> f64 {
+    fn min(&self) -> T {
         self.0
     }
 }
 
-impl Max<f64> for Dirac {
+impl<T: SpecialFloat> Max<T> for Dirac<T> {
     /// Returns the maximum value in the domain of the
     /// dirac distribution representable by a double precision float
     ///
@@ -102,19 +103,19 @@ impl Max<f64> for Dirac {
     /// ```ignore
     /// v
     /// ```
-    fn max(&self) -> f64 {
+    fn max(&self) -> T {
         self.0
     }
 }
 
-impl Distribution<f64> for Dirac {
+impl<T: SpecialFloat> Distribution<T> for Dirac<T> {
     /// Returns the mean of the dirac distribution
     ///
     /// # Remarks
     ///
     /// Since the only value that can be produced by this distribution is `v` with probability
     /// 1, it is just `v`.
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         Some(self.0)
     }
     /// Returns the variance of the dirac distribution
@@ -126,8 +127,8 @@ impl Distribution<f64> for Dirac {
     /// ```
     ///
     /// Since only one value can be produced there is no variance.
-    fn variance(&self) -> Option<f64> {
-        Some(0.0)
+    fn variance(&self) -> Option<T> {
+        Some(T::zero())
     }
     /// Returns the entropy of the dirac distribution
     ///
@@ -138,8 +139,8 @@ impl Distribution<f64> for Dirac {
     /// ```
     ///
     /// Since this distribution has full certainty, it encodes no information
-    fn entropy(&self) -> Option<f64> {
-        Some(0.0)
+    fn entropy(&self) -> Option<T> {
+        Some(T::zero())
     }
     /// Returns the skewness of the dirac distribution
     ///
@@ -148,12 +149,12 @@ impl Distribution<f64> for Dirac {
     /// ```ignore
     /// 0
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        Some(0.0)
+    fn skewness(&self) -> Option<T> {
+        Some(T::zero())
     }
 }
 
-impl Median<f64> for Dirac {
+impl<T: SpecialFloat> Median<T> for Dirac<T> {
     /// Returns the median of the dirac distribution
     ///
     /// # Formula
@@ -163,12 +164,12 @@ impl Median<f64> for Dirac {
     /// ```
     ///
     /// where `v` is the point of the dirac distribution
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.0
     }
 }
 
-impl Mode<Option<f64>> for Dirac {
+impl<T: SpecialFloat> Mode<Option<T>> for Dirac<T> {
     /// Returns the mode of the dirac distribution
     ///
     /// # Formula
@@ -178,7 +179,7 @@ impl Mode<Option<f64>> for Dirac {
     /// ```
     ///
     /// where `v` is the point of the dirac distribution
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.0)
     }
 }
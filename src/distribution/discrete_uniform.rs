@@ -100,6 +100,42 @@ impl DiscreteCDF<i64, f64> for DiscreteUniform {
             }
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// discrete uniform distribution at `p`, i.e. the smallest `x` such that
+    /// `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min + ceil(p * (max - min + 1)) - 1
+    /// ```
+    fn inverse_cdf(&self, p: f64) -> i64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return self.min;
+        }
+        if p == 1.0 {
+            return self.max;
+        }
+        let n = self.max as f64 - self.min as f64 + 1.0;
+        let offset = ((p * n).ceil() as i64).saturating_sub(1).max(0);
+        let mut x = self.min.saturating_add(offset).min(self.max);
+        // correct for rounding in `p * n`
+        while x > self.min && self.cdf(x - 1) >= p {
+            x -= 1;
+        }
+        while x < self.max && self.cdf(x) < p {
+            x += 1;
+        }
+        x
+    }
 }
 
 impl Min<i64> for DiscreteUniform {
@@ -408,4 +444,31 @@ mod tests {
         let cdf = |arg: i64| move |x: DiscreteUniform| x.cdf(arg);
         test_case(0, 3, 1.0, cdf(5));
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: DiscreteUniform| x.inverse_cdf(arg);
+        test_case(-10, 10, -10, inverse_cdf(0.0));
+        test_case(-10, 10, -10, inverse_cdf(0.01));
+        test_case(-10, 10, -5, inverse_cdf(0.2857142857142857142857));
+        test_case(-10, 10, 0, inverse_cdf(0.5));
+        test_case(-10, 10, 10, inverse_cdf(0.99));
+        test_case(-10, 10, 10, inverse_cdf(1.0));
+        test_case(0, 3, 1, inverse_cdf(0.5));
+        test_case(0, 3, 2, inverse_cdf(0.5 + 1e-15));
+        test_case(5, 5, 5, inverse_cdf(0.5));
+        test_case(i64::MIN, i64::MAX, i64::MIN, inverse_cdf(0.0));
+        test_case(i64::MIN, i64::MAX, i64::MAX, inverse_cdf(1.0));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        let n = try_create(-7, 1000);
+        for i in 1..1000 {
+            let p = i as f64 / 1000.0;
+            let x = n.inverse_cdf(p);
+            assert!(n.cdf(x) >= p);
+            assert!(x == n.min() || n.cdf(x - 1) < p);
+        }
+    }
 }
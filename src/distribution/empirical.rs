@@ -7,7 +7,7 @@ use rand::Rng;
 use std::collections::BTreeMap;
 
 #[derive(Clone, Debug, PartialEq)]
-struct NonNAN<T>(T);
+pub(crate) struct NonNAN<T>(pub(crate) T);
 
 impl<T: PartialEq> Eq for NonNAN<T> {}
 
@@ -1,4 +1,5 @@
 use crate::distribution::{Continuous, ContinuousCDF, Gamma};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::Result;
 use rand::Rng;
@@ -21,11 +22,11 @@ use rand::Rng;
 /// assert!(prec::almost_eq(n.pdf(2.0), 0.270670566473225383788, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Erlang {
-    g: Gamma,
+pub struct Erlang<T = f64> {
+    g: Gamma<T>,
 }
 
-impl Erlang {
+impl<T: SpecialFloat> Erlang<T> {
     /// Constructs a new erlang distribution with a shape (k)
     /// of `shape` and a rate (λ) of `rate`
     ///
@@ -45,8 +46,8 @@ impl Erlang {
     /// result = Erlang::new(0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(shape: u64, rate: f64) -> Result<Erlang> {
-        Gamma::new(shape as f64, rate).map(|g| Erlang { g })
+    pub fn new(shape: u64, rate: T) -> Result<Erlang<T>> {
+        Gamma::new(T::from(shape).unwrap(), rate).map(|g| Erlang { g })
     }
 
     /// Returns the shape (k) of the erlang distribution
@@ -60,7 +61,7 @@ impl Erlang {
     /// assert_eq!(n.shape(), 3);
     /// ```
     pub fn shape(&self) -> u64 {
-        self.g.shape() as u64
+        self.g.shape().to_u64().unwrap()
     }
 
     /// Returns the rate (λ) of the erlang distribution
@@ -73,18 +74,18 @@ impl Erlang {
     /// let n = Erlang::new(3, 1.0).unwrap();
     /// assert_eq!(n.rate(), 1.0);
     /// ```
-    pub fn rate(&self) -> f64 {
+    pub fn rate(&self) -> T {
         self.g.rate()
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Erlang {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Erlang<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         ::rand::distributions::Distribution::sample(&self.g, rng)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Erlang {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Erlang<T> {
     /// Calculates the cumulative distribution function for the erlang
     /// distribution
     /// at `x`
@@ -97,7 +98,7 @@ impl ContinuousCDF<f64, f64> for Erlang {
     ///
     /// where `k` is the shape, `λ` is the rate, and `γ` is the lower
     /// incomplete gamma function
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         self.g.cdf(x)
     }
 
@@ -113,12 +114,31 @@ impl ContinuousCDF<f64, f64> for Erlang {
     ///
     /// where `k` is the shape, `λ` is the rate, and `γ` is the upper
     /// incomplete gamma function
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         self.g.sf(x)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// erlang distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// P^-1(k, p) / λ
+    /// ```
+    ///
+    /// where `k` is the shape, `λ` is the rate and `P^-1` is the inverse of the
+    /// lower regularized incomplete gamma function
+    fn inverse_cdf(&self, p: T) -> T {
+        self.g.inverse_cdf(p)
+    }
 }
 
-impl Min<f64> for Erlang {
+impl<T: SpecialFloat> Min<T> for Erlang<T> {
     /// Returns the minimum value in the domain of the
     /// erlang distribution representable by a double precision
     /// float
@@ -128,12 +148,12 @@ impl Min<f64> for Erlang {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
+    fn min(&self) -> T {
         self.g.min()
     }
 }
 
-impl Max<f64> for Erlang {
+impl<T: SpecialFloat> Max<T> for Erlang<T> {
     /// Returns the maximum value in the domain of the
     /// erlang distribution representable by a double precision
     /// float
@@ -143,12 +163,12 @@ impl Max<f64> for Erlang {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
+    fn max(&self) -> T {
         self.g.max()
     }
 }
 
-impl Distribution<f64> for Erlang {
+impl<T: SpecialFloat> Distribution<T> for Erlang<T> {
     /// Returns the mean of the erlang distribution
     ///
     /// # Remarks
@@ -163,7 +183,7 @@ impl Distribution<f64> for Erlang {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the rate
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         self.g.mean()
     }
     /// Returns the variance of the erlang distribution
@@ -175,7 +195,7 @@ impl Distribution<f64> for Erlang {
     /// ```
     ///
     /// where `α` is the shape and `λ` is the rate
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         self.g.variance()
     }
     /// Returns the entropy of the erlang distribution
@@ -188,7 +208,7 @@ impl Distribution<f64> for Erlang {
     ///
     /// where `k` is the shape, `λ` is the rate, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         self.g.entropy()
     }
     /// Returns the skewness of the erlang distribution
@@ -200,12 +220,12 @@ impl Distribution<f64> for Erlang {
     /// ```
     ///
     /// where `k` is the shape
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         self.g.skewness()
     }
 }
 
-impl Mode<Option<f64>> for Erlang {
+impl<T: SpecialFloat> Mode<Option<T>> for Erlang<T> {
     /// Returns the mode for the erlang distribution
     ///
     /// # Remarks
@@ -220,12 +240,12 @@ impl Mode<Option<f64>> for Erlang {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the rate
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         self.g.mode()
     }
 }
 
-impl Continuous<f64, f64> for Erlang {
+impl<T: SpecialFloat> Continuous<T, T> for Erlang<T> {
     /// Calculates the probability density function for the erlang distribution
     /// at `x`
     ///
@@ -241,7 +261,7 @@ impl Continuous<f64, f64> for Erlang {
     /// ```
     ///
     /// where `k` is the shape, `λ` is the rate, and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         self.g.pdf(x)
     }
 
@@ -261,7 +281,7 @@ impl Continuous<f64, f64> for Erlang {
     /// ```
     ///
     /// where `k` is the shape, `λ` is the rate, and `Γ` is the gamma function
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         self.g.ln_pdf(x)
     }
 }
@@ -269,7 +289,7 @@ impl Continuous<f64, f64> for Erlang {
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
-    use crate::distribution::Erlang;
+    use crate::distribution::{ContinuousCDF, Erlang};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
 
@@ -313,4 +333,30 @@ mod tests {
         test::check_continuous_distribution(&try_create(2, 1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(3, 0.5), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let test = [
+            ((1, 1.0), 0.1, 0.1053605156578263074),
+            ((1, 1.0), 0.5, 0.69314718055994530942),
+            ((1, 1.0), 0.9, 2.3025850929940459061),
+            ((3, 2.0), 0.1, 0.5510326641246605508),
+            ((3, 2.0), 0.5, 1.337030156861780159),
+            ((3, 2.0), 0.9, 2.6611601689171051128),
+            ((10, 0.5), 0.1, 12.442609210450065618),
+            ((10, 0.5), 0.5, 19.337429229428262304),
+            ((10, 0.5), 0.9, 28.411980584305634262),
+        ];
+        for &((shape, rate), p, res) in test.iter() {
+            let n = try_create(shape, rate);
+            assert_almost_eq!(n.inverse_cdf(p), res, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Erlang::<f32>::new(3, 2.0).unwrap();
+        let d64 = Erlang::new(3, 2.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 1.0, 2.0, 4.0], 1e-4);
+    }
 }
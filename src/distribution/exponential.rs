@@ -1,8 +1,9 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
+use crate::distribution::{ziggurat, Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
-use std::f64;
 
 /// Implements the
 /// [Exp](https://en.wikipedia.org/wiki/Exp_distribution)
@@ -21,11 +22,11 @@ use std::f64;
 /// assert_eq!(n.pdf(1.0), 0.3678794411714423215955);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Exp {
-    rate: f64,
+pub struct Exp<T = f64> {
+    rate: T,
 }
 
-impl Exp {
+impl<T: SpecialFloat> Exp<T> {
     /// Constructs a new exponential distribution with a
     /// rate (λ) of `rate`.
     ///
@@ -44,8 +45,8 @@ impl Exp {
     /// result = Exp::new(-1.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(rate: f64) -> Result<Exp> {
-        if rate.is_nan() || rate <= 0.0 {
+    pub fn new(rate: T) -> Result<Exp<T>> {
+        if rate.is_nan() || rate <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Exp { rate })
@@ -62,18 +63,18 @@ impl Exp {
     /// let n = Exp::new(1.0).unwrap();
     /// assert_eq!(n.rate(), 1.0);
     /// ```
-    pub fn rate(&self) -> f64 {
+    pub fn rate(&self) -> T {
         self.rate
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Exp {
-    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
-        ziggurat::sample_exp_1(r) / self.rate
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Exp<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
+        cast::<T>(ziggurat::sample_exp_1(r)) / self.rate
     }
 }
 
-impl ContinuousCDF<f64, f64> for Exp {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Exp<T> {
     /// Calculates the cumulative distribution function for the
     /// exponential distribution at `x`
     ///
@@ -84,11 +85,11 @@ impl ContinuousCDF<f64, f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn cdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
         } else {
-            1.0 - (-self.rate * x).exp()
+            T::one() - (-self.rate * x).exp()
         }
     }
 
@@ -102,16 +103,37 @@ impl ContinuousCDF<f64, f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn sf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::one()
         } else {
             (-self.rate * x).exp()
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// exponential distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -ln(1 - p) / λ
+    /// ```
+    ///
+    /// where `λ` is the rate
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        -(-p).ln_1p() / self.rate
+    }
 }
 
-impl Min<f64> for Exp {
+impl<T: SpecialFloat> Min<T> for Exp<T> {
     /// Returns the minimum value in the domain of the exponential
     /// distribution representable by a double precision float
     ///
@@ -120,12 +142,12 @@ impl Min<f64> for Exp {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for Exp {
+impl<T: SpecialFloat> Max<T> for Exp<T> {
     /// Returns the maximum value in the domain of the exponential
     /// distribution representable by a double precision float
     ///
@@ -134,12 +156,12 @@ impl Max<f64> for Exp {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Exp {
+impl<T: SpecialFloat> Distribution<T> for Exp<T> {
     /// Returns the mean of the exponential distribution
     ///
     /// # Formula
@@ -149,8 +171,8 @@ impl Distribution<f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn mean(&self) -> Option<f64> {
-        Some(1.0 / self.rate)
+    fn mean(&self) -> Option<T> {
+        Some(T::one() / self.rate)
     }
     /// Returns the variance of the exponential distribution
     ///
@@ -161,8 +183,8 @@ impl Distribution<f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn variance(&self) -> Option<f64> {
-        Some(1.0 / (self.rate * self.rate))
+    fn variance(&self) -> Option<T> {
+        Some(T::one() / (self.rate * self.rate))
     }
     /// Returns the entropy of the exponential distribution
     ///
@@ -173,8 +195,8 @@ impl Distribution<f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn entropy(&self) -> Option<f64> {
-        Some(1.0 - self.rate.ln())
+    fn entropy(&self) -> Option<T> {
+        Some(T::one() - self.rate.ln())
     }
     /// Returns the skewness of the exponential distribution
     ///
@@ -183,12 +205,12 @@ impl Distribution<f64> for Exp {
     /// ```ignore
     /// 2
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        Some(2.0)
+    fn skewness(&self) -> Option<T> {
+        Some(cast(2.0))
     }
 }
 
-impl Median<f64> for Exp {
+impl<T: SpecialFloat> Median<T> for Exp<T> {
     /// Returns the median of the exponential distribution
     ///
     /// # Formula
@@ -198,12 +220,12 @@ impl Median<f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn median(&self) -> f64 {
-        f64::consts::LN_2 / self.rate
+    fn median(&self) -> T {
+        T::LN_2() / self.rate
     }
 }
 
-impl Mode<Option<f64>> for Exp {
+impl<T: SpecialFloat> Mode<Option<T>> for Exp<T> {
     /// Returns the mode of the exponential distribution
     ///
     /// # Formula
@@ -211,12 +233,12 @@ impl Mode<Option<f64>> for Exp {
     /// ```ignore
     /// 0
     /// ```
-    fn mode(&self) -> Option<f64> {
-        Some(0.0)
+    fn mode(&self) -> Option<T> {
+        Some(T::zero())
     }
 }
 
-impl Continuous<f64, f64> for Exp {
+impl<T: SpecialFloat> Continuous<T, T> for Exp<T> {
     /// Calculates the probability density function for the exponential
     /// distribution at `x`
     ///
@@ -227,9 +249,9 @@ impl Continuous<f64, f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
+    fn pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
         } else {
             self.rate * (-self.rate * x).exp()
         }
@@ -245,19 +267,63 @@ impl Continuous<f64, f64> for Exp {
     /// ```
     ///
     /// where `λ` is the rate
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            f64::NEG_INFINITY
+    fn ln_pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::neg_infinity()
         } else {
             self.rate.ln() - self.rate * x
         }
     }
 }
 
+impl Fit<f64> for Exp {
+    /// Returns the maximum likelihood estimate of the exponential
+    /// distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is negative or all are zero
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ = n / Σ x_i
+    /// ```
+    ///
+    /// where `n` is the number of observations
+    fn fit_mle(data: &[f64]) -> Result<Exp> {
+        let rate = 1.0 / data.mean();
+        if !rate.is_finite() || data.iter().any(|&x| x.is_nan() || x < 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        Exp::new(rate)
+    }
+
+    /// Returns the method of moments estimate of the exponential
+    /// distribution for the observations in `data`, which coincides with
+    /// the maximum likelihood estimate
+    fn fit_mom(data: &[f64]) -> Result<Exp> {
+        Exp::fit_mle(data)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to `λ`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n / λ^2
+    /// ```
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let n = data.len() as f64;
+        Some(DMatrix::from_element(1, 1, n / (self.rate * self.rate)))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::f64;
+    use crate::distribution::Fit;
     use crate::statistics::*;
     use crate::distribution::{ContinuousCDF, Continuous, Exp};
     use crate::distribution::internal::*;
@@ -488,4 +554,50 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.5), 0.0, 20.0);
         test::check_continuous_distribution(&try_create(2.5), 0.0, 50.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Exp| x.inverse_cdf(arg);
+        test_almost(0.1, 1.0536051565782630155, 1e-14, inverse_cdf(0.1));
+        test_almost(0.1, 6.9314718055994527094, 1e-14, inverse_cdf(0.5));
+        test_almost(0.1, 23.025850929940457782, 1e-13, inverse_cdf(0.9));
+        test_almost(1.0, 0.1053605156578263074, 1e-15, inverse_cdf(0.1));
+        test_almost(1.0, 0.69314718055994530942, 1e-15, inverse_cdf(0.5));
+        test_almost(1.0, 2.3025850929940459061, 1e-14, inverse_cdf(0.9));
+        test_almost(10.0, 0.01053605156578263074, 1e-16, inverse_cdf(0.1));
+        test_almost(10.0, 0.069314718055994530942, 1e-16, inverse_cdf(0.5));
+        test_almost(10.0, 0.23025850929940459061, 1e-15, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_inverse_cdf_bounds() {
+        let inverse_cdf = |arg: f64| move |x: Exp| x.inverse_cdf(arg);
+        test_case(1.0, 0.0, inverse_cdf(0.0));
+        test_case(1.0, f64::INFINITY, inverse_cdf(1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_cdf_out_of_range() {
+        try_create(1.0).inverse_cdf(1.5);
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Exp::<f32>::new(1.5).unwrap();
+        let d64 = Exp::new(1.5).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.125, 0.5, 1.0, 3.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+        let n = Exp::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.rate(), 0.6747638326585695, 1e-15);
+        assert_eq!(Exp::fit_mom(&data).unwrap(), n);
+        test::check_observed_information(n.observed_information(&data), &[21.96324], 1e-14);
+        assert!(Exp::fit_mle(&[]).is_err());
+        assert!(Exp::fit_mle(&[1.0, -1.0]).is_err());
+        assert!(Exp::fit_mle(&[0.0, 0.0]).is_err());
+    }
 }
@@ -1,9 +1,8 @@
 use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::beta;
+use crate::function::{beta, float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
-use std::f64;
 
 /// Implements the
 /// [Fisher-Snedecor](https://en.wikipedia.org/wiki/F-distribution) distribution
@@ -21,12 +20,12 @@ use std::f64;
 /// assert!(prec::almost_eq(n.pdf(1.0), 0.318309886183790671538, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct FisherSnedecor {
-    freedom_1: f64,
-    freedom_2: f64,
+pub struct FisherSnedecor<T = f64> {
+    freedom_1: T,
+    freedom_2: T,
 }
 
-impl FisherSnedecor {
+impl<T: SpecialFloat> FisherSnedecor<T> {
     /// Constructs a new fisher-snedecor distribution with
     /// degrees of freedom `freedom_1` and `freedom_2`
     ///
@@ -46,8 +45,8 @@ impl FisherSnedecor {
     /// result = FisherSnedecor::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(freedom_1: f64, freedom_2: f64) -> Result<FisherSnedecor> {
-        if !freedom_1.is_finite() || freedom_1 <= 0.0 || !freedom_2.is_finite() || freedom_2 <= 0.0
+    pub fn new(freedom_1: T, freedom_2: T) -> Result<FisherSnedecor<T>> {
+        if !freedom_1.is_finite() || freedom_1 <= T::zero() || !freedom_2.is_finite() || freedom_2 <= T::zero()
         {
             Err(StatsError::BadParams)
         } else {
@@ -69,7 +68,7 @@ impl FisherSnedecor {
     /// let n = FisherSnedecor::new(2.0, 3.0).unwrap();
     /// assert_eq!(n.freedom_1(), 2.0);
     /// ```
-    pub fn freedom_1(&self) -> f64 {
+    pub fn freedom_1(&self) -> T {
         self.freedom_1
     }
 
@@ -84,19 +83,19 @@ impl FisherSnedecor {
     /// let n = FisherSnedecor::new(2.0, 3.0).unwrap();
     /// assert_eq!(n.freedom_2(), 3.0);
     /// ```
-    pub fn freedom_2(&self) -> f64 {
+    pub fn freedom_2(&self) -> T {
         self.freedom_2
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for FisherSnedecor {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        (super::gamma::sample_unchecked(rng, self.freedom_1 / 2.0, 0.5) * self.freedom_2)
-            / (super::gamma::sample_unchecked(rng, self.freedom_2 / 2.0, 0.5) * self.freedom_1)
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for FisherSnedecor<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        (super::gamma::sample_unchecked(rng, self.freedom_1 / cast(2.0), cast(0.5)) * self.freedom_2)
+            / (super::gamma::sample_unchecked(rng, self.freedom_2 / cast(2.0), cast(0.5)) * self.freedom_1)
     }
 }
 
-impl ContinuousCDF<f64, f64> for FisherSnedecor {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for FisherSnedecor<T> {
     /// Calculates the cumulative distribution function for the fisher-snedecor
     /// distribution
     /// at `x`
@@ -110,15 +109,15 @@ impl ContinuousCDF<f64, f64> for FisherSnedecor {
     /// where `d1` is the first degree of freedom, `d2` is
     /// the second degree of freedom, and `I` is the regularized incomplete
     /// beta function
-    fn cdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
         } else if x.is_infinite() {
-            1.0
+            T::one()
         } else {
             beta::beta_reg(
-                self.freedom_1 / 2.0,
-                self.freedom_2 / 2.0,
+                self.freedom_1 / cast(2.0),
+                self.freedom_2 / cast(2.0),
                 self.freedom_1 * x / (self.freedom_1 * x + self.freedom_2),
             )
         }
@@ -136,22 +135,57 @@ impl ContinuousCDF<f64, f64> for FisherSnedecor {
     /// where `d1` is the first degree of freedom, `d2` is
     /// the second degree of freedom, and `I` is the regularized incomplete
     /// beta function
-    fn sf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::one()
         } else if x.is_infinite() {
-            0.0
+            T::zero()
         } else {
             beta::beta_reg(
-                self.freedom_2 / 2.0,
-                self.freedom_1 / 2.0, 
-                1. - ((self.freedom_1 * x) / (self.freedom_1 * x + self.freedom_2))
+                self.freedom_2 / cast(2.0),
+                self.freedom_1 / cast(2.0), 
+                T::one() - ((self.freedom_1 * x) / (self.freedom_1 * x + self.freedom_2))
             )
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// fisher-snedecor distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// d2 * y / (d1 * (1 - y))
+    /// ```
+    ///
+    /// where `y = I^-1(d1 / 2, d2 / 2, p)`, `d1` is the first degree of freedom,
+    /// `d2` is the second degree of freedom and `I^-1` is the inverse of the
+    /// regularized lower incomplete beta function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == T::one() {
+            return T::infinity();
+        }
+        let a = self.freedom_1 / cast(2.0);
+        let b = self.freedom_2 / cast(2.0);
+        // evaluate whichever tail keeps `1 - y` accurate
+        if p <= cast(0.5) {
+            let y = beta::inv_beta_reg(a, b, p);
+            self.freedom_2 * y / (self.freedom_1 * (T::one() - y))
+        } else {
+            let w = beta::inv_beta_reg(b, a, T::one() - p);
+            self.freedom_2 * (T::one() - w) / (self.freedom_1 * w)
+        }
+    }
 }
 
-impl Min<f64> for FisherSnedecor {
+impl<T: SpecialFloat> Min<T> for FisherSnedecor<T> {
     /// Returns the minimum value in the domain of the
     /// fisher-snedecor distribution representable by a double precision
     /// float
@@ -161,12 +195,12 @@ impl Min<f64> for FisherSnedecor {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for FisherSnedecor {
+impl<T: SpecialFloat> Max<T> for FisherSnedecor<T> {
     /// Returns the maximum value in the domain of the
     /// fisher-snedecor distribution representable by a double precision
     /// float
@@ -176,12 +210,12 @@ impl Max<f64> for FisherSnedecor {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for FisherSnedecor {
+impl<T: SpecialFloat> Distribution<T> for FisherSnedecor<T> {
     /// Returns the mean of the fisher-snedecor distribution
     ///
     /// # Panics
@@ -199,11 +233,11 @@ impl Distribution<f64> for FisherSnedecor {
     /// ```
     ///
     /// where `d2` is the second degree of freedom
-    fn mean(&self) -> Option<f64> {
-        if self.freedom_2 <= 2.0 {
+    fn mean(&self) -> Option<T> {
+        if self.freedom_2 <= cast(2.0) {
             None
         } else {
-            Some(self.freedom_2 / (self.freedom_2 - 2.0))
+            Some(self.freedom_2 / (self.freedom_2 - cast(2.0)))
         }
     }
     /// Returns the variance of the fisher-snedecor distribution
@@ -224,16 +258,16 @@ impl Distribution<f64> for FisherSnedecor {
     ///
     /// where `d1` is the first degree of freedom and `d2` is
     /// the second degree of freedom
-    fn variance(&self) -> Option<f64> {
-        if self.freedom_2 <= 4.0 {
+    fn variance(&self) -> Option<T> {
+        if self.freedom_2 <= cast(4.0) {
             None
         } else {
             let val =
-                (2.0 * self.freedom_2 * self.freedom_2 * (self.freedom_1 + self.freedom_2 - 2.0))
+                (cast::<T>(2.0) * self.freedom_2 * self.freedom_2 * (self.freedom_1 + self.freedom_2 - cast(2.0)))
                     / (self.freedom_1
-                        * (self.freedom_2 - 2.0)
-                        * (self.freedom_2 - 2.0)
-                        * (self.freedom_2 - 4.0));
+                        * (self.freedom_2 - cast(2.0))
+                        * (self.freedom_2 - cast(2.0))
+                        * (self.freedom_2 - cast(4.0)));
             Some(val)
         }
     }
@@ -256,20 +290,20 @@ impl Distribution<f64> for FisherSnedecor {
     ///
     /// where `d1` is the first degree of freedom and `d2` is
     /// the second degree of freedom
-    fn skewness(&self) -> Option<f64> {
-        if self.freedom_2 <= 6.0 {
+    fn skewness(&self) -> Option<T> {
+        if self.freedom_2 <= cast(6.0) {
             None
         } else {
-            let val = ((2.0 * self.freedom_1 + self.freedom_2 - 2.0)
-                * (8.0 * (self.freedom_2 - 4.0)).sqrt())
-                / ((self.freedom_2 - 6.0)
-                    * (self.freedom_1 * (self.freedom_1 + self.freedom_2 - 2.0)).sqrt());
+            let val = ((cast::<T>(2.0) * self.freedom_1 + self.freedom_2 - cast(2.0))
+                * (cast::<T>(8.0) * (self.freedom_2 - cast(4.0))).sqrt())
+                / ((self.freedom_2 - cast(6.0))
+                    * (self.freedom_1 * (self.freedom_1 + self.freedom_2 - cast(2.0))).sqrt());
             Some(val)
         }
     }
 }
 
-impl Mode<Option<f64>> for FisherSnedecor {
+impl<T: SpecialFloat> Mode<Option<T>> for FisherSnedecor<T> {
     /// Returns the mode for the fisher-snedecor distribution
     ///
     /// # Panics
@@ -288,18 +322,18 @@ impl Mode<Option<f64>> for FisherSnedecor {
     ///
     /// where `d1` is the first degree of freedom and `d2` is
     /// the second degree of freedom
-    fn mode(&self) -> Option<f64> {
-        if self.freedom_1 <= 2.0 {
+    fn mode(&self) -> Option<T> {
+        if self.freedom_1 <= cast(2.0) {
             None
         } else {
-            let val = (self.freedom_2 * (self.freedom_1 - 2.0))
-                / (self.freedom_1 * (self.freedom_2 + 2.0));
+            let val = (self.freedom_2 * (self.freedom_1 - cast(2.0)))
+                / (self.freedom_1 * (self.freedom_2 + cast(2.0)));
             Some(val)
         }
     }
 }
 
-impl Continuous<f64, f64> for FisherSnedecor {
+impl<T: SpecialFloat> Continuous<T, T> for FisherSnedecor<T> {
     /// Calculates the probability density function for the fisher-snedecor
     /// distribution
     /// at `x`
@@ -318,14 +352,14 @@ impl Continuous<f64, f64> for FisherSnedecor {
     ///
     /// where `d1` is the first degree of freedom, `d2` is
     /// the second degree of freedom, and `β` is the beta function
-    fn pdf(&self, x: f64) -> f64 {
-        if x.is_infinite() || x <= 0.0 {
-            0.0
+    fn pdf(&self, x: T) -> T {
+        if x.is_infinite() || x <= T::zero() {
+            T::zero()
         } else {
             ((self.freedom_1 * x).powf(self.freedom_1) * self.freedom_2.powf(self.freedom_2)
                 / (self.freedom_1 * x + self.freedom_2).powf(self.freedom_1 + self.freedom_2))
             .sqrt()
-                / (x * beta::beta(self.freedom_1 / 2.0, self.freedom_2 / 2.0))
+                / (x * beta::beta(self.freedom_1 / cast(2.0), self.freedom_2 / cast(2.0)))
         }
     }
 
@@ -347,7 +381,7 @@ impl Continuous<f64, f64> for FisherSnedecor {
     ///
     /// where `d1` is the first degree of freedom, `d2` is
     /// the second degree of freedom, and `β` is the beta function
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         self.pdf(x).ln()
     }
 }
@@ -589,4 +623,25 @@ mod tests {
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(10.0, 10.0), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: FisherSnedecor| x.inverse_cdf(arg);
+        test_almost(2.0, 5.0, 0.051823112072844070916, 1e-14, inverse_cdf(0.05));
+        test_almost(2.0, 5.0, 0.79876977693223564844, 1e-13, inverse_cdf(0.5));
+        test_almost(2.0, 5.0, 5.7861350433499640573, 1e-12, inverse_cdf(0.95));
+        test_almost(10.0, 20.0, 0.36048813576055830368, 1e-13, inverse_cdf(0.05));
+        test_almost(10.0, 20.0, 0.96626388859291627883, 1e-13, inverse_cdf(0.5));
+        test_almost(10.0, 20.0, 2.34787756699831147, 1e-12, inverse_cdf(0.95));
+        test_almost(1.0, 1.0, 0.0061939586571081946937, 1e-15, inverse_cdf(0.05));
+        test_almost(1.0, 1.0, 1.0, 1e-12, inverse_cdf(0.5));
+        test_almost(1.0, 1.0, 161.44763879758820773, 1e-10, inverse_cdf(0.95));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = FisherSnedecor::<f32>::new(5.0, 8.0).unwrap();
+        let d64 = FisherSnedecor::new(5.0, 8.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 1.0, 2.0, 4.0], 1e-4);
+    }
 }
@@ -1,8 +1,8 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::distribution::{internal, Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, gamma, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
-use core::f64::INFINITY as INF;
+use nalgebra::DMatrix;
 use rand::Rng;
 
 /// Implements the [Gamma](https://en.wikipedia.org/wiki/Gamma_distribution)
@@ -20,12 +20,12 @@ use rand::Rng;
 /// assert!(prec::almost_eq(n.pdf(2.0), 0.270670566473225383788, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Gamma {
-    shape: f64,
-    rate: f64,
+pub struct Gamma<T = f64> {
+    shape: T,
+    rate: T,
 }
 
-impl Gamma {
+impl<T: SpecialFloat> Gamma<T> {
     /// Constructs a new gamma distribution with a shape (α)
     /// of `shape` and a rate (β) of `rate`
     ///
@@ -45,12 +45,12 @@ impl Gamma {
     /// result = Gamma::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(shape: f64, rate: f64) -> Result<Gamma> {
+    pub fn new(shape: T, rate: T) -> Result<Gamma<T>> {
         if shape.is_nan()
             || rate.is_nan()
             || shape.is_infinite() && rate.is_infinite()
-            || shape <= 0.0
-            || rate <= 0.0
+            || shape <= T::zero()
+            || rate <= T::zero()
         {
             return Err(StatsError::BadParams);
         }
@@ -67,7 +67,7 @@ impl Gamma {
     /// let n = Gamma::new(3.0, 1.0).unwrap();
     /// assert_eq!(n.shape(), 3.0);
     /// ```
-    pub fn shape(&self) -> f64 {
+    pub fn shape(&self) -> T {
         self.shape
     }
 
@@ -81,18 +81,18 @@ impl Gamma {
     /// let n = Gamma::new(3.0, 1.0).unwrap();
     /// assert_eq!(n.rate(), 1.0);
     /// ```
-    pub fn rate(&self) -> f64 {
+    pub fn rate(&self) -> T {
         self.rate
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Gamma {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Gamma<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         sample_unchecked(rng, self.shape, self.rate)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Gamma {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Gamma<T> {
     /// Calculates the cumulative distribution function for the gamma
     /// distribution
     /// at `x`
@@ -105,15 +105,15 @@ impl ContinuousCDF<f64, f64> for Gamma {
     ///
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `γ` is the lower incomplete gamma function
-    fn cdf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
         } else if ulps_eq!(x, self.shape) && self.rate.is_infinite() {
-            1.0
+            T::one()
         } else if self.rate.is_infinite() {
-            0.0
+            T::zero()
         } else if x.is_infinite() {
-            1.0
+            T::one()
         } else {
             gamma::gamma_lr(self.shape, x * self.rate)
         }
@@ -130,26 +130,48 @@ impl ContinuousCDF<f64, f64> for Gamma {
     ///
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `γ` is the upper incomplete gamma function
-    fn sf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
         }
         else if ulps_eq!(x, self.shape) && self.rate.is_infinite() {
-            0.0
+            T::zero()
         }
         else if self.rate.is_infinite() {
-            1.0
+            T::one()
         }
         else if x.is_infinite() {
-            0.0
+            T::zero()
         }
         else {
             gamma::gamma_ur(self.shape, x * self.rate)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// gamma distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// P^-1(α, p) / β
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate and `P^-1` is the inverse of the
+    /// lower regularized incomplete gamma function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        gamma::inv_gamma_lr(self.shape, p) / self.rate
+    }
 }
 
-impl Min<f64> for Gamma {
+impl<T: SpecialFloat> Min<T> for Gamma<T> {
     /// Returns the minimum value in the domain of the
     /// gamma distribution representable by a double precision
     /// float
@@ -159,12 +181,12 @@ impl Min<f64> for Gamma {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for Gamma {
+impl<T: SpecialFloat> Max<T> for Gamma<T> {
     /// Returns the maximum value in the domain of the
     /// gamma distribution representable by a double precision
     /// float
@@ -174,12 +196,12 @@ impl Max<f64> for Gamma {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        INF
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Gamma {
+impl<T: SpecialFloat> Distribution<T> for Gamma<T> {
     /// Returns the mean of the gamma distribution
     ///
     /// # Formula
@@ -189,7 +211,7 @@ impl Distribution<f64> for Gamma {
     /// ```
     ///
     /// where `α` is the shape and `β` is the rate
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         Some(self.shape / self.rate)
     }
     /// Returns the variance of the gamma distribution
@@ -201,7 +223,7 @@ impl Distribution<f64> for Gamma {
     /// ```
     ///
     /// where `α` is the shape and `β` is the rate
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         Some(self.shape / (self.rate * self.rate))
     }
     /// Returns the entropy of the gamma distribution
@@ -214,10 +236,10 @@ impl Distribution<f64> for Gamma {
     ///
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         let entr = self.shape - self.rate.ln()
             + gamma::ln_gamma(self.shape)
-            + (1.0 - self.shape) * gamma::digamma(self.shape);
+            + (T::one() - self.shape) * gamma::digamma(self.shape);
         Some(entr)
     }
     /// Returns the skewness of the gamma distribution
@@ -229,12 +251,12 @@ impl Distribution<f64> for Gamma {
     /// ```
     ///
     /// where `α` is the shape
-    fn skewness(&self) -> Option<f64> {
-        Some(2.0 / self.shape.sqrt())
+    fn skewness(&self) -> Option<T> {
+        Some(cast::<T>(2.0) / self.shape.sqrt())
     }
 }
 
-impl Mode<Option<f64>> for Gamma {
+impl<T: SpecialFloat> Mode<Option<T>> for Gamma<T> {
     /// Returns the mode for the gamma distribution
     ///
     /// # Formula
@@ -244,12 +266,12 @@ impl Mode<Option<f64>> for Gamma {
     /// ```
     ///
     /// where `α` is the shape and `β` is the rate
-    fn mode(&self) -> Option<f64> {
-        Some((self.shape - 1.0) / self.rate)
+    fn mode(&self) -> Option<T> {
+        Some((self.shape - T::one()) / self.rate)
     }
 }
 
-impl Continuous<f64, f64> for Gamma {
+impl<T: SpecialFloat> Continuous<T, T> for Gamma<T> {
     /// Calculates the probability density function for the gamma distribution
     /// at `x`
     ///
@@ -265,17 +287,17 @@ impl Continuous<f64, f64> for Gamma {
     /// ```
     ///
     /// where `α` is the shape, `β` is the rate, and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
-        } else if ulps_eq!(self.shape, 1.0) {
+    fn pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
+        } else if ulps_eq!(self.shape, T::one()) {
             self.rate * (-self.rate * x).exp()
-        } else if self.shape > 160.0 {
+        } else if self.shape > internal::gamma_overflow_threshold(160.0, 30.0) {
             self.ln_pdf(x).exp()
         } else if x.is_infinite() {
-            0.0
+            T::zero()
         } else {
-            self.rate.powf(self.shape) * x.powf(self.shape - 1.0) * (-self.rate * x).exp()
+            self.rate.powf(self.shape) * x.powf(self.shape - T::one()) * (-self.rate * x).exp()
                 / gamma::gamma(self.shape)
         }
     }
@@ -296,20 +318,111 @@ impl Continuous<f64, f64> for Gamma {
     /// ```
     ///
     /// where `α` is the shape, `β` is the rate, and `Γ` is the gamma function
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            f64::NEG_INFINITY
-        } else if ulps_eq!(self.shape, 1.0) {
+    fn ln_pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::neg_infinity()
+        } else if ulps_eq!(self.shape, T::one()) {
             self.rate.ln() - self.rate * x
         } else if x.is_infinite() {
-            f64::NEG_INFINITY
+            T::neg_infinity()
         } else {
-            self.shape * self.rate.ln() + (self.shape - 1.0) * x.ln()
+            self.shape * self.rate.ln() + (self.shape - T::one()) * x.ln()
                 - self.rate * x
                 - gamma::ln_gamma(self.shape)
         }
     }
 }
+
+impl Fit<f64> for Gamma {
+    /// Returns the maximum likelihood estimate of the gamma distribution for
+    /// the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is not positive, if all
+    /// observations are equal or if the likelihood equation for the shape
+    /// fails to converge
+    ///
+    /// # Remarks
+    ///
+    /// The shape is found by Newton's method starting from the approximation
+    /// of Minka (2002), "Estimating a Gamma distribution".
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(α) - ψ(α) = ln(m) - (1 / n) * Σ ln(x_i)
+    /// β = α / m
+    /// ```
+    ///
+    /// where `m` is the mean of the `n` observations and `ψ` is the digamma
+    /// function
+    fn fit_mle(data: &[f64]) -> Result<Gamma> {
+        if data.iter().any(|&x| x.is_nan() || x <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let mean = data.mean();
+        let s = mean.ln() - data.iter().map(|x| x.ln()).mean();
+        if !s.is_finite() || s <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let guess = (3.0 - s + ((s - 3.0) * (s - 3.0) + 24.0 * s).sqrt()) / (12.0 * s);
+        let shape = internal::positive_root(
+            |a| {
+                (
+                    a.ln() - gamma::digamma(a) - s,
+                    1.0 / a - gamma::trigamma(a),
+                )
+            },
+            guess,
+        )?;
+        Gamma::new(shape, shape / mean)
+    }
+
+    /// Returns the method of moments estimate of the gamma distribution for
+    /// the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// α = m^2 / s^2
+    /// β = m / s^2
+    /// ```
+    ///
+    /// where `m` and `s^2` are the mean and the population variance of the
+    /// observations
+    fn fit_mom(data: &[f64]) -> Result<Gamma> {
+        let mean = data.mean();
+        let var = data.population_variance();
+        Gamma::new(mean * mean / var, mean / var)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(α, β)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// n * [ ψ'(α), -1 / β  ]
+    ///     [ -1 / β, α / β^2 ]
+    /// ```
+    ///
+    /// where `ψ'` is the trigamma function
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let n = data.len() as f64;
+        let cross = -n / self.rate;
+        Some(DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                n * gamma::trigamma(self.shape),
+                cross,
+                cross,
+                n * self.shape / (self.rate * self.rate),
+            ],
+        ))
+    }
+}
 /// Samples from a gamma distribution with a shape of `shape` and a
 /// rate of `rate` using `rng` as the source of randomness. Implementation from:
 /// <br />
@@ -321,31 +434,31 @@ impl Continuous<f64, f64> for Gamma {
 /// Pages 363-372
 /// </div>
 /// <br />
-pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, shape: f64, rate: f64) -> f64 {
+pub fn sample_unchecked<T: SpecialFloat, R: Rng + ?Sized>(rng: &mut R, shape: T, rate: T) -> T {
     let mut a = shape;
-    let mut afix = 1.0;
-    if shape < 1.0 {
-        a = shape + 1.0;
-        afix = rng.gen::<f64>().powf(1.0 / shape);
+    let mut afix = T::one();
+    if shape < T::one() {
+        a = shape + T::one();
+        afix = cast::<T>(rng.gen::<f64>()).powf(T::one() / shape);
     }
 
-    let d = a - 1.0 / 3.0;
-    let c = 1.0 / (9.0 * d).sqrt();
+    let d = a - T::one() / cast(3.0);
+    let c = T::one() / (cast::<T>(9.0) * d).sqrt();
     loop {
         let mut x;
         let mut v;
         loop {
-            x = super::normal::sample_unchecked(rng, 0.0, 1.0);
-            v = 1.0 + c * x;
-            if v > 0.0 {
+            x = super::normal::sample_unchecked(rng, T::zero(), T::one());
+            v = T::one() + c * x;
+            if v > T::zero() {
                 break;
             };
         }
 
         v *= v * v;
         x *= x;
-        let u: f64 = rng.gen();
-        if u < 1.0 - 0.0331 * x * x || u.ln() < 0.5 * x + d * (1.0 - v + v.ln()) {
+        let u: T = cast(rng.gen::<f64>());
+        if u < T::one() - cast::<T>(0.0331) * x * x || u.ln() < cast::<T>(0.5) * x + d * (T::one() - v + v.ln()) {
             return afix * d * v / rate;
         }
     }
@@ -354,6 +467,8 @@ pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, shape: f64, rate: f64) ->
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use super::*;
+    use crate::distribution::Fit;
+    use core::f64::INFINITY as INF;
     use crate::consts::ACC;
     use crate::distribution::internal::*;
     use crate::testing_boiler;
@@ -593,4 +708,50 @@ mod tests {
         test::check_continuous_distribution(&try_create((1.0, 0.5)), 0.0, 20.0);
         test::check_continuous_distribution(&try_create((9.0, 2.0)), 0.0, 20.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let f = |arg: f64| move |x: Gamma| x.inverse_cdf(arg);
+        let test = [
+            ((1.0, 0.1), 0.1, 1.0536051565782630155),
+            ((1.0, 0.1), 0.5, 6.9314718055994527094),
+            ((1.0, 0.1), 0.9, 23.025850929940457782),
+            ((10.0, 10.0), 0.1, 0.6221304605225032809),
+            ((10.0, 10.0), 0.5, 0.96687146147141311518),
+            ((10.0, 10.0), 0.9, 1.4205990292152817131),
+            ((10.0, 1.0), 0.1, 6.221304605225032809),
+            ((10.0, 1.0), 0.5, 9.6687146147141311518),
+            ((10.0, 1.0), 0.9, 14.205990292152817131),
+            ((0.5, 2.0), 0.1, 0.0039476935233578066576),
+            ((0.5, 2.0), 0.5, 0.11373410577989318799),
+            ((0.5, 2.0), 0.9, 0.6763858635238537303),
+        ];
+        for &(arg, p, res) in test.iter() {
+            test_case(arg, res, f(p));
+        }
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Gamma::<f32>::new(2.5, 1.5).unwrap();
+        let d64 = Gamma::new(2.5, 1.5).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 1.0, 2.0, 5.0], 1e-4);
+        let d32 = Gamma::<f32>::new(50.0, 2.0).unwrap();
+        let d64 = Gamma::new(50.0, 2.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[20.0, 25.0, 30.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+        let n = Gamma::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.shape(), 2.2256651589730201, 1e-12);
+        assert_almost_eq!(n.rate(), 1.5017983528832794, 1e-12);
+        test::check_observed_information(n.observed_information(&data), &[5.6481795567301748, -6.6586835581495709, -6.6586835581495709, 9.8681690331776641], 1e-12);
+        let n = Gamma::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.shape(), 2.2276989114691312, 1e-13);
+        assert_almost_eq!(n.rate(), 1.5031706555122343, 1e-13);
+        assert!(Gamma::fit_mle(&[2.0, 2.0]).is_err());
+        assert!(Gamma::fit_mle(&[1.0, 0.0]).is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::OpenClosed01;
@@ -116,6 +116,26 @@ impl DiscreteCDF<u64, f64> for Geometric {
             ((-self.p).ln_1p() * (x as f64)).exp()
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// geometric distribution at `p`, i.e. the smallest `x` such that `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The closed form `ceil(ln(1 - p) / ln(1 - q))`, where `q` is the
+    /// probability of success, is used as the initial guess and corrected
+    /// against `cdf` for rounding
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let guess = ((-p).ln_1p() / (-self.p).ln_1p()).ceil();
+        internal::discrete_inverse_cdf(self, p, guess)
+    }
 }
 
 impl Min<u64> for Geometric {
@@ -518,4 +538,31 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.6), 100);
         test::check_discrete_distribution(&try_create(1.0), 1);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Geometric| x.inverse_cdf(arg);
+        test_case(0.5, 1, inverse_cdf(0.01));
+        test_case(0.5, 1, inverse_cdf(0.3));
+        test_case(0.5, 4, inverse_cdf(0.9));
+        test_case(0.5, 10, inverse_cdf(0.999));
+        test_case(0.01, 1, inverse_cdf(0.01));
+        test_case(0.01, 36, inverse_cdf(0.3));
+        test_case(0.01, 69, inverse_cdf(0.5));
+        test_case(0.01, 230, inverse_cdf(0.9));
+        test_case(0.01, 688, inverse_cdf(0.999));
+        test_case(1.0, 1, inverse_cdf(0.01));
+        test_case(1.0, 1, inverse_cdf(0.3));
+        test_case(1.0, 1, inverse_cdf(0.5));
+        test_case(1.0, 1, inverse_cdf(0.9));
+        test_case(1.0, 1, inverse_cdf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        test::check_discrete_inverse_cdf(&try_create(0.5));
+        test::check_discrete_inverse_cdf(&try_create(0.01));
+        test::check_discrete_inverse_cdf(&try_create(1.0));
+        test::check_discrete_inverse_cdf(&try_create(1e-9));
+    }
 }
@@ -0,0 +1,750 @@
+use crate::distribution::{Continuous, ContinuousCDF, Uniform};
+use crate::function::gamma::ln_gamma;
+use crate::generate;
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+
+/// Enumeration of the rules for the number of equal-width bins spanning the
+/// range of a sample, computed as numpy's `histogram_bin_edges`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BinRule {
+    /// Sturges' rule `ceil(log2(n)) + 1`, suited to small, roughly normal
+    /// samples
+    Sturges,
+    /// Scott's rule, the bin width `(24 * sqrt(π) / n)^(1 / 3) * σ` with the
+    /// population standard deviation `σ`
+    Scott,
+    /// The Freedman-Diaconis rule, the bin width `2 * IQR * n^(-1 / 3)`
+    FreedmanDiaconis,
+    /// Doane's rule, which adds `log2(1 + |g_1| / σ_g1)` bins to Sturges' for
+    /// the skewness `g_1` of the sample
+    Doane,
+    /// The number of bins maximizing the posterior probability of Knuth
+    /// (2006) for a piecewise constant density, searched up to `n` or 1000
+    /// bins, whichever is fewer
+    Knuth,
+}
+
+impl BinRule {
+    /// Returns the number of bins the rule chooses for `data`
+    ///
+    /// # Remarks
+    ///
+    /// For the rules defined by a bin width, the number of bins is the range
+    /// of the data divided by the width, rounded up, and `1` if the width is
+    /// zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` has fewer than two entries, or three for
+    /// `Doane`, or an entry is not finite
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::BinRule;
+    ///
+    /// let data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+    /// assert_eq!(BinRule::Sturges.bins(&data).unwrap(), 8);
+    /// assert_eq!(BinRule::FreedmanDiaconis.bins(&data).unwrap(), 5);
+    /// ```
+    pub fn bins(self, data: &[f64]) -> Result<usize> {
+        let min_len = if let BinRule::Doane = self { 3 } else { 2 };
+        if data.len() < min_len || data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        let n = data.len() as f64;
+        let range = data.max() - data.min();
+        let bins_of_width = |width: f64| {
+            if width > 0.0 {
+                (range / width).ceil().max(1.0) as usize
+            } else {
+                1
+            }
+        };
+        let bins = match self {
+            BinRule::Sturges => n.log2().ceil() as usize + 1,
+            BinRule::Scott => bins_of_width(
+                (24.0 * std::f64::consts::PI.sqrt() / n).cbrt() * data.population_std_dev(),
+            ),
+            BinRule::FreedmanDiaconis => {
+                let iqr =
+                    Data::new(data.to_vec()).interquartile_range_with(QuantileDefinition::Linear);
+                bins_of_width(2.0 * iqr * n.cbrt().recip())
+            }
+            BinRule::Doane => {
+                let std_dev = data.population_std_dev();
+                if std_dev > 0.0 {
+                    let mean = data.mean();
+                    let g1 = data.iter().map(|x| ((x - mean) / std_dev).powi(3)).mean();
+                    let sigma_g1 = (6.0 * (n - 2.0) / ((n + 1.0) * (n + 3.0))).sqrt();
+                    bins_of_width(range / (1.0 + n.log2() + (1.0 + g1.abs() / sigma_g1).log2()))
+                } else {
+                    1
+                }
+            }
+            BinRule::Knuth => knuth_bins(data),
+        };
+        Ok(bins)
+    }
+}
+
+/// The largest number of bins searched by `BinRule::Knuth`. Each number of
+/// bins `M` costs `O(M * ln(n))` to evaluate, so the search is quadratic in
+/// the cap, which keeps it fast for large samples.
+const MAX_KNUTH_BINS: usize = 1000;
+
+/// Returns the number of equal-width bins spanning the range of `data`, up
+/// to `MAX_KNUTH_BINS`, that maximizes the log posterior of Knuth (2006)
+///
+/// # Formula
+///
+/// ```ignore
+/// n * ln(M) + ln(Γ(M / 2)) - M * ln(Γ(1 / 2)) - ln(Γ(n + M / 2)) + Σ ln(Γ(n_k + 1 / 2))
+/// ```
+///
+/// where `M` is the number of bins and `n_k` the count of the `k`th bin
+fn knuth_bins(data: &[f64]) -> usize {
+    let mut sorted = data.to_vec();
+    sorted.sort_unstable_by(|a, b| a.total_cmp(b));
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    if min == max {
+        return 1;
+    }
+    let n = sorted.len() as f64;
+    let ln_gamma_half = ln_gamma(0.5);
+    let mut best = (f64::NEG_INFINITY, 1);
+    for bins in 1..=sorted.len().min(MAX_KNUTH_BINS) {
+        let m = bins as f64;
+        let mut ln_posterior =
+            n * m.ln() + ln_gamma(m / 2.0) - m * ln_gamma_half - ln_gamma(n + m / 2.0);
+        let edges = uniform_edges(min, max, bins);
+        let mut below = 0;
+        for upper in &edges[1..bins] {
+            let count = sorted.partition_point(|x| x < upper);
+            ln_posterior += ln_gamma((count - below) as f64 + 0.5);
+            below = count;
+        }
+        ln_posterior += ln_gamma((sorted.len() - below) as f64 + 0.5);
+        if ln_posterior > best.0 {
+            best = (ln_posterior, bins);
+        }
+    }
+    best.1
+}
+
+/// Returns `bins + 1` equally spaced edges from `min` to `max`
+fn uniform_edges(min: f64, max: f64, bins: usize) -> Vec<f64> {
+    let width = (max - min) / bins as f64;
+    let mut edges: Vec<f64> = (0..=bins).map(|i| min + i as f64 * width).collect();
+    edges[bins] = max;
+    edges
+}
+
+/// Implements a histogram, the weighted counts of observations in bins
+/// given by their edges, which also serves as the piecewise constant
+/// distribution with the density of each bin proportional to its count
+///
+/// # Remarks
+///
+/// Each bin includes its lower edge, and the last bin also its upper edge.
+/// Observations outside the edges are counted as underflow or overflow and
+/// are not part of the distribution, while `NaN` observations are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Continuous, ContinuousCDF, Histogram};
+///
+/// let mut histogram = Histogram::new(0.0, 4.0, 4).unwrap();
+/// histogram.extend(vec![0.5, 1.5, 1.7, 3.0, 5.0]);
+/// assert_eq!(histogram.counts(), [1.0, 2.0, 0.0, 1.0]);
+/// assert_eq!(histogram.overflow(), 1.0);
+/// assert_eq!(histogram.pdf(1.2), 0.5);
+/// assert_eq!(histogram.cdf(1.5), 0.5);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    edges: Vec<f64>,
+    counts: Vec<f64>,
+    underflow: f64,
+    overflow: f64,
+}
+
+impl Histogram {
+    /// Constructs a new empty histogram with `bins` equal-width bins from
+    /// `min` to `max`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bins` is zero or `min` is not less than `max`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let histogram = Histogram::new(0.0, 1.0, 4).unwrap();
+    /// assert_eq!(histogram.edges(), [0.0, 0.25, 0.5, 0.75, 1.0]);
+    /// assert!(Histogram::new(0.0, 1.0, 0).is_err());
+    /// assert!(Histogram::new(1.0, 0.0, 4).is_err());
+    /// ```
+    pub fn new(min: f64, max: f64, bins: usize) -> Result<Histogram> {
+        if bins == 0 {
+            return Err(StatsError::ArgMustBePositive("bins"));
+        }
+        if min >= max || !min.is_finite() || !max.is_finite() {
+            return Err(StatsError::ArgLtArg("min", "max"));
+        }
+        Histogram::with_edges(uniform_edges(min, max, bins))
+    }
+
+    /// Constructs a new empty histogram with `bins` bins whose edges are
+    /// equally spaced on a log scale from `10^start_exp` to `10^stop_exp`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bins` is zero or `start_exp` is not less than
+    /// `stop_exp`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let histogram = Histogram::log_spaced(0.0, 3.0, 3).unwrap();
+    /// assert_eq!(histogram.edges(), [1.0, 10.0, 100.0, 1000.0]);
+    /// ```
+    pub fn log_spaced(start_exp: f64, stop_exp: f64, bins: usize) -> Result<Histogram> {
+        if bins == 0 {
+            return Err(StatsError::ArgMustBePositive("bins"));
+        }
+        if start_exp >= stop_exp || !start_exp.is_finite() || !stop_exp.is_finite() {
+            return Err(StatsError::ArgLtArg("start_exp", "stop_exp"));
+        }
+        Histogram::with_edges(generate::log_spaced(bins + 1, start_exp, stop_exp))
+    }
+
+    /// Constructs a new empty histogram with the bin `edges`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two edges, or they are not
+    /// finite and strictly increasing
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// assert!(Histogram::with_edges(vec![0.0, 1.0, 5.0]).is_ok());
+    /// assert!(Histogram::with_edges(vec![0.0, 1.0, 1.0]).is_err());
+    /// assert!(Histogram::with_edges(vec![0.0]).is_err());
+    /// ```
+    pub fn with_edges(edges: Vec<f64>) -> Result<Histogram> {
+        if edges.len() < 2
+            || edges.iter().any(|x| !x.is_finite())
+            || edges.windows(2).any(|w| w[0] >= w[1])
+        {
+            return Err(StatsError::BadParams);
+        }
+        Ok(Histogram {
+            counts: vec![0.0; edges.len() - 1],
+            edges,
+            underflow: 0.0,
+            overflow: 0.0,
+        })
+    }
+
+    /// Constructs the histogram of `data` with equal-width bins spanning
+    /// its range, as many as the `rule` chooses
+    ///
+    /// # Remarks
+    ///
+    /// If all entries are equal, the single bin extends `0.5` on either side
+    /// of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `rule` cannot be applied to `data`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{BinRule, Histogram};
+    ///
+    /// let data: Vec<f64> = (0..100).map(|x| x as f64).collect();
+    /// let histogram = Histogram::from_data(&data, BinRule::Sturges).unwrap();
+    /// assert_eq!(histogram.bins(), 8);
+    /// assert_eq!(histogram.total(), 100.0);
+    /// ```
+    pub fn from_data(data: &[f64], rule: BinRule) -> Result<Histogram> {
+        let bins = rule.bins(data)?;
+        let (min, max) = (data.min(), data.max());
+        let mut histogram = if min == max {
+            Histogram::new(min - 0.5, max + 0.5, bins)?
+        } else {
+            Histogram::new(min, max, bins)?
+        };
+        histogram.extend(data.iter().cloned());
+        Ok(histogram)
+    }
+
+    /// Adds the observation `x` with unit weight
+    pub fn add(&mut self, x: f64) {
+        self.fill(x, 1.0);
+    }
+
+    /// Adds the observation `x` with the weight `weight`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weight` is negative, infinite or `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let mut histogram = Histogram::new(0.0, 2.0, 2).unwrap();
+    /// histogram.add_weighted(0.5, 2.5).unwrap();
+    /// histogram.add_weighted(-1.0, 0.5).unwrap();
+    /// assert_eq!(histogram.counts(), [2.5, 0.0]);
+    /// assert_eq!(histogram.underflow(), 0.5);
+    /// assert!(histogram.add_weighted(0.5, -1.0).is_err());
+    /// ```
+    pub fn add_weighted(&mut self, x: f64, weight: f64) -> Result<()> {
+        if !(weight >= 0.0 && weight.is_finite()) {
+            return Err(StatsError::ArgNotNegative("weight"));
+        }
+        self.fill(x, weight);
+        Ok(())
+    }
+
+    fn fill(&mut self, x: f64, weight: f64) {
+        if x.is_nan() {
+            return;
+        }
+        match self.bin_index(x) {
+            Some(i) => self.counts[i] += weight,
+            None if x < self.edges[0] => self.underflow += weight,
+            None => self.overflow += weight,
+        }
+    }
+
+    /// Returns the index of the bin containing `x`, or `None` if it is
+    /// outside the edges or `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let histogram = Histogram::new(0.0, 2.0, 2).unwrap();
+    /// assert_eq!(histogram.bin_index(1.0), Some(1));
+    /// assert_eq!(histogram.bin_index(2.0), Some(1));
+    /// assert_eq!(histogram.bin_index(2.5), None);
+    /// ```
+    pub fn bin_index(&self, x: f64) -> Option<usize> {
+        let last = self.edges.len() - 1;
+        if x.is_nan() || x < self.edges[0] || x > self.edges[last] {
+            return None;
+        }
+        Some((self.edges.partition_point(|&e| e <= x) - 1).min(last - 1))
+    }
+
+    /// Returns the number of bins
+    pub fn bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns the bin edges
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Returns the weighted counts of the bins
+    pub fn counts(&self) -> &[f64] {
+        &self.counts
+    }
+
+    /// Returns the total weight of the observations below the first edge
+    pub fn underflow(&self) -> f64 {
+        self.underflow
+    }
+
+    /// Returns the total weight of the observations above the last edge
+    pub fn overflow(&self) -> f64 {
+        self.overflow
+    }
+
+    /// Returns the total weight of the observations within the edges
+    pub fn total(&self) -> f64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the densities of the bins, the counts divided by the total
+    /// and the bin widths, so that they integrate to one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let mut histogram = Histogram::with_edges(vec![0.0, 1.0, 3.0]).unwrap();
+    /// histogram.extend(vec![0.5, 1.5, 2.5, 2.0]);
+    /// assert_eq!(histogram.densities(), [0.25, 0.375]);
+    /// ```
+    pub fn densities(&self) -> Vec<f64> {
+        let total = self.total();
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(count, w)| count / (total * (w[1] - w[0])))
+            .collect()
+    }
+
+    /// Returns the cumulative counts, the total weight of each bin and those
+    /// before it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let mut histogram = Histogram::new(0.0, 3.0, 3).unwrap();
+    /// histogram.extend(vec![0.5, 2.5, 2.0, -1.0]);
+    /// assert_eq!(histogram.cumulative_counts(), [1.0, 1.0, 3.0]);
+    /// ```
+    pub fn cumulative_counts(&self) -> Vec<f64> {
+        self.counts
+            .iter()
+            .scan(0.0, |sum, count| {
+                *sum += count;
+                Some(*sum)
+            })
+            .collect()
+    }
+
+    /// Adds the counts of `other`, which must have the same edges
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the edges of the histograms differ
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Histogram;
+    ///
+    /// let mut a = Histogram::new(0.0, 2.0, 2).unwrap();
+    /// let mut b = a.clone();
+    /// a.add(0.5);
+    /// b.add(1.5);
+    /// a.merge(&b).unwrap();
+    /// assert_eq!(a.counts(), [1.0, 1.0]);
+    /// assert!(a.merge(&Histogram::new(0.0, 2.0, 4).unwrap()).is_err());
+    /// ```
+    pub fn merge(&mut self, other: &Histogram) -> Result<()> {
+        if self.edges != other.edges {
+            return Err(StatsError::BadParams);
+        }
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+        Ok(())
+    }
+}
+
+impl Extend<f64> for Histogram {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Histogram {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let uniform = Uniform::new(0.0, 1.0).unwrap();
+        self.inverse_cdf(uniform.sample(rng))
+    }
+}
+
+impl Min<f64> for Histogram {
+    /// Returns the first edge of the histogram
+    fn min(&self) -> f64 {
+        self.edges[0]
+    }
+}
+
+impl Max<f64> for Histogram {
+    /// Returns the last edge of the histogram
+    fn max(&self) -> f64 {
+        self.edges[self.edges.len() - 1]
+    }
+}
+
+impl Distribution<f64> for Histogram {
+    /// Returns the mean of the piecewise constant distribution, or `None` if
+    /// the histogram is empty
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ c_i * m_i / Σ c_i
+    /// ```
+    ///
+    /// where `c_i` is the count and `m_i` the midpoint of the `i`th bin
+    fn mean(&self) -> Option<f64> {
+        let total = self.total();
+        if total <= 0.0 {
+            return None;
+        }
+        let sum: f64 = self
+            .counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(count, w)| count * (w[0] + w[1]) / 2.0)
+            .sum();
+        Some(sum / total)
+    }
+
+    /// Returns the variance of the piecewise constant distribution, or
+    /// `None` if the histogram is empty
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ c_i * ((m_i - μ)^2 + w_i^2 / 12) / Σ c_i
+    /// ```
+    ///
+    /// where `c_i` is the count, `m_i` the midpoint and `w_i` the width of
+    /// the `i`th bin and `μ` the mean
+    fn variance(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let sum: f64 = self
+            .counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(count, w)| {
+                let (mid, width) = ((w[0] + w[1]) / 2.0, w[1] - w[0]);
+                count * ((mid - mean) * (mid - mean) + width * width / 12.0)
+            })
+            .sum();
+        Some(sum / self.total())
+    }
+}
+
+impl ContinuousCDF<f64, f64> for Histogram {
+    /// Calculates the cumulative distribution function of the piecewise
+    /// constant distribution at `x`, `NaN` if the histogram is empty or `x`
+    /// is `NaN`
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() || self.total() <= 0.0 {
+            return f64::NAN;
+        }
+        if x < self.min() {
+            return 0.0;
+        }
+        if x >= self.max() {
+            return 1.0;
+        }
+        let i = self.edges.partition_point(|&e| e <= x) - 1;
+        let (low, high) = (self.edges[i], self.edges[i + 1]);
+        let below: f64 = self.counts[..i].iter().sum();
+        (below + self.counts[i] * (x - low) / (high - low)) / self.total()
+    }
+
+    /// Calculates the survival function of the piecewise constant
+    /// distribution at `x`, `NaN` if the histogram is empty or `x` is `NaN`
+    fn sf(&self, x: f64) -> f64 {
+        if x.is_nan() || self.total() <= 0.0 {
+            return f64::NAN;
+        }
+        if x < self.min() {
+            return 1.0;
+        }
+        if x >= self.max() {
+            return 0.0;
+        }
+        let i = self.edges.partition_point(|&e| e <= x) - 1;
+        let (low, high) = (self.edges[i], self.edges[i + 1]);
+        let above: f64 = self.counts[i + 1..].iter().sum();
+        (above + self.counts[i] * (high - x) / (high - low)) / self.total()
+    }
+
+    /// Calculates the inverse cumulative distribution function of the
+    /// piecewise constant distribution at `p`, the smallest `x` with
+    /// `cdf(x) = p`
+    ///
+    /// # Panics
+    ///
+    /// If `p` is not in `[0, 1]` or the histogram is empty
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let total = self.total();
+        if total <= 0.0 {
+            panic!("histogram must not be empty");
+        }
+        let target = p * total;
+        let mut below = 0.0;
+        for (i, &count) in self.counts.iter().enumerate() {
+            if count > 0.0 && below + count >= target {
+                let (low, high) = (self.edges[i], self.edges[i + 1]);
+                let fraction = ((target - below) / count).clamp(0.0, 1.0);
+                return low + fraction * (high - low);
+            }
+            below += count;
+        }
+        self.max()
+    }
+}
+
+impl Continuous<f64, f64> for Histogram {
+    /// Calculates the probability density function of the piecewise
+    /// constant distribution at `x`, the density of its bin
+    fn pdf(&self, x: f64) -> f64 {
+        match self.bin_index(x) {
+            Some(i) => self.counts[i] / (self.total() * (self.edges[i + 1] - self.edges[i])),
+            None => 0.0,
+        }
+    }
+
+    /// Calculates the log probability density function of the piecewise
+    /// constant distribution at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const DATA: [f64; 30] = [
+        0.8, 1.2, 1.9, 2.3, 2.4, 2.9, 3.1, 3.3, 3.6, 4.0, 4.2, 4.8, 5.5, 6.1, 7.4,
+        9.8, 12.5, 2.2, 3.9, 1.5, 2.7, 3.0, 5.1, 4.4, 6.8, 3.4, 2.0, 1.1, 8.9, 15.2,
+    ];
+
+    #[test]
+    fn test_bin_rules() {
+        assert_eq!(BinRule::Sturges.bins(&DATA).unwrap(), 6);
+        assert_eq!(BinRule::Scott.bins(&DATA).unwrap(), 4);
+        assert_eq!(BinRule::FreedmanDiaconis.bins(&DATA).unwrap(), 8);
+        assert_eq!(BinRule::Doane.bins(&DATA).unwrap(), 9);
+        assert_eq!(BinRule::Knuth.bins(&DATA).unwrap(), 3);
+        assert_eq!(BinRule::FreedmanDiaconis.bins(&[1.0, 1.0, 1.0, 1.0, 5.0]).unwrap(), 1);
+        assert_eq!(BinRule::Knuth.bins(&[2.0, 2.0]).unwrap(), 1);
+        assert!(BinRule::Sturges.bins(&[1.0]).is_err());
+        assert!(BinRule::Doane.bins(&[1.0, 2.0]).is_err());
+        assert!(BinRule::Scott.bins(&[1.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_knuth_large_sample() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let data: Vec<f64> = (0..50_000).map(|_| rand::distributions::Distribution::sample(&normal, &mut rng)).collect();
+        let bins = BinRule::Knuth.bins(&data).unwrap();
+        assert!(bins > 20 && bins < 80);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut histogram = Histogram::new(0.0, 16.0, 4).unwrap();
+        histogram.extend(DATA.iter().cloned());
+        histogram.add(f64::NAN);
+        histogram.add(-0.1);
+        histogram.add(16.0);
+        histogram.add_weighted(20.0, 0.5).unwrap();
+        assert_eq!(histogram.counts(), [17.0, 9.0, 2.0, 3.0]);
+        assert_eq!(histogram.underflow(), 1.0);
+        assert_eq!(histogram.overflow(), 0.5);
+        assert_eq!(histogram.total(), 31.0);
+        assert_eq!(histogram.cumulative_counts(), [17.0, 26.0, 28.0, 31.0]);
+        let densities = histogram.densities();
+        assert_almost_eq!(densities.iter().map(|d| d * 4.0).sum::<f64>(), 1.0, 1e-15);
+        assert!(histogram.add_weighted(1.0, f64::NAN).is_err());
+
+        let constant = Histogram::from_data(&[3.0, 3.0, 3.0], BinRule::Sturges).unwrap();
+        assert_eq!(constant.edges(), [2.5, 2.5 + 1.0 / 3.0, 2.5 + 2.0 * (1.0 / 3.0), 3.5]);
+        assert_eq!(constant.counts(), [0.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_log_spaced() {
+        let mut histogram = Histogram::log_spaced(-1.0, 2.0, 3).unwrap();
+        assert_eq!(histogram.edges(), [0.1, 1.0, 10.0, 100.0]);
+        histogram.extend(DATA.iter().cloned());
+        assert_eq!(histogram.counts(), [1.0, 27.0, 2.0]);
+        assert!(Histogram::log_spaced(2.0, 1.0, 3).is_err());
+        assert!(Histogram::log_spaced(1.0, 2.0, 0).is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = Histogram::from_data(&DATA[..15], BinRule::Sturges).unwrap();
+        let mut b = Histogram::with_edges(a.edges().to_vec()).unwrap();
+        b.extend(DATA[15..].iter().cloned());
+        let mut all = Histogram::with_edges(a.edges().to_vec()).unwrap();
+        all.extend(DATA.iter().cloned());
+        a.merge(&b).unwrap();
+        assert_eq!(a, all);
+    }
+
+    #[test]
+    fn test_distribution() {
+        let mut histogram = Histogram::with_edges(vec![0.0, 1.0, 3.0, 4.0]).unwrap();
+        histogram.extend(vec![0.5, 1.5, 2.5, 2.0]);
+        assert_eq!(histogram.pdf(-1.0), 0.0);
+        assert_eq!(histogram.pdf(0.5), 0.25);
+        assert_eq!(histogram.pdf(2.0), 0.375);
+        assert_eq!(histogram.pdf(3.5), 0.0);
+        assert_eq!(histogram.ln_pdf(3.5), f64::NEG_INFINITY);
+        assert_eq!(histogram.cdf(-1.0), 0.0);
+        assert_eq!(histogram.cdf(0.5), 0.125);
+        assert_eq!(histogram.cdf(2.0), 0.625);
+        assert_eq!(histogram.cdf(3.5), 1.0);
+        assert_eq!(histogram.sf(2.0), 0.375);
+        assert_eq!(histogram.sf(0.5), 0.875);
+        assert!(histogram.cdf(f64::NAN).is_nan());
+        assert!(histogram.sf(f64::NAN).is_nan());
+        assert_eq!(histogram.inverse_cdf(0.0), 0.0);
+        assert_eq!(histogram.inverse_cdf(0.125), 0.5);
+        assert_eq!(histogram.inverse_cdf(0.625), 2.0);
+        assert_eq!(histogram.inverse_cdf(1.0), 3.0);
+        assert_eq!(histogram.min(), 0.0);
+        assert_eq!(histogram.max(), 4.0);
+        assert_eq!(histogram.mean().unwrap(), 1.625);
+        assert_almost_eq!(histogram.variance().unwrap(), 0.25 * (1.125f64.powi(2) + 1.0 / 12.0) + 0.75 * (0.375f64.powi(2) + 4.0 / 12.0), 1e-15);
+        let empty = Histogram::new(0.0, 1.0, 2).unwrap();
+        assert!(empty.mean().is_none());
+        for &x in &[-1.0, 0.5, 1.0, 2.0] {
+            assert!(empty.cdf(x).is_nan());
+            assert!(empty.sf(x).is_nan());
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let histogram = Histogram::from_data(&DATA, BinRule::FreedmanDiaconis).unwrap();
+        let mut rng = StdRng::seed_from_u64(11);
+        let n = 20_000;
+        let samples: Vec<f64> = (0..n).map(|_| rand::distributions::Distribution::sample(&histogram, &mut rng)).collect();
+        assert!(samples.iter().all(|&x| x >= histogram.min() && x <= histogram.max()));
+        for &x in &[2.0, 4.0, 8.0] {
+            let below = samples.iter().filter(|&&s| s <= x).count() as f64 / n as f64;
+            assert!((below - histogram.cdf(x)).abs() < 0.015);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF};
 use crate::function::factorial;
 use crate::statistics::*;
 use crate::{Result, StatsError};
@@ -201,6 +201,31 @@ impl DiscreteCDF<u64, f64> for Hypergeometric {
             })
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// hypergeometric distribution at `p`, i.e. the smallest `x` such that `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The Cornish-Fisher expansion of the quantile is used as the initial
+    /// guess of a local search over `[max(0, n + K - N), min(K, n)]`
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let mean = self.mean().unwrap_or(0.0);
+        let guess = internal::cornish_fisher_quantile(
+            p,
+            mean,
+            self.std_dev().unwrap_or(0.0),
+            self.skewness().unwrap_or(0.0),
+        );
+        internal::discrete_inverse_cdf(self, p, guess)
+    }
 }
 
 impl Min<u64> for Hypergeometric {
@@ -589,4 +614,32 @@ mod tests {
         test::check_discrete_distribution(&try_create(5, 4, 3), 4);
         test::check_discrete_distribution(&try_create(3, 2, 1), 2);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Hypergeometric| x.inverse_cdf(arg);
+        test_case(10, 5, 3, 0, inverse_cdf(0.01));
+        test_case(10, 5, 3, 1, inverse_cdf(0.3));
+        test_case(10, 5, 3, 2, inverse_cdf(0.9));
+        test_case(10, 5, 3, 3, inverse_cdf(0.999));
+        test_case(100, 30, 20, 2, inverse_cdf(0.01));
+        test_case(100, 30, 20, 5, inverse_cdf(0.3));
+        test_case(100, 30, 20, 6, inverse_cdf(0.5));
+        test_case(100, 30, 20, 8, inverse_cdf(0.9));
+        test_case(100, 30, 20, 12, inverse_cdf(0.999));
+        test_case(50, 45, 40, 35, inverse_cdf(0.01));
+        test_case(50, 45, 40, 35, inverse_cdf(0.3));
+        test_case(50, 45, 40, 36, inverse_cdf(0.5));
+        test_case(50, 45, 40, 37, inverse_cdf(0.9));
+        test_case(50, 45, 40, 39, inverse_cdf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        test::check_discrete_inverse_cdf(&try_create(10, 5, 3));
+        test::check_discrete_inverse_cdf(&try_create(100, 30, 20));
+        test::check_discrete_inverse_cdf(&try_create(50, 45, 40));
+        test::check_discrete_inverse_cdf(&try_create(2, 2, 2));
+        test::check_discrete_inverse_cdf(&try_create(0, 0, 0));
+    }
 }
@@ -1,3 +1,8 @@
+use crate::distribution::{Continuous, ContinuousCDF, DiscreteCDF};
+use crate::function::{erf, float::cast, SpecialFloat};
+use crate::{Result, StatsError};
+use std::f64;
+
 /// Returns true if there are no elements in `x` in `arr`
 /// such that `x <= 0.0` or `x` is `f64::NAN` and `sum(arr) > 0.0`.
 /// IF `incl_zero` is true, it tests for `x < 0.0` instead of `x <= 0.0`
@@ -12,12 +17,442 @@ pub fn is_valid_multinomial(arr: &[f64], incl_zero: bool) -> bool {
     sum != 0.0
 }
 
+/// Returns the Cornish-Fisher approximation of the `p` quantile of a
+/// distribution with the given mean, standard deviation and skewness, i.e.
+/// the normal quantile corrected for the skewness of the distribution.
+/// Falls back to the mean if the correction is not finite.
+pub fn cornish_fisher_quantile(p: f64, mean: f64, std_dev: f64, skewness: f64) -> f64 {
+    let z = -f64::consts::SQRT_2 * erf::erfc_inv(2.0 * p);
+    let x = mean + std_dev * (z + skewness * (z * z - 1.0) / 6.0);
+    if x.is_finite() {
+        x
+    } else {
+        mean
+    }
+}
+
+/// Returns the parameter value beyond which a density switches to its log
+/// space evaluation to avoid overflowing the gamma function, i.e.
+/// `f64_threshold` for `f64` and the (much smaller) `f32_threshold` for `f32`.
+pub fn gamma_overflow_threshold<T: SpecialFloat>(f64_threshold: f64, f32_threshold: f64) -> T {
+    if T::SINGLE_PRECISION {
+        cast(f32_threshold)
+    } else {
+        cast(f64_threshold)
+    }
+}
+
+/// Returns the root on `(0, INF)` of the function `f`, which returns the
+/// value and the derivative of the function at `x` and must change sign
+/// exactly once on `(0, INF)`. Starting from the guess `x0` a bracket is
+/// found by repeatedly halving and doubling, which is then refined with
+/// Newton steps that fall back to bisection whenever they would leave the
+/// bracket. This is used to solve the one dimensional likelihood equations
+/// of the distributions' `Fit` implementations.
+///
+/// # Errors
+///
+/// Returns `StatsError::ComputationFailedToConverge` if no sign change is
+/// found, `f` is not finite along the way or the iteration limit is reached
+pub fn positive_root<F: Fn(f64) -> (f64, f64)>(f: F, x0: f64) -> Result<f64> {
+    let (f0, _) = f(x0);
+    if f0 == 0.0 {
+        return Ok(x0);
+    }
+    if !f0.is_finite() {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+
+    let (mut lo, mut hi) = (x0, x0);
+    let (mut down, mut up) = (true, true);
+    let (mut a, mut b) = loop {
+        if down {
+            let x = lo / 2.0;
+            let (fx, _) = f(x);
+            if fx.is_nan() || x <= f64::MIN_POSITIVE {
+                down = false;
+            } else if fx.signum() != f0.signum() {
+                break (x, lo);
+            }
+            lo = x;
+        }
+        if up {
+            let x = hi * 2.0;
+            let (fx, _) = f(x);
+            if fx.is_nan() || x >= f64::MAX / 2.0 {
+                up = false;
+            } else if fx.signum() != f0.signum() {
+                break (hi, x);
+            }
+            hi = x;
+        }
+        if !down && !up {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    };
+
+    // invariant: the root lies in [a, b] and f(a) has the sign `sign_a`
+    let sign_a = f(a).0.signum();
+    let mut x = if x0 > a && x0 < b { x0 } else { (a + b) / 2.0 };
+    for _ in 0..500 {
+        let (fx, dfx) = f(x);
+        if fx == 0.0 {
+            return Ok(x);
+        }
+        if !fx.is_finite() {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+        if fx.signum() == sign_a {
+            a = x;
+        } else {
+            b = x;
+        }
+        let newton = x - fx / dfx;
+        let next = if newton > a && newton < b {
+            newton
+        } else if b / a > 4.0 {
+            (a * b).sqrt()
+        } else {
+            (a + b) / 2.0
+        };
+        if (next - x).abs() <= 4.0 * f64::EPSILON * x || b - a <= 4.0 * f64::EPSILON * a {
+            return Ok(next);
+        }
+        x = next;
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Returns the root of the continuous function `f` in `[a, b]`, where `f(a)`
+/// and `f(b)` must have opposite signs, computed with Brent's method to
+/// full precision relative to the root
+///
+/// # Errors
+///
+/// Returns `StatsError::ComputationFailedToConverge` if `f` does not change
+/// sign on `[a, b]`, is `NaN` at an endpoint, or the iteration limit is
+/// reached
+pub fn find_root<F: Fn(f64) -> f64>(f: F, a: f64, b: f64) -> Result<f64> {
+    let (mut a, mut b) = (a, b);
+    let (mut fa, mut fb) = (f(a), f(b));
+    if fa == 0.0 {
+        return Ok(a);
+    }
+    if fb == 0.0 {
+        return Ok(b);
+    }
+    if fa.is_nan() || fb.is_nan() || fa.signum() == fb.signum() {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let (mut c, mut fc) = (a, fa);
+    let (mut d, mut e) = (b - a, b - a);
+    for _ in 0..1000 {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol = 2.0 * f64::EPSILON * b.abs() + f64::MIN_POSITIVE;
+        let m = (c - b) / 2.0;
+        if m.abs() <= tol || fb == 0.0 {
+            return Ok(b);
+        }
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // inverse quadratic interpolation, or the secant method if only
+            // two distinct points are available
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+        if fb.is_nan() {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// The maximum number of terms summed on each side of the largest weight by
+/// `outward_series`
+const MAX_SERIES_TERMS: usize = 1_000_000;
+
+/// Returns the logarithm of the largest weight and the sum divided by it of
+/// the series `Σ_(j >= 0) w_j * t_j` with positive weights satisfying
+/// `w_(j + 1) = w_j * ratio(j)` for a decreasing `ratio`, so that the
+/// weights are unimodal, and terms satisfying `|t_j| <= 1`. Scaling by the
+/// largest weight keeps the sum representable where the weights underflow.
+///
+/// Following Benton and Krishnamoorthy (2003) the series is summed outwards
+/// from the largest weight, whose logarithm `ln_weight` is evaluated only
+/// there, with the weights updated by `ratio`. Each direction is truncated
+/// once the geometric bound on its remaining weights is negligible relative
+/// to the sum. This evaluates the Poisson mixtures of the noncentral
+/// distributions accurately for any noncentrality with `O(sqrt(λ))` terms.
+pub fn outward_series<T, L, R, F>(ln_weight: L, ratio: R, term: F) -> (T, T)
+where
+    T: SpecialFloat,
+    L: Fn(T) -> T,
+    R: Fn(T) -> T,
+    F: Fn(T) -> T,
+{
+    // the largest weight is at the first j with ratio(j) <= 1
+    let peak = if ratio(T::zero()) <= T::one() {
+        T::zero()
+    } else {
+        let (mut lo, mut hi) = (T::zero(), T::one());
+        while ratio(hi) > T::one() {
+            lo = hi;
+            hi = hi + hi;
+        }
+        while hi - lo > T::one() {
+            let mid = ((lo + hi) / cast(2.0)).floor();
+            if ratio(mid) > T::one() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        hi
+    };
+
+    let eps = T::epsilon();
+    let mut sum = term(peak);
+    let (mut j, mut w) = (peak, T::one());
+    for _ in 0..MAX_SERIES_TERMS {
+        // the remaining weights are bounded by w * (r + r^2 + ...)
+        let r = ratio(j);
+        if w * r <= eps * sum.abs() * (T::one() - r) || w * r < T::min_positive_value() {
+            break;
+        }
+        w *= r;
+        j += T::one();
+        sum += w * term(j);
+    }
+    let (mut j, mut w) = (peak, T::one());
+    for _ in 0..MAX_SERIES_TERMS {
+        if j <= T::zero() {
+            break;
+        }
+        let r = T::one() / ratio(j - T::one());
+        if w * r <= eps * sum.abs() * (T::one() - r) || w * r < T::min_positive_value() {
+            break;
+        }
+        w *= r;
+        j -= T::one();
+        sum += w * term(j);
+    }
+    (ln_weight(peak), sum)
+}
+
+/// Returns the `p` quantile of a continuous distribution, starting from the
+/// initial guess `guess`. A bracket is found by expanding outwards from the
+/// guess, geometrically towards a finite bound of the support, and refined
+/// with Newton steps on `cdf(x) - p`, or `(1 - p) - sf(x)` for `p > 0.5`,
+/// that fall back to bisection whenever they would leave the bracket.
+///
+/// By convention `p == 0` maps to `min` and `p == 1` maps to `max`.
+///
+/// # Panics
+///
+/// If `p` is not in `[0, 1]`
+pub fn newton_inverse_cdf<T, D>(dist: &D, p: T, guess: T) -> T
+where
+    T: SpecialFloat,
+    D: ContinuousCDF<T, T> + Continuous<T, T>,
+{
+    assert!(p >= T::zero() && p <= T::one(), "p must be in [0, 1]");
+    let (min, max) = (dist.min(), dist.max());
+    if p == T::zero() {
+        return min;
+    }
+    if p == T::one() {
+        return max;
+    }
+    let upper = p > cast(0.5);
+    let q = T::one() - p;
+    // increasing in x with the quantile as its root
+    let f = |x: T| {
+        if upper {
+            q - dist.sf(x)
+        } else {
+            dist.cdf(x) - p
+        }
+    };
+
+    let mut x = if guess > min && guess < max {
+        guess
+    } else if min.is_finite() && max.is_finite() {
+        (min + max) / cast(2.0)
+    } else if min.is_finite() {
+        min + T::one()
+    } else if max.is_finite() {
+        max - T::one()
+    } else {
+        T::zero()
+    };
+    let (mut lo, mut hi) = (min, max);
+    let mut step = x.abs().max(T::one());
+    let below = f(x) < T::zero();
+    for _ in 0..2000 {
+        let next = match (below, min.is_finite(), max.is_finite()) {
+            (true, _, true) => x + (max - x) / cast(2.0),
+            (true, true, false) => min + (x - min) * cast(2.0),
+            (true, false, false) => x + step,
+            (false, true, _) => min + (x - min) / cast(2.0),
+            (false, false, true) => max - (max - x) * cast(2.0),
+            (false, false, false) => x - step,
+        };
+        step *= cast(2.0);
+        if below {
+            lo = x;
+            if next >= max || f(next) >= T::zero() {
+                hi = next.min(max);
+                break;
+            }
+        } else {
+            hi = x;
+            if next <= min || f(next) < T::zero() {
+                lo = next.max(min);
+                break;
+            }
+        }
+        x = next;
+    }
+
+    let tol = cast::<T>(4.0) * T::epsilon();
+    let mut x = (lo + hi) / cast(2.0);
+    for _ in 0..200 {
+        let fx = f(x);
+        if fx == T::zero() {
+            return x;
+        }
+        if fx < T::zero() {
+            lo = x;
+        } else {
+            hi = x;
+        }
+        let newton = x - fx / dist.pdf(x);
+        let next = if newton > lo && newton < hi {
+            newton
+        } else {
+            (lo + hi) / cast(2.0)
+        };
+        if (next - x).abs() <= tol * x.abs() || hi - lo <= tol * lo.abs().max(hi.abs()) {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
+
+/// Returns `inf { x | F(x) >= p }` for a discrete distribution on `u64` by
+/// searching the support outwards from the initial guess `x0` and bisecting
+/// the bracket found. The result is exact with respect to `cdf` regardless
+/// of the guess, which only affects the number of `cdf` evaluations.
+///
+/// By convention `p == 0` maps to `min` and `p == 1` maps to `max`.
+pub fn discrete_inverse_cdf<D: DiscreteCDF<u64, f64>>(dist: &D, p: f64, x0: f64) -> u64 {
+    let min = dist.min();
+    let max = dist.max();
+    if p <= 0.0 {
+        return min;
+    }
+    if p >= 1.0 {
+        return max;
+    }
+
+    // `as` saturates, and maps NaN to zero
+    let x = (x0.round() as u64).clamp(min, max);
+    let (mut low, mut high) = if dist.cdf(x) >= p {
+        let mut high = x;
+        let mut step = 1u64;
+        let low = loop {
+            if high - min < step {
+                if dist.cdf(min) >= p {
+                    return min;
+                }
+                break min;
+            }
+            let candidate = high - step;
+            if dist.cdf(candidate) < p {
+                break candidate;
+            }
+            high = candidate;
+            step = step.saturating_mul(2);
+        };
+        (low, high)
+    } else {
+        let mut low = x;
+        let mut step = 1u64;
+        let high = loop {
+            if max - low <= step {
+                break max;
+            }
+            let candidate = low + step;
+            if dist.cdf(candidate) >= p {
+                break candidate;
+            }
+            low = candidate;
+            step = step.saturating_mul(2);
+        };
+        (low, high)
+    };
+
+    // invariant: cdf(low) < p <= cdf(high)
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if dist.cdf(mid) >= p {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high
+}
+
 #[macro_use]
 #[cfg(all(test, feature = "nightly"))]
 pub mod test {
     use super::is_valid_multinomial;
     use crate::consts::ACC;
     use crate::distribution::{Continuous, ContinuousCDF, Discrete, DiscreteCDF};
+    use crate::statistics::{Max, Min};
+    use nalgebra::DMatrix;
 
     #[macro_export]
     macro_rules! testing_boiler {
@@ -161,6 +596,41 @@ pub mod test {
         check_integrate_pdf_is_cdf(dist, x_min, x_max, (x_max - x_min) / 100000.0);
     }
 
+    /// the `f32` instantiation of a distribution should agree with the `f64`
+    /// one to within the relative accuracy `acc` at the points `xs`
+    pub fn check_f32_matches_f64<D32, D64>(d32: &D32, d64: &D64, xs: &[f64], acc: f64)
+    where
+        D32: ContinuousCDF<f32, f32> + Continuous<f32, f32>,
+        D64: ContinuousCDF<f64, f64> + Continuous<f64, f64>,
+    {
+        let check = |name: &str, x: f64, actual: f32, expected: f64| {
+            let err = (actual as f64 - expected).abs() / expected.abs().max(1e-30);
+            if err > acc {
+                panic!("{}({}): f32 gave {}, f64 gave {}", name, x, actual, expected);
+            }
+        };
+        for &x in xs {
+            check("pdf", x, d32.pdf(x as f32), d64.pdf(x));
+            check("cdf", x, d32.cdf(x as f32), d64.cdf(x));
+            check("sf", x, d32.sf(x as f32), d64.sf(x));
+        }
+        for &p in &[1e-4, 0.05, 0.3, 0.5, 0.7, 0.95, 0.9999] {
+            check("inverse_cdf", p, d32.inverse_cdf(p as f32), d64.inverse_cdf(p));
+        }
+    }
+
+    /// the observed information matrix should equal `expected` (given row
+    /// by row) to within the accuracy `acc`, relative for entries larger
+    /// than one
+    pub fn check_observed_information(actual: Option<DMatrix<f64>>, expected: &[f64], acc: f64) {
+        let actual = actual.expect("observed information should be available");
+        assert_eq!(actual.len(), expected.len());
+        for (i, &e) in expected.iter().enumerate() {
+            let a = actual[(i / actual.ncols(), i % actual.ncols())];
+            assert!((a - e).abs() <= acc * e.abs().max(1.0), "entry {}: expected {}, got {}", i, e, a);
+        }
+    }
+
     /// Does a series of checks that all positive discrete distributions must
     /// obey.
     /// 99% of the probability mass should be between 0 and x_max (inclusive).
@@ -177,6 +647,103 @@ pub mod test {
         check_sum_pmf_is_cdf(dist, x_max);
     }
 
+    /// Checks that `inverse_cdf(p)` is the smallest `x` with `cdf(x) >= p`
+    /// over a grid of probabilities, including values close to `0` and `1`.
+    pub fn check_discrete_inverse_cdf<D: DiscreteCDF<u64, f64>>(dist: &D) {
+        let probs = [
+            1e-12, 1e-6, 0.001, 0.01, 0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.99, 0.999,
+            1.0 - 1e-6, 1.0 - 1e-12,
+        ];
+        for &p in probs.iter() {
+            let x = dist.inverse_cdf(p);
+            assert!(x >= dist.min() && x <= dist.max());
+            assert!(dist.cdf(x) >= p, "cdf({}) < {}", x, p);
+            if x > dist.min() {
+                assert!(dist.cdf(x - 1) < p, "cdf({}) >= {}", x - 1, p);
+            }
+        }
+        assert_eq!(dist.inverse_cdf(0.0), dist.min());
+        assert_eq!(dist.inverse_cdf(1.0), dist.max());
+    }
+
+    /// Wraps a distribution to only expose `cdf` and `sf`, so that the
+    /// default implementation of `DiscreteCDF::inverse_cdf` is used.
+    struct DefaultInverse<D>(D);
+
+    impl<D: Min<u64>> Min<u64> for DefaultInverse<D> {
+        fn min(&self) -> u64 {
+            self.0.min()
+        }
+    }
+
+    impl<D: Max<u64>> Max<u64> for DefaultInverse<D> {
+        fn max(&self) -> u64 {
+            self.0.max()
+        }
+    }
+
+    impl<D: DiscreteCDF<u64, f64>> DiscreteCDF<u64, f64> for DefaultInverse<D> {
+        fn cdf(&self, x: u64) -> f64 {
+            self.0.cdf(x)
+        }
+
+        fn sf(&self, x: u64) -> f64 {
+            self.0.sf(x)
+        }
+    }
+
+    /// A distribution on all of `i64` with a linear cdf, whose default
+    /// inverse cdf must search the whole range without overflowing
+    struct FullRange;
+
+    impl Min<i64> for FullRange {
+        fn min(&self) -> i64 {
+            i64::MIN
+        }
+    }
+
+    impl Max<i64> for FullRange {
+        fn max(&self) -> i64 {
+            i64::MAX
+        }
+    }
+
+    impl DiscreteCDF<i64, f64> for FullRange {
+        fn cdf(&self, x: i64) -> f64 {
+            ((x as f64 - i64::MIN as f64 + 1.0) / 2f64.powi(64)).min(1.0)
+        }
+
+        fn sf(&self, x: i64) -> f64 {
+            1.0 - self.cdf(x)
+        }
+    }
+
+    #[test]
+    fn test_default_discrete_inverse_cdf() {
+        use crate::distribution::{Binomial, Hypergeometric, Poisson};
+
+        check_discrete_inverse_cdf(&DefaultInverse(Binomial::new(0.3, 10).unwrap()));
+        check_discrete_inverse_cdf(&DefaultInverse(Poisson::new(1e6).unwrap()));
+        check_discrete_inverse_cdf(&DefaultInverse(Hypergeometric::new(50, 45, 40).unwrap()));
+
+        for &p in [1e-12, 0.25, 0.5, 0.75, 1.0 - 1e-12].iter() {
+            let x = FullRange.inverse_cdf(p);
+            assert!(FullRange.cdf(x) >= p);
+            assert!(x == i64::MIN || FullRange.cdf(x - 1) < p);
+        }
+        assert_eq!(FullRange.inverse_cdf(0.0), i64::MIN);
+        assert_eq!(FullRange.inverse_cdf(1.0), i64::MAX);
+    }
+
+    #[test]
+    fn test_find_root() {
+        let x = super::find_root(|x| x * x - 2.0, 0.0, 2.0).unwrap();
+        assert_almost_eq!(x, 2f64.sqrt(), 1e-15);
+        let x = super::find_root(|x| (x * 1e12).ln(), 1e-20, 1.0).unwrap();
+        assert_almost_eq!(x * 1e12, 1.0, 1e-14);
+        assert!(super::find_root(|x| x * x + 1.0, -1.0, 1.0).is_err());
+    }
+
     #[test]
     fn test_is_valid_multinomial() {
         use std::f64;
@@ -1,9 +1,8 @@
 use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::function::{float::cast, gamma, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Inverse
 /// Gamma](https://en.wikipedia.org/wiki/Inverse-gamma_distribution)
@@ -21,12 +20,12 @@ use std::f64;
 /// assert_eq!(n.pdf(1.0), 0.07554920138253064);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct InverseGamma {
-    shape: f64,
-    rate: f64,
+pub struct InverseGamma<T = f64> {
+    shape: T,
+    rate: T,
 }
 
-impl InverseGamma {
+impl<T: SpecialFloat> InverseGamma<T> {
     /// Constructs a new inverse gamma distribution with a shape (α)
     /// of `shape` and a rate (β) of `rate`
     ///
@@ -46,11 +45,11 @@ impl InverseGamma {
     /// result = InverseGamma::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(shape: f64, rate: f64) -> Result<InverseGamma> {
+    pub fn new(shape: T, rate: T) -> Result<InverseGamma<T>> {
         let is_nan = shape.is_nan() || rate.is_nan();
         match (shape, rate, is_nan) {
             (_, _, true) => Err(StatsError::BadParams),
-            (_, _, false) if shape <= 0.0 || rate <= 0.0 => Err(StatsError::BadParams),
+            (_, _, false) if shape <= T::zero() || rate <= T::zero() => Err(StatsError::BadParams),
             (_, _, false) if shape.is_infinite() || rate.is_infinite() => {
                 Err(StatsError::BadParams)
             }
@@ -68,7 +67,7 @@ impl InverseGamma {
     /// let n = InverseGamma::new(3.0, 1.0).unwrap();
     /// assert_eq!(n.shape(), 3.0);
     /// ```
-    pub fn shape(&self) -> f64 {
+    pub fn shape(&self) -> T {
         self.shape
     }
 
@@ -82,18 +81,18 @@ impl InverseGamma {
     /// let n = InverseGamma::new(3.0, 1.0).unwrap();
     /// assert_eq!(n.rate(), 1.0);
     /// ```
-    pub fn rate(&self) -> f64 {
+    pub fn rate(&self) -> T {
         self.rate
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for InverseGamma {
-    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
-        1.0 / super::gamma::sample_unchecked(r, self.shape, self.rate)
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for InverseGamma<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
+        T::one() / super::gamma::sample_unchecked(r, self.shape, self.rate)
     }
 }
 
-impl ContinuousCDF<f64, f64> for InverseGamma {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for InverseGamma<T> {
     /// Calculates the cumulative distribution function for the inverse gamma
     /// distribution at `x`
     ///
@@ -106,11 +105,11 @@ impl ContinuousCDF<f64, f64> for InverseGamma {
     /// where the numerator is the upper incomplete gamma function,
     /// the denominator is the gamma function, `α` is the shape,
     /// and `β` is the rate
-    fn cdf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
         } else if x.is_infinite() {
-            1.0
+            T::one()
         } else {
             gamma::gamma_ur(self.shape, self.rate / x)
         }
@@ -128,18 +127,40 @@ impl ContinuousCDF<f64, f64> for InverseGamma {
     /// where the numerator is the lower incomplete gamma function,
     /// the denominator is the gamma function, `α` is the shape,
     /// and `β` is the rate
-    fn sf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
         } else if x.is_infinite() {
-            0.0
+            T::zero()
         } else {
             gamma::gamma_lr(self.shape, self.rate / x)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// inverse gamma distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// β / Q^-1(α, p)
+    /// ```
+    ///
+    /// where `α` is the shape, `β` is the rate and `Q^-1` is the inverse of the
+    /// upper regularized incomplete gamma function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.rate / gamma::inv_gamma_ur(self.shape, p)
+    }
 }
 
-impl Min<f64> for InverseGamma {
+impl<T: SpecialFloat> Min<T> for InverseGamma<T> {
     /// Returns the minimum value in the domain of the
     /// inverse gamma distribution representable by a double precision
     /// float
@@ -149,12 +170,12 @@ impl Min<f64> for InverseGamma {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for InverseGamma {
+impl<T: SpecialFloat> Max<T> for InverseGamma<T> {
     /// Returns the maximum value in the domain of the
     /// inverse gamma distribution representable by a double precision
     /// float
@@ -164,12 +185,12 @@ impl Max<f64> for InverseGamma {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for InverseGamma {
+impl<T: SpecialFloat> Distribution<T> for InverseGamma<T> {
     /// Returns the mean of the inverse distribution
     ///
     /// # None
@@ -183,11 +204,11 @@ impl Distribution<f64> for InverseGamma {
     /// ```
     ///
     /// where `α` is the shape and `β` is the rate
-    fn mean(&self) -> Option<f64> {
-        if self.shape <= 1.0 {
+    fn mean(&self) -> Option<T> {
+        if self.shape <= T::one() {
             None
         } else {
-            Some(self.rate / (self.shape - 1.0))
+            Some(self.rate / (self.shape - T::one()))
         }
     }
     /// Returns the variance of the inverse gamma distribution
@@ -203,12 +224,12 @@ impl Distribution<f64> for InverseGamma {
     /// ```
     ///
     /// where `α` is the shape and `β` is the rate
-    fn variance(&self) -> Option<f64> {
-        if self.shape <= 2.0 {
+    fn variance(&self) -> Option<T> {
+        if self.shape <= cast(2.0) {
             None
         } else {
             let val = self.rate * self.rate
-                / ((self.shape - 1.0) * (self.shape - 1.0) * (self.shape - 2.0));
+                / ((self.shape - T::one()) * (self.shape - T::one()) * (self.shape - cast(2.0)));
             Some(val)
         }
     }
@@ -222,9 +243,9 @@ impl Distribution<f64> for InverseGamma {
     ///
     /// where `α` is the shape, `β` is the rate, `Γ` is the gamma function,
     /// and `ψ` is the digamma function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         let entr = self.shape + self.rate.ln() + gamma::ln_gamma(self.shape)
-            - (1.0 + self.shape) * gamma::digamma(self.shape);
+            - (T::one() + self.shape) * gamma::digamma(self.shape);
         Some(entr)
     }
     /// Returns the skewness of the inverse gamma distribution
@@ -240,16 +261,16 @@ impl Distribution<f64> for InverseGamma {
     /// ```
     ///
     /// where `α` is the shape
-    fn skewness(&self) -> Option<f64> {
-        if self.shape <= 3.0 {
+    fn skewness(&self) -> Option<T> {
+        if self.shape <= cast(3.0) {
             None
         } else {
-            Some(4.0 * (self.shape - 2.0).sqrt() / (self.shape - 3.0))
+            Some(cast::<T>(4.0) * (self.shape - cast(2.0)).sqrt() / (self.shape - cast(3.0)))
         }
     }
 }
 
-impl Mode<Option<f64>> for InverseGamma {
+impl<T: SpecialFloat> Mode<Option<T>> for InverseGamma<T> {
     /// Returns the mode of the inverse gamma distribution
     ///
     /// # Formula
@@ -259,12 +280,12 @@ impl Mode<Option<f64>> for InverseGamma {
     /// ```
     ///
     /// /// where `α` is the shape and `β` is the rate
-    fn mode(&self) -> Option<f64> {
-        Some(self.rate / (self.shape + 1.0))
+    fn mode(&self) -> Option<T> {
+        Some(self.rate / (self.shape + T::one()))
     }
 }
 
-impl Continuous<f64, f64> for InverseGamma {
+impl<T: SpecialFloat> Continuous<T, T> for InverseGamma<T> {
     /// Calculates the probability density function for the
     /// inverse gamma distribution at `x`
     ///
@@ -275,13 +296,13 @@ impl Continuous<f64, f64> for InverseGamma {
     /// ```
     ///
     /// where `α` is the shape, `β` is the rate, and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
-        if x <= 0.0 || x.is_infinite() {
-            0.0
-        } else if ulps_eq!(self.shape, 1.0) {
+    fn pdf(&self, x: T) -> T {
+        if x <= T::zero() || x.is_infinite() {
+            T::zero()
+        } else if ulps_eq!(self.shape, T::one()) {
             self.rate / (x * x) * (-self.rate / x).exp()
         } else {
-            self.rate.powf(self.shape) * x.powf(-self.shape - 1.0) * (-self.rate / x).exp()
+            self.rate.powf(self.shape) * x.powf(-self.shape - T::one()) * (-self.rate / x).exp()
                 / gamma::gamma(self.shape)
         }
     }
@@ -296,7 +317,7 @@ impl Continuous<f64, f64> for InverseGamma {
     /// ```
     ///
     /// where `α` is the shape, `β` is the rate, and `Γ` is the gamma function
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         self.pdf(x).ln()
     }
 }
@@ -477,4 +498,26 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.0, 0.5), 0.0, 100.0);
         test::check_continuous_distribution(&try_create(9.0, 2.0), 0.0, 100.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: InverseGamma| x.inverse_cdf(arg);
+        test_almost(1.0, 1.0, 0.43429448190325183812, 1e-14, inverse_cdf(0.1));
+        test_almost(1.0, 1.0, 1.4426950408889634074, 1e-13, inverse_cdf(0.5));
+        test_almost(1.0, 1.0, 9.4912215810299052485, 1e-13, inverse_cdf(0.9));
+        test_almost(2.0, 3.0, 0.77126370766724003443, 1e-14, inverse_cdf(0.1));
+        test_almost(2.0, 3.0, 1.7874730421330928303, 1e-13, inverse_cdf(0.5));
+        test_almost(2.0, 3.0, 5.6410953666174241147, 1e-13, inverse_cdf(0.9));
+        test_almost(10.0, 0.5, 0.035196419940973264344, 1e-15, inverse_cdf(0.1));
+        test_almost(10.0, 0.5, 0.051713182147199324263, 1e-15, inverse_cdf(0.5));
+        test_almost(10.0, 0.5, 0.080368995207222194586, 1e-15, inverse_cdf(0.9));
+        test_almost(2.0, 3.0, 0.04087926852221289981884, 1e-15, inverse_cdf(1e-30));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = InverseGamma::<f32>::new(3.0, 2.0).unwrap();
+        let d64 = InverseGamma::new(3.0, 2.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 0.5, 1.0, 3.0], 1e-4);
+    }
 }
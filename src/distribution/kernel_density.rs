@@ -0,0 +1,710 @@
+use crate::distribution::{internal, ziggurat, Continuous, ContinuousCDF};
+use crate::function::erf;
+use crate::statistics::*;
+use crate::{consts, Result, StatsError};
+use rand::Rng;
+use std::f64::consts::{PI, SQRT_2};
+
+/// Enumeration of the kernels of `KernelDensity`. Each kernel is scaled to
+/// unit variance, so that the bandwidth is the standard deviation of the
+/// kernel as in R's `density`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kernel {
+    /// The standard normal density
+    Gaussian,
+    /// The parabolic kernel `(3 / 4) * (1 - t^2)` on `[-1, 1]`, scaled by
+    /// `sqrt(5)`
+    Epanechnikov,
+    /// The triangular kernel `1 - |t|` on `[-1, 1]`, scaled by `sqrt(6)`
+    Triangular,
+    /// The quartic kernel `(15 / 16) * (1 - t^2)^2` on `[-1, 1]`, scaled by
+    /// `sqrt(7)`
+    Biweight,
+    /// The kernel `(π / 4) * cos(π * t / 2)` on `[-1, 1]`, scaled by
+    /// `1 / sqrt(1 - 8 / π^2)`
+    Cosine,
+}
+
+impl Kernel {
+    /// Returns the half-width of the support of the kernel, which for the
+    /// Gaussian kernel is where its density and distribution function
+    /// underflow
+    fn radius(self) -> f64 {
+        match self {
+            Kernel::Gaussian => 40.0,
+            Kernel::Epanechnikov => 5f64.sqrt(),
+            Kernel::Triangular => 6f64.sqrt(),
+            Kernel::Biweight => 7f64.sqrt(),
+            Kernel::Cosine => 1.0 / (1.0 - 8.0 / (PI * PI)).sqrt(),
+        }
+    }
+
+    fn pdf(self, u: f64) -> f64 {
+        if let Kernel::Gaussian = self {
+            return (-u * u / 2.0).exp() / consts::SQRT_2PI;
+        }
+        let a = self.radius();
+        let t = u / a;
+        if t.abs() >= 1.0 {
+            return 0.0;
+        }
+        let density = match self {
+            Kernel::Epanechnikov => 0.75 * (1.0 - t * t),
+            Kernel::Triangular => 1.0 - t.abs(),
+            Kernel::Biweight => 15.0 / 16.0 * (1.0 - t * t) * (1.0 - t * t),
+            _ => PI / 4.0 * (PI * t / 2.0).cos(),
+        };
+        density / a
+    }
+
+    fn cdf(self, u: f64) -> f64 {
+        if let Kernel::Gaussian = self {
+            return 0.5 * erf::erfc(-u / SQRT_2);
+        }
+        let t = (u / self.radius()).clamp(-1.0, 1.0);
+        match self {
+            Kernel::Epanechnikov => 0.5 + 0.75 * (t - t * t * t / 3.0),
+            Kernel::Triangular if t <= 0.0 => (1.0 + t) * (1.0 + t) / 2.0,
+            Kernel::Triangular => 1.0 - (1.0 - t) * (1.0 - t) / 2.0,
+            Kernel::Biweight => {
+                let t2 = t * t;
+                0.5 + 15.0 / 16.0 * t * (1.0 - 2.0 * t2 / 3.0 + t2 * t2 / 5.0)
+            }
+            _ => 0.5 + 0.5 * (PI * t / 2.0).sin(),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(self, rng: &mut R) -> f64 {
+        let t = match self {
+            Kernel::Gaussian => return ziggurat::sample_std_normal(rng),
+            // Devroye's method for the Epanechnikov kernel
+            Kernel::Epanechnikov => {
+                let u1: f64 = rng.gen_range(-1.0..1.0);
+                let u2: f64 = rng.gen_range(-1.0..1.0);
+                let u3: f64 = rng.gen_range(-1.0..1.0);
+                if u3.abs() >= u2.abs() && u3.abs() >= u1.abs() {
+                    u2
+                } else {
+                    u3
+                }
+            }
+            Kernel::Triangular => rng.gen::<f64>() + rng.gen::<f64>() - 1.0,
+            // the median of five uniforms has the Beta(3, 3) distribution
+            Kernel::Biweight => {
+                let mut u = [0.0; 5];
+                for u in u.iter_mut() {
+                    *u = rng.gen::<f64>();
+                }
+                u.sort_unstable_by(|a, b| a.total_cmp(b));
+                2.0 * u[2] - 1.0
+            }
+            Kernel::Cosine => (2.0 * rng.gen::<f64>() - 1.0).asin() * 2.0 / PI,
+        };
+        t * self.radius()
+    }
+}
+
+/// Enumeration of the ways to choose the bandwidth of `KernelDensity`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Bandwidth {
+    /// Silverman's rule of thumb `0.9 * min(s, IQR / 1.34) * n^(-1 / 5)`,
+    /// R's `bw.nrd0`
+    Silverman,
+    /// Scott's rule of thumb `1.06 * min(s, IQR / 1.34) * n^(-1 / 5)`, R's
+    /// `bw.nrd`
+    Scott,
+    /// The solve-the-equation plug-in bandwidth of Sheather and Jones
+    /// (1991), computed on binned pairwise distances as R's `bw.SJ`
+    SheatherJones,
+    /// The given bandwidth
+    Fixed(f64),
+}
+
+impl Bandwidth {
+    /// Returns the bandwidth for the sorted, finite `data`
+    fn select(self, data: &[f64]) -> Result<f64> {
+        if let Bandwidth::Fixed(bandwidth) = self {
+            return if bandwidth > 0.0 && bandwidth.is_finite() {
+                Ok(bandwidth)
+            } else {
+                Err(StatsError::ArgMustBePositive("bandwidth"))
+            };
+        }
+        if data.len() < 2 {
+            return Err(StatsError::BadParams);
+        }
+        let n = data.len() as f64;
+        let std_dev = data.std_dev();
+        let iqr = Data::new(data.to_vec()).interquartile_range_with(QuantileDefinition::Linear);
+        let bandwidth = match self {
+            Bandwidth::Silverman => {
+                let mut scale = std_dev.min(iqr / 1.34);
+                if scale == 0.0 {
+                    scale = std_dev;
+                }
+                if scale == 0.0 {
+                    scale = data[0].abs();
+                }
+                if scale == 0.0 {
+                    scale = 1.0;
+                }
+                0.9 * scale * n.powf(-0.2)
+            }
+            Bandwidth::Scott => 1.06 * std_dev.min(iqr / 1.34) * n.powf(-0.2),
+            _ => sheather_jones(data, std_dev.min(iqr / 1.349))?,
+        };
+        if bandwidth > 0.0 && bandwidth.is_finite() {
+            Ok(bandwidth)
+        } else {
+            Err(StatsError::BadParams)
+        }
+    }
+}
+
+/// Returns the Sheather-Jones bandwidth of the sorted `data` with the
+/// robust `scale`, following R's `bw.SJ` with the `ste` method
+fn sheather_jones(data: &[f64], scale: f64) -> Result<f64> {
+    const BINS: usize = 1000;
+    if scale <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let n = data.len() as f64;
+    let (min, max) = (data[0], data[data.len() - 1]);
+    let width = (max - min) * 1.01 / BINS as f64;
+    let mut bins = vec![0.0; BINS];
+    for x in data {
+        bins[((x - min) / width) as usize] += 1.0;
+    }
+    // counts of the pairs of observations by their distance in bins
+    let mut pairs = vec![0.0; BINS];
+    for (i, &a) in bins.iter().enumerate().filter(|&(_, &a)| a > 0.0) {
+        pairs[0] += a * (a - 1.0) / 2.0;
+        for (j, b) in bins[i + 1..].iter().enumerate() {
+            pairs[j + 1] += a * b;
+        }
+    }
+
+    // estimates of the integrated squared second and third derivatives of
+    // the density with the Gaussian kernel and bandwidth `h`
+    let functional = |h: f64, order: i32| {
+        let mut sum = 0.0;
+        for (i, count) in pairs.iter().enumerate() {
+            let d = (i as f64 * width / h).powi(2);
+            if d >= 1000.0 {
+                break;
+            }
+            let polynomial = if order == 4 {
+                d * d - 6.0 * d + 3.0
+            } else {
+                d * d * d - 15.0 * d * d + 45.0 * d - 15.0
+            };
+            sum += (-d / 2.0).exp() * polynomial * count;
+        }
+        let diagonal = if order == 4 { 3.0 * n } else { -15.0 * n };
+        (2.0 * sum + diagonal) / (n * (n - 1.0) * h.powi(order + 1) * consts::SQRT_2PI)
+    };
+
+    let a = 1.24 * scale * n.powf(-1.0 / 7.0);
+    let b = 1.23 * scale * n.powf(-1.0 / 9.0);
+    let c1 = 1.0 / (2.0 * PI.sqrt() * n);
+    let td = -functional(b, 6);
+    if !td.is_finite() || td <= 0.0 {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let alpha2 = 1.357 * (functional(a, 4) / td).powf(1.0 / 7.0);
+    if !alpha2.is_finite() {
+        return Err(StatsError::ComputationFailedToConverge);
+    }
+    let f = |h: f64| (c1 / functional(alpha2 * h.powf(5.0 / 7.0), 4)).powf(0.2) - h;
+
+    let mut upper = 1.144 * scale * n.powf(-0.2);
+    let mut lower = 0.1 * upper;
+    for i in 0..100 {
+        if f(lower) * f(upper) <= 0.0 {
+            return internal::find_root(f, lower, upper);
+        }
+        if i % 2 == 0 {
+            upper *= 1.2;
+        } else {
+            lower /= 1.2;
+        }
+    }
+    Err(StatsError::ComputationFailedToConverge)
+}
+
+/// Implements the [kernel density
+/// estimate](https://en.wikipedia.org/wiki/Kernel_density_estimation) of the
+/// distribution of a sample, a smooth alternative to `Empirical`
+///
+/// # Remarks
+///
+/// The density and distribution function sum the kernels of the data
+/// points within reach of `x` in a sorted copy of the data. For large
+/// samples, `binned_pdf` evaluates the density on a grid in `O(m log m)`
+/// time with linear binning and a fast Fourier transform.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Bandwidth, Continuous, Kernel, KernelDensity};
+/// use statrs::statistics::Distribution;
+///
+/// let kde = KernelDensity::new(vec![0.0, 1.0, 3.0], Kernel::Gaussian, Bandwidth::Fixed(1.0)).unwrap();
+/// assert!((kde.mean().unwrap() - 4.0 / 3.0).abs() < 1e-15);
+/// assert!((kde.pdf(1.0) - 0.231634657144588).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct KernelDensity {
+    data: Vec<f64>,
+    kernel: Kernel,
+    bandwidth: f64,
+}
+
+impl KernelDensity {
+    /// Constructs a new kernel density estimate from the sample `data`
+    /// with the `kernel` and the `bandwidth` chosen as given
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or has an entry that is not
+    /// finite, a fixed bandwidth is not positive and finite, or the data is
+    /// insufficient to select the bandwidth, that is it has fewer than two
+    /// entries or, except for `Silverman`, no spread
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Bandwidth, Kernel, KernelDensity};
+    ///
+    /// let data = vec![1.0, 2.0, 2.5, 4.0];
+    /// let kde = KernelDensity::new(data.clone(), Kernel::Epanechnikov, Bandwidth::Silverman);
+    /// assert!(kde.is_ok());
+    ///
+    /// assert!(KernelDensity::new(data.clone(), Kernel::Gaussian, Bandwidth::Fixed(0.0)).is_err());
+    /// assert!(KernelDensity::new(vec![], Kernel::Gaussian, Bandwidth::Fixed(1.0)).is_err());
+    /// assert!(KernelDensity::new(vec![1.0], Kernel::Gaussian, Bandwidth::Scott).is_err());
+    /// ```
+    pub fn new(mut data: Vec<f64>, kernel: Kernel, bandwidth: Bandwidth) -> Result<KernelDensity> {
+        if data.is_empty() || data.iter().any(|x| !x.is_finite()) {
+            return Err(StatsError::BadParams);
+        }
+        data.sort_unstable_by(|a, b| a.total_cmp(b));
+        let bandwidth = bandwidth.select(&data)?;
+        Ok(KernelDensity {
+            data,
+            kernel,
+            bandwidth,
+        })
+    }
+
+    /// Returns the kernel of the kernel density estimate
+    pub fn kernel(&self) -> Kernel {
+        self.kernel
+    }
+
+    /// Returns the bandwidth of the kernel density estimate, the standard
+    /// deviation of the kernel
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Bandwidth, Kernel, KernelDensity};
+    ///
+    /// let data = vec![1.0, 2.0, 2.5, 4.0, 4.5];
+    /// let kde = KernelDensity::new(data, Kernel::Gaussian, Bandwidth::Fixed(0.5)).unwrap();
+    /// assert_eq!(kde.bandwidth(), 0.5);
+    /// ```
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Returns the indices of the data points in `[low, high]`
+    fn window(&self, low: f64, high: f64) -> (usize, usize) {
+        (
+            self.data.partition_point(|&x| x < low),
+            self.data.partition_point(|&x| x <= high),
+        )
+    }
+
+    /// Evaluates the probability density function at `points` equally
+    /// spaced points from `min` to `max` inclusive, approximately by binning
+    /// the data linearly onto a grid and convolving the bin counts with the
+    /// kernel by a fast Fourier transform
+    ///
+    /// # Remarks
+    ///
+    /// The error of the approximation is of the order of the square of the
+    /// grid spacing relative to the bandwidth, so the spacing should be a
+    /// small fraction of the bandwidth. The cost is `O(n + m log m)`, where
+    /// `m` is the number of grid points within reach of the kernel of
+    /// `[min, max]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `points < 2` or `min` is not less than `max`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Bandwidth, Continuous, Kernel, KernelDensity};
+    ///
+    /// let data = vec![1.0, 2.0, 2.5, 4.0, 4.5];
+    /// let kde = KernelDensity::new(data, Kernel::Gaussian, Bandwidth::Fixed(0.5)).unwrap();
+    /// let grid = kde.binned_pdf(0.0, 5.0, 501).unwrap();
+    /// assert!((grid[300] - kde.pdf(3.0)).abs() < 1e-4);
+    /// ```
+    pub fn binned_pdf(&self, min: f64, max: f64, points: usize) -> Result<Vec<f64>> {
+        if points < 2 {
+            return Err(StatsError::ArgGte("points", 2.0));
+        }
+        if min >= max || !min.is_finite() || !max.is_finite() {
+            return Err(StatsError::ArgLtArg("min", "max"));
+        }
+        let delta = (max - min) / (points - 1) as f64;
+        // beyond eight standard deviations the Gaussian kernel is negligible
+        let radius = match self.kernel {
+            Kernel::Gaussian => 8.0,
+            kernel => kernel.radius(),
+        };
+        let pad = (radius * self.bandwidth / delta).ceil() as usize;
+        let size = points + 2 * pad;
+        let origin = min - pad as f64 * delta;
+
+        let len = (2 * size).next_power_of_two();
+        let mut weights = vec![0.0; len];
+        for x in &self.data {
+            let position = (x - origin) / delta;
+            if position < 0.0 || position > (size - 1) as f64 {
+                continue;
+            }
+            let i = position as usize;
+            let fraction = position - i as f64;
+            weights[i] += 1.0 - fraction;
+            if fraction > 0.0 {
+                weights[i + 1] += fraction;
+            }
+        }
+        let mut kernel = vec![0.0; len];
+        for i in 0..size {
+            let k = self.kernel.pdf(i as f64 * delta / self.bandwidth);
+            kernel[i] = k;
+            if i > 0 {
+                kernel[len - i] = k;
+            }
+        }
+
+        let mut weights_im = vec![0.0; len];
+        let mut kernel_im = vec![0.0; len];
+        fft(&mut weights, &mut weights_im, false);
+        fft(&mut kernel, &mut kernel_im, false);
+        for i in 0..len {
+            let re = weights[i] * kernel[i] - weights_im[i] * kernel_im[i];
+            let im = weights[i] * kernel_im[i] + weights_im[i] * kernel[i];
+            weights[i] = re;
+            weights_im[i] = im;
+        }
+        fft(&mut weights, &mut weights_im, true);
+
+        let scale = self.data.len() as f64 * self.bandwidth;
+        Ok(weights[pad..pad + points]
+            .iter()
+            .map(|y| (y / scale).max(0.0))
+            .collect())
+    }
+}
+
+/// Computes the discrete Fourier transform, or its inverse, of the complex
+/// sequence with the real parts `re` and imaginary parts `im` in place by
+/// the radix-2 Cooley-Tukey algorithm. The length must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let angle = sign * 2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+    if inverse {
+        for (re, im) in re.iter_mut().zip(im.iter_mut()) {
+            *re /= n as f64;
+            *im /= n as f64;
+        }
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for KernelDensity {
+    /// Draws a smoothed bootstrap sample, a data point chosen uniformly at
+    /// random plus a draw from the kernel scaled by the bandwidth
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let x = self.data[rng.gen_range(0..self.data.len())];
+        x + self.bandwidth * self.kernel.sample(rng)
+    }
+}
+
+impl Min<f64> for KernelDensity {
+    /// Returns the minimum of the support of the kernel density estimate,
+    /// `NEG_INF` for the Gaussian kernel
+    fn min(&self) -> f64 {
+        match self.kernel {
+            Kernel::Gaussian => f64::NEG_INFINITY,
+            kernel => self.data[0] - kernel.radius() * self.bandwidth,
+        }
+    }
+}
+
+impl Max<f64> for KernelDensity {
+    /// Returns the maximum of the support of the kernel density estimate,
+    /// `INF` for the Gaussian kernel
+    fn max(&self) -> f64 {
+        match self.kernel {
+            Kernel::Gaussian => f64::INFINITY,
+            kernel => self.data[self.data.len() - 1] + kernel.radius() * self.bandwidth,
+        }
+    }
+}
+
+impl Distribution<f64> for KernelDensity {
+    /// Returns the mean of the kernel density estimate, the mean of the
+    /// data
+    fn mean(&self) -> Option<f64> {
+        Some(self.data.iter().mean())
+    }
+
+    /// Returns the variance of the kernel density estimate
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / n) * Σ (x_i - x̄)^2 + h^2
+    /// ```
+    ///
+    /// where `x̄` is the mean of the data and `h` the bandwidth
+    fn variance(&self) -> Option<f64> {
+        Some(self.data.iter().population_variance() + self.bandwidth * self.bandwidth)
+    }
+}
+
+impl ContinuousCDF<f64, f64> for KernelDensity {
+    /// Calculates the cumulative distribution function of the kernel
+    /// density estimate at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / n) * Σ F((x - x_i) / h)
+    /// ```
+    ///
+    /// where `F` is the distribution function of the kernel and `h` the
+    /// bandwidth
+    fn cdf(&self, x: f64) -> f64 {
+        let reach = self.kernel.radius() * self.bandwidth;
+        let (low, high) = self.window(x - reach, x + reach);
+        let sum: f64 = self.data[low..high]
+            .iter()
+            .map(|xi| self.kernel.cdf((x - xi) / self.bandwidth))
+            .sum();
+        (low as f64 + sum) / self.data.len() as f64
+    }
+
+    /// Calculates the survival function of the kernel density estimate at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / n) * Σ F((x_i - x) / h)
+    /// ```
+    ///
+    /// where `F` is the distribution function of the kernel and `h` the
+    /// bandwidth
+    fn sf(&self, x: f64) -> f64 {
+        let reach = self.kernel.radius() * self.bandwidth;
+        let (low, high) = self.window(x - reach, x + reach);
+        let sum: f64 = self.data[low..high]
+            .iter()
+            .map(|xi| self.kernel.cdf((xi - x) / self.bandwidth))
+            .sum();
+        ((self.data.len() - high) as f64 + sum) / self.data.len() as f64
+    }
+}
+
+impl Continuous<f64, f64> for KernelDensity {
+    /// Calculates the probability density function of the kernel density
+    /// estimate at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (1 / (n * h)) * Σ K((x - x_i) / h)
+    /// ```
+    ///
+    /// where `K` is the kernel and `h` the bandwidth
+    fn pdf(&self, x: f64) -> f64 {
+        let reach = self.kernel.radius() * self.bandwidth;
+        let (low, high) = self.window(x - reach, x + reach);
+        let sum: f64 = self.data[low..high]
+            .iter()
+            .map(|xi| self.kernel.pdf((x - xi) / self.bandwidth))
+            .sum();
+        sum / (self.data.len() as f64 * self.bandwidth)
+    }
+
+    /// Calculates the log probability density function of the kernel
+    /// density estimate at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln((1 / (n * h)) * Σ K((x - x_i) / h))
+    /// ```
+    ///
+    /// where `K` is the kernel and `h` the bandwidth
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution as RandDistribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const DATA: [f64; 20] = [
+        -1.2, 0.3, 2.5, 1.1, -0.4, 0.9, 3.8, 1.7, -2.1, 0.0,
+        1.4, 2.2, 0.6, -0.8, 1.9, 4.4, 0.2, 1.0, -1.5, 2.9,
+    ];
+    const KERNELS: [Kernel; 5] = [
+        Kernel::Gaussian, Kernel::Epanechnikov, Kernel::Triangular, Kernel::Biweight, Kernel::Cosine,
+    ];
+
+    fn create(kernel: Kernel, bandwidth: Bandwidth) -> KernelDensity {
+        KernelDensity::new(DATA.to_vec(), kernel, bandwidth).unwrap()
+    }
+
+    #[test]
+    fn test_bandwidth() {
+        assert_almost_eq!(create(Kernel::Gaussian, Bandwidth::Silverman).bandwidth(), 0.7655081397851399, 1e-14);
+        assert_almost_eq!(create(Kernel::Gaussian, Bandwidth::Scott).bandwidth(), 0.9015984757469425, 1e-14);
+        assert_almost_eq!(create(Kernel::Gaussian, Bandwidth::SheatherJones).bandwidth(), 1.159109158698611, 1e-10);
+        let constant = KernelDensity::new(vec![2.0, 2.0, 2.0], Kernel::Gaussian, Bandwidth::Silverman).unwrap();
+        assert_almost_eq!(constant.bandwidth(), 0.9 * 2.0 * 3f64.powf(-0.2), 1e-15);
+        assert!(KernelDensity::new(vec![2.0, 2.0], Kernel::Gaussian, Bandwidth::Scott).is_err());
+        assert!(KernelDensity::new(vec![2.0, 2.0], Kernel::Gaussian, Bandwidth::SheatherJones).is_err());
+        assert!(KernelDensity::new(vec![1.0, f64::NAN], Kernel::Gaussian, Bandwidth::Fixed(1.0)).is_err());
+        assert!(KernelDensity::new(vec![1.0], Kernel::Gaussian, Bandwidth::Fixed(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn test_pdf_cdf() {
+        let expected = [
+            ([0.008401939394337395, 0.19000025720969185, 0.2249726996032, 0.021664107882622276],
+             [0.001872241950401163, 0.30166197925495675, 0.5807836764060138, 0.993835957521617]),
+            ([0.011806438921198894, 0.1862197411661825, 0.223114862794929, 0.023881205999697765],
+             [0.0013334565261422266, 0.30219362665260685, 0.5801869176962471, 0.9931926490649383]),
+            ([0.010824829046386302, 0.19158162379719634, 0.22158162379719637, 0.021649658092772608],
+             [0.0017576538582523282, 0.30176445728780993, 0.5809064615223303, 0.9934846922834953]),
+            ([0.010223553316865917, 0.1882297786608983, 0.22342646675627315, 0.023466965637468827],
+             [0.0015833884866087167, 0.30230160858093885, 0.5802600459036471, 0.9934780555405615]),
+            ([0.011405997246197234, 0.18723582512435225, 0.22345282637691039, 0.02331059883115898],
+             [0.0014327597649418018, 0.3022089814184303, 0.5802351888337534, 0.9932854812924568]),
+        ];
+        for (&kernel, (pdf, cdf)) in KERNELS.iter().zip(expected.iter()) {
+            let kde = create(kernel, Bandwidth::Fixed(0.5));
+            for (i, &x) in [-3.0, 0.0, 1.25, 5.0].iter().enumerate() {
+                assert_almost_eq!(kde.pdf(x), pdf[i], 1e-14);
+                assert_almost_eq!(kde.ln_pdf(x), pdf[i].ln(), 1e-12);
+                assert_almost_eq!(kde.cdf(x), cdf[i], 1e-11);
+                assert_almost_eq!(kde.sf(x), 1.0 - cdf[i], 1e-11);
+            }
+            assert_almost_eq!(kde.inverse_cdf(cdf[2]), 1.25, 1e-10);
+            assert_eq!(kde.pdf(-100.0), 0.0);
+            assert_eq!(kde.cdf(-100.0), 0.0);
+            assert_eq!(kde.cdf(100.0), 1.0);
+            assert_eq!(kde.sf(100.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_support_and_moments() {
+        let kde = create(Kernel::Epanechnikov, Bandwidth::Fixed(0.5));
+        assert_almost_eq!(kde.min(), -2.1 - 0.5 * 5f64.sqrt(), 1e-15);
+        assert_almost_eq!(kde.max(), 4.4 + 0.5 * 5f64.sqrt(), 1e-15);
+        assert_eq!(kde.cdf(kde.min()), 0.0);
+        assert_eq!(kde.sf(kde.max()), 0.0);
+        assert_almost_eq!(kde.mean().unwrap(), 0.945, 1e-15);
+        assert_almost_eq!(kde.variance().unwrap(), 2.815475 + 0.25, 1e-14);
+        let kde = create(Kernel::Gaussian, Bandwidth::Fixed(0.5));
+        assert_eq!(kde.min(), f64::NEG_INFINITY);
+        assert_eq!(kde.max(), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_binned_pdf() {
+        for &kernel in KERNELS.iter() {
+            let kde = create(kernel, Bandwidth::Fixed(0.5));
+            let grid = kde.binned_pdf(-3.0, 6.0, 901).unwrap();
+            for (i, y) in grid.iter().enumerate() {
+                assert_almost_eq!(*y, kde.pdf(-3.0 + i as f64 * 0.01), 2e-4);
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..100_000).map(|_| normal.sample(&mut rng)).collect();
+        let kde = KernelDensity::new(data, Kernel::Gaussian, Bandwidth::Silverman).unwrap();
+        let grid = kde.binned_pdf(-4.0, 4.0, 801).unwrap();
+        for i in (0..801).step_by(50) {
+            assert_almost_eq!(grid[i], kde.pdf(-4.0 + i as f64 * 0.01), 1e-5);
+        }
+
+        let kde = create(Kernel::Gaussian, Bandwidth::Fixed(0.5));
+        assert!(kde.binned_pdf(0.0, 1.0, 1).is_err());
+        assert!(kde.binned_pdf(1.0, 1.0, 10).is_err());
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for &kernel in KERNELS.iter() {
+            let kde = create(kernel, Bandwidth::Fixed(0.5));
+            let n = 20_000;
+            let samples: Vec<f64> = (0..n).map(|_| kde.sample(&mut rng)).collect();
+            assert!((samples.iter().mean() - kde.mean().unwrap()).abs() < 0.05);
+            assert!((samples.iter().population_variance() / kde.variance().unwrap() - 1.0).abs() < 0.05);
+            for &x in &[-1.0, 0.5, 2.0] {
+                let below = samples.iter().filter(|&&s| s <= x).count() as f64 / n as f64;
+                assert!((below - kde.cdf(x)).abs() < 0.015);
+            }
+            assert!(samples.iter().all(|&s| s >= kde.min() && s <= kde.max()));
+        }
+    }
+}
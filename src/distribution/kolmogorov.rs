@@ -0,0 +1,383 @@
+use crate::distribution::{Continuous, ContinuousCDF};
+use crate::statistics::*;
+use rand::Rng;
+use std::f64;
+
+/// Implements the
+/// [Kolmogorov](https://en.wikipedia.org/wiki/Kolmogorov%E2%80%93Smirnov_test#Kolmogorov_distribution)
+/// distribution, i.e. the distribution of the supremum of the absolute value
+/// of a Brownian bridge. It is the limiting distribution of `sqrt(n) * D_n`
+/// where `D_n` is the two-sided Kolmogorov-Smirnov statistic of a sample of
+/// size `n`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{Kolmogorov, ContinuousCDF};
+/// use statrs::prec;
+///
+/// let k = Kolmogorov::new();
+/// assert!(prec::almost_eq(k.sf(1.0), 0.26999967167735452, 1e-15));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Kolmogorov {
+    _private: (),
+}
+
+impl Kolmogorov {
+    /// Constructs a new Kolmogorov distribution. The distribution has no
+    /// parameters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::Kolmogorov;
+    ///
+    /// let k = Kolmogorov::new();
+    /// ```
+    pub fn new() -> Kolmogorov {
+        Kolmogorov { _private: () }
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for Kolmogorov {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+        self.inverse_cdf(r.gen())
+    }
+}
+
+/// Below this point the series in `exp(-π^2 / (8 * x^2))` is used, above
+/// it the series in `exp(-2 * x^2)`. Both need at most a handful of terms.
+const SERIES_SWITCH: f64 = 1.0;
+
+impl ContinuousCDF<f64, f64> for Kolmogorov {
+    /// Calculates the cumulative distribution function for the Kolmogorov
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt(2π) / x * Σ exp(-(2k - 1)^2 * π^2 / (8 * x^2))
+    /// ```
+    ///
+    /// for `0 < x < 1` and `1 - sf(x)` otherwise
+    fn cdf(&self, x: f64) -> f64 {
+        if x.is_nan() || x <= 0.0 {
+            0.0
+        } else if x < SERIES_SWITCH {
+            let a = f64::consts::PI * f64::consts::PI / (8.0 * x * x);
+            let mut sum = 0.0;
+            for k in 1.. {
+                let i = (2 * k - 1) as f64;
+                let term = (-a * i * i).exp();
+                sum += term;
+                if term <= sum * f64::EPSILON {
+                    break;
+                }
+            }
+            (2.0 * f64::consts::PI).sqrt() / x * sum
+        } else {
+            1.0 - self.sf(x)
+        }
+    }
+
+    /// Calculates the survival function for the Kolmogorov distribution at
+    /// `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2 * Σ (-1)^(k - 1) * exp(-2 * k^2 * x^2)
+    /// ```
+    ///
+    /// for `x >= 1` and `1 - cdf(x)` otherwise
+    fn sf(&self, x: f64) -> f64 {
+        if x < SERIES_SWITCH {
+            1.0 - self.cdf(x)
+        } else {
+            let mut sum = 0.0;
+            let mut sign = 1.0;
+            for k in 1.. {
+                let k = k as f64;
+                let term = (-2.0 * k * k * x * x).exp();
+                sum += sign * term;
+                sign = -sign;
+                if term <= sum * f64::EPSILON {
+                    break;
+                }
+            }
+            2.0 * sum
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Kolmogorov distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The cdf is inverted by Newton's method safeguarded by bisection. For
+    /// `p > 0.5` the survival function is inverted instead to retain
+    /// precision in the upper tail.
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == 0.0 {
+            return 0.0;
+        }
+        if p == 1.0 {
+            return f64::INFINITY;
+        }
+        // f is increasing in x and has derivative pdf(x)
+        let f = |x: f64| {
+            if p > 0.5 {
+                (1.0 - p) - self.sf(x)
+            } else {
+                self.cdf(x) - p
+            }
+        };
+        let (mut low, mut high) = (0.0, 7.0);
+        let mut x = if p > 0.5 {
+            (-(0.5 * (1.0 - p)).ln() / 2.0).sqrt()
+        } else {
+            self.median()
+        };
+        for _ in 0..100 {
+            let fx = f(x);
+            if fx > 0.0 {
+                high = x;
+            } else {
+                low = x;
+            }
+            let mut next = x - fx / self.pdf(x);
+            if !(next > low && next < high) {
+                next = (low + high) / 2.0;
+            }
+            if (next - x).abs() <= 4.0 * f64::EPSILON * x {
+                return next;
+            }
+            x = next;
+        }
+        x
+    }
+}
+
+impl Min<f64> for Kolmogorov {
+    /// Returns the minimum value in the domain of the Kolmogorov
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn min(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Max<f64> for Kolmogorov {
+    /// Returns the maximum value in the domain of the Kolmogorov
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// INF
+    /// ```
+    fn max(&self) -> f64 {
+        f64::INFINITY
+    }
+}
+
+impl Distribution<f64> for Kolmogorov {
+    /// Returns the mean of the Kolmogorov distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt(π / 2) * ln(2)
+    /// ```
+    fn mean(&self) -> Option<f64> {
+        Some(0.86873116063615914183)
+    }
+
+    /// Returns the variance of the Kolmogorov distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// π^2 / 12 - π / 2 * ln(2)^2
+    /// ```
+    fn variance(&self) -> Option<f64> {
+        Some(0.067773203963865079378)
+    }
+
+    /// Returns the skewness of the Kolmogorov distribution
+    ///
+    /// # Remarks
+    ///
+    /// The value was obtained by numerical integration
+    fn skewness(&self) -> Option<f64> {
+        Some(0.86042613714366825587)
+    }
+}
+
+impl Median<f64> for Kolmogorov {
+    /// Returns the median of the Kolmogorov distribution
+    ///
+    /// # Remarks
+    ///
+    /// The value was obtained by numerically inverting the cdf
+    fn median(&self) -> f64 {
+        0.82757355518990769011
+    }
+}
+
+impl Mode<Option<f64>> for Kolmogorov {
+    /// Returns the mode of the Kolmogorov distribution
+    ///
+    /// # Remarks
+    ///
+    /// The value was obtained by numerically maximizing the pdf
+    fn mode(&self) -> Option<f64> {
+        Some(0.73546790791657198206)
+    }
+}
+
+impl Continuous<f64, f64> for Kolmogorov {
+    /// Calculates the probability density function for the Kolmogorov
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// sqrt(2π) / x^2 * Σ exp(-a_k / x^2) * (2 * a_k / x^2 - 1)
+    /// ```
+    ///
+    /// where `a_k = (2k - 1)^2 * π^2 / 8`, for `0 < x < 1` and
+    ///
+    /// ```ignore
+    /// 8 * x * Σ (-1)^(k - 1) * k^2 * exp(-2 * k^2 * x^2)
+    /// ```
+    ///
+    /// otherwise
+    fn pdf(&self, x: f64) -> f64 {
+        if x.is_nan() || x <= 0.0 || x.is_infinite() {
+            0.0
+        } else if x < SERIES_SWITCH {
+            let x_2 = x * x;
+            let mut sum = 0.0;
+            for k in 1.. {
+                let i = (2 * k - 1) as f64;
+                let a = i * i * f64::consts::PI * f64::consts::PI / 8.0;
+                let term = (-a / x_2).exp() * (2.0 * a / x_2 - 1.0);
+                sum += term;
+                if term.abs() <= sum.abs() * f64::EPSILON {
+                    break;
+                }
+            }
+            (2.0 * f64::consts::PI).sqrt() / x_2 * sum
+        } else {
+            let mut sum = 0.0;
+            let mut sign = 1.0;
+            for k in 1.. {
+                let k = k as f64;
+                let term = k * k * (-2.0 * k * k * x * x).exp();
+                sum += sign * term;
+                sign = -sign;
+                if term <= sum * f64::EPSILON {
+                    break;
+                }
+            }
+            8.0 * x * sum
+        }
+    }
+
+    /// Calculates the log probability density function for the Kolmogorov
+    /// distribution at `x`
+    fn ln_pdf(&self, x: f64) -> f64 {
+        self.pdf(x).ln()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::statistics::*;
+    use crate::distribution::{ContinuousCDF, Continuous, Kolmogorov};
+    use crate::distribution::internal::*;
+
+    #[test]
+    fn test_cdf() {
+        let k = Kolmogorov::new();
+        assert_eq!(k.cdf(0.0), 0.0);
+        assert_eq!(k.cdf(-1.0), 0.0);
+        assert_eq!(k.cdf(f64::INFINITY), 1.0);
+        assert_almost_eq!(k.cdf(0.2), 5.0504073386700878765e-13, 1e-26);
+        assert_almost_eq!(k.cdf(0.3), 9.3058013345666228456e-6, 1e-19);
+        assert_almost_eq!(k.cdf(0.5), 0.036054756335124905614, 1e-16);
+        assert_almost_eq!(k.cdf(0.8), 0.45585758842580192326, 1e-15);
+        assert_almost_eq!(k.cdf(1.0), 0.7300003283226454788, 1e-15);
+        assert_almost_eq!(k.cdf(1.5), 0.97778203738347487128, 1e-15);
+    }
+
+    #[test]
+    fn test_sf() {
+        let k = Kolmogorov::new();
+        assert_eq!(k.sf(0.0), 1.0);
+        assert_eq!(k.sf(f64::INFINITY), 0.0);
+        assert_almost_eq!(k.sf(0.8), 0.54414241157419807674, 1e-15);
+        assert_almost_eq!(k.sf(1.2), 0.11224966667072498483, 1e-15);
+        assert_almost_eq!(k.sf(2.0), 0.00067092525577969534654, 1e-18);
+        assert_almost_eq!(k.sf(3.0), 3.0459959489425256872e-8, 1e-22);
+        assert_almost_eq!(k.sf(5.0), 3.857499695927835566e-22, 1e-36);
+    }
+
+    #[test]
+    fn test_pdf() {
+        let k = Kolmogorov::new();
+        assert_eq!(k.pdf(0.0), 0.0);
+        assert_eq!(k.pdf(f64::INFINITY), 0.0);
+        assert_almost_eq!(k.pdf(0.2), 1.5324205413389084969e-10, 1e-23);
+        assert_almost_eq!(k.pdf(0.5), 0.63958285094045663465, 1e-14);
+        assert_almost_eq!(k.pdf(0.8), 1.6270243456365922413, 1e-14);
+        assert_almost_eq!(k.pdf(1.0), 1.0719485583569417625, 1e-14);
+        assert_almost_eq!(k.pdf(2.0), 0.005367402045629682826, 1e-16);
+        assert_almost_eq!(k.pdf(5.0), 7.7149993918556711321e-21, 1e-34);
+        assert_almost_eq!(k.ln_pdf(1.0), 1.0719485583569417625f64.ln(), 1e-14);
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let k = Kolmogorov::new();
+        assert_eq!(k.inverse_cdf(0.0), 0.0);
+        assert_eq!(k.inverse_cdf(1.0), f64::INFINITY);
+        assert_almost_eq!(k.inverse_cdf(1e-10), 0.22013554252928297614, 1e-14);
+        assert_almost_eq!(k.inverse_cdf(0.01), 0.44102769851792936842, 1e-14);
+        assert_almost_eq!(k.inverse_cdf(0.25), 0.676447691502820083, 1e-14);
+        assert_almost_eq!(k.inverse_cdf(0.5), 0.82757355518990769011, 1e-14);
+        assert_almost_eq!(k.inverse_cdf(0.9), 1.2238478702170823883, 1e-14);
+        assert_almost_eq!(k.inverse_cdf(0.99), 1.6276236115189503465, 1e-13);
+        assert_almost_eq!(k.inverse_cdf(0.999999), 2.693386134452709643, 1e-9);
+    }
+
+    #[test]
+    fn test_moments() {
+        let k = Kolmogorov::new();
+        assert_almost_eq!(k.mean().unwrap(), (std::f64::consts::PI / 2.0).sqrt() * std::f64::consts::LN_2, 1e-15);
+        let mean = k.mean().unwrap();
+        assert_almost_eq!(k.variance().unwrap(), std::f64::consts::PI.powi(2) / 12.0 - mean * mean, 1e-15);
+        assert_almost_eq!(k.cdf(k.median()), 0.5, 1e-15);
+        assert_almost_eq!(k.pdf(k.mode().unwrap() * (1.0 + 1e-6)), k.pdf(k.mode().unwrap() * (1.0 - 1e-6)), 1e-10);
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&Kolmogorov::new(), 0.0, 5.0);
+    }
+}
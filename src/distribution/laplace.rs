@@ -1,8 +1,10 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::statistics::{Distribution, Max, Median, Min, Mode};
+use crate::distribution::{Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, SpecialFloat};
+use crate::statistics::{
+    Data, Distribution, Max, Median, Min, Mode, OrderStatistics, Statistics,
+};
 use crate::{Result, StatsError};
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Laplace](https://en.wikipedia.org/wiki/Laplace_distribution)
 /// distribution.
@@ -18,12 +20,12 @@ use std::f64;
 /// assert_eq!(n.pdf(1.0), 0.18393972058572117);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Laplace {
-    location: f64,
-    scale: f64,
+pub struct Laplace<T = f64> {
+    location: T,
+    scale: T,
 }
 
-impl Laplace {
+impl<T: SpecialFloat> Laplace<T> {
     /// Constructs a new laplace distribution with the given
     /// location and scale.
     ///
@@ -42,8 +44,8 @@ impl Laplace {
     /// result = Laplace::new(0.0, -1.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(location: f64, scale: f64) -> Result<Laplace> {
-        if location.is_nan() || scale.is_nan() || scale <= 0.0 {
+    pub fn new(location: T, scale: T) -> Result<Laplace<T>> {
+        if location.is_nan() || scale.is_nan() || scale <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Laplace { location, scale })
@@ -60,7 +62,7 @@ impl Laplace {
     /// let n = Laplace::new(0.0, 1.0).unwrap();
     /// assert_eq!(n.location(), 0.0);
     /// ```
-    pub fn location(&self) -> f64 {
+    pub fn location(&self) -> T {
         self.location
     }
 
@@ -74,19 +76,19 @@ impl Laplace {
     /// let n = Laplace::new(0.0, 1.0).unwrap();
     /// assert_eq!(n.scale(), 1.0);
     /// ```
-    pub fn scale(&self) -> f64 {
+    pub fn scale(&self) -> T {
         self.scale
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Laplace {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let x: f64 = rng.gen_range(-0.5..0.5);
-        self.location - self.scale * x.signum() * (1. - 2. * x.abs()).ln()
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Laplace<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let x: T = cast(rng.gen_range(-0.5..0.5));
+        self.location - self.scale * x.signum() * (T::one() - cast::<T>(2.0) * x.abs()).ln()
     }
 }
 
-impl ContinuousCDF<f64, f64> for Laplace {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Laplace<T> {
     /// Calculates the cumulative distribution function for the
     /// laplace distribution at `x`
     ///
@@ -97,10 +99,10 @@ impl ContinuousCDF<f64, f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location, `b` is the scale
-    fn cdf(&self, x: f64) -> f64 {
-        let y = (-(x - self.location).abs() / self.scale).exp() / 2.;
+    fn cdf(&self, x: T) -> T {
+        let y = (-(x - self.location).abs() / self.scale).exp() / cast(2.0);
         if x >= self.location {
-            1. - y
+            T::one() - y
         } else {
             y
         }
@@ -116,12 +118,12 @@ impl ContinuousCDF<f64, f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location, `b` is the scale
-    fn sf(&self, x: f64) -> f64 {
-        let y = (-(x - self.location).abs() / self.scale).exp() / 2.;
+    fn sf(&self, x: T) -> T {
+        let y = (-(x - self.location).abs() / self.scale).exp() / cast(2.0);
         if x >= self.location {
             y
         } else {
-            1. - y
+            T::one() - y
         }
     }
 
@@ -140,19 +142,19 @@ impl ContinuousCDF<f64, f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location, `b` is the scale
-    fn inverse_cdf(&self, p: f64) -> f64 {
-        if p <= 0. || 1. <= p {
+    fn inverse_cdf(&self, p: T) -> T {
+        if p <= T::zero() || T::one() <= p {
             panic!("p must be in [0, 1]");
         };
-        if p <= 0.5 {
-            self.location + self.scale * (2. * p).ln()
+        if p <= cast(0.5) {
+            self.location + self.scale * (cast::<T>(2.0) * p).ln()
         } else {
-            self.location - self.scale * (2. - 2. * p).ln()
+            self.location - self.scale * (cast::<T>(2.0) - cast::<T>(2.0) * p).ln()
         }
     }
 }
 
-impl Min<f64> for Laplace {
+impl<T: SpecialFloat> Min<T> for Laplace<T> {
     /// Returns the minimum value in the domain of the laplace
     /// distribution representable by a double precision float
     ///
@@ -161,12 +163,12 @@ impl Min<f64> for Laplace {
     /// ```ignore
     /// NEG_INF
     /// ```
-    fn min(&self) -> f64 {
-        f64::NEG_INFINITY
+    fn min(&self) -> T {
+        T::neg_infinity()
     }
 }
 
-impl Max<f64> for Laplace {
+impl<T: SpecialFloat> Max<T> for Laplace<T> {
     /// Returns the maximum value in the domain of the laplace
     /// distribution representable by a double precision float
     ///
@@ -175,12 +177,12 @@ impl Max<f64> for Laplace {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Laplace {
+impl<T: SpecialFloat> Distribution<T> for Laplace<T> {
     /// Returns the mode of the laplace distribution
     ///
     /// # Formula
@@ -190,7 +192,7 @@ impl Distribution<f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         Some(self.location)
     }
     /// Returns the variance of the laplace distribution
@@ -202,8 +204,8 @@ impl Distribution<f64> for Laplace {
     /// ```
     ///
     /// where `b` is the scale
-    fn variance(&self) -> Option<f64> {
-        Some(2. * self.scale * self.scale)
+    fn variance(&self) -> Option<T> {
+        Some(cast::<T>(2.0) * self.scale * self.scale)
     }
     /// Returns the entropy of the laplace distribution
     ///
@@ -214,8 +216,8 @@ impl Distribution<f64> for Laplace {
     /// ```
     ///
     /// where `b` is the scale
-    fn entropy(&self) -> Option<f64> {
-        Some((2. * self.scale).ln() + 1.)
+    fn entropy(&self) -> Option<T> {
+        Some((cast::<T>(2.0) * self.scale).ln() + T::one())
     }
     /// Returns the skewness of the laplace distribution
     ///
@@ -224,12 +226,12 @@ impl Distribution<f64> for Laplace {
     /// ```ignore
     /// 0
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        Some(0.)
+    fn skewness(&self) -> Option<T> {
+        Some(T::zero())
     }
 }
 
-impl Median<f64> for Laplace {
+impl<T: SpecialFloat> Median<T> for Laplace<T> {
     /// Returns the median of the laplace distribution
     ///
     /// # Formula
@@ -239,12 +241,12 @@ impl Median<f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.location
     }
 }
 
-impl Mode<Option<f64>> for Laplace {
+impl<T: SpecialFloat> Mode<Option<T>> for Laplace<T> {
     /// Returns the mode of the laplace distribution
     ///
     /// # Formula
@@ -254,12 +256,12 @@ impl Mode<Option<f64>> for Laplace {
     /// ```
     ///
     /// where `μ` is the location
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.location)
     }
 }
 
-impl Continuous<f64, f64> for Laplace {
+impl<T: SpecialFloat> Continuous<T, T> for Laplace<T> {
     /// Calculates the probability density function for the laplace
     /// distribution at `x`
     ///
@@ -269,8 +271,8 @@ impl Continuous<f64, f64> for Laplace {
     /// (1 / 2b) * exp(-|x - μ| / b)
     /// ```
     /// where `μ` is the location and `b` is the scale
-    fn pdf(&self, x: f64) -> f64 {
-        (-(x - self.location).abs() / self.scale).exp() / (2. * self.scale)
+    fn pdf(&self, x: T) -> T {
+        (-(x - self.location).abs() / self.scale).exp() / (cast::<T>(2.0) * self.scale)
     }
 
     /// Calculates the log probability density function for the laplace
@@ -283,16 +285,60 @@ impl Continuous<f64, f64> for Laplace {
     /// ```
     ///
     /// where `μ` is the location and `b` is the scale
-    fn ln_pdf(&self, x: f64) -> f64 {
-        ((-(x - self.location).abs() / self.scale).exp() / (2. * self.scale)).ln()
+    fn ln_pdf(&self, x: T) -> T {
+        ((-(x - self.location).abs() / self.scale).exp() / (cast::<T>(2.0) * self.scale)).ln()
+    }
+}
+
+impl Fit<f64> for Laplace {
+    /// Returns the maximum likelihood estimate of the laplace distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or all observations are equal
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ = median(x_i)
+    /// b = (1 / n) * Σ |x_i - μ|
+    /// ```
+    ///
+    /// where `μ` is the location and `b` is the scale
+    fn fit_mle(data: &[f64]) -> Result<Laplace> {
+        if data.is_empty() {
+            return Err(StatsError::BadParams);
+        }
+        let location = OrderStatistics::median(&mut Data::new(data.to_vec()));
+        let scale = data.iter().map(|x| (x - location).abs()).mean();
+        Laplace::new(location, scale)
+    }
+
+    /// Returns the method of moments estimate of the laplace distribution
+    /// for the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ = m
+    /// b = sqrt(s^2 / 2)
+    /// ```
+    ///
+    /// where `μ` is the location, `b` is the scale and `m` and `s^2` are the
+    /// mean and the population variance of the observations
+    fn fit_mom(data: &[f64]) -> Result<Laplace> {
+        Laplace::new(data.mean(), (data.population_variance() / 2.0).sqrt())
     }
 }
 
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use super::*;
+    use crate::distribution::Fit;
     use core::f64::INFINITY as INF;
     use rand::thread_rng;
+    use std::f64;
 
     fn try_create(location: f64, scale: f64) -> Laplace {
         let n = Laplace::new(location, scale);
@@ -586,4 +632,24 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Laplace::<f32>::new(1.0, 2.0).unwrap();
+        let d64 = Laplace::new(1.0, 2.0).unwrap();
+        crate::distribution::internal::test::check_f32_matches_f64(&d32, &d64, &[-3.0, 0.0, 1.0, 2.0, 6.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+        let n = Laplace::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.location(), 0.2, 1e-15);
+        assert_almost_eq!(n.scale(), 1.62, 1e-15);
+        assert!(n.observed_information(&data).is_none());
+        let n = Laplace::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.location(), 0.5, 1e-15);
+        assert_almost_eq!(n.scale(), 1.5394804318340652, 1e-15);
+        assert!(Laplace::fit_mle(&[1.0, 1.0]).is_err());
+    }
 }
@@ -1,9 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::erf;
+use crate::distribution::{Continuous, ContinuousCDF, Fit};
+use crate::function::{erf, float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
-use std::f64;
 
 /// Implements the
 /// [Log-normal](https://en.wikipedia.org/wiki/Log-normal_distribution)
@@ -21,12 +21,12 @@ use std::f64;
 /// assert!(prec::almost_eq(n.pdf(1.0), 0.3989422804014326779399, 1e-16));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct LogNormal {
-    location: f64,
-    scale: f64,
+pub struct LogNormal<T = f64> {
+    location: T,
+    scale: T,
 }
 
-impl LogNormal {
+impl<T: SpecialFloat> LogNormal<T> {
     /// Constructs a new log-normal distribution with a location of `location`
     /// and a scale of `scale`
     ///
@@ -46,8 +46,8 @@ impl LogNormal {
     /// result = LogNormal::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(location: f64, scale: f64) -> Result<LogNormal> {
-        if location.is_nan() || scale.is_nan() || scale <= 0.0 {
+    pub fn new(location: T, scale: T) -> Result<LogNormal<T>> {
+        if location.is_nan() || scale.is_nan() || scale <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(LogNormal { location, scale })
@@ -55,13 +55,13 @@ impl LogNormal {
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for LogNormal {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for LogNormal<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         super::normal::sample_unchecked(rng, self.location, self.scale).exp()
     }
 }
 
-impl ContinuousCDF<f64, f64> for LogNormal {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for LogNormal<T> {
     /// Calculates the cumulative distribution function for the log-normal
     /// distribution
     /// at `x`
@@ -74,13 +74,13 @@ impl ContinuousCDF<f64, f64> for LogNormal {
     ///
     /// where `μ` is the location, `σ` is the scale, and `erf` is the
     /// error function
-    fn cdf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
         } else if x.is_infinite() {
-            1.0
+            T::one()
         } else {
-            0.5 * erf::erfc((self.location - x.ln()) / (self.scale * f64::consts::SQRT_2))
+            cast::<T>(0.5) * erf::erfc((self.location - x.ln()) / (self.scale * T::SQRT_2()))
         }
     }
 
@@ -104,18 +104,40 @@ impl ContinuousCDF<f64, f64> for LogNormal {
     ///  Φ(-x) + Φ(x) = 1
     ///  Φ(-x)        = 1 - Φ(x) 
     /// ```
-    fn sf(&self, x: f64) -> f64 {
-        if x <= 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
         } else if x.is_infinite() {
-            0.0
+            T::zero()
         } else {
-            0.5 * erf::erfc((x.ln() - self.location) / (self.scale * f64::consts::SQRT_2))
+            cast::<T>(0.5) * erf::erfc((x.ln() - self.location) / (self.scale * T::SQRT_2()))
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// log-normal distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// exp(μ - sqrt(2) * σ * erfc_inv(2p))
+    /// ```
+    ///
+    /// where `μ` is the location, `σ` is the scale and `erfc_inv` is
+    /// the inverse of the complementary error function
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        (self.location - self.scale * T::SQRT_2() * erf::erfc_inv(cast::<T>(2.0) * p)).exp()
+    }
 }
 
-impl Min<f64> for LogNormal {
+impl<T: SpecialFloat> Min<T> for LogNormal<T> {
     /// Returns the minimum value in the domain of the log-normal
     /// distribution representable by a double precision float
     ///
@@ -124,12 +146,12 @@ impl Min<f64> for LogNormal {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for LogNormal {
+impl<T: SpecialFloat> Max<T> for LogNormal<T> {
     /// Returns the maximum value in the domain of the log-normal
     /// distribution representable by a double precision float
     ///
@@ -138,12 +160,12 @@ impl Max<f64> for LogNormal {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for LogNormal {
+impl<T: SpecialFloat> Distribution<T> for LogNormal<T> {
     /// Returns the mean of the log-normal distribution
     ///
     /// # Formula
@@ -153,8 +175,8 @@ impl Distribution<f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn mean(&self) -> Option<f64> {
-        Some((self.location + self.scale * self.scale / 2.0).exp())
+    fn mean(&self) -> Option<T> {
+        Some((self.location + self.scale * self.scale / cast(2.0)).exp())
     }
     /// Returns the variance of the log-normal distribution
     ///
@@ -165,9 +187,9 @@ impl Distribution<f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         let sigma2 = self.scale * self.scale;
-        Some((sigma2.exp() - 1.0) * (self.location + self.location + sigma2).exp())
+        Some((sigma2.exp() - T::one()) * (self.location + self.location + sigma2).exp())
     }
     /// Returns the entropy of the log-normal distribution
     ///
@@ -178,8 +200,8 @@ impl Distribution<f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn entropy(&self) -> Option<f64> {
-        Some(0.5 + self.scale.ln() + self.location + consts::LN_SQRT_2PI)
+    fn entropy(&self) -> Option<T> {
+        Some(cast::<T>(0.5) + self.scale.ln() + self.location + cast(consts::LN_SQRT_2PI))
     }
     /// Returns the skewness of the log-normal distribution
     ///
@@ -190,13 +212,13 @@ impl Distribution<f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         let expsigma2 = (self.scale * self.scale).exp();
-        Some((expsigma2 + 2.0) * (expsigma2 - 1.0).sqrt())
+        Some((expsigma2 + cast(2.0)) * (expsigma2 - T::one()).sqrt())
     }
 }
 
-impl Median<f64> for LogNormal {
+impl<T: SpecialFloat> Median<T> for LogNormal<T> {
     /// Returns the median of the log-normal distribution
     ///
     /// # Formula
@@ -206,12 +228,12 @@ impl Median<f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.location.exp()
     }
 }
 
-impl Mode<Option<f64>> for LogNormal {
+impl<T: SpecialFloat> Mode<Option<T>> for LogNormal<T> {
     /// Returns the mode of the log-normal distribution
     ///
     /// # Formula
@@ -221,12 +243,12 @@ impl Mode<Option<f64>> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some((self.location - self.scale * self.scale).exp())
     }
 }
 
-impl Continuous<f64, f64> for LogNormal {
+impl<T: SpecialFloat> Continuous<T, T> for LogNormal<T> {
     /// Calculates the probability density function for the log-normal
     /// distribution at `x`
     ///
@@ -237,12 +259,12 @@ impl Continuous<f64, f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn pdf(&self, x: f64) -> f64 {
-        if x <= 0.0 || x.is_infinite() {
-            0.0
+    fn pdf(&self, x: T) -> T {
+        if x <= T::zero() || x.is_infinite() {
+            T::zero()
         } else {
             let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d).exp() / (x * consts::SQRT_2PI * self.scale)
+            (-cast::<T>(0.5) * d * d).exp() / (x * cast(consts::SQRT_2PI) * self.scale)
         }
     }
 
@@ -256,20 +278,75 @@ impl Continuous<f64, f64> for LogNormal {
     /// ```
     ///
     /// where `μ` is the location and `σ` is the scale
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if x <= 0.0 || x.is_infinite() {
-            f64::NEG_INFINITY
+    fn ln_pdf(&self, x: T) -> T {
+        if x <= T::zero() || x.is_infinite() {
+            T::neg_infinity()
         } else {
             let d = (x.ln() - self.location) / self.scale;
-            (-0.5 * d * d) - consts::LN_SQRT_2PI - (x * self.scale).ln()
+            (-cast::<T>(0.5) * d * d) - cast(consts::LN_SQRT_2PI) - (x * self.scale).ln()
         }
     }
 }
 
+impl Fit<f64> for LogNormal {
+    /// Returns the maximum likelihood estimate of the log-normal
+    /// distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is not positive
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ = (1 / n) * Σ ln(x_i)
+    /// σ = sqrt((1 / n) * Σ (ln(x_i) - μ)^2)
+    /// ```
+    ///
+    /// where `n` is the number of observations
+    fn fit_mle(data: &[f64]) -> Result<LogNormal> {
+        if data.iter().any(|&x| x.is_nan() || x <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let ln_data: Vec<f64> = data.iter().map(|x| x.ln()).collect();
+        LogNormal::new(ln_data.iter().mean(), ln_data.iter().population_std_dev())
+    }
+
+    /// Returns the method of moments estimate of the log-normal
+    /// distribution for the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// σ = sqrt(ln(1 + s^2 / m^2))
+    /// μ = ln(m) - σ^2 / 2
+    /// ```
+    ///
+    /// where `m` and `s^2` are the mean and the population variance of the
+    /// observations
+    fn fit_mom(data: &[f64]) -> Result<LogNormal> {
+        let mean = data.mean();
+        let var = (data.population_variance() / (mean * mean)).ln_1p();
+        LogNormal::new(mean.ln() - var / 2.0, var.sqrt())
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(μ, σ)`, which is that of the normal distribution for the logarithms
+    /// of the observations
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        Some(super::normal::observed_information_unchecked(
+            data.iter().map(|x| x.ln()),
+            self.location,
+            self.scale,
+        ))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
+    use crate::distribution::Fit;
     use crate::distribution::{ContinuousCDF, Continuous, LogNormal};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
@@ -655,4 +732,39 @@ mod tests {
         test::check_continuous_distribution(&try_create(0.0, 0.25), 0.0, 10.0);
         test::check_continuous_distribution(&try_create(0.0, 0.5), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: LogNormal| x.inverse_cdf(arg);
+        test_almost(0.0, 1.0, 0.27760624185200983052, 1e-15, inverse_cdf(0.1));
+        test_almost(0.0, 1.0, 1.0, 1e-14, inverse_cdf(0.5));
+        test_almost(0.0, 1.0, 3.6022244792791581731, 1e-14, inverse_cdf(0.9));
+        test_almost(2.0, 0.5, 3.8931718742913673446, 1e-14, inverse_cdf(0.1));
+        test_almost(2.0, 0.5, 7.3890560989306502272, 1e-14, inverse_cdf(0.5));
+        test_almost(2.0, 0.5, 14.024079027613483866, 1e-13, inverse_cdf(0.9));
+        test_almost(-1.0, 3.0, 0.0078703346388770765507, 1e-17, inverse_cdf(0.1));
+        test_almost(-1.0, 3.0, 0.3678794411714423216, 1e-15, inverse_cdf(0.5));
+        test_almost(-1.0, 3.0, 17.195619938204066118, 1e-13, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = LogNormal::<f32>::new(0.0, 0.5).unwrap();
+        let d64 = LogNormal::new(0.0, 0.5).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 0.5, 1.0, 2.0, 4.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+        let n = LogNormal::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.location, 0.15222993625933121, 1e-15);
+        assert_almost_eq!(n.scale, 0.72518366866616543, 1e-15);
+        test::check_observed_information(n.observed_information(&data), &[19.015334512056791, 0.0, 0.0, 38.030669024113581], 1e-13);
+        let n = LogNormal::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.location, 0.20799237808139064, 1e-15);
+        assert_almost_eq!(n.scale, 0.60893373825483591, 1e-15);
+        assert!(LogNormal::fit_mle(&[1.0, 0.0]).is_err());
+        assert!(LogNormal::fit_mle(&[1.0, -2.0]).is_err());
+    }
 }
@@ -2,6 +2,7 @@
 //! and provides
 //! concrete implementations for a variety of distributions.
 use super::statistics::{Max, Min};
+use ::nalgebra::DMatrix;
 use ::num_traits::{float::Float, Bounded, Num};
 
 pub use self::bernoulli::Bernoulli;
@@ -20,13 +21,19 @@ pub use self::exponential::Exp;
 pub use self::fisher_snedecor::FisherSnedecor;
 pub use self::gamma::Gamma;
 pub use self::geometric::Geometric;
+pub use self::histogram::{BinRule, Histogram};
 pub use self::hypergeometric::Hypergeometric;
 pub use self::inverse_gamma::InverseGamma;
+pub use self::kernel_density::{Bandwidth, Kernel, KernelDensity};
+pub use self::kolmogorov::Kolmogorov;
 pub use self::laplace::Laplace;
 pub use self::log_normal::LogNormal;
 pub use self::multinomial::Multinomial;
 pub use self::multivariate_normal::MultivariateNormal;
 pub use self::negative_binomial::NegativeBinomial;
+pub use self::noncentral_chi_squared::NoncentralChiSquared;
+pub use self::noncentral_f::NoncentralF;
+pub use self::noncentral_t::NoncentralT;
 pub use self::normal::Normal;
 pub use self::pareto::Pareto;
 pub use self::poisson::Poisson;
@@ -34,6 +41,7 @@ pub use self::students_t::StudentsT;
 pub use self::triangular::Triangular;
 pub use self::uniform::Uniform;
 pub use self::weibull::Weibull;
+pub use self::weighted_empirical::WeightedEmpirical;
 pub use self::wishart::Wishart;
 pub use self::inverse_wishart::InverseWishart;
 
@@ -53,15 +61,21 @@ mod exponential;
 mod fisher_snedecor;
 mod gamma;
 mod geometric;
+mod histogram;
 mod hypergeometric;
 #[macro_use]
-mod internal;
+pub(crate) mod internal;
 mod inverse_gamma;
+mod kernel_density;
+mod kolmogorov;
 mod laplace;
 mod log_normal;
 mod multinomial;
 mod multivariate_normal;
 mod negative_binomial;
+mod noncentral_chi_squared;
+mod noncentral_f;
+mod noncentral_t;
 mod normal;
 mod pareto;
 mod poisson;
@@ -69,6 +83,7 @@ mod students_t;
 mod triangular;
 mod uniform;
 mod weibull;
+mod weighted_empirical;
 mod ziggurat;
 mod ziggurat_tables;
 mod wishart;
@@ -111,8 +126,9 @@ pub trait ContinuousCDF<K: Float, T: Float>: Min<K> + Max<K> {
     /// implementation may be ill-behaved.
     /// Specialized inverse cdfs should be used whenever possible.
     /// Performs a binary search on the domain of `cdf` to obtain an approximation
-    /// of `F^-1(p) := inf { x | F(x) >= p }`. Needless to say, performance may
-    /// may be lacking.
+    /// of `F^-1(p) := inf { x | F(x) >= p }`. The search is started from the
+    /// bounds of the support where these are finite and runs until the bracket
+    /// can no longer be split. Needless to say, performance may be lacking.
     fn inverse_cdf(&self, p: T) -> K {
         if p == T::zero() {
             return self.min();
@@ -121,31 +137,43 @@ pub trait ContinuousCDF<K: Float, T: Float>: Min<K> + Max<K> {
             return self.max();
         };
         let two = K::one() + K::one();
-        let mut high = two;
-        let mut low = -high;
-        while self.cdf(low) > p {
-            low = low + low;
+        let mut low = self.min();
+        if low.is_finite() {
+            if self.cdf(low) >= p {
+                return low;
+            }
+        } else {
+            low = -two;
+            while self.cdf(low) >= p {
+                low = low + low;
+            }
         }
-        while self.cdf(high) < p {
-            high = high + high;
+        let mut high = self.max();
+        if !high.is_finite() {
+            high = low.abs().max(two);
+            while self.cdf(high) < p {
+                high = high + high;
+            }
         }
-        let mut i = 16;
-        while i != 0 {
-            let mid = (high + low) / two;
+        loop {
+            let mid = low + (high - low) / two;
+            if mid <= low || mid >= high {
+                break;
+            }
             if self.cdf(mid) >= p {
                 high = mid;
             } else {
                 low = mid;
             }
-            i -= 1;
         }
-        (high + low) / two
+        high
     }
 }
 
 /// The `DiscreteCDF` trait is used to specify an interface for univariate
-/// discrete distributions.
-pub trait DiscreteCDF<K: Bounded + Clone + Num, T: Float>: Min<K> + Max<K> {
+/// discrete distributions. The support type `K` must be `PartialOrd`, which
+/// the default `inverse_cdf` relies on to search it.
+pub trait DiscreteCDF<K: Bounded + Clone + Num + PartialOrd, T: Float>: Min<K> + Max<K> {
     /// Returns the cumulative distribution function calculated
     /// at `x` for a given distribution. May panic depending
     /// on the implementor.
@@ -175,8 +203,11 @@ pub trait DiscreteCDF<K: Bounded + Clone + Num, T: Float>: Min<K> + Max<K> {
 
     /// Due to issues with rounding and floating-point accuracy the default implementation may be ill-behaved
     /// Specialized inverse cdfs should be used whenever possible.
+    /// Performs an exponential search upwards from `min` followed by a binary
+    /// search on the bracket found to obtain `F^-1(p) := inf { x | F(x) >= p }`.
+    /// The search never leaves `[min, max]` and does not overflow `K`, even
+    /// for a support spanning its whole range.
     fn inverse_cdf(&self, p: T) -> K {
-        // TODO: fix integer implementation
         if p == T::zero() {
             return self.min();
         };
@@ -184,13 +215,45 @@ pub trait DiscreteCDF<K: Bounded + Clone + Num, T: Float>: Min<K> + Max<K> {
             return self.max();
         };
         let two = K::one() + K::one();
-        let mut high = two.clone();
-        let mut low = K::min_value();
-        while self.cdf(high.clone()) < p {
-            high = high.clone() + high.clone();
+        let max = self.max();
+        let mut low = self.min();
+        if self.cdf(low.clone()) >= p {
+            return low;
+        }
+        // invariant: cdf(low) < p <= cdf(high)
+        let mut high;
+        let mut step = K::one();
+        loop {
+            // `max - low` cannot overflow for a non-negative `low`, nor
+            // `low + step` for a negative one
+            let reaches_max = if low >= K::zero() {
+                max.clone() - low.clone() <= step
+            } else {
+                low.clone() + step.clone() >= max
+            };
+            if reaches_max {
+                high = max;
+                break;
+            }
+            high = low.clone() + step.clone();
+            if self.cdf(high.clone()) >= p {
+                break;
+            }
+            low = high.clone();
+            step = if step > K::max_value() / two.clone() {
+                K::max_value()
+            } else {
+                step.clone() + step
+            };
         }
-        while high != low {
-            let mid = (high.clone() + low.clone()) / two.clone();
+        while low.clone() + K::one() < high {
+            // the difference of bounds of the same sign and the sum of
+            // bounds of opposite signs cannot overflow
+            let mid = if (low >= K::zero()) == (high >= K::zero()) {
+                low.clone() + (high.clone() - low.clone()) / two.clone()
+            } else {
+                (low.clone() + high.clone()) / two.clone()
+            };
             if self.cdf(mid.clone()) >= p {
                 high = mid;
             } else {
@@ -278,3 +341,52 @@ pub trait Discrete<K, T> {
     /// ```
     fn ln_pmf(&self, x: K) -> T;
 }
+
+/// The `Fit` trait is used to specify an interface for estimating the
+/// parameters of a univariate distribution from a sample of observations.
+pub trait Fit<K>: Sized {
+    /// Returns the maximum likelihood estimate of the distribution for the
+    /// observations in `data`. Closed forms are used where they exist, the
+    /// likelihood equations are solved iteratively otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StatsError::BadParams` if `data` is too small, lies outside
+    /// the support of the distribution or does not determine the parameters
+    /// (e.g. a constant sample), and
+    /// `StatsError::ComputationFailedToConverge` if the likelihood equations
+    /// could not be solved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::{Exp, Fit};
+    /// use statrs::statistics::Distribution;
+    ///
+    /// let n = Exp::fit_mle(&[0.5, 1.0, 1.5]).unwrap();
+    /// assert_eq!(n.mean().unwrap(), 1.0);
+    /// ```
+    fn fit_mle(data: &[K]) -> Result<Self>;
+
+    /// Returns the method of moments estimate of the distribution for the
+    /// observations in `data`, i.e. the distribution whose leading moments
+    /// equal the (population) moments of the sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StatsError::BadParams` if `data` is too small or its moments
+    /// cannot be matched by any distribution of the family.
+    fn fit_mom(data: &[K]) -> Result<Self>;
+
+    /// Returns the observed Fisher information of `data`, i.e. the negative
+    /// Hessian of the log-likelihood with respect to the parameters, ordered
+    /// as they are passed to `new`, evaluated at the parameters of the
+    /// distribution. At the maximum likelihood estimate its inverse is the
+    /// usual estimate of the covariance of the estimated parameters.
+    ///
+    /// Returns `None` if the log-likelihood is not twice differentiable in the
+    /// parameters, e.g. because the support depends on them.
+    fn observed_information(&self, _data: &[K]) -> Option<DMatrix<f64>> {
+        None
+    }
+}
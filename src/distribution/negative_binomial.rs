@@ -1,7 +1,8 @@
-use crate::distribution::{self, poisson, Discrete, DiscreteCDF};
+use crate::distribution::{self, internal, poisson, Discrete, DiscreteCDF, Fit};
 use crate::function::{beta, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
 use std::f64;
 
@@ -145,6 +146,30 @@ impl DiscreteCDF<u64, f64> for NegativeBinomial {
     fn sf(&self, x: u64) -> f64 {
         beta::beta_reg(x as f64 + 1.0, self.r, 1. - self.p)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// negative binomial distribution at `p`, i.e. the smallest `x` such that `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The Cornish-Fisher expansion of the quantile is used as the initial
+    /// guess of a local search over the support
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let guess = internal::cornish_fisher_quantile(
+            p,
+            self.mean().unwrap(),
+            self.std_dev().unwrap(),
+            self.skewness().unwrap(),
+        );
+        internal::discrete_inverse_cdf(self, p, guess)
+    }
 }
 
 impl Min<u64> for NegativeBinomial {
@@ -280,10 +305,105 @@ impl Discrete<u64, f64> for NegativeBinomial {
     }
 }
 
+impl Fit<u64> for NegativeBinomial {
+    /// Returns the maximum likelihood estimate of the negative binomial
+    /// distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the observations are not overdispersed, i.e. if
+    /// their variance does not exceed their mean, in which case the
+    /// likelihood increases towards the poisson limit `r -> ∞`
+    ///
+    /// # Remarks
+    ///
+    /// `p` is profiled out of the likelihood and `r` is found by solving
+    /// the remaining likelihood equation with Newton's method
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ ψ(x_i + r) - n * ψ(r) + n * ln(r / (r + m)) = 0
+    /// p = r / (r + m)
+    /// ```
+    ///
+    /// where `m` is the mean of the observations and `ψ` is the digamma
+    /// function
+    fn fit_mle(data: &[u64]) -> Result<NegativeBinomial> {
+        let guess = NegativeBinomial::fit_mom(data)?;
+        let n = data.len() as f64;
+        let mean = data.iter().map(|&x| x as f64).mean();
+        let r = internal::positive_root(
+            |r| {
+                data.iter().fold(
+                    (
+                        n * ((r / (r + mean)).ln() - gamma::digamma(r)),
+                        n * (1.0 / r - 1.0 / (r + mean) - gamma::trigamma(r)),
+                    ),
+                    |(s, ds), &x| {
+                        let x = x as f64;
+                        (s + gamma::digamma(x + r), ds + gamma::trigamma(x + r))
+                    },
+                )
+            },
+            guess.r,
+        )?;
+        NegativeBinomial::new(r, r / (r + mean))
+    }
+
+    /// Returns the method of moments estimate of the negative binomial
+    /// distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the observations are not overdispersed, i.e. if
+    /// their variance does not exceed their mean
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// p = m / s^2
+    /// r = m^2 / (s^2 - m)
+    /// ```
+    ///
+    /// where `m` and `s^2` are the mean and population variance of the
+    /// observations
+    fn fit_mom(data: &[u64]) -> Result<NegativeBinomial> {
+        let mean = data.iter().map(|&x| x as f64).mean();
+        let var = data.iter().map(|&x| x as f64).population_variance();
+        if mean.is_nan() || var <= mean || mean <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        NegativeBinomial::new(mean * mean / (var - mean), mean / var)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(r, p)`
+    fn observed_information(&self, data: &[u64]) -> Option<DMatrix<f64>> {
+        let (r, p) = (self.r, self.p);
+        let n = data.len() as f64;
+        let (trigamma_sum, sum) = data.iter().fold((0.0, 0.0), |(t, s), &x| {
+            let x = x as f64;
+            (t + gamma::trigamma(x + r), s + x)
+        });
+        Some(DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                n * gamma::trigamma(r) - trigamma_sum,
+                -n / p,
+                -n / p,
+                n * r / (p * p) + sum / ((1.0 - p) * (1.0 - p)),
+            ],
+        ))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
+    use crate::distribution::Fit;
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, NegativeBinomial};
     use crate::distribution::internal::test;
@@ -502,4 +622,48 @@ mod tests {
         let sf = |arg: u64| move |x: NegativeBinomial| x.sf(arg);
         test_almost(3.0, 0.5, 5.282409836586059e-28, 1e-28, sf(100));
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: NegativeBinomial| x.inverse_cdf(arg);
+        test_case(5.0, 0.3, 1, inverse_cdf(0.01));
+        test_case(5.0, 0.3, 8, inverse_cdf(0.3));
+        test_case(5.0, 0.3, 11, inverse_cdf(0.5));
+        test_case(5.0, 0.3, 20, inverse_cdf(0.9));
+        test_case(5.0, 0.3, 39, inverse_cdf(0.999));
+        test_case(10.0, 0.7, 0, inverse_cdf(0.01));
+        test_case(10.0, 0.7, 3, inverse_cdf(0.3));
+        test_case(10.0, 0.7, 4, inverse_cdf(0.5));
+        test_case(10.0, 0.7, 8, inverse_cdf(0.9));
+        test_case(10.0, 0.7, 14, inverse_cdf(0.999));
+        test_case(0.5, 0.1, 0, inverse_cdf(0.01));
+        test_case(0.5, 0.1, 0, inverse_cdf(0.3));
+        test_case(0.5, 0.1, 2, inverse_cdf(0.5));
+        test_case(0.5, 0.1, 13, inverse_cdf(0.9));
+        test_case(0.5, 0.1, 51, inverse_cdf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        test::check_discrete_inverse_cdf(&try_create(5.0, 0.3));
+        test::check_discrete_inverse_cdf(&try_create(10.0, 0.7));
+        test::check_discrete_inverse_cdf(&try_create(0.5, 0.1));
+        test::check_discrete_inverse_cdf(&try_create(1.0, 1.0));
+        test::check_discrete_inverse_cdf(&try_create(1e3, 0.01));
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0, 3, 1, 7, 2, 0, 5, 1, 9, 2, 4, 0];
+        let n = NegativeBinomial::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.r(), 1.2374598237586843, 1e-12);
+        assert_almost_eq!(n.p(), 0.30398494249279597, 1e-12);
+        test::check_observed_information(n.observed_information(&data), &[8.2008143469954536, -39.47563948923024, -39.47563948923024, 230.88173362244625], 1e-12);
+        let n = NegativeBinomial::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.r(), 1.6145251396648045, 1e-14);
+        assert_almost_eq!(n.p(), 0.36298932384341637, 1e-14);
+        // not overdispersed
+        assert!(NegativeBinomial::fit_mle(&[1, 2, 3]).is_err());
+        assert!(NegativeBinomial::fit_mom(&[]).is_err());
+    }
 }
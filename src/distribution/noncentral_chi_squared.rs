@@ -0,0 +1,459 @@
+use crate::distribution::{internal, ChiSquared, Continuous, ContinuousCDF};
+use crate::function::{float::cast, gamma, SpecialFloat};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+
+/// Implements the [noncentral
+/// chi-squared](https://en.wikipedia.org/wiki/Noncentral_chi-squared_distribution)
+/// distribution, the distribution of the sum of the squares of `k`
+/// independent normal variables with unit variance whose means have the
+/// sum of squares `λ`
+///
+/// # Remarks
+///
+/// The distribution is a Poisson mixture of central chi-squared
+/// distributions. The series are summed outwards from their largest term
+/// as in Benton and Krishnamoorthy (2003), which keeps them accurate and
+/// their number of terms of order `sqrt(λ)` for large noncentrality.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, NoncentralChiSquared};
+/// use statrs::statistics::Distribution;
+/// use statrs::prec;
+///
+/// let n = NoncentralChiSquared::new(3.0, 2.0).unwrap();
+/// assert_eq!(n.mean().unwrap(), 5.0);
+/// assert!(prec::almost_eq(n.cdf(5.0), 0.593405180083155627, 1e-14));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NoncentralChiSquared<T = f64> {
+    freedom: T,
+    noncentrality: T,
+}
+
+impl<T: SpecialFloat> NoncentralChiSquared<T> {
+    /// Constructs a new noncentral chi-squared distribution with `freedom`
+    /// degrees of freedom and noncentrality parameter `noncentrality`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `freedom` or `noncentrality` are `NaN` or
+    /// infinite, `freedom <= 0.0` or `noncentrality < 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralChiSquared;
+    ///
+    /// let mut result = NoncentralChiSquared::new(3.0, 2.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NoncentralChiSquared::new(3.0, -1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(freedom: T, noncentrality: T) -> Result<NoncentralChiSquared<T>> {
+        if !freedom.is_finite()
+            || !noncentrality.is_finite()
+            || freedom <= T::zero()
+            || noncentrality < T::zero()
+        {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(NoncentralChiSquared {
+                freedom,
+                noncentrality,
+            })
+        }
+    }
+
+    /// Returns the degrees of freedom of the noncentral chi-squared
+    /// distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralChiSquared;
+    ///
+    /// let n = NoncentralChiSquared::new(3.0, 2.0).unwrap();
+    /// assert_eq!(n.freedom(), 3.0);
+    /// ```
+    pub fn freedom(&self) -> T {
+        self.freedom
+    }
+
+    /// Returns the noncentrality parameter of the noncentral chi-squared
+    /// distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralChiSquared;
+    ///
+    /// let n = NoncentralChiSquared::new(3.0, 2.0).unwrap();
+    /// assert_eq!(n.noncentrality(), 2.0);
+    /// ```
+    pub fn noncentrality(&self) -> T {
+        self.noncentrality
+    }
+
+    /// Returns the central chi-squared distribution with the same degrees
+    /// of freedom
+    fn central(&self) -> ChiSquared<T> {
+        ChiSquared::new(self.freedom).unwrap()
+    }
+
+    /// Returns the Poisson mixture `Σ w_j * term(j)` with the Poisson
+    /// weights of mean `λ / 2`, which is positive
+    fn mixture<F: Fn(T) -> T>(&self, term: F) -> T {
+        let mean = self.noncentrality / cast(2.0);
+        let (ln_weight, sum) = internal::outward_series(
+            |j: T| -mean + j * mean.ln() - gamma::ln_gamma(j + T::one()),
+            |j| mean / (j + T::one()),
+            term,
+        );
+        ln_weight.exp() * sum
+    }
+}
+
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for NoncentralChiSquared<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
+        // a chi-squared variable with k + 2J degrees of freedom, where J is
+        // Poisson distributed with mean λ / 2
+        let j = if self.noncentrality > T::zero() {
+            let mean = (self.noncentrality / cast(2.0)).to_f64().unwrap();
+            cast(super::poisson::sample_unchecked(r, mean))
+        } else {
+            T::zero()
+        };
+        super::gamma::sample_unchecked(r, self.freedom / cast(2.0) + j, cast(0.5))
+    }
+}
+
+impl<T: SpecialFloat> ContinuousCDF<T, T> for NoncentralChiSquared<T> {
+    /// Calculates the cumulative distribution function for the noncentral
+    /// chi-squared distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * P(k / 2 + j, x / 2)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom, `λ` is the noncentrality and
+    /// `P` is the lower regularized incomplete gamma function
+    fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
+        } else if x.is_infinite() {
+            T::one()
+        } else if self.noncentrality == T::zero() {
+            self.central().cdf(x)
+        } else {
+            let a = self.freedom / cast(2.0);
+            let sum = self.mixture(|j| gamma::gamma_lr(a + j, x / cast(2.0)));
+            sum.min(T::one())
+        }
+    }
+
+    /// Calculates the survival function for the noncentral chi-squared
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * Q(k / 2 + j, x / 2)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom, `λ` is the noncentrality and
+    /// `Q` is the upper regularized incomplete gamma function
+    fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
+        } else if x.is_infinite() {
+            T::zero()
+        } else if self.noncentrality == T::zero() {
+            self.central().sf(x)
+        } else {
+            let a = self.freedom / cast(2.0);
+            let sum = self.mixture(|j| gamma::gamma_ur(a + j, x / cast(2.0)));
+            sum.min(T::one())
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// noncentral chi-squared distribution at `p`, by Newton's method
+    /// starting from Patnaik's approximation by a scaled central
+    /// chi-squared distribution
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: T) -> T {
+        let (k, lambda) = (self.freedom, self.noncentrality);
+        let scale = (k + lambda * cast(2.0)) / (k + lambda);
+        let freedom = (k + lambda) / scale;
+        let guess = if p > T::zero() && p < T::one() {
+            scale * gamma::inv_gamma_lr(freedom / cast(2.0), p) * cast(2.0)
+        } else {
+            k + lambda
+        };
+        internal::newton_inverse_cdf(self, p, guess)
+    }
+}
+
+impl<T: SpecialFloat> Min<T> for NoncentralChiSquared<T> {
+    /// Returns the minimum value in the domain of the noncentral
+    /// chi-squared distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn min(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: SpecialFloat> Max<T> for NoncentralChiSquared<T> {
+    /// Returns the maximum value in the domain of the noncentral
+    /// chi-squared distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// INF
+    /// ```
+    fn max(&self) -> T {
+        T::infinity()
+    }
+}
+
+impl<T: SpecialFloat> Distribution<T> for NoncentralChiSquared<T> {
+    /// Returns the mean of the noncentral chi-squared distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// k + λ
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom and `λ` is the noncentrality
+    fn mean(&self) -> Option<T> {
+        Some(self.freedom + self.noncentrality)
+    }
+
+    /// Returns the variance of the noncentral chi-squared distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2 * (k + 2λ)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom and `λ` is the noncentrality
+    fn variance(&self) -> Option<T> {
+        Some((self.freedom + self.noncentrality * cast(2.0)) * cast(2.0))
+    }
+
+    /// Returns the skewness of the noncentral chi-squared distribution
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2^(3 / 2) * (k + 3λ) / (k + 2λ)^(3 / 2)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom and `λ` is the noncentrality
+    fn skewness(&self) -> Option<T> {
+        let (k, lambda) = (self.freedom, self.noncentrality);
+        Some(
+            cast::<T>(8.0).sqrt() * (k + lambda * cast(3.0))
+                / (k + lambda * cast(2.0)).powf(cast(1.5)),
+        )
+    }
+}
+
+impl<T: SpecialFloat> Continuous<T, T> for NoncentralChiSquared<T> {
+    /// Calculates the probability density function for the noncentral
+    /// chi-squared distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * f_(k + 2j)(x)
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom, `λ` is the noncentrality and
+    /// `f_ν` is the density of the central chi-squared distribution with
+    /// `ν` degrees of freedom
+    fn pdf(&self, x: T) -> T {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the noncentral
+    /// chi-squared distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * f_(k + 2j)(x))
+    /// ```
+    ///
+    /// where `k` is the degrees of freedom, `λ` is the noncentrality and
+    /// `f_ν` is the density of the central chi-squared distribution with
+    /// `ν` degrees of freedom
+    fn ln_pdf(&self, x: T) -> T {
+        let (k, lambda) = (self.freedom, self.noncentrality);
+        let two = cast::<T>(2.0);
+        if x < T::zero() || x.is_infinite() {
+            T::neg_infinity()
+        } else if lambda == T::zero() {
+            self.central().ln_pdf(x)
+        } else if x == T::zero() {
+            // only the first term of the mixture can be nonzero
+            self.central().ln_pdf(x) - lambda / two
+        } else {
+            let mean = lambda / two;
+            let (ln_weight, sum) = internal::outward_series(
+                |j: T| {
+                    let a = k / two + j;
+                    -mean + j * mean.ln() - gamma::ln_gamma(j + T::one()) + (a - T::one()) * x.ln()
+                        - x / two
+                        - a * two.ln()
+                        - gamma::ln_gamma(a)
+                },
+                |j| mean * x / (two * (j + T::one()) * (k / two + j)),
+                |_| T::one(),
+            );
+            ln_weight + sum.ln()
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::consts::ACC;
+    use crate::distribution::internal::*;
+    use crate::distribution::{ChiSquared, Continuous, ContinuousCDF, NoncentralChiSquared};
+    use crate::statistics::*;
+    use crate::testing_boiler;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!((f64, f64), NoncentralChiSquared);
+
+    #[test]
+    fn test_create() {
+        try_create((1.0, 0.0));
+        try_create((3.0, 2.0));
+        try_create((0.5, 1e4));
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case((f64::NAN, 1.0));
+        bad_create_case((1.0, f64::NAN));
+        bad_create_case((0.0, 1.0));
+        bad_create_case((f64::INFINITY, 1.0));
+        bad_create_case((1.0, -1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        test_case((3.0, 2.0), 5.0, |x| x.mean().unwrap());
+        test_case((3.0, 2.0), 14.0, |x| x.variance().unwrap());
+        test_case((3.0, 2.0), 1.3744863869781825, |x| x.skewness().unwrap());
+        test_case((3.0, 0.0), ChiSquared::new(3.0).unwrap().skewness().unwrap(), |x| x.skewness().unwrap());
+    }
+
+    #[test]
+    fn test_pdf() {
+        let pdf = |arg: f64| move |x: NoncentralChiSquared| x.pdf(arg);
+        test_case((3.0, 2.0), 0.10044198178668671815, pdf(5.0));
+        test_case((1.0, 1.0), 0.22646662345731036495, pdf(1.0));
+        test_case((10.0, 100.0), 0.01842940292402944411, pdf(100.0));
+        test_case((2.0, 3.0), 0.12265451030192285649, pdf(0.5));
+        test_case((4.0, 1e4), 0.0019946365979918311322, pdf(1e4));
+        test_case((2.0, 3.0), 0.5 * (-1.5f64).exp(), pdf(0.0));
+        test_case((3.0, 2.0), 0.0, pdf(0.0));
+        test_case((3.0, 2.0), 0.0, pdf(-1.0));
+        test_case((1.0, 2.0), f64::INFINITY, pdf(0.0));
+        test_case((3.0, 0.0), ChiSquared::new(3.0).unwrap().pdf(2.5), pdf(2.5));
+    }
+
+    #[test]
+    fn test_ln_pdf() {
+        let ln_pdf = |arg: f64| move |x: NoncentralChiSquared| x.ln_pdf(arg);
+        test_case((3.0, 2.0), 0.10044198178668671815f64.ln(), ln_pdf(5.0));
+        test_case((4.0, 1e4), 0.0019946365979918311322f64.ln(), ln_pdf(1e4));
+        test_case((3.0, 2.0), f64::NEG_INFINITY, ln_pdf(-1.0));
+    }
+
+    #[test]
+    fn test_cdf() {
+        let cdf = |arg: f64| move |x: NoncentralChiSquared| x.cdf(arg);
+        test_case((3.0, 2.0), 0.59340518008315562709, cdf(5.0));
+        test_case((1.0, 1.0), 0.4772498680518207928, cdf(1.0));
+        test_case((10.0, 100.0), 0.32609718206308195062, cdf(100.0));
+        test_case((2.0, 3.0), 0.058784949352640399018, cdf(0.5));
+        // limited by the accuracy of gamma_lr for shapes near 5000
+        test_case_special((4.0, 1e4), 0.15383416959083317848, 1e-11, cdf(9800.0));
+        test_case((3.0, 2.0), 0.0, cdf(0.0));
+        test_case((3.0, 2.0), 1.0, cdf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: NoncentralChiSquared| x.sf(arg);
+        test_case((3.0, 2.0), 1.0 - 0.59340518008315562709, sf(5.0));
+        test_case((10.0, 500.0), 0.026071390810439585548, sf(600.0));
+        test_case_special((4.0, 1e4), 0.0070503134212698753867, 1e-13, sf(10500.0));
+        test_case((3.0, 2.0), 1.0, sf(0.0));
+        test_case((3.0, 2.0), 0.0, sf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &(k, lambda) in &[(3.0, 2.0), (1.0, 1.0), (10.0, 500.0), (3.0, 1e4)] {
+            let n = try_create((k, lambda));
+            for &p in &[1e-10, 0.05, 0.5, 0.95, 1.0 - 1e-6] {
+                let x = n.inverse_cdf(p);
+                let (expected, actual) = if p > 0.5 { (1.0 - p, n.sf(x)) } else { (p, n.cdf(x)) };
+                assert_relative_eq!(expected, actual, max_relative = 1e-10);
+            }
+            assert_eq!(n.inverse_cdf(0.0), 0.0);
+            assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create((3.0, 2.0)), 0.0, 40.0);
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = NoncentralChiSquared::<f32>::new(4.5, 3.0).unwrap();
+        let d64 = NoncentralChiSquared::new(4.5, 3.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.5, 2.0, 7.5, 20.0], 1e-4);
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = try_create((3.0, 20.0));
+        let mut rng = StdRng::seed_from_u64(7);
+        let count = 20_000;
+        let samples: Vec<f64> = (0..count).map(|_| rand::distributions::Distribution::sample(&n, &mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        assert!((mean - 23.0).abs() < 0.15);
+        for &x in &[15.0, 23.0, 30.0] {
+            let below = samples.iter().filter(|&&s| s <= x).count() as f64 / count as f64;
+            assert!((below - n.cdf(x)).abs() < 0.015);
+        }
+    }
+}
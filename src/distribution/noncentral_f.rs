@@ -0,0 +1,483 @@
+use crate::distribution::{
+    internal, Continuous, ContinuousCDF, FisherSnedecor, NoncentralChiSquared,
+};
+use crate::function::{beta, float::cast, gamma, SpecialFloat};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+
+/// Implements the [noncentral
+/// F](https://en.wikipedia.org/wiki/Noncentral_F-distribution) distribution,
+/// the distribution of the ratio of a noncentral chi-squared variable with
+/// `d1` degrees of freedom and noncentrality `λ` to an independent central
+/// chi-squared variable with `d2` degrees of freedom, each divided by its
+/// degrees of freedom
+///
+/// # Remarks
+///
+/// The distribution is a Poisson mixture of beta distributions of
+/// `d1 * x / (d1 * x + d2)`, whose series are summed outwards from their
+/// largest term as for the noncentral chi-squared distribution.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, NoncentralF};
+/// use statrs::statistics::Distribution;
+/// use statrs::prec;
+///
+/// let n = NoncentralF::new(3.0, 10.0, 4.0).unwrap();
+/// assert!(prec::almost_eq(n.mean().unwrap(), 2.9166666666666667, 1e-15));
+/// assert!(prec::almost_eq(n.cdf(2.0), 0.466364216048080100, 1e-14));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NoncentralF<T = f64> {
+    freedom_1: T,
+    freedom_2: T,
+    noncentrality: T,
+}
+
+impl<T: SpecialFloat> NoncentralF<T> {
+    /// Constructs a new noncentral F distribution with `freedom_1` and
+    /// `freedom_2` degrees of freedom and noncentrality parameter
+    /// `noncentrality`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any argument is `NaN` or infinite,
+    /// `freedom_1 <= 0.0`, `freedom_2 <= 0.0` or `noncentrality < 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralF;
+    ///
+    /// let mut result = NoncentralF::new(3.0, 10.0, 4.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NoncentralF::new(0.0, 10.0, 4.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(freedom_1: T, freedom_2: T, noncentrality: T) -> Result<NoncentralF<T>> {
+        if !freedom_1.is_finite()
+            || !freedom_2.is_finite()
+            || !noncentrality.is_finite()
+            || freedom_1 <= T::zero()
+            || freedom_2 <= T::zero()
+            || noncentrality < T::zero()
+        {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(NoncentralF {
+                freedom_1,
+                freedom_2,
+                noncentrality,
+            })
+        }
+    }
+
+    /// Returns the first degree of freedom of the noncentral F distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralF;
+    ///
+    /// let n = NoncentralF::new(3.0, 10.0, 4.0).unwrap();
+    /// assert_eq!(n.freedom_1(), 3.0);
+    /// ```
+    pub fn freedom_1(&self) -> T {
+        self.freedom_1
+    }
+
+    /// Returns the second degree of freedom of the noncentral F distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralF;
+    ///
+    /// let n = NoncentralF::new(3.0, 10.0, 4.0).unwrap();
+    /// assert_eq!(n.freedom_2(), 10.0);
+    /// ```
+    pub fn freedom_2(&self) -> T {
+        self.freedom_2
+    }
+
+    /// Returns the noncentrality parameter of the noncentral F distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralF;
+    ///
+    /// let n = NoncentralF::new(3.0, 10.0, 4.0).unwrap();
+    /// assert_eq!(n.noncentrality(), 4.0);
+    /// ```
+    pub fn noncentrality(&self) -> T {
+        self.noncentrality
+    }
+
+    /// Returns the central F distribution with the same degrees of freedom
+    fn central(&self) -> FisherSnedecor<T> {
+        FisherSnedecor::new(self.freedom_1, self.freedom_2).unwrap()
+    }
+
+    /// Returns the Poisson mixture `Σ w_j * term(j)` with the Poisson
+    /// weights of mean `λ / 2`, which is positive
+    fn mixture<F: Fn(T) -> T>(&self, term: F) -> T {
+        let mean = self.noncentrality / cast(2.0);
+        let (ln_weight, sum) = internal::outward_series(
+            |j: T| -mean + j * mean.ln() - gamma::ln_gamma(j + T::one()),
+            |j| mean / (j + T::one()),
+            term,
+        );
+        ln_weight.exp() * sum
+    }
+}
+
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for NoncentralF<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let chi_squared = NoncentralChiSquared::new(self.freedom_1, self.noncentrality).unwrap();
+        let numerator = ::rand::distributions::Distribution::sample(&chi_squared, rng);
+        (numerator * self.freedom_2)
+            / (super::gamma::sample_unchecked(rng, self.freedom_2 / cast(2.0), cast(0.5))
+                * self.freedom_1)
+    }
+}
+
+impl<T: SpecialFloat> ContinuousCDF<T, T> for NoncentralF<T> {
+    /// Calculates the cumulative distribution function for the noncentral
+    /// F distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * I_y(d1 / 2 + j, d2 / 2)
+    /// ```
+    ///
+    /// where `y = d1 * x / (d1 * x + d2)`, `d1` and `d2` are the degrees of
+    /// freedom, `λ` is the noncentrality and `I` is the regularized
+    /// incomplete beta function
+    fn cdf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
+        } else if x.is_infinite() {
+            T::one()
+        } else if self.noncentrality == T::zero() {
+            self.central().cdf(x)
+        } else {
+            let (d1, d2) = (self.freedom_1, self.freedom_2);
+            let y = d1 * x / (d1 * x + d2);
+            let (a, b) = (d1 / cast(2.0), d2 / cast(2.0));
+            let sum = self.mixture(|j| beta::beta_reg(a + j, b, y));
+            sum.min(T::one())
+        }
+    }
+
+    /// Calculates the survival function for the noncentral F distribution
+    /// at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * I_(1 - y)(d2 / 2, d1 / 2 + j)
+    /// ```
+    ///
+    /// where `y = d1 * x / (d1 * x + d2)`, `d1` and `d2` are the degrees of
+    /// freedom, `λ` is the noncentrality and `I` is the regularized
+    /// incomplete beta function
+    fn sf(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::one()
+        } else if x.is_infinite() {
+            T::zero()
+        } else if self.noncentrality == T::zero() {
+            self.central().sf(x)
+        } else {
+            let (d1, d2) = (self.freedom_1, self.freedom_2);
+            let y_c = d2 / (d1 * x + d2);
+            let (a, b) = (d1 / cast(2.0), d2 / cast(2.0));
+            let sum = self.mixture(|j| beta::beta_reg(b, a + j, y_c));
+            sum.min(T::one())
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// noncentral F distribution at `p`, by Newton's method starting from
+    /// Patnaik's approximation of the numerator by a scaled central
+    /// chi-squared variable
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: T) -> T {
+        let (d1, d2, lambda) = (self.freedom_1, self.freedom_2, self.noncentrality);
+        let guess = if p > T::zero() && p < T::one() {
+            let freedom = (d1 + lambda) * (d1 + lambda) / (d1 + lambda * cast(2.0));
+            let central = FisherSnedecor::new(freedom, d2).unwrap();
+            (d1 + lambda) / d1 * central.inverse_cdf(p)
+        } else {
+            T::one()
+        };
+        internal::newton_inverse_cdf(self, p, guess)
+    }
+}
+
+impl<T: SpecialFloat> Min<T> for NoncentralF<T> {
+    /// Returns the minimum value in the domain of the noncentral F
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 0
+    /// ```
+    fn min(&self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: SpecialFloat> Max<T> for NoncentralF<T> {
+    /// Returns the maximum value in the domain of the noncentral F
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// INF
+    /// ```
+    fn max(&self) -> T {
+        T::infinity()
+    }
+}
+
+impl<T: SpecialFloat> Distribution<T> for NoncentralF<T> {
+    /// Returns the mean of the noncentral F distribution, or `None` if
+    /// `freedom_2 <= 2.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// d2 * (d1 + λ) / (d1 * (d2 - 2))
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom and `λ` is the
+    /// noncentrality
+    fn mean(&self) -> Option<T> {
+        let (d1, d2) = (self.freedom_1, self.freedom_2);
+        if d2 <= cast(2.0) {
+            None
+        } else {
+            Some(d2 * (d1 + self.noncentrality) / (d1 * (d2 - cast(2.0))))
+        }
+    }
+
+    /// Returns the variance of the noncentral F distribution, or `None` if
+    /// `freedom_2 <= 4.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2 * (d2 / d1)^2 * ((d1 + λ)^2 + (d1 + 2λ) * (d2 - 2)) / ((d2 - 2)^2 * (d2 - 4))
+    /// ```
+    ///
+    /// where `d1` and `d2` are the degrees of freedom and `λ` is the
+    /// noncentrality
+    fn variance(&self) -> Option<T> {
+        let (d1, d2, lambda) = (self.freedom_1, self.freedom_2, self.noncentrality);
+        if d2 <= cast(4.0) {
+            None
+        } else {
+            let ratio = d2 / d1;
+            let numerator =
+                (d1 + lambda) * (d1 + lambda) + (d1 + lambda * cast(2.0)) * (d2 - cast(2.0));
+            let denominator = (d2 - cast(2.0)) * (d2 - cast(2.0)) * (d2 - cast(4.0));
+            Some(cast::<T>(2.0) * ratio * ratio * numerator / denominator)
+        }
+    }
+}
+
+impl<T: SpecialFloat> Continuous<T, T> for NoncentralF<T> {
+    /// Calculates the probability density function for the noncentral F
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * b_(d1 / 2 + j, d2 / 2)(y) * d1 * d2 / (d1 * x + d2)^2
+    /// ```
+    ///
+    /// where `y = d1 * x / (d1 * x + d2)`, `d1` and `d2` are the degrees of
+    /// freedom, `λ` is the noncentrality and `b_(a, b)` is the density of
+    /// the beta distribution
+    fn pdf(&self, x: T) -> T {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the noncentral
+    /// F distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(Σ_(j >= 0) e^(-λ / 2) * (λ / 2)^j / j! * b_(d1 / 2 + j, d2 / 2)(y) * d1 * d2 / (d1 * x + d2)^2)
+    /// ```
+    ///
+    /// where `y = d1 * x / (d1 * x + d2)`, `d1` and `d2` are the degrees of
+    /// freedom, `λ` is the noncentrality and `b_(a, b)` is the density of
+    /// the beta distribution
+    fn ln_pdf(&self, x: T) -> T {
+        let (d1, d2, lambda) = (self.freedom_1, self.freedom_2, self.noncentrality);
+        let two = cast::<T>(2.0);
+        if x < T::zero() || x.is_infinite() {
+            T::neg_infinity()
+        } else if lambda == T::zero() {
+            self.central().ln_pdf(x)
+        } else if x == T::zero() {
+            // only the first term of the mixture can be nonzero
+            if d1 < two {
+                T::infinity()
+            } else if d1 == two {
+                -lambda / two
+            } else {
+                T::neg_infinity()
+            }
+        } else {
+            let mean = lambda / two;
+            let (a, b) = (d1 / two, d2 / two);
+            let y = d1 * x / (d1 * x + d2);
+            let y_c = d2 / (d1 * x + d2);
+            let jacobian = (d1 * d2).ln() - (d1 * x + d2).ln() * two;
+            let (ln_weight, sum) = internal::outward_series(
+                |j: T| {
+                    -mean + j * mean.ln() - gamma::ln_gamma(j + T::one())
+                        + (a + j - T::one()) * y.ln()
+                        + (b - T::one()) * y_c.ln()
+                        - beta::ln_beta(a + j, b)
+                },
+                |j| mean / (j + T::one()) * y * (a + b + j) / (a + j),
+                |_| T::one(),
+            );
+            ln_weight + sum.ln() + jacobian
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::consts::ACC;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Continuous, ContinuousCDF, FisherSnedecor, NoncentralF};
+    use crate::statistics::*;
+    use crate::testing_boiler;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!((f64, f64, f64), NoncentralF);
+
+    #[test]
+    fn test_create() {
+        try_create((1.0, 1.0, 0.0));
+        try_create((3.0, 10.0, 4.0));
+        try_create((0.5, 2.0, 1e4));
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case((f64::NAN, 1.0, 1.0));
+        bad_create_case((1.0, f64::NAN, 1.0));
+        bad_create_case((1.0, 1.0, f64::NAN));
+        bad_create_case((0.0, 1.0, 1.0));
+        bad_create_case((1.0, 0.0, 1.0));
+        bad_create_case((1.0, f64::INFINITY, 1.0));
+        bad_create_case((1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_moments() {
+        test_case((3.0, 10.0, 4.0), 2.9166666666666667, |x| x.mean().unwrap());
+        test_case((3.0, 10.0, 4.0), 7.9282407407407407, |x| x.variance().unwrap());
+        test_none((3.0, 2.0, 4.0), |x| x.mean());
+        test_none((3.0, 4.0, 4.0), |x| x.variance());
+    }
+
+    #[test]
+    fn test_pdf() {
+        let pdf = |arg: f64| move |x: NoncentralF| x.pdf(arg);
+        test_case((3.0, 10.0, 4.0), 0.22713302262485229118, pdf(2.0));
+        test_case((5.0, 8.0, 10.0), 0.16197108546305041229, pdf(1.0));
+        test_case((2.0, 3.0, 1.0), 0.39855580612369567078, pdf(0.5));
+        test_case((4.0, 12.0, 200.0), 0.018212752095368410968, pdf(50.0));
+        test_case((2.0, 3.0, 1.0), (-0.5f64).exp(), pdf(0.0));
+        test_case((3.0, 10.0, 4.0), 0.0, pdf(0.0));
+        test_case((1.0, 10.0, 4.0), f64::INFINITY, pdf(0.0));
+        test_case((3.0, 10.0, 0.0), FisherSnedecor::new(3.0, 10.0).unwrap().pdf(2.0), pdf(2.0));
+    }
+
+    #[test]
+    fn test_ln_pdf() {
+        let ln_pdf = |arg: f64| move |x: NoncentralF| x.ln_pdf(arg);
+        test_case((3.0, 10.0, 4.0), 0.22713302262485229118f64.ln(), ln_pdf(2.0));
+        test_case((3.0, 10.0, 4.0), f64::NEG_INFINITY, ln_pdf(-1.0));
+    }
+
+    #[test]
+    fn test_cdf() {
+        let cdf = |arg: f64| move |x: NoncentralF| x.cdf(arg);
+        test_case((3.0, 10.0, 4.0), 0.46636421604808010219, cdf(2.0));
+        test_case((5.0, 8.0, 10.0), 0.067255202834621952191, cdf(1.0));
+        test_case((2.0, 3.0, 1.0), 0.24744718436135845688, cdf(0.5));
+        test_case((4.0, 12.0, 200.0), 0.43632629108999309923, cdf(50.0));
+        test_case((3.0, 10.0, 4.0), 0.0, cdf(0.0));
+        test_case((3.0, 10.0, 4.0), 1.0, cdf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: NoncentralF| x.sf(arg);
+        test_case((3.0, 10.0, 4.0), 0.53363578395191989781, sf(2.0));
+        test_case((4.0, 12.0, 200.0), 1.0 - 0.43632629108999309923, sf(50.0));
+        test_case((3.0, 10.0, 4.0), 1.0, sf(0.0));
+        test_case((3.0, 10.0, 4.0), 0.0, sf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &arg in &[(3.0, 10.0, 4.0), (1.0, 1.0, 1.0), (4.0, 12.0, 200.0)] {
+            let n = try_create(arg);
+            for &p in &[1e-10, 0.05, 0.5, 0.95, 1.0 - 1e-6] {
+                let x = n.inverse_cdf(p);
+                let (expected, actual) = if p > 0.5 { (1.0 - p, n.sf(x)) } else { (p, n.cdf(x)) };
+                assert_relative_eq!(expected, actual, max_relative = 1e-10);
+            }
+            assert_eq!(n.inverse_cdf(0.0), 0.0);
+            assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create((3.0, 10.0, 4.0)), 0.0, 100.0);
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = NoncentralF::<f32>::new(3.0, 10.0, 4.0).unwrap();
+        let d64 = NoncentralF::new(3.0, 10.0, 4.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.5, 2.0, 5.0, 10.0], 1e-4);
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = try_create((3.0, 10.0, 4.0));
+        let mut rng = StdRng::seed_from_u64(7);
+        let count = 20_000;
+        let samples: Vec<f64> = (0..count).map(|_| rand::distributions::Distribution::sample(&n, &mut rng)).collect();
+        for &x in &[1.0, 2.0, 5.0] {
+            let below = samples.iter().filter(|&&s| s <= x).count() as f64 / count as f64;
+            assert!((below - n.cdf(x)).abs() < 0.015);
+        }
+    }
+}
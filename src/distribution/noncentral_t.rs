@@ -0,0 +1,540 @@
+use crate::distribution::{internal, Continuous, ContinuousCDF, StudentsT};
+use crate::function::{beta, erf, float::cast, gamma, SpecialFloat};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+
+/// Implements the [noncentral
+/// t](https://en.wikipedia.org/wiki/Noncentral_t-distribution) distribution,
+/// the distribution of `(Z + δ) / sqrt(V / ν)` for a standard normal `Z`
+/// and an independent chi-squared variable `V` with `ν` degrees of freedom
+///
+/// # Remarks
+///
+/// The cumulative distribution function is evaluated by the series of
+/// Lenth (1989), a mixture of incomplete beta functions with Poisson
+/// weights of mean `δ^2 / 2`, summed outwards from its largest term as for
+/// the noncentral chi-squared distribution. The tail on the opposite side
+/// of zero from `δ`, and the density there, are differences of two series
+/// and are therefore only accurate to absolute rather than relative
+/// precision, with densities below the rounding error of the series
+/// returned as zero.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, NoncentralT};
+/// use statrs::prec;
+///
+/// let n = NoncentralT::new(10.0, 1.0).unwrap();
+/// assert!(prec::almost_eq(n.cdf(1.5), 0.669516848215354760, 1e-10));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NoncentralT<T = f64> {
+    freedom: T,
+    noncentrality: T,
+}
+
+impl<T: SpecialFloat> NoncentralT<T> {
+    /// Constructs a new noncentral t distribution with `freedom` degrees of
+    /// freedom and noncentrality parameter `noncentrality`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `freedom` or `noncentrality` are `NaN` or
+    /// infinite, or `freedom <= 0.0`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralT;
+    ///
+    /// let mut result = NoncentralT::new(10.0, -1.0);
+    /// assert!(result.is_ok());
+    ///
+    /// result = NoncentralT::new(0.0, 1.0);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn new(freedom: T, noncentrality: T) -> Result<NoncentralT<T>> {
+        if !freedom.is_finite() || !noncentrality.is_finite() || freedom <= T::zero() {
+            Err(StatsError::BadParams)
+        } else {
+            Ok(NoncentralT {
+                freedom,
+                noncentrality,
+            })
+        }
+    }
+
+    /// Returns the degrees of freedom of the noncentral t distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralT;
+    ///
+    /// let n = NoncentralT::new(10.0, 1.0).unwrap();
+    /// assert_eq!(n.freedom(), 10.0);
+    /// ```
+    pub fn freedom(&self) -> T {
+        self.freedom
+    }
+
+    /// Returns the noncentrality parameter of the noncentral t distribution
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::NoncentralT;
+    ///
+    /// let n = NoncentralT::new(10.0, 1.0).unwrap();
+    /// assert_eq!(n.noncentrality(), 1.0);
+    /// ```
+    pub fn noncentrality(&self) -> T {
+        self.noncentrality
+    }
+
+    /// Returns the central t distribution with the same degrees of freedom
+    fn central(&self) -> StudentsT<T> {
+        StudentsT::new(T::zero(), T::one(), self.freedom).unwrap()
+    }
+
+    /// Returns `t^2 / (t^2 + ν)` and its complement `ν / (t^2 + ν)`
+    fn beta_args(&self, t: T) -> (T, T) {
+        let tt = t * t;
+        if tt.is_infinite() {
+            (T::one(), self.freedom / t / t)
+        } else {
+            (tt / (tt + self.freedom), self.freedom / (tt + self.freedom))
+        }
+    }
+
+    /// Returns the lower tail if `upper` is false, or the upper tail
+    /// otherwise, at `t >= 0` of the noncentral t distribution with
+    /// noncentrality `delta`, by the series of Lenth (1989)
+    fn tail(&self, t: T, delta: T, upper: bool) -> T {
+        let half = cast::<T>(0.5);
+        let b = self.freedom * half;
+        let (x, x_c) = self.beta_args(t);
+        let lambda = delta * delta * half;
+        let term = |a: T| {
+            if upper {
+                beta::beta_reg(b, a, x_c)
+            } else {
+                beta::beta_reg(a, b, x)
+            }
+        };
+        let (ln_p, p) = internal::outward_series(
+            |j: T| -lambda + j * lambda.ln() - gamma::ln_gamma(j + T::one()),
+            |j| lambda / (j + T::one()),
+            |j| term(j + half),
+        );
+        let (ln_q, q) = internal::outward_series(
+            |j: T| -lambda + j * lambda.ln() - gamma::ln_gamma(j + cast(1.5)),
+            |j| lambda / (j + cast(1.5)),
+            |j| term(j + T::one()),
+        );
+        let series = (ln_p.exp() * p + delta * half.sqrt() * ln_q.exp() * q) * half;
+        let tail = if upper {
+            series
+        } else {
+            erf::erfc(delta * half.sqrt()) * half + series
+        };
+        tail.max(T::zero()).min(T::one())
+    }
+
+    /// Returns the log density at `t > 0` of the noncentral t distribution
+    /// with noncentrality `delta`, the derivative of the series of `tail`
+    fn ln_density(&self, t: T, delta: T) -> T {
+        let half = cast::<T>(0.5);
+        let b = self.freedom * half;
+        let v = self.freedom;
+        let (x, _) = self.beta_args(t);
+        // the logarithms of x and 1 - x, written to avoid overflow of t^2
+        let (ln_x, ln_x_c) = if t * t > v {
+            let ln_scale = (v / t / t).ln_1p();
+            (-ln_scale, v.ln() - t.ln() * cast(2.0) - ln_scale)
+        } else {
+            let ln_scale = (t * t + v).ln();
+            (t.ln() * cast(2.0) - ln_scale, v.ln() - ln_scale)
+        };
+        let lambda = delta * delta * half;
+        // ln of d/dt x = 2 * t * ν / (t^2 + ν)^2
+        let ln_jacobian = cast::<T>(2.0).ln() - t.ln() + ln_x + ln_x_c;
+        let ln_beta_density =
+            |a: T| (a - T::one()) * ln_x + (b - T::one()) * ln_x_c - beta::ln_beta(a, b);
+        let (ln_p, p) = internal::outward_series(
+            |j: T| {
+                -lambda + j * lambda.ln() - gamma::ln_gamma(j + T::one())
+                    + ln_beta_density(j + half)
+            },
+            |j| lambda * x * (j + half + b) / ((j + T::one()) * (j + half)),
+            |_| T::one(),
+        );
+        let (ln_q, q) = internal::outward_series(
+            |j: T| {
+                -lambda + j * lambda.ln() - gamma::ln_gamma(j + cast(1.5))
+                    + ln_beta_density(j + T::one())
+            },
+            |j| lambda * x * (j + T::one() + b) / ((j + cast(1.5)) * (j + T::one())),
+            |_| T::one(),
+        );
+        let q = delta * half.sqrt() * (ln_q - ln_p).exp() * q;
+        // for negative `delta` the series cancel and what remains below
+        // their rounding error is noise
+        let sum = p + q;
+        if sum <= cast::<T>(1e3) * T::epsilon() * (p + q.abs()) {
+            T::neg_infinity()
+        } else {
+            ln_p + sum.ln() + ln_jacobian + half.ln()
+        }
+    }
+}
+
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for NoncentralT<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
+        let gamma = super::gamma::sample_unchecked(r, cast::<T>(0.5) * self.freedom, cast(0.5));
+        super::normal::sample_unchecked(r, self.noncentrality, T::one())
+            * (self.freedom / gamma).sqrt()
+    }
+}
+
+impl<T: SpecialFloat> ContinuousCDF<T, T> for NoncentralT<T> {
+    /// Calculates the cumulative distribution function for the noncentral
+    /// t distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Φ(-δ) + 1 / 2 * Σ_(j >= 0) (p_j * I_y(j + 1 / 2, ν / 2) + q_j * I_y(j + 1, ν / 2))
+    /// ```
+    ///
+    /// for `x >= 0`, where `y = x^2 / (x^2 + ν)`, `p_j = e^(-δ^2 / 2) * (δ^2 /
+    /// 2)^j / j!`, `q_j = δ / sqrt(2) * e^(-δ^2 / 2) * (δ^2 / 2)^j / Γ(j + 3 /
+    /// 2)`, `ν` is the degrees of freedom, `δ` is the noncentrality, `Φ` is
+    /// the standard normal cdf and `I` is the regularized incomplete beta
+    /// function. For `x < 0` the reflection `F(x; δ) = 1 - F(-x; -δ)` is used.
+    fn cdf(&self, x: T) -> T {
+        if x == T::neg_infinity() {
+            T::zero()
+        } else if x == T::infinity() {
+            T::one()
+        } else if self.noncentrality == T::zero() {
+            self.central().cdf(x)
+        } else if x >= T::zero() {
+            self.tail(x, self.noncentrality, false)
+        } else {
+            self.tail(-x, -self.noncentrality, true)
+        }
+    }
+
+    /// Calculates the survival function for the noncentral t distribution
+    /// at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1 / 2 * Σ_(j >= 0) (p_j * I_(1 - y)(ν / 2, j + 1 / 2) + q_j * I_(1 - y)(ν / 2, j + 1))
+    /// ```
+    ///
+    /// for `x >= 0`, with the notation of `cdf`. For `x < 0` the reflection
+    /// `S(x; δ) = 1 - S(-x; -δ)` is used.
+    fn sf(&self, x: T) -> T {
+        if x == T::neg_infinity() {
+            T::one()
+        } else if x == T::infinity() {
+            T::zero()
+        } else if self.noncentrality == T::zero() {
+            self.central().sf(x)
+        } else if x >= T::zero() {
+            self.tail(x, self.noncentrality, true)
+        } else {
+            self.tail(-x, -self.noncentrality, false)
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// noncentral t distribution at `p`, by Newton's method starting from
+    /// the quantile of the central t distribution shifted by `δ`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    fn inverse_cdf(&self, p: T) -> T {
+        let guess = if p > T::zero() && p < T::one() {
+            self.central().inverse_cdf(p) + self.noncentrality
+        } else {
+            self.noncentrality
+        };
+        internal::newton_inverse_cdf(self, p, guess)
+    }
+}
+
+impl<T: SpecialFloat> Min<T> for NoncentralT<T> {
+    /// Returns the minimum value in the domain of the noncentral t
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// -INF
+    /// ```
+    fn min(&self) -> T {
+        T::neg_infinity()
+    }
+}
+
+impl<T: SpecialFloat> Max<T> for NoncentralT<T> {
+    /// Returns the maximum value in the domain of the noncentral t
+    /// distribution representable by a double precision float
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// INF
+    /// ```
+    fn max(&self) -> T {
+        T::infinity()
+    }
+}
+
+impl<T: SpecialFloat> Distribution<T> for NoncentralT<T> {
+    /// Returns the mean of the noncentral t distribution, or `None` if
+    /// `freedom <= 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// δ * sqrt(ν / 2) * Γ((ν - 1) / 2) / Γ(ν / 2)
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom and `δ` is the noncentrality
+    fn mean(&self) -> Option<T> {
+        let v = self.freedom;
+        if v <= T::one() {
+            None
+        } else {
+            let half = cast::<T>(0.5);
+            let ratio = (gamma::ln_gamma((v - T::one()) * half) - gamma::ln_gamma(v * half)).exp();
+            Some(self.noncentrality * (v * half).sqrt() * ratio)
+        }
+    }
+
+    /// Returns the variance of the noncentral t distribution, or `None` if
+    /// `freedom <= 2.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ν * (1 + δ^2) / (ν - 2) - μ^2
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom, `δ` is the noncentrality and `μ`
+    /// is the mean
+    fn variance(&self) -> Option<T> {
+        let (v, delta) = (self.freedom, self.noncentrality);
+        if v <= cast(2.0) {
+            None
+        } else {
+            let mean = self.mean().unwrap();
+            Some(v * (T::one() + delta * delta) / (v - cast(2.0)) - mean * mean)
+        }
+    }
+
+    /// Returns the skewness of the noncentral t distribution, or `None` if
+    /// `freedom <= 3.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ * (ν * (2ν - 3 + δ^2) / ((ν - 2) * (ν - 3)) - 2σ^2) / σ^3
+    /// ```
+    ///
+    /// where `ν` is the degrees of freedom, `δ` is the noncentrality, `μ` is
+    /// the mean and `σ^2` is the variance
+    fn skewness(&self) -> Option<T> {
+        let (v, delta) = (self.freedom, self.noncentrality);
+        if v <= cast(3.0) {
+            None
+        } else {
+            let mean = self.mean().unwrap();
+            let variance = self.variance().unwrap();
+            let moment = v * (v * cast(2.0) - cast(3.0) + delta * delta)
+                / ((v - cast(2.0)) * (v - cast(3.0)));
+            Some(mean * (moment - variance * cast(2.0)) / variance.powf(cast(1.5)))
+        }
+    }
+}
+
+impl<T: SpecialFloat> Continuous<T, T> for NoncentralT<T> {
+    /// Calculates the probability density function for the noncentral t
+    /// distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ν * |x| / (x^2 + ν)^2 * Σ_(j >= 0) (p_j * b_(j + 1 / 2, ν / 2)(y) + q_j * b_(j + 1, ν / 2)(y))
+    /// ```
+    ///
+    /// for `x != 0`, with the notation of `cdf` after reflecting `x < 0` to
+    /// `-x` and `-δ`, where `b_(a, b)` is the density of the beta
+    /// distribution, and `Γ((ν + 1) / 2) / (Γ(ν / 2) * sqrt(νπ)) * e^(-δ^2 /
+    /// 2)` at `x = 0`
+    fn pdf(&self, x: T) -> T {
+        self.ln_pdf(x).exp()
+    }
+
+    /// Calculates the log probability density function for the noncentral
+    /// t distribution at `x`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// ln(f(x))
+    /// ```
+    ///
+    /// where `f` is the density given for `pdf`
+    fn ln_pdf(&self, x: T) -> T {
+        let (v, delta) = (self.freedom, self.noncentrality);
+        if x.is_infinite() {
+            T::neg_infinity()
+        } else if delta == T::zero() {
+            self.central().ln_pdf(x)
+        } else if x * x == T::zero() {
+            let half = cast::<T>(0.5);
+            gamma::ln_gamma((v + T::one()) * half)
+                - gamma::ln_gamma(v * half)
+                - (v * T::PI()).ln() * half
+                - delta * delta * half
+        } else if x > T::zero() {
+            self.ln_density(x, delta)
+        } else {
+            self.ln_density(-x, -delta)
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use crate::consts::ACC;
+    use crate::distribution::internal::*;
+    use crate::distribution::{Continuous, ContinuousCDF, NoncentralT, StudentsT};
+    use crate::statistics::*;
+    use crate::testing_boiler;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    testing_boiler!((f64, f64), NoncentralT);
+
+    #[test]
+    fn test_create() {
+        try_create((1.0, 0.0));
+        try_create((10.0, -1.0));
+        try_create((0.5, 40.0));
+    }
+
+    #[test]
+    fn test_bad_create() {
+        bad_create_case((f64::NAN, 1.0));
+        bad_create_case((1.0, f64::NAN));
+        bad_create_case((0.0, 1.0));
+        bad_create_case((f64::INFINITY, 1.0));
+        bad_create_case((1.0, f64::INFINITY));
+    }
+
+    #[test]
+    fn test_moments() {
+        test_case((10.0, 1.0), 1.0837223079391436365, |x| x.mean().unwrap());
+        test_case((10.0, 1.0), 1.3255459592750559335, |x| x.variance().unwrap());
+        test_case((10.0, 1.0), 0.39992972990581230504, |x| x.skewness().unwrap());
+        test_case((10.0, -1.0), -0.39992972990581230504, |x| x.skewness().unwrap());
+        test_none((1.0, 1.0), |x| x.mean());
+        test_none((2.0, 1.0), |x| x.variance());
+        test_none((3.0, 1.0), |x| x.skewness());
+    }
+
+    #[test]
+    fn test_pdf() {
+        let pdf = |arg: f64| move |x: NoncentralT| x.pdf(arg);
+        test_case((10.0, 1.0), 0.32474376767444853037, pdf(1.5));
+        test_case((5.0, 2.0), 0.0050696803835706396011, pdf(-1.0));
+        test_case((20.0, -1.0), 0.00041780962547089609988, pdf(3.0));
+        test_case((4.0, 5.0), 0.019984996690985466254, pdf(2.0));
+        test_case((30.0, 40.0), 0.075425079279444047791, pdf(40.0));
+        test_case((10.0, 1.0), 0.23600616482663348656, pdf(0.0));
+        test_case((8.0, 0.0), StudentsT::new(0.0, 1.0, 8.0).unwrap().pdf(0.7), pdf(0.7));
+    }
+
+    #[test]
+    fn test_ln_pdf() {
+        let ln_pdf = |arg: f64| move |x: NoncentralT| x.ln_pdf(arg);
+        test_case((10.0, 1.0), 0.32474376767444853037f64.ln(), ln_pdf(1.5));
+        test_case((30.0, 40.0), 0.075425079279444047791f64.ln(), ln_pdf(40.0));
+    }
+
+    #[test]
+    fn test_cdf() {
+        // Φ(-δ) and the cancelling series are only accurate to about 1e-11
+        let cdf = |arg: f64| move |x: NoncentralT| x.cdf(arg);
+        test_case_special((10.0, 1.0), 0.66951684821535476277, 1e-10, cdf(1.5));
+        test_case_special((5.0, 2.0), 0.0023163756540751190482, 1e-10, cdf(-1.0));
+        test_case_special((20.0, -1.0), 0.99984691520552831105, 1e-10, cdf(3.0));
+        test_case_special((4.0, 5.0), 0.0059720384807749511015, 1e-10, cdf(2.0));
+        test_case((8.0, 0.0), StudentsT::new(0.0, 1.0, 8.0).unwrap().cdf(0.7), cdf(0.7));
+        test_case((10.0, 1.0), 0.0, cdf(f64::NEG_INFINITY));
+        test_case((10.0, 1.0), 1.0, cdf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_sf() {
+        let sf = |arg: f64| move |x: NoncentralT| x.sf(arg);
+        test_case((10.0, 1.0), 0.33048315178464523723, sf(1.5));
+        test_case((4.0, 5.0), 0.9940279615192250489, sf(2.0));
+        test_case((30.0, 40.0), 0.2181352459265607202, sf(45.0));
+        test_case_special((5.0, 2.0), 0.99768362434592488095, 1e-10, sf(-1.0));
+        test_case_special((20.0, -1.0), 0.00015308479447168895451, 1e-10, sf(3.0));
+        test_case((10.0, 1.0), 1.0, sf(f64::NEG_INFINITY));
+        test_case((10.0, 1.0), 0.0, sf(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_inverse_cdf() {
+        for &arg in &[(10.0, 1.0), (3.0, -2.0), (30.0, 40.0)] {
+            let n = try_create(arg);
+            for &p in &[1e-10, 0.05, 0.5, 0.95, 1.0 - 1e-6] {
+                let x = n.inverse_cdf(p);
+                let (expected, actual) = if p > 0.5 { (1.0 - p, n.sf(x)) } else { (p, n.cdf(x)) };
+                assert_abs_diff_eq!(expected, actual, epsilon = 1e-12);
+            }
+            assert_eq!(n.inverse_cdf(0.0), f64::NEG_INFINITY);
+            assert_eq!(n.inverse_cdf(1.0), f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn test_continuous() {
+        test::check_continuous_distribution(&try_create((10.0, 1.0)), -10.0, 20.0);
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = NoncentralT::<f32>::new(10.0, 1.0).unwrap();
+        let d64 = NoncentralT::new(10.0, 1.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[-0.5, 0.5, 1.5, 3.0], 1e-4);
+    }
+
+    #[test]
+    fn test_sample() {
+        let n = try_create((10.0, 1.0));
+        let mut rng = StdRng::seed_from_u64(7);
+        let count = 20_000;
+        let samples: Vec<f64> = (0..count).map(|_| rand::distributions::Distribution::sample(&n, &mut rng)).collect();
+        let mean = samples.iter().sum::<f64>() / count as f64;
+        assert!((mean - n.mean().unwrap()).abs() < 0.03);
+        for &x in &[0.0, 1.0, 2.5] {
+            let below = samples.iter().filter(|&&s| s <= x).count() as f64 / count as f64;
+            assert!((below - n.cdf(x)).abs() < 0.015);
+        }
+    }
+}
@@ -1,9 +1,9 @@
-use crate::distribution::{ziggurat, Continuous, ContinuousCDF};
-use crate::function::erf;
+use crate::distribution::{ziggurat, Continuous, ContinuousCDF, Fit};
+use crate::function::{erf, float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Normal](https://en.wikipedia.org/wiki/Normal_distribution)
 /// distribution
@@ -17,14 +17,18 @@ use std::f64;
 /// let n = Normal::new(0.0, 1.0).unwrap();
 /// assert_eq!(n.mean().unwrap(), 0.0);
 /// assert_eq!(n.pdf(1.0), 0.2419707245191433497978);
+///
+/// // single precision
+/// let n = Normal::<f32>::new(0.0, 1.0).unwrap();
+/// assert!((n.pdf(1.0) - 0.24197072).abs() < 1e-7);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Normal {
-    mean: f64,
-    std_dev: f64,
+pub struct Normal<T = f64> {
+    mean: T,
+    std_dev: T,
 }
 
-impl Normal {
+impl<T: SpecialFloat> Normal<T> {
     ///  Constructs a new normal distribution with a mean of `mean`
     /// and a standard deviation of `std_dev`
     ///
@@ -44,8 +48,8 @@ impl Normal {
     /// result = Normal::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(mean: f64, std_dev: f64) -> Result<Normal> {
-        if mean.is_nan() || std_dev.is_nan() || std_dev <= 0.0 {
+    pub fn new(mean: T, std_dev: T) -> Result<Normal<T>> {
+        if mean.is_nan() || std_dev.is_nan() || std_dev <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Normal { mean, std_dev })
@@ -53,13 +57,13 @@ impl Normal {
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Normal {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Normal<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         sample_unchecked(rng, self.mean, self.std_dev)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Normal {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Normal<T> {
     /// Calculates the cumulative distribution function for the
     /// normal distribution at `x`
     ///
@@ -71,7 +75,7 @@ impl ContinuousCDF<f64, f64> for Normal {
     ///
     /// where `μ` is the mean, `σ` is the standard deviation, and
     /// `erf` is the error function
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         cdf_unchecked(x, self.mean, self.std_dev)
     }
 
@@ -95,7 +99,7 @@ impl ContinuousCDF<f64, f64> for Normal {
     ///  Φ(-x) + Φ(x) = 1
     ///  Φ(-x)        = 1 - Φ(x) 
     /// ```
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         sf_unchecked(x, self.mean, self.std_dev)
     }
 
@@ -114,16 +118,16 @@ impl ContinuousCDF<f64, f64> for Normal {
     ///
     /// where `μ` is the mean, `σ` is the standard deviation and `erfc_inv` is
     /// the inverse of the complementary error function
-    fn inverse_cdf(&self, x: f64) -> f64 {
-        if !(0.0..=1.0).contains(&x) {
+    fn inverse_cdf(&self, x: T) -> T {
+        if !(T::zero()..=T::one()).contains(&x) {
             panic!("x must be in [0, 1]");
         } else {
-            self.mean - (self.std_dev * f64::consts::SQRT_2 * erf::erfc_inv(2.0 * x))
+            self.mean - (self.std_dev * T::SQRT_2() * erf::erfc_inv(cast::<T>(2.0) * x))
         }
     }
 }
 
-impl Min<f64> for Normal {
+impl<T: SpecialFloat> Min<T> for Normal<T> {
     /// Returns the minimum value in the domain of the
     /// normal distribution representable by a double precision float
     ///
@@ -132,12 +136,12 @@ impl Min<f64> for Normal {
     /// ```ignore
     /// -INF
     /// ```
-    fn min(&self) -> f64 {
-        f64::NEG_INFINITY
+    fn min(&self) -> T {
+        T::neg_infinity()
     }
 }
 
-impl Max<f64> for Normal {
+impl<T: SpecialFloat> Max<T> for Normal<T> {
     /// Returns the maximum value in the domain of the
     /// normal distribution representable by a double precision float
     ///
@@ -146,18 +150,18 @@ impl Max<f64> for Normal {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Normal {
+impl<T: SpecialFloat> Distribution<T> for Normal<T> {
     /// Returns the mean of the normal distribution
     ///
     /// # Remarks
     ///
     /// This is the same mean used to construct the distribution
-    fn mean(&self) -> Option<f64> {
+    fn mean(&self) -> Option<T> {
         Some(self.mean)
     }
     /// Returns the variance of the normal distribution
@@ -169,7 +173,7 @@ impl Distribution<f64> for Normal {
     /// ```
     ///
     /// where `σ` is the standard deviation
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         Some(self.std_dev * self.std_dev)
     }
     /// Returns the entropy of the normal distribution
@@ -181,8 +185,8 @@ impl Distribution<f64> for Normal {
     /// ```
     ///
     /// where `σ` is the standard deviation
-    fn entropy(&self) -> Option<f64> {
-        Some(self.std_dev.ln() + consts::LN_SQRT_2PIE)
+    fn entropy(&self) -> Option<T> {
+        Some(self.std_dev.ln() + cast(consts::LN_SQRT_2PIE))
     }
     /// Returns the skewness of the normal distribution
     ///
@@ -191,12 +195,12 @@ impl Distribution<f64> for Normal {
     /// ```ignore
     /// 0
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        Some(0.0)
+    fn skewness(&self) -> Option<T> {
+        Some(T::zero())
     }
 }
 
-impl Median<f64> for Normal {
+impl<T: SpecialFloat> Median<T> for Normal<T> {
     /// Returns the median of the normal distribution
     ///
     /// # Formula
@@ -206,12 +210,12 @@ impl Median<f64> for Normal {
     /// ```
     ///
     /// where `μ` is the mean
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.mean
     }
 }
 
-impl Mode<Option<f64>> for Normal {
+impl<T: SpecialFloat> Mode<Option<T>> for Normal<T> {
     /// Returns the mode of the normal distribution
     ///
     /// # Formula
@@ -221,12 +225,12 @@ impl Mode<Option<f64>> for Normal {
     /// ```
     ///
     /// where `μ` is the mean
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.mean)
     }
 }
 
-impl Continuous<f64, f64> for Normal {
+impl<T: SpecialFloat> Continuous<T, T> for Normal<T> {
     /// Calculates the probability density function for the normal distribution
     /// at `x`
     ///
@@ -237,7 +241,7 @@ impl Continuous<f64, f64> for Normal {
     /// ```
     ///
     /// where `μ` is the mean and `σ` is the standard deviation
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         pdf_unchecked(x, self.mean, self.std_dev)
     }
 
@@ -252,46 +256,104 @@ impl Continuous<f64, f64> for Normal {
     /// ```
     ///
     /// where `μ` is the mean and `σ` is the standard deviation
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         ln_pdf_unchecked(x, self.mean, self.std_dev)
     }
 }
 
+impl Fit<f64> for Normal {
+    /// Returns the maximum likelihood estimate of the normal distribution
+    /// for the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ = (1 / n) * Σ x_i
+    /// σ = sqrt((1 / n) * Σ (x_i - μ)^2)
+    /// ```
+    ///
+    /// where `n` is the number of observations
+    fn fit_mle(data: &[f64]) -> Result<Normal> {
+        Normal::new(data.mean(), data.population_std_dev())
+    }
+
+    /// Returns the method of moments estimate of the normal distribution
+    /// for the observations in `data`, which coincides with the maximum
+    /// likelihood estimate
+    fn fit_mom(data: &[f64]) -> Result<Normal> {
+        Normal::fit_mle(data)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(μ, σ)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// [ n / σ^2,            2 * Σ (x_i - μ) / σ^3          ]
+    /// [ 2 * Σ (x_i - μ) / σ^3, 3 * Σ (x_i - μ)^2 / σ^4 - n / σ^2 ]
+    /// ```
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        Some(observed_information_unchecked(
+            data.iter().cloned(),
+            self.mean,
+            self.std_dev,
+        ))
+    }
+}
+
 /// performs an unchecked cdf calculation for a normal distribution
 /// with the given mean and standard deviation at x
-pub fn cdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
-    0.5 * erf::erfc((mean - x) / (std_dev * f64::consts::SQRT_2))
+pub fn cdf_unchecked<T: SpecialFloat>(x: T, mean: T, std_dev: T) -> T {
+    cast::<T>(0.5) * erf::erfc((mean - x) / (std_dev * T::SQRT_2()))
 }
 
 /// performs an unchecked sf calculation for a normal distribution
 /// with the given mean and standard deviation at x
-pub fn sf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
-    0.5 * erf::erfc((x - mean) / (std_dev * f64::consts::SQRT_2))
+pub fn sf_unchecked<T: SpecialFloat>(x: T, mean: T, std_dev: T) -> T {
+    cast::<T>(0.5) * erf::erfc((x - mean) / (std_dev * T::SQRT_2()))
 }
 
 /// performs an unchecked pdf calculation for a normal distribution
 /// with the given mean and standard deviation at x
-pub fn pdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
+pub fn pdf_unchecked<T: SpecialFloat>(x: T, mean: T, std_dev: T) -> T {
     let d = (x - mean) / std_dev;
-    (-0.5 * d * d).exp() / (consts::SQRT_2PI * std_dev)
+    (-cast::<T>(0.5) * d * d).exp() / (cast::<T>(consts::SQRT_2PI) * std_dev)
 }
 
 /// performs an unchecked log(pdf) calculation for a normal distribution
 /// with the given mean and standard deviation at x
-pub fn ln_pdf_unchecked(x: f64, mean: f64, std_dev: f64) -> f64 {
+pub fn ln_pdf_unchecked<T: SpecialFloat>(x: T, mean: T, std_dev: T) -> T {
     let d = (x - mean) / std_dev;
-    (-0.5 * d * d) - consts::LN_SQRT_2PI - std_dev.ln()
+    (-cast::<T>(0.5) * d * d) - cast(consts::LN_SQRT_2PI) - std_dev.ln()
+}
+
+/// computes the observed Fisher information with respect to the mean and
+/// standard deviation of a normal distribution for the observations `data`
+pub fn observed_information_unchecked<I: Iterator<Item = f64>>(
+    data: I,
+    mean: f64,
+    std_dev: f64,
+) -> DMatrix<f64> {
+    let (n, s1, s2) = data.fold((0.0, 0.0, 0.0), |(n, s1, s2), x| {
+        let d = x - mean;
+        (n + 1.0, s1 + d, s2 + d * d)
+    });
+    let var = std_dev * std_dev;
+    let cross = 2.0 * s1 / (var * std_dev);
+    DMatrix::from_row_slice(2, 2, &[n / var, cross, cross, 3.0 * s2 / (var * var) - n / var])
 }
 
 /// draws a sample from a normal distribution using the Box-Muller algorithm
-pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, mean: f64, std_dev: f64) -> f64 {
-    mean + std_dev * ziggurat::sample_std_normal(rng)
+pub fn sample_unchecked<T: SpecialFloat, R: Rng + ?Sized>(rng: &mut R, mean: T, std_dev: T) -> T {
+    mean + std_dev * cast(ziggurat::sample_std_normal(rng))
 }
 
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
+    use crate::distribution::Fit;
     use crate::distribution::{ContinuousCDF, Continuous, Normal};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
@@ -508,4 +570,23 @@ mod tests {
         test_almost(5.0, 2.0, 10.0, 1e-14, inverse_cdf(0.9937903346742238648330218954258077788721022530769078));
         test_case(5.0, 2.0, f64::INFINITY, inverse_cdf(1.0));
     }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Normal::<f32>::new(1.0, 2.0).unwrap();
+        let d64 = Normal::new(1.0, 2.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[-3.0, 0.0, 1.0, 2.5, 6.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+        let n = Normal::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.mean().unwrap(), 0.5, 1e-15);
+        assert_almost_eq!(n.std_dev().unwrap(), 2.1771541057077241, 1e-15);
+        assert_eq!(Normal::fit_mom(&data).unwrap(), n);
+        test::check_observed_information(n.observed_information(&data), &[2.109704641350211, 0.0, 0.0, 4.2194092827004219], 1e-14);
+        assert!(Normal::fit_mle(&[]).is_err());
+        assert!(Normal::fit_mle(&[1.0, 1.0]).is_err());
+    }
 }
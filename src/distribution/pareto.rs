@@ -1,9 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
+use crate::distribution::{Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::OpenClosed01;
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Pareto](https://en.wikipedia.org/wiki/Pareto_distribution)
 /// distribution
@@ -20,12 +20,12 @@ use std::f64;
 /// assert!(prec::almost_eq(p.pdf(2.0), 0.25, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Pareto {
-    scale: f64,
-    shape: f64,
+pub struct Pareto<T = f64> {
+    scale: T,
+    shape: T,
 }
 
-impl Pareto {
+impl<T: SpecialFloat> Pareto<T> {
     /// Constructs a new Pareto distribution with scale `scale`, and `shape`
     /// shape.
     ///
@@ -45,9 +45,9 @@ impl Pareto {
     /// result = Pareto::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(scale: f64, shape: f64) -> Result<Pareto> {
+    pub fn new(scale: T, shape: T) -> Result<Pareto<T>> {
         let is_nan = scale.is_nan() || shape.is_nan();
-        if is_nan || scale <= 0.0 || shape <= 0.0 {
+        if is_nan || scale <= T::zero() || shape <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(Pareto { scale, shape })
@@ -64,7 +64,7 @@ impl Pareto {
     /// let n = Pareto::new(1.0, 2.0).unwrap();
     /// assert_eq!(n.scale(), 1.0);
     /// ```
-    pub fn scale(&self) -> f64 {
+    pub fn scale(&self) -> T {
         self.scale
     }
 
@@ -78,20 +78,20 @@ impl Pareto {
     /// let n = Pareto::new(1.0, 2.0).unwrap();
     /// assert_eq!(n.shape(), 2.0);
     /// ```
-    pub fn shape(&self) -> f64 {
+    pub fn shape(&self) -> T {
         self.shape
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Pareto {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Pareto<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         // Inverse transform sampling
-        let u: f64 = rng.sample(OpenClosed01);
-        self.scale * u.powf(-1.0 / self.shape)
+        let u: T = cast(rng.sample::<f64, _>(OpenClosed01));
+        self.scale * u.powf(-T::one() / self.shape)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Pareto {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Pareto<T> {
     /// Calculates the cumulative distribution function for the Pareto
     /// distribution at `x`
     ///
@@ -106,11 +106,11 @@ impl ContinuousCDF<f64, f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         if x < self.scale {
-            0.0
+            T::zero()
         } else {
-            1.0 - (self.scale / x).powf(self.shape)
+            T::one() - (self.scale / x).powf(self.shape)
         }
     }
 
@@ -128,16 +128,37 @@ impl ContinuousCDF<f64, f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         if x < self.scale {
-            1.0
+            T::one()
         } else {
             (self.scale / x).powf(self.shape)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// Pareto distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x_m * (1 - p)^(-1 / α)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.scale * (T::one() - p).powf(-T::one() / self.shape)
+    }
 }
 
-impl Min<f64> for Pareto {
+impl<T: SpecialFloat> Min<T> for Pareto<T> {
     /// Returns the minimum value in the domain of the Pareto distribution
     /// representable by a double precision float
     ///
@@ -148,12 +169,12 @@ impl Min<f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale
-    fn min(&self) -> f64 {
+    fn min(&self) -> T {
         self.scale
     }
 }
 
-impl Max<f64> for Pareto {
+impl<T: SpecialFloat> Max<T> for Pareto<T> {
     /// Returns the maximum value in the domain of the Pareto distribution
     /// representable by a double precision float
     ///
@@ -162,12 +183,12 @@ impl Max<f64> for Pareto {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Pareto {
+impl<T: SpecialFloat> Distribution<T> for Pareto<T> {
     /// Returns the mean of the Pareto distribution
     ///
     /// # Formula
@@ -181,11 +202,11 @@ impl Distribution<f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn mean(&self) -> Option<f64> {
-        if self.shape <= 1.0 {
+    fn mean(&self) -> Option<T> {
+        if self.shape <= T::one() {
             None
         } else {
-            Some((self.shape * self.scale) / (self.shape - 1.0))
+            Some((self.shape * self.scale) / (self.shape - T::one()))
         }
     }
     /// Returns the variance of the Pareto distribution
@@ -201,12 +222,12 @@ impl Distribution<f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn variance(&self) -> Option<f64> {
-        if self.shape <= 2.0 {
+    fn variance(&self) -> Option<T> {
+        if self.shape <= cast(2.0) {
             None
         } else {
-            let a = self.scale / (self.shape - 1.0); // just a temporary variable
-            Some(a * a * self.shape / (self.shape - 2.0))
+            let a = self.scale / (self.shape - T::one()); // just a temporary variable
+            Some(a * a * self.shape / (self.shape - cast(2.0)))
         }
     }
     /// Returns the entropy for the Pareto distribution
@@ -218,8 +239,8 @@ impl Distribution<f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn entropy(&self) -> Option<f64> {
-        Some(self.shape.ln() - self.scale.ln() - (1.0 / self.shape) - 1.0)
+    fn entropy(&self) -> Option<T> {
+        Some(self.shape.ln() - self.scale.ln() - (T::one() / self.shape) - T::one())
     }
     /// Returns the skewness of the Pareto distribution
     ///
@@ -236,19 +257,19 @@ impl Distribution<f64> for Pareto {
     /// ```
     ///
     /// where `α` is the shape
-    fn skewness(&self) -> Option<f64> {
-        if self.shape <= 3.0 {
+    fn skewness(&self) -> Option<T> {
+        if self.shape <= cast(3.0) {
             None
         } else {
             Some(
-                (2.0 * (self.shape + 1.0) / (self.shape - 3.0))
-                    * ((self.shape - 2.0) / self.shape).sqrt(),
+                (cast::<T>(2.0) * (self.shape + T::one()) / (self.shape - cast(3.0)))
+                    * ((self.shape - cast(2.0)) / self.shape).sqrt(),
             )
         }
     }
 }
 
-impl Median<f64> for Pareto {
+impl<T: SpecialFloat> Median<T> for Pareto<T> {
     /// Returns the median of the Pareto distribution
     ///
     /// # Formula
@@ -258,12 +279,12 @@ impl Median<f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn median(&self) -> f64 {
-        self.scale * (2f64.powf(1.0 / self.shape))
+    fn median(&self) -> T {
+        self.scale * cast::<T>(2.0).powf(T::one() / self.shape)
     }
 }
 
-impl Mode<Option<f64>> for Pareto {
+impl<T: SpecialFloat> Mode<Option<T>> for Pareto<T> {
     /// Returns the mode of the Pareto distribution
     ///
     /// # Formula
@@ -273,12 +294,12 @@ impl Mode<Option<f64>> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.scale)
     }
 }
 
-impl Continuous<f64, f64> for Pareto {
+impl<T: SpecialFloat> Continuous<T, T> for Pareto<T> {
     /// Calculates the probability density function for the Pareto distribution
     /// at `x`
     ///
@@ -293,11 +314,11 @@ impl Continuous<f64, f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         if x < self.scale {
-            0.0
+            T::zero()
         } else {
-            (self.shape * self.scale.powf(self.shape)) / x.powf(self.shape + 1.0)
+            (self.shape * self.scale.powf(self.shape)) / x.powf(self.shape + T::one())
         }
     }
 
@@ -315,12 +336,63 @@ impl Continuous<f64, f64> for Pareto {
     /// ```
     ///
     /// where `x_m` is the scale and `α` is the shape
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         if x < self.scale {
-            f64::NEG_INFINITY
+            T::neg_infinity()
         } else {
-            self.shape.ln() + self.shape * self.scale.ln() - (self.shape + 1.0) * x.ln()
+            self.shape.ln() + self.shape * self.scale.ln() - (self.shape + T::one()) * x.ln()
+        }
+    }
+}
+
+impl Fit<f64> for Pareto {
+    /// Returns the maximum likelihood estimate of the pareto distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is not positive or if all
+    /// observations are equal
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// x_m = min(x_i)
+    /// α = n / Σ ln(x_i / x_m)
+    /// ```
+    ///
+    /// where `x_m` is the scale and `α` is the shape
+    fn fit_mle(data: &[f64]) -> Result<Pareto> {
+        if data.is_empty() || data.iter().any(|&x| x.is_nan() || x <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let scale = Statistics::min(data);
+        let shape = 1.0 / data.iter().map(|x| (x / scale).ln()).mean();
+        if !shape.is_finite() {
+            return Err(StatsError::BadParams);
         }
+        Pareto::new(scale, shape)
+    }
+
+    /// Returns the method of moments estimate of the pareto distribution
+    /// for the observations in `data`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// α = 1 + sqrt(1 + m^2 / s^2)
+    /// x_m = m * (α - 1) / α
+    /// ```
+    ///
+    /// where `x_m` is the scale, `α` is the shape and `m` and `s^2` are the
+    /// mean and the population variance of the observations
+    fn fit_mom(data: &[f64]) -> Result<Pareto> {
+        let mean = data.mean();
+        let shape = 1.0 + (1.0 + mean * mean / data.population_variance()).sqrt();
+        if !shape.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        Pareto::new(mean * (shape - 1.0) / shape, shape)
     }
 }
 
@@ -328,6 +400,7 @@ impl Continuous<f64, f64> for Pareto {
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
+    use crate::distribution::Fit;
     use crate::distribution::{ContinuousCDF, Continuous, Pareto};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
@@ -527,4 +600,36 @@ mod tests {
         test::check_continuous_distribution(&try_create(1.0, 10.0), 1.0, 10.0);
         test::check_continuous_distribution(&try_create(0.1, 2.0), 0.1, 100.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Pareto| x.inverse_cdf(arg);
+        test_almost(1.0, 1.0, 1.111111111111111118, 1e-14, inverse_cdf(0.1));
+        test_almost(1.0, 1.0, 2.0, 1e-14, inverse_cdf(0.5));
+        test_almost(1.0, 1.0, 10.00000000000000222, 1e-13, inverse_cdf(0.9));
+        test_almost(2.0, 3.0, 2.0714883373025725822, 1e-14, inverse_cdf(0.1));
+        test_almost(2.0, 3.0, 2.5198420997897463295, 1e-14, inverse_cdf(0.5));
+        test_almost(2.0, 3.0, 4.3088693800637677624, 1e-14, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Pareto::<f32>::new(1.0, 3.0).unwrap();
+        let d64 = Pareto::new(1.0, 3.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[1.25, 2.0, 4.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [1.2, 1.5, 3.4, 1.05, 2.2, 1.8, 6.5, 1.3];
+        let n = Pareto::fit_mle(&data).unwrap();
+        assert_eq!(n.scale(), 1.05);
+        assert_almost_eq!(n.shape(), 1.6062833261233245, 1e-14);
+        assert!(n.observed_information(&data).is_none());
+        let n = Pareto::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.scale(), 1.4934685247537457, 1e-14);
+        assert_almost_eq!(n.shape(), 2.7062722872474466, 1e-14);
+        assert!(Pareto::fit_mle(&[2.0, 2.0]).is_err());
+        assert!(Pareto::fit_mle(&[]).is_err());
+    }
 }
@@ -1,7 +1,8 @@
-use crate::distribution::{Discrete, DiscreteCDF};
+use crate::distribution::{internal, Discrete, DiscreteCDF, Fit};
 use crate::function::{factorial, gamma};
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
 use std::f64;
 use std::u64;
@@ -106,6 +107,27 @@ impl DiscreteCDF<u64, f64> for Poisson {
     fn sf(&self, x: u64) -> f64 {
         gamma::gamma_lr(x as f64 + 1.0, self.lambda)
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// poisson distribution at `p`, i.e. the smallest `x` such that `cdf(x) >= p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Remarks
+    ///
+    /// The Cornish-Fisher expansion `λ + sqrt(λ) * (z + (z^2 - 1) / (6 * sqrt(λ)))`
+    /// where `z` is the standard normal quantile is used as the initial guess
+    /// of a local search over the support
+    fn inverse_cdf(&self, p: f64) -> u64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let std_dev = self.lambda.sqrt();
+        let guess = internal::cornish_fisher_quantile(p, self.lambda, std_dev, 1.0 / std_dev);
+        internal::discrete_inverse_cdf(self, p, guess)
+    }
 }
 
 impl Min<u64> for Poisson {
@@ -252,6 +274,48 @@ impl Discrete<u64, f64> for Poisson {
         -self.lambda + x as f64 * self.lambda.ln() - factorial::ln_factorial(x as u64)
     }
 }
+
+impl Fit<u64> for Poisson {
+    /// Returns the maximum likelihood estimate of the poisson distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or all observations are zero
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ = (1 / n) * Σ x_i
+    /// ```
+    fn fit_mle(data: &[u64]) -> Result<Poisson> {
+        Poisson::new(data.iter().map(|&x| x as f64).mean())
+    }
+
+    /// Returns the method of moments estimate of the poisson distribution
+    /// for the observations in `data`, which coincides with the maximum
+    /// likelihood estimate
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty or all observations are zero
+    fn fit_mom(data: &[u64]) -> Result<Poisson> {
+        Poisson::fit_mle(data)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `λ`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ x_i / λ^2
+    /// ```
+    fn observed_information(&self, data: &[u64]) -> Option<DMatrix<f64>> {
+        let sum = data.iter().map(|&x| x as f64).sum::<f64>();
+        Some(DMatrix::from_element(1, 1, sum / (self.lambda * self.lambda)))
+    }
+}
 /// Generates one sample from the Poisson distribution either by
 /// Knuth's method if lambda < 30.0 or Rejection method PA by
 /// A. C. Atkinson from the Journal of the Royal Statistical Society
@@ -297,6 +361,7 @@ pub fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> f64 {
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use std::fmt::Debug;
+    use crate::distribution::Fit;
     use crate::statistics::*;
     use crate::distribution::{DiscreteCDF, Discrete, Poisson};
     use crate::distribution::internal::*;
@@ -476,4 +541,44 @@ mod tests {
         test::check_discrete_distribution(&try_create(0.3), 10);
         test::check_discrete_distribution(&try_create(4.5), 30);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Poisson| x.inverse_cdf(arg);
+        test_case(0.3, 0, inverse_cdf(0.01));
+        test_case(0.3, 0, inverse_cdf(0.3));
+        test_case(0.3, 0, inverse_cdf(0.5));
+        test_case(0.3, 1, inverse_cdf(0.9));
+        test_case(0.3, 3, inverse_cdf(0.999));
+        test_case(4.5, 0, inverse_cdf(0.01));
+        test_case(4.5, 3, inverse_cdf(0.3));
+        test_case(4.5, 4, inverse_cdf(0.5));
+        test_case(4.5, 7, inverse_cdf(0.9));
+        test_case(4.5, 12, inverse_cdf(0.999));
+        test_case(1000.0, 927, inverse_cdf(0.01));
+        test_case(1000.0, 983, inverse_cdf(0.3));
+        test_case(1000.0, 1000, inverse_cdf(0.5));
+        test_case(1000.0, 1041, inverse_cdf(0.9));
+        test_case(1000.0, 1099, inverse_cdf(0.999));
+    }
+
+    #[test]
+    fn test_inverse_cdf_is_infimum() {
+        test::check_discrete_inverse_cdf(&try_create(0.3));
+        test::check_discrete_inverse_cdf(&try_create(4.5));
+        test::check_discrete_inverse_cdf(&try_create(1000.0));
+        test::check_discrete_inverse_cdf(&try_create(1e-8));
+        test::check_discrete_inverse_cdf(&try_create(1e7));
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0, 3, 1, 7, 2, 0, 5, 1, 9, 2, 4, 0];
+        let n = Poisson::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.lambda(), 2.8333333333333333, 1e-15);
+        assert_eq!(Poisson::fit_mom(&data).unwrap(), n);
+        test::check_observed_information(n.observed_information(&data), &[4.2352941176470588], 1e-14);
+        assert!(Poisson::fit_mle(&[]).is_err());
+        assert!(Poisson::fit_mle(&[0, 0]).is_err());
+    }
 }
@@ -1,10 +1,10 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::{beta, gamma};
+use crate::distribution::{internal, Continuous, ContinuousCDF, Fit};
+use crate::function::{beta, float::cast, gamma, SpecialFloat};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Student's
 /// T](https://en.wikipedia.org/wiki/Student%27s_t-distribution) distribution
@@ -21,13 +21,13 @@ use std::f64;
 /// assert!(prec::almost_eq(n.pdf(0.0), 0.353553390593274, 1e-15));
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct StudentsT {
-    location: f64,
-    scale: f64,
-    freedom: f64,
+pub struct StudentsT<T = f64> {
+    location: T,
+    scale: T,
+    freedom: T,
 }
 
-impl StudentsT {
+impl<T: SpecialFloat> StudentsT<T> {
     /// Constructs a new student's t-distribution with location `location`,
     /// scale `scale`,
     /// and `freedom` freedom.
@@ -48,9 +48,9 @@ impl StudentsT {
     /// result = StudentsT::new(0.0, 0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(location: f64, scale: f64, freedom: f64) -> Result<StudentsT> {
+    pub fn new(location: T, scale: T, freedom: T) -> Result<StudentsT<T>> {
         let is_nan = location.is_nan() || scale.is_nan() || freedom.is_nan();
-        if is_nan || scale <= 0.0 || freedom <= 0.0 {
+        if is_nan || scale <= T::zero() || freedom <= T::zero() {
             Err(StatsError::BadParams)
         } else {
             Ok(StudentsT {
@@ -71,7 +71,7 @@ impl StudentsT {
     /// let n = StudentsT::new(0.0, 1.0, 2.0).unwrap();
     /// assert_eq!(n.location(), 0.0);
     /// ```
-    pub fn location(&self) -> f64 {
+    pub fn location(&self) -> T {
         self.location
     }
 
@@ -85,7 +85,7 @@ impl StudentsT {
     /// let n = StudentsT::new(0.0, 1.0, 2.0).unwrap();
     /// assert_eq!(n.scale(), 1.0);
     /// ```
-    pub fn scale(&self) -> f64 {
+    pub fn scale(&self) -> T {
         self.scale
     }
 
@@ -99,16 +99,16 @@ impl StudentsT {
     /// let n = StudentsT::new(0.0, 1.0, 2.0).unwrap();
     /// assert_eq!(n.freedom(), 2.0);
     /// ```
-    pub fn freedom(&self) -> f64 {
+    pub fn freedom(&self) -> T {
         self.freedom
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for StudentsT {
-    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for StudentsT<T> {
+    fn sample<R: Rng + ?Sized>(&self, r: &mut R) -> T {
         // based on method 2, section 5 in chapter 9 of L. Devroye's
         // "Non-Uniform Random Variate Generation"
-        let gamma = super::gamma::sample_unchecked(r, 0.5 * self.freedom, 0.5);
+        let gamma = super::gamma::sample_unchecked(r, cast::<T>(0.5) * self.freedom, cast(0.5));
         super::normal::sample_unchecked(
             r,
             self.location,
@@ -117,7 +117,7 @@ impl ::rand::distributions::Distribution<f64> for StudentsT {
     }
 }
 
-impl ContinuousCDF<f64, f64> for StudentsT {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for StudentsT<T> {
     /// Calculates the cumulative distribution function for the student's
     /// t-distribution
     /// at `x`
@@ -135,17 +135,17 @@ impl ContinuousCDF<f64, f64> for StudentsT {
     /// where `t = v / (v + k^2)`, `k = (x - μ) / σ`, `μ` is the location,
     /// `σ` is the scale, `v` is the freedom, and `I` is the regularized
     /// incomplete beta function
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         if self.freedom.is_infinite() {
             super::normal::cdf_unchecked(x, self.location, self.scale)
         } else {
             let k = (x - self.location) / self.scale;
             let h = self.freedom / (self.freedom + k * k);
-            let ib = 0.5 * beta::beta_reg(self.freedom / 2.0, 0.5, h);
+            let ib = cast::<T>(0.5) * beta::beta_reg(self.freedom / cast(2.0), cast(0.5), h);
             if x <= self.location {
                 ib
             } else {
-                1.0 - ib
+                T::one() - ib
             }
         }
     }
@@ -167,15 +167,15 @@ impl ContinuousCDF<f64, f64> for StudentsT {
     /// where `t = v / (v + k^2)`, `k = (x - μ) / σ`, `μ` is the location,
     /// `σ` is the scale, `v` is the freedom, and `I` is the regularized
     /// incomplete beta function
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         if self.freedom.is_infinite() {
             super::normal::sf_unchecked(x, self.location, self.scale)
         } else {
             let k = (x - self.location) / self.scale;
             let h = self.freedom / (self.freedom + k * k);
-            let ib = 0.5 * beta::beta_reg(self.freedom / 2.0, 0.5, h);
+            let ib = cast::<T>(0.5) * beta::beta_reg(self.freedom / cast(2.0), cast(0.5), h);
             if x <= self.location {
-                1.0 - ib
+                T::one() - ib
             } else {
                 ib
             }
@@ -184,15 +184,15 @@ impl ContinuousCDF<f64, f64> for StudentsT {
 
     /// Calculates the inverse cumulative distribution function for the
     /// Student's T-distribution at `x`
-    fn inverse_cdf(&self, x: f64) -> f64 {
+    fn inverse_cdf(&self, x: T) -> T {
         // first calculate inverse_cdf for normal Student's T
-        assert!((0.0..=1.0).contains(&x));
-        let x1 = if x >= 0.5 { 1.0 - x } else { x };
-        let a = 0.5 * self.freedom;
-        let b = 0.5;
-        let mut y = beta::inv_beta_reg(a, b, 2.0 * x1);
-        y = (self.freedom * (1. - y) / y).sqrt();
-        y = if x >= 0.5 { y } else { -y };
+        assert!((T::zero()..=T::one()).contains(&x));
+        let x1 = if x >= cast(0.5) { T::one() - x } else { x };
+        let a = cast::<T>(0.5) * self.freedom;
+        let b = cast(0.5);
+        let mut y = beta::inv_beta_reg(a, b, cast::<T>(2.0) * x1);
+        y = (self.freedom * (T::one() - y) / y).sqrt();
+        y = if x >= cast(0.5) { y } else { -y };
         // generalised Student's T is related to normal Student's T by `Y = μ + σ X`
         // where `X` is distributed as Student's T, so this result has to be scaled and shifted back
         // formally: F_Y(t) = P(Y <= t) = P(X <= (t - μ) / σ) = F_X((t - μ) / σ)
@@ -203,7 +203,7 @@ impl ContinuousCDF<f64, f64> for StudentsT {
     }
 }
 
-impl Min<f64> for StudentsT {
+impl<T: SpecialFloat> Min<T> for StudentsT<T> {
     /// Returns the minimum value in the domain of the student's t-distribution
     /// representable by a double precision float
     ///
@@ -212,12 +212,12 @@ impl Min<f64> for StudentsT {
     /// ```ignore
     /// -INF
     /// ```
-    fn min(&self) -> f64 {
-        f64::NEG_INFINITY
+    fn min(&self) -> T {
+        T::neg_infinity()
     }
 }
 
-impl Max<f64> for StudentsT {
+impl<T: SpecialFloat> Max<T> for StudentsT<T> {
     /// Returns the maximum value in the domain of the student's t-distribution
     /// representable by a double precision float
     ///
@@ -226,12 +226,12 @@ impl Max<f64> for StudentsT {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for StudentsT {
+impl<T: SpecialFloat> Distribution<T> for StudentsT<T> {
     /// Returns the mean of the student's t-distribution
     ///
     /// # None
@@ -245,8 +245,8 @@ impl Distribution<f64> for StudentsT {
     /// ```
     ///
     /// where `μ` is the location
-    fn mean(&self) -> Option<f64> {
-        if self.freedom <= 1.0 {
+    fn mean(&self) -> Option<T> {
+        if self.freedom <= T::one() {
             None
         } else {
             Some(self.location)
@@ -271,11 +271,11 @@ impl Distribution<f64> for StudentsT {
     /// ```
     ///
     /// where `σ` is the scale and `v` is the freedom
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         if self.freedom.is_infinite() {
             Some(self.scale * self.scale)
-        } else if self.freedom > 2.0 {
-            Some(self.freedom * self.scale * self.scale / (self.freedom - 2.0))
+        } else if self.freedom > cast(2.0) {
+            Some(self.freedom * self.scale * self.scale / (self.freedom - cast(2.0)))
         } else {
             None
         }
@@ -291,14 +291,14 @@ impl Distribution<f64> for StudentsT {
     ///
     /// where `σ` is the scale, `v` is the freedom, `ψ` is the digamma function, and `B` is the
     /// beta function
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         // generalised Student's T is related to normal Student's T by `Y = μ + σ X`
         // where `X` is distributed as Student's T, plugging into the definition
         // of entropy shows scaling affects the entropy by an additive constant `- ln σ`
         let shift = -self.scale.ln();
-        let result = (self.freedom + 1.0) / 2.0
-            * (gamma::digamma((self.freedom + 1.0) / 2.0) - gamma::digamma(self.freedom / 2.0))
-            + (self.freedom.sqrt() * beta::beta(self.freedom / 2.0, 0.5)).ln();
+        let result = (self.freedom + T::one()) / cast(2.0)
+            * (gamma::digamma((self.freedom + T::one()) / cast(2.0)) - gamma::digamma(self.freedom / cast(2.0)))
+            + (self.freedom.sqrt() * beta::beta(self.freedom / cast(2.0), cast(0.5))).ln();
         Some(result + shift)
     }
     /// Returns the skewness of the student's t-distribution
@@ -312,16 +312,16 @@ impl Distribution<f64> for StudentsT {
     /// ```ignore
     /// 0
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        if self.freedom <= 3.0 {
+    fn skewness(&self) -> Option<T> {
+        if self.freedom <= cast(3.0) {
             None
         } else {
-            Some(0.0)
+            Some(T::zero())
         }
     }
 }
 
-impl Median<f64> for StudentsT {
+impl<T: SpecialFloat> Median<T> for StudentsT<T> {
     /// Returns the median of the student's t-distribution
     ///
     /// # Formula
@@ -331,12 +331,12 @@ impl Median<f64> for StudentsT {
     /// ```
     ///
     /// where `μ` is the location
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         self.location
     }
 }
 
-impl Mode<Option<f64>> for StudentsT {
+impl<T: SpecialFloat> Mode<Option<T>> for StudentsT<T> {
     /// Returns the mode of the student's t-distribution
     ///
     /// # Formula
@@ -346,12 +346,12 @@ impl Mode<Option<f64>> for StudentsT {
     /// ```
     ///
     /// where `μ` is the location
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.location)
     }
 }
 
-impl Continuous<f64, f64> for StudentsT {
+impl<T: SpecialFloat> Continuous<T, T> for StudentsT<T> {
     /// Calculates the probability density function for the student's
     /// t-distribution
     /// at `x`
@@ -366,17 +366,17 @@ impl Continuous<f64, f64> for StudentsT {
     /// where `k = (x - μ) / σ`, `μ` is the location, `σ` is the scale, `v` is
     /// the freedom,
     /// and `Γ` is the gamma function
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         if x.is_infinite() {
-            0.0
-        } else if self.freedom >= 1e8 {
+            T::zero()
+        } else if self.freedom >= cast(1e8) {
             super::normal::pdf_unchecked(x, self.location, self.scale)
         } else {
             let d = (x - self.location) / self.scale;
-            (gamma::ln_gamma((self.freedom + 1.0) / 2.0) - gamma::ln_gamma(self.freedom / 2.0))
+            (gamma::ln_gamma((self.freedom + T::one()) / cast(2.0)) - gamma::ln_gamma(self.freedom / cast(2.0)))
                 .exp()
-                * (1.0 + d * d / self.freedom).powf(-0.5 * (self.freedom + 1.0))
-                / (self.freedom * f64::consts::PI).sqrt()
+                * (T::one() + d * d / self.freedom).powf(-cast::<T>(0.5) * (self.freedom + T::one()))
+                / (self.freedom * T::PI()).sqrt()
                 / self.scale
         }
     }
@@ -395,25 +395,199 @@ impl Continuous<f64, f64> for StudentsT {
     /// where `k = (x - μ) / σ`, `μ` is the location, `σ` is the scale, `v` is
     /// the freedom,
     /// and `Γ` is the gamma function
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         if x.is_infinite() {
-            f64::NEG_INFINITY
-        } else if self.freedom >= 1e8 {
+            T::neg_infinity()
+        } else if self.freedom >= cast(1e8) {
             super::normal::ln_pdf_unchecked(x, self.location, self.scale)
         } else {
             let d = (x - self.location) / self.scale;
-            gamma::ln_gamma((self.freedom + 1.0) / 2.0)
-                - 0.5 * ((self.freedom + 1.0) * (1.0 + d * d / self.freedom).ln())
-                - gamma::ln_gamma(self.freedom / 2.0)
-                - 0.5 * (self.freedom * f64::consts::PI).ln()
+            gamma::ln_gamma((self.freedom + T::one()) / cast(2.0))
+                - cast::<T>(0.5) * ((self.freedom + T::one()) * (T::one() + d * d / self.freedom).ln())
+                - gamma::ln_gamma(self.freedom / cast(2.0))
+                - cast::<T>(0.5) * (self.freedom * T::PI()).ln()
                 - self.scale.ln()
         }
     }
 }
 
+impl Fit<f64> for StudentsT {
+    /// Returns the maximum likelihood estimate of the student's
+    /// t-distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two distinct observations or
+    /// if the iteration fails to converge
+    ///
+    /// # Remarks
+    ///
+    /// The estimate is computed by the ECME algorithm of Liu and Rubin
+    /// (1995), "ML estimation of the t distribution using EM and its
+    /// extensions, ECM and ECME". Location and scale are updated as weighted
+    /// mean and standard deviation, and the freedom by solving its likelihood
+    /// equation with Newton's method. If the likelihood keeps increasing
+    /// towards the normal limit the freedom is `INF`.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// w_i = (ν + 1) / (ν + z_i^2)
+    /// μ = Σ w_i * x_i / Σ w_i
+    /// σ^2 = (1 / n) * Σ w_i * (x_i - μ)^2
+    /// Σ ψ((ν + 1) / 2) - ψ(ν / 2) - ln(1 + z_i^2 / ν) + (z_i^2 - 1) / (ν + z_i^2) = 0
+    /// ```
+    ///
+    /// where `z_i = (x_i - μ) / σ`, `μ` is the location, `σ` the scale, `ν`
+    /// the freedom and `ψ` is the digamma function
+    fn fit_mle(data: &[f64]) -> Result<StudentsT> {
+        if data.iter().any(|x| x.is_nan()) {
+            return Err(StatsError::BadParams);
+        }
+        let n = data.len() as f64;
+        let mut location = OrderStatistics::median(&mut Data::new(data.to_vec()));
+        let mut scale = data.population_std_dev();
+        let mut freedom: f64 = 10.0;
+        if scale.is_nan() || scale <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let mut z_2 = vec![0.0; data.len()];
+        for _ in 0..10000 {
+            let weight = |z_2: f64| {
+                if freedom.is_infinite() {
+                    1.0
+                } else {
+                    (freedom + 1.0) / (freedom + z_2)
+                }
+            };
+            for (z_2, x) in z_2.iter_mut().zip(data) {
+                *z_2 = (x - location) * (x - location) / (scale * scale);
+            }
+            let (sum_w, sum_wx) = z_2
+                .iter()
+                .zip(data)
+                .fold((0.0, 0.0), |(sw, swx), (&z_2, x)| {
+                    let w = weight(z_2);
+                    (sw + w, swx + w * x)
+                });
+            let next_location = sum_wx / sum_w;
+            let next_scale = (z_2
+                .iter()
+                .zip(data)
+                .map(|(&z_2, x)| weight(z_2) * (x - next_location) * (x - next_location))
+                .sum::<f64>()
+                / n)
+                .sqrt();
+
+            for (z_2, x) in z_2.iter_mut().zip(data) {
+                *z_2 = (x - next_location) * (x - next_location) / (next_scale * next_scale);
+            }
+            let freedom_score = |v: f64| {
+                let (s, ds) = z_2.iter().fold((0.0, 0.0), |(s, ds), &z_2| {
+                    let q = v + z_2;
+                    (
+                        s - (z_2 / v).ln_1p() + (z_2 - 1.0) / q,
+                        ds - 1.0 / q + 1.0 / v - (z_2 - 1.0) / (q * q),
+                    )
+                });
+                (
+                    n * (gamma::digamma((v + 1.0) / 2.0) - gamma::digamma(v / 2.0)) + s,
+                    n * (gamma::trigamma((v + 1.0) / 2.0) - gamma::trigamma(v / 2.0)) / 2.0 + ds,
+                )
+            };
+            let next_freedom = if freedom_score(1e6).0 >= 0.0 {
+                f64::INFINITY
+            } else {
+                let guess = if freedom.is_finite() { freedom } else { 1e5 };
+                internal::positive_root(freedom_score, guess)?
+            };
+
+            let converged = (next_location - location).abs() <= 1e-10 * next_scale
+                && (next_scale - scale).abs() <= 1e-10 * next_scale
+                && (next_freedom == freedom || (next_freedom - freedom).abs() <= 1e-8 * freedom);
+            location = next_location;
+            scale = next_scale;
+            freedom = next_freedom;
+            if scale.is_nan() || scale <= 0.0 {
+                return Err(StatsError::ComputationFailedToConverge);
+            }
+            if converged {
+                return StudentsT::new(location, scale, freedom);
+            }
+        }
+        Err(StatsError::ComputationFailedToConverge)
+    }
+
+    /// Returns the method of moments estimate of the student's
+    /// t-distribution for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sample has no positive excess kurtosis, as
+    /// the kurtosis of the distribution is only defined for `ν > 4`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// μ = m
+    /// ν = 4 + 6 / κ
+    /// σ = sqrt(s^2 * (ν - 2) / ν)
+    /// ```
+    ///
+    /// where `m`, `s^2` and `κ` are the mean, the population variance and
+    /// the excess kurtosis of the observations
+    fn fit_mom(data: &[f64]) -> Result<StudentsT> {
+        let mean = data.mean();
+        let var = data.population_variance();
+        let m4 = data.iter().map(|x| (x - mean).powi(4)).mean();
+        let kurtosis = m4 / (var * var) - 3.0;
+        if !kurtosis.is_finite() || kurtosis <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let freedom = 4.0 + 6.0 / kurtosis;
+        StudentsT::new(mean, (var * (freedom - 2.0) / freedom).sqrt(), freedom)
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(μ, σ, ν)`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `None` if the freedom is infinite
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let (scale, v) = (self.scale, self.freedom);
+        if v.is_infinite() {
+            return None;
+        }
+        let n = data.len() as f64;
+        let scale_2 = scale * scale;
+        let mut info = DMatrix::zeros(3, 3);
+        info[(2, 2)] = n * (gamma::trigamma(v / 2.0) - gamma::trigamma((v + 1.0) / 2.0)) / 4.0;
+        for x in data {
+            let z = (x - self.location) / scale;
+            let z_2 = z * z;
+            let q = v + z_2;
+            let w = (v + 1.0) / q;
+            let c = 2.0 * z_2 * w * w / (v + 1.0);
+            info[(0, 0)] += (w - c) / scale_2;
+            info[(0, 1)] += z * (2.0 * w - c) / scale_2;
+            info[(1, 1)] -= (1.0 - 3.0 * w * z_2 + c * z_2) / scale_2;
+            info[(0, 2)] -= z * (z_2 - 1.0) / (scale * q * q);
+            info[(1, 2)] -= z_2 * (z_2 - 1.0) / (scale * q * q);
+            info[(2, 2)] += (1.0 / q - 1.0 / v + (z_2 - 1.0) / (q * q)) / 2.0;
+        }
+        info[(1, 0)] = info[(0, 1)];
+        info[(2, 0)] = info[(0, 2)];
+        info[(2, 1)] = info[(1, 2)];
+        Some(info)
+    }
+}
+
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::consts::ACC;
+    use crate::distribution::Fit;
     use crate::distribution::internal::*;
     use crate::distribution::{Continuous, ContinuousCDF, StudentsT};
     use crate::statistics::*;
@@ -1158,4 +1332,36 @@ mod tests {
         let d = StudentsT::new(0.0, 1.0, 12.0).unwrap();
         assert_eq!(d.inverse_cdf(1.0), std::f64::INFINITY);
     }
+
+    #[test]
+    fn test_f32() {
+        let d32 = StudentsT::<f32>::new(0.0, 1.0, 5.0).unwrap();
+        let d64 = StudentsT::new(0.0, 1.0, 5.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[-3.0, -1.0, 0.0, 0.5, 2.5], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+        let n = StudentsT::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.location(), 0.38395145239853566, 1e-8);
+        assert_almost_eq!(n.scale(), 1.8863345679899484, 1e-8);
+        assert_almost_eq!(n.freedom(), 7.281903744391168, 1e-6);
+        test::check_observed_information(n.observed_information(&data), &[
+            2.278152291049886, -0.18088845976480024, -0.023429048678899443,
+            -0.18088845976480024, 3.8755134668444005, -0.1381402741373731,
+            -0.023429048678899443, -0.1381402741373731, 0.0062105430527766566,
+        ], 1e-6);
+        let n = StudentsT::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.location(), 0.5, 1e-15);
+        assert_almost_eq!(n.scale(), 2.1478539914439937, 1e-13);
+        assert_almost_eq!(n.freedom(), 74.808698392688308, 1e-11);
+
+        // a platykurtic sample is fitted best by the normal limit
+        let n = StudentsT::fit_mle(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!(n.freedom(), f64::INFINITY);
+        assert_almost_eq!(n.location(), 2.5, 1e-9);
+        assert!(StudentsT::fit_mom(&[1.0, 2.0, 3.0, 4.0]).is_err());
+        assert!(StudentsT::fit_mle(&[1.0, 1.0]).is_err());
+    }
 }
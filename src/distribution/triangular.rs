@@ -1,8 +1,8 @@
 use crate::distribution::{Continuous, ContinuousCDF};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::Rng;
-use std::f64;
 
 /// Implements the
 /// [Triangular](https://en.wikipedia.org/wiki/Triangular_distribution)
@@ -19,13 +19,13 @@ use std::f64;
 /// assert_eq!(n.pdf(2.5), 5.0 / 12.5);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Triangular {
-    min: f64,
-    max: f64,
-    mode: f64,
+pub struct Triangular<T = f64> {
+    min: T,
+    max: T,
+    mode: T,
 }
 
-impl Triangular {
+impl<T: SpecialFloat> Triangular<T> {
     /// Constructs a new triangular distribution with a minimum of `min`,
     /// maximum of `max`, and a mode of `mode`.
     ///
@@ -45,7 +45,7 @@ impl Triangular {
     /// result = Triangular::new(2.5, 1.5, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(min: f64, max: f64, mode: f64) -> Result<Triangular> {
+    pub fn new(min: T, max: T, mode: T) -> Result<Triangular<T>> {
         if !min.is_finite() || !max.is_finite() || !mode.is_finite() {
             return Err(StatsError::BadParams);
         }
@@ -59,13 +59,13 @@ impl Triangular {
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Triangular {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Triangular<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
         sample_unchecked(rng, self.min, self.max, self.mode)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Triangular {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Triangular<T> {
     /// Calculates the cumulative distribution function for the triangular
     /// distribution
     /// at `x`
@@ -83,18 +83,18 @@ impl ContinuousCDF<f64, f64> for Triangular {
     ///     1
     /// }
     /// ```
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
         if x <= a {
-            0.0
+            T::zero()
         } else if x <= c {
             (x - a) * (x - a) / ((b - a) * (c - a))
         } else if x < b {
-            1.0 - (b - x) * (b - x) / ((b - a) * (b - c))
+            T::one() - (b - x) * (b - x) / ((b - a) * (b - c))
         } else {
-            1.0
+            T::one()
         }
     }
 
@@ -114,47 +114,79 @@ impl ContinuousCDF<f64, f64> for Triangular {
     ///     0
     /// }
     /// ```
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
         if x <= a {
-            1.0
+            T::one()
         } else if x <= c {
-            1.0 - ((x - a) * (x - a) / ((b - a) * (c - a)))
+            T::one() - ((x - a) * (x - a) / ((b - a) * (c - a)))
         } else if x < b {
             (b - x) * (b - x) / ((b - a) * (b - c))
         } else {
-            0.0
+            T::zero()
+        }
+    }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// triangular distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// if p < (c - a) / (b - a) {
+    ///     a + sqrt(p * (b - a) * (c - a))
+    /// } else {
+    ///     b - sqrt((1 - p) * (b - a) * (b - c))
+    /// }
+    /// ```
+    ///
+    /// where `a` is the minimum, `b` is the maximum, and `c` is the mode
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let a = self.min;
+        let b = self.max;
+        let c = self.mode;
+        if p < (c - a) / (b - a) {
+            a + (p * (b - a) * (c - a)).sqrt()
+        } else {
+            b - ((T::one() - p) * (b - a) * (b - c)).sqrt()
         }
     }
 }
 
-impl Min<f64> for Triangular {
+impl<T: SpecialFloat> Min<T> for Triangular<T> {
     /// Returns the minimum value in the domain of the
     /// triangular distribution representable by a double precision float
     ///
     /// # Remarks
     ///
     /// The return value is the same min used to construct the distribution
-    fn min(&self) -> f64 {
+    fn min(&self) -> T {
         self.min
     }
 }
 
-impl Max<f64> for Triangular {
+impl<T: SpecialFloat> Max<T> for Triangular<T> {
     /// Returns the maximum value in the domain of the
     /// triangular distribution representable by a double precision float
     ///
     /// # Remarks
     ///
     /// The return value is the same max used to construct the distribution
-    fn max(&self) -> f64 {
+    fn max(&self) -> T {
         self.max
     }
 }
 
-impl Distribution<f64> for Triangular {
+impl<T: SpecialFloat> Distribution<T> for Triangular<T> {
     /// Returns the mean of the triangular distribution
     ///
     /// # Formula
@@ -162,8 +194,8 @@ impl Distribution<f64> for Triangular {
     /// ```ignore
     /// (min + max + mode) / 3
     /// ```
-    fn mean(&self) -> Option<f64> {
-        Some((self.min + self.max + self.mode) / 3.0)
+    fn mean(&self) -> Option<T> {
+        Some((self.min + self.max + self.mode) / cast(3.0))
     }
     /// Returns the variance of the triangular distribution
     ///
@@ -172,11 +204,11 @@ impl Distribution<f64> for Triangular {
     /// ```ignore
     /// (min^2 + max^2 + mode^2 - min * max - min * mode - max * mode) / 18
     /// ```
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
-        Some((a * a + b * b + c * c - a * b - a * c - b * c) / 18.0)
+        Some((a * a + b * b + c * c - a * b - a * c - b * c) / cast(18.0))
     }
     /// Returns the entropy of the triangular distribution
     ///
@@ -185,8 +217,8 @@ impl Distribution<f64> for Triangular {
     /// ```ignore
     /// 1 / 2 + ln((max - min) / 2)
     /// ```
-    fn entropy(&self) -> Option<f64> {
-        Some(0.5 + ((self.max - self.min) / 2.0).ln())
+    fn entropy(&self) -> Option<T> {
+        Some(cast::<T>(0.5) + ((self.max - self.min) / cast(2.0)).ln())
     }
     /// Returns the skewness of the triangular distribution
     ///
@@ -198,17 +230,17 @@ impl Distribution<f64> for Triangular {
     /// ( 5 * (min^2 + max^2 + mode^2 - min * max - min * mode - max * mode)^(3
     /// / 2))
     /// ```
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
-        let q = f64::consts::SQRT_2 * (a + b - 2.0 * c) * (2.0 * a - b - c) * (a - 2.0 * b + c);
-        let d = 5.0 * (a * a + b * b + c * c - a * b - a * c - b * c).powf(3.0 / 2.0);
+        let q = T::SQRT_2() * (a + b - cast::<T>(2.0) * c) * (cast::<T>(2.0) * a - b - c) * (a - cast::<T>(2.0) * b + c);
+        let d = cast::<T>(5.0) * (a * a + b * b + c * c - a * b - a * c - b * c).powf(cast::<T>(3.0) / cast(2.0));
         Some(q / d)
     }
 }
 
-impl Median<f64> for Triangular {
+impl<T: SpecialFloat> Median<T> for Triangular<T> {
     /// Returns the median of the triangular distribution
     ///
     /// # Formula
@@ -220,19 +252,19 @@ impl Median<f64> for Triangular {
     ///     max - sqrt((max - min) * (max - mode) / 2)
     /// }
     /// ```
-    fn median(&self) -> f64 {
+    fn median(&self) -> T {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
-        if c >= (a + b) / 2.0 {
-            a + ((b - a) * (c - a) / 2.0).sqrt()
+        if c >= (a + b) / cast(2.0) {
+            a + ((b - a) * (c - a) / cast(2.0)).sqrt()
         } else {
-            b - ((b - a) * (b - c) / 2.0).sqrt()
+            b - ((b - a) * (b - c) / cast(2.0)).sqrt()
         }
     }
 }
 
-impl Mode<Option<f64>> for Triangular {
+impl<T: SpecialFloat> Mode<Option<T>> for Triangular<T> {
     /// Returns the mode of the triangular distribution
     ///
     /// # Formula
@@ -240,12 +272,12 @@ impl Mode<Option<f64>> for Triangular {
     /// ```ignore
     /// mode
     /// ```
-    fn mode(&self) -> Option<f64> {
+    fn mode(&self) -> Option<T> {
         Some(self.mode)
     }
 }
 
-impl Continuous<f64, f64> for Triangular {
+impl<T: SpecialFloat> Continuous<T, T> for Triangular<T> {
     /// Calculates the probability density function for the triangular
     /// distribution
     /// at `x`
@@ -263,16 +295,16 @@ impl Continuous<f64, f64> for Triangular {
     ///     0
     /// }
     /// ```
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         let a = self.min;
         let b = self.max;
         let c = self.mode;
         if a <= x && x <= c {
-            2.0 * (x - a) / ((b - a) * (c - a))
+            cast::<T>(2.0) * (x - a) / ((b - a) * (c - a))
         } else if c < x && x <= b {
-            2.0 * (b - x) / ((b - a) * (b - c))
+            cast::<T>(2.0) * (b - x) / ((b - a) * (b - c))
         } else {
-            0.0
+            T::zero()
         }
     }
 
@@ -293,17 +325,17 @@ impl Continuous<f64, f64> for Triangular {
     ///     0
     /// } )
     /// ```
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         self.pdf(x).ln()
     }
 }
 
-fn sample_unchecked<R: Rng + ?Sized>(rng: &mut R, min: f64, max: f64, mode: f64) -> f64 {
-    let f: f64 = rng.gen();
+fn sample_unchecked<T: SpecialFloat, R: Rng + ?Sized>(rng: &mut R, min: T, max: T, mode: T) -> T {
+    let f: T = cast(rng.gen::<f64>());
     if f < (mode - min) / (max - min) {
         min + (f * (max - min) * (mode - min)).sqrt()
     } else {
-        max - ((1.0 - f) * (max - min) * (max - mode)).sqrt()
+        max - ((T::one() - f) * (max - min) * (max - mode)).sqrt()
     }
 }
 
@@ -535,4 +567,25 @@ mod tests {
         test::check_continuous_distribution(&try_create(-5.0, 5.0, 0.0), -5.0, 5.0);
         test::check_continuous_distribution(&try_create(-15.0, -2.0, -3.0), -15.0, -2.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Triangular| x.inverse_cdf(arg);
+        test_almost(0.0, 1.0, 0.5, 0.22360679774997897585, 1e-15, inverse_cdf(0.1));
+        test_almost(0.0, 1.0, 0.5, 0.5, 1e-15, inverse_cdf(0.5));
+        test_almost(0.0, 1.0, 0.5, 0.77639320225002105518, 1e-15, inverse_cdf(0.9));
+        test_almost(-1.0, 3.0, 0.0, -0.36754446796632411605, 1e-15, inverse_cdf(0.1));
+        test_almost(-1.0, 3.0, 0.0, 0.5505102572168219018, 1e-15, inverse_cdf(0.5));
+        test_almost(-1.0, 3.0, 0.0, 1.9045548849896678947, 1e-14, inverse_cdf(0.9));
+        test_almost(0.0, 4.0, 4.0, 1.2649110640673517679, 1e-14, inverse_cdf(0.1));
+        test_almost(0.0, 4.0, 4.0, 2.8284271247461900976, 1e-14, inverse_cdf(0.5));
+        test_almost(0.0, 4.0, 4.0, 3.7947331922020552452, 1e-14, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Triangular::<f32>::new(0.0, 4.0, 1.0).unwrap();
+        let d64 = Triangular::new(0.0, 4.0, 1.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.5, 1.0, 2.0, 3.5], 1e-4);
+    }
 }
@@ -1,9 +1,9 @@
 use crate::distribution::{Continuous, ContinuousCDF};
+use crate::function::{float::cast, SpecialFloat};
 use crate::statistics::*;
 use crate::{Result, StatsError};
 use rand::distributions::Uniform as RandUniform;
 use rand::Rng;
-use std::f64;
 
 /// Implements the [Continuous
 /// Uniform](https://en.wikipedia.org/wiki/Uniform_distribution_(continuous))
@@ -20,12 +20,12 @@ use std::f64;
 /// assert_eq!(n.pdf(0.5), 1.0);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Uniform {
-    min: f64,
-    max: f64,
+pub struct Uniform<T = f64> {
+    min: T,
+    max: T,
 }
 
-impl Uniform {
+impl<T: SpecialFloat> Uniform<T> {
     /// Constructs a new uniform distribution with a min of `min` and a max
     /// of `max`
     ///
@@ -45,7 +45,7 @@ impl Uniform {
     /// result = Uniform::new(f64::NAN, f64::NAN);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(min: f64, max: f64) -> Result<Uniform> {
+    pub fn new(min: T, max: T) -> Result<Uniform<T>> {
         if min > max || min.is_nan() || max.is_nan() {
             Err(StatsError::BadParams)
         } else {
@@ -54,14 +54,14 @@ impl Uniform {
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Uniform {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let d = RandUniform::new_inclusive(self.min, self.max);
-        rng.sample(d)
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Uniform<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let d = RandUniform::new_inclusive(self.min.to_f64().unwrap(), self.max.to_f64().unwrap());
+        cast(rng.sample(d))
     }
 }
 
-impl ContinuousCDF<f64, f64> for Uniform {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Uniform<T> {
     /// Calculates the cumulative distribution function for the uniform
     /// distribution
     /// at `x`
@@ -71,11 +71,11 @@ impl ContinuousCDF<f64, f64> for Uniform {
     /// ```ignore
     /// (x - min) / (max - min)
     /// ```
-    fn cdf(&self, x: f64) -> f64 {
+    fn cdf(&self, x: T) -> T {
         if x <= self.min {
-            0.0
+            T::zero()
         } else if x >= self.max {
-            1.0
+            T::one()
         } else {
             (x - self.min) / (self.max - self.min)
         }
@@ -89,34 +89,57 @@ impl ContinuousCDF<f64, f64> for Uniform {
     /// ```ignore
     /// (max - x) / (max - min)
     /// ```
-    fn sf(&self, x: f64) -> f64 {
+    fn sf(&self, x: T) -> T {
         if x <= self.min {
-            1.0
+            T::one()
         } else if x >= self.max {
-            0.0
+            T::zero()
         } else if x.is_infinite() && self.max.is_infinite() {
-            0.0
+            T::zero()
         } else if self.max.is_infinite() {
-            1.0
+            T::one()
         } else {
             (self.max - x) / (self.max - self.min)
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// continuous uniform distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// min + p * (max - min)
+    /// ```
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        if p == T::one() {
+            self.max
+        } else {
+            self.min + p * (self.max - self.min)
+        }
+    }
 }
 
-impl Min<f64> for Uniform {
-    fn min(&self) -> f64 {
+impl<T: SpecialFloat> Min<T> for Uniform<T> {
+    fn min(&self) -> T {
         self.min
     }
 }
 
-impl Max<f64> for Uniform {
-    fn max(&self) -> f64 {
+impl<T: SpecialFloat> Max<T> for Uniform<T> {
+    fn max(&self) -> T {
         self.max
     }
 }
 
-impl Distribution<f64> for Uniform {
+impl<T: SpecialFloat> Distribution<T> for Uniform<T> {
     /// Returns the mean for the continuous uniform distribution
     ///
     /// # Formula
@@ -124,8 +147,8 @@ impl Distribution<f64> for Uniform {
     /// ```ignore
     /// (min + max) / 2
     /// ```
-    fn mean(&self) -> Option<f64> {
-        Some((self.min + self.max) / 2.0)
+    fn mean(&self) -> Option<T> {
+        Some((self.min + self.max) / cast(2.0))
     }
     /// Returns the variance for the continuous uniform distribution
     ///
@@ -134,8 +157,8 @@ impl Distribution<f64> for Uniform {
     /// ```ignore
     /// (max - min)^2 / 12
     /// ```
-    fn variance(&self) -> Option<f64> {
-        Some((self.max - self.min) * (self.max - self.min) / 12.0)
+    fn variance(&self) -> Option<T> {
+        Some((self.max - self.min) * (self.max - self.min) / cast(12.0))
     }
     /// Returns the entropy for the continuous uniform distribution
     ///
@@ -144,7 +167,7 @@ impl Distribution<f64> for Uniform {
     /// ```ignore
     /// ln(max - min)
     /// ```
-    fn entropy(&self) -> Option<f64> {
+    fn entropy(&self) -> Option<T> {
         Some((self.max - self.min).ln())
     }
     /// Returns the skewness for the continuous uniform distribution
@@ -154,12 +177,12 @@ impl Distribution<f64> for Uniform {
     /// ```ignore
     /// 0
     /// ```
-    fn skewness(&self) -> Option<f64> {
-        Some(0.0)
+    fn skewness(&self) -> Option<T> {
+        Some(T::zero())
     }
 }
 
-impl Median<f64> for Uniform {
+impl<T: SpecialFloat> Median<T> for Uniform<T> {
     /// Returns the median for the continuous uniform distribution
     ///
     /// # Formula
@@ -167,12 +190,12 @@ impl Median<f64> for Uniform {
     /// ```ignore
     /// (min + max) / 2
     /// ```
-    fn median(&self) -> f64 {
-        (self.min + self.max) / 2.0
+    fn median(&self) -> T {
+        (self.min + self.max) / cast(2.0)
     }
 }
 
-impl Mode<Option<f64>> for Uniform {
+impl<T: SpecialFloat> Mode<Option<T>> for Uniform<T> {
     /// Returns the mode for the continuous uniform distribution
     ///
     /// # Remarks
@@ -185,12 +208,12 @@ impl Mode<Option<f64>> for Uniform {
     /// ```ignore
     /// N/A // (max + min) / 2 for the middle element
     /// ```
-    fn mode(&self) -> Option<f64> {
-        Some((self.min + self.max) / 2.0)
+    fn mode(&self) -> Option<T> {
+        Some((self.min + self.max) / cast(2.0))
     }
 }
 
-impl Continuous<f64, f64> for Uniform {
+impl<T: SpecialFloat> Continuous<T, T> for Uniform<T> {
     /// Calculates the probability density function for the continuous uniform
     /// distribution at `x`
     ///
@@ -203,11 +226,11 @@ impl Continuous<f64, f64> for Uniform {
     /// ```ignore
     /// 1 / (max - min)
     /// ```
-    fn pdf(&self, x: f64) -> f64 {
+    fn pdf(&self, x: T) -> T {
         if x < self.min || x > self.max {
-            0.0
+            T::zero()
         } else {
-            1.0 / (self.max - self.min)
+            T::one() / (self.max - self.min)
         }
     }
 
@@ -224,9 +247,9 @@ impl Continuous<f64, f64> for Uniform {
     /// ```ignore
     /// ln(1 / (max - min))
     /// ```
-    fn ln_pdf(&self, x: f64) -> f64 {
+    fn ln_pdf(&self, x: T) -> T {
         if x < self.min || x > self.max {
-            f64::NEG_INFINITY
+            T::neg_infinity()
         } else {
             -(self.max - self.min).ln()
         }
@@ -484,4 +507,22 @@ mod tests {
             .all(|v| (min <= v) && (v < max))
         );
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Uniform| x.inverse_cdf(arg);
+        test_case(0.0, 1.0, 0.0, inverse_cdf(0.0));
+        test_case(0.0, 1.0, 0.25, inverse_cdf(0.25));
+        test_case(0.0, 1.0, 1.0, inverse_cdf(1.0));
+        test_case(-5.0, 5.0, -2.5, inverse_cdf(0.25));
+        test_case(-5.0, 5.0, 5.0, inverse_cdf(1.0));
+        test_case(10.0, 110.0, 60.0, inverse_cdf(0.5));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Uniform::<f32>::new(-1.0, 3.0).unwrap();
+        let d64 = Uniform::new(-1.0, 3.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[-0.5, 0.0, 1.0, 2.5], 1e-4);
+    }
 }
@@ -1,8 +1,9 @@
-use crate::distribution::{Continuous, ContinuousCDF};
-use crate::function::gamma;
+use crate::distribution::{internal, Continuous, ContinuousCDF, Fit};
+use crate::function::{float::cast, gamma, SpecialFloat};
 use crate::is_zero;
 use crate::statistics::*;
 use crate::{consts, Result, StatsError};
+use nalgebra::DMatrix;
 use rand::Rng;
 use std::f64;
 
@@ -22,13 +23,13 @@ use std::f64;
 /// assert_eq!(n.pdf(1.0), 3.6787944117144232159552377016146086744581113103177);
 /// ```
 #[derive(Debug, Copy, Clone, PartialEq)]
-pub struct Weibull {
-    shape: f64,
-    scale: f64,
-    scale_pow_shape_inv: f64,
+pub struct Weibull<T = f64> {
+    shape: T,
+    scale: T,
+    scale_pow_shape_inv: T,
 }
 
-impl Weibull {
+impl<T: SpecialFloat> Weibull<T> {
     /// Constructs a new weibull distribution with a shape (k) of `shape`
     /// and a scale (λ) of `scale`
     ///
@@ -48,11 +49,11 @@ impl Weibull {
     /// result = Weibull::new(0.0, 0.0);
     /// assert!(result.is_err());
     /// ```
-    pub fn new(shape: f64, scale: f64) -> Result<Weibull> {
+    pub fn new(shape: T, scale: T) -> Result<Weibull<T>> {
         let is_nan = shape.is_nan() || scale.is_nan();
         match (shape, scale, is_nan) {
             (_, _, true) => Err(StatsError::BadParams),
-            (_, _, false) if shape <= 0.0 || scale <= 0.0 => Err(StatsError::BadParams),
+            (_, _, false) if shape <= T::zero() || scale <= T::zero() => Err(StatsError::BadParams),
             (_, _, false) => Ok(Weibull {
                 shape,
                 scale,
@@ -71,7 +72,7 @@ impl Weibull {
     /// let n = Weibull::new(10.0, 1.0).unwrap();
     /// assert_eq!(n.shape(), 10.0);
     /// ```
-    pub fn shape(&self) -> f64 {
+    pub fn shape(&self) -> T {
         self.shape
     }
 
@@ -85,19 +86,19 @@ impl Weibull {
     /// let n = Weibull::new(10.0, 1.0).unwrap();
     /// assert_eq!(n.scale(), 1.0);
     /// ```
-    pub fn scale(&self) -> f64 {
+    pub fn scale(&self) -> T {
         self.scale
     }
 }
 
-impl ::rand::distributions::Distribution<f64> for Weibull {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
-        let x: f64 = rng.gen();
-        self.scale * (-x.ln()).powf(1.0 / self.shape)
+impl<T: SpecialFloat> ::rand::distributions::Distribution<T> for Weibull<T> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let x: T = cast(rng.gen::<f64>());
+        self.scale * (-x.ln()).powf(T::one() / self.shape)
     }
 }
 
-impl ContinuousCDF<f64, f64> for Weibull {
+impl<T: SpecialFloat> ContinuousCDF<T, T> for Weibull<T> {
     /// Calculates the cumulative distribution function for the weibull
     /// distribution at `x`
     ///
@@ -108,9 +109,9 @@ impl ContinuousCDF<f64, f64> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn cdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
+    fn cdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
         } else {
             -(-x.powf(self.shape) * self.scale_pow_shape_inv).exp_m1()
         }
@@ -126,16 +127,37 @@ impl ContinuousCDF<f64, f64> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn sf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            1.0
+    fn sf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::one()
         } else {
             (-x.powf(self.shape) * self.scale_pow_shape_inv).exp()
         }
     }
+
+    /// Calculates the inverse cumulative distribution function for the
+    /// weibull distribution at `p`
+    ///
+    /// # Panics
+    ///
+    /// If `p < 0.0` or `p > 1.0`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// λ * (-ln(1 - p))^(1 / k)
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the scale
+    fn inverse_cdf(&self, p: T) -> T {
+        if !(T::zero()..=T::one()).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        self.scale * (-(-p).ln_1p()).powf(T::one() / self.shape)
+    }
 }
 
-impl Min<f64> for Weibull {
+impl<T: SpecialFloat> Min<T> for Weibull<T> {
     /// Returns the minimum value in the domain of the weibull
     /// distribution representable by a double precision float
     ///
@@ -144,12 +166,12 @@ impl Min<f64> for Weibull {
     /// ```ignore
     /// 0
     /// ```
-    fn min(&self) -> f64 {
-        0.0
+    fn min(&self) -> T {
+        T::zero()
     }
 }
 
-impl Max<f64> for Weibull {
+impl<T: SpecialFloat> Max<T> for Weibull<T> {
     /// Returns the maximum value in the domain of the weibull
     /// distribution representable by a double precision float
     ///
@@ -158,12 +180,12 @@ impl Max<f64> for Weibull {
     /// ```ignore
     /// INF
     /// ```
-    fn max(&self) -> f64 {
-        f64::INFINITY
+    fn max(&self) -> T {
+        T::infinity()
     }
 }
 
-impl Distribution<f64> for Weibull {
+impl<T: SpecialFloat> Distribution<T> for Weibull<T> {
     /// Returns the mean of the weibull distribution
     ///
     /// # Formula
@@ -174,8 +196,8 @@ impl Distribution<f64> for Weibull {
     ///
     /// where `k` is the shape, `λ` is the scale, and `Γ` is
     /// the gamma function
-    fn mean(&self) -> Option<f64> {
-        Some(self.scale * gamma::gamma(1.0 + 1.0 / self.shape))
+    fn mean(&self) -> Option<T> {
+        Some(self.scale * gamma::gamma(T::one() + T::one() / self.shape))
     }
     /// Returns the variance of the weibull distribution
     ///
@@ -187,9 +209,9 @@ impl Distribution<f64> for Weibull {
     ///
     /// where `k` is the shape, `λ` is the scale, and `Γ` is
     /// the gamma function
-    fn variance(&self) -> Option<f64> {
+    fn variance(&self) -> Option<T> {
         let mean = self.mean()?;
-        Some(self.scale * self.scale * gamma::gamma(1.0 + 2.0 / self.shape) - mean * mean)
+        Some(self.scale * self.scale * gamma::gamma(T::one() + cast::<T>(2.0) / self.shape) - mean * mean)
     }
     /// Returns the entropy of the weibull distribution
     ///
@@ -201,10 +223,10 @@ impl Distribution<f64> for Weibull {
     ///
     /// where `k` is the shape, `λ` is the scale, and `γ` is
     /// the Euler-Mascheroni constant
-    fn entropy(&self) -> Option<f64> {
-        let entr = consts::EULER_MASCHERONI * (1.0 - 1.0 / self.shape)
+    fn entropy(&self) -> Option<T> {
+        let entr = cast::<T>(consts::EULER_MASCHERONI) * (T::one() - T::one() / self.shape)
             + (self.scale / self.shape).ln()
-            + 1.0;
+            + T::one();
         Some(entr)
     }
     /// Returns the skewness of the weibull distribution
@@ -218,20 +240,20 @@ impl Distribution<f64> for Weibull {
     /// where `k` is the shape, `λ` is the scale, and `Γ` is
     /// the gamma function, `μ` is the mean of the distribution.
     /// and `σ` the standard deviation of the distribution
-    fn skewness(&self) -> Option<f64> {
+    fn skewness(&self) -> Option<T> {
         let mu = self.mean()?;
         let sigma = self.std_dev()?;
         let sigma2 = sigma * sigma;
         let sigma3 = sigma2 * sigma;
-        let skew = (self.scale * self.scale * self.scale * gamma::gamma(1.0 + 3.0 / self.shape)
-            - 3.0 * sigma2 * mu
+        let skew = (self.scale * self.scale * self.scale * gamma::gamma(T::one() + cast::<T>(3.0) / self.shape)
+            - cast::<T>(3.0) * sigma2 * mu
             - (mu * mu * mu))
             / sigma3;
         Some(skew)
     }
 }
 
-impl Median<f64> for Weibull {
+impl<T: SpecialFloat> Median<T> for Weibull<T> {
     /// Returns the median of the weibull distribution
     ///
     /// # Formula
@@ -241,12 +263,12 @@ impl Median<f64> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn median(&self) -> f64 {
-        self.scale * f64::consts::LN_2.powf(1.0 / self.shape)
+    fn median(&self) -> T {
+        self.scale * T::LN_2().powf(T::one() / self.shape)
     }
 }
 
-impl Mode<Option<f64>> for Weibull {
+impl<T: SpecialFloat> Mode<Option<T>> for Weibull<T> {
     /// Returns the median of the weibull distribution
     ///
     /// # Formula
@@ -260,17 +282,17 @@ impl Mode<Option<f64>> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn mode(&self) -> Option<f64> {
-        let mode = if ulps_eq!(self.shape, 1.0) {
-            0.0
+    fn mode(&self) -> Option<T> {
+        let mode = if ulps_eq!(self.shape, T::one()) {
+            T::zero()
         } else {
-            self.scale * ((self.shape - 1.0) / self.shape).powf(1.0 / self.shape)
+            self.scale * ((self.shape - T::one()) / self.shape).powf(T::one() / self.shape)
         };
         Some(mode)
     }
 }
 
-impl Continuous<f64, f64> for Weibull {
+impl<T: SpecialFloat> Continuous<T, T> for Weibull<T> {
     /// Calculates the probability density function for the weibull
     /// distribution at `x`
     ///
@@ -281,16 +303,16 @@ impl Continuous<f64, f64> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            0.0
-        } else if is_zero(x) && ulps_eq!(self.shape, 1.0) {
-            1.0 / self.scale
+    fn pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::zero()
+        } else if is_zero(x) && ulps_eq!(self.shape, T::one()) {
+            T::one() / self.scale
         } else if x.is_infinite() {
-            0.0
+            T::zero()
         } else {
             self.shape
-                * (x / self.scale).powf(self.shape - 1.0)
+                * (x / self.scale).powf(self.shape - T::one())
                 * (-(x.powf(self.shape)) * self.scale_pow_shape_inv).exp()
                 / self.scale
         }
@@ -306,25 +328,148 @@ impl Continuous<f64, f64> for Weibull {
     /// ```
     ///
     /// where `k` is the shape and `λ` is the scale
-    fn ln_pdf(&self, x: f64) -> f64 {
-        if x < 0.0 {
-            f64::NEG_INFINITY
-        } else if is_zero(x) && ulps_eq!(self.shape, 1.0) {
-            0.0 - self.scale.ln()
+    fn ln_pdf(&self, x: T) -> T {
+        if x < T::zero() {
+            T::neg_infinity()
+        } else if is_zero(x) && ulps_eq!(self.shape, T::one()) {
+            T::zero() - self.scale.ln()
         } else if x.is_infinite() {
-            f64::NEG_INFINITY
+            T::neg_infinity()
         } else {
-            self.shape.ln() + (self.shape - 1.0) * (x / self.scale).ln()
+            self.shape.ln() + (self.shape - T::one()) * (x / self.scale).ln()
                 - x.powf(self.shape) * self.scale_pow_shape_inv
                 - self.scale.ln()
         }
     }
 }
 
+impl Fit<f64> for Weibull {
+    /// Returns the maximum likelihood estimate of the weibull distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any observation is not positive, if all
+    /// observations are equal or if the likelihood equation for the shape
+    /// fails to converge
+    ///
+    /// # Remarks
+    ///
+    /// The likelihood equation for the shape is solved by Newton's method on
+    /// the observations scaled by their maximum, which leaves the equation
+    /// unchanged but avoids overflowing `x_i^k`.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ x_i^k * ln(x_i) / Σ x_i^k - 1 / k = (1 / n) * Σ ln(x_i)
+    /// λ = ((1 / n) * Σ x_i^k)^(1 / k)
+    /// ```
+    ///
+    /// where `k` is the shape and `λ` is the scale
+    fn fit_mle(data: &[f64]) -> Result<Weibull> {
+        if data.is_empty() || data.iter().any(|&x| x.is_nan() || x <= 0.0) {
+            return Err(StatsError::BadParams);
+        }
+        let max = Statistics::max(data);
+        let ln_data: Vec<f64> = data.iter().map(|x| (x / max).ln()).collect();
+        let ln_mean = ln_data.iter().mean();
+        let ln_std_dev = ln_data.iter().population_std_dev();
+        if ln_std_dev.is_nan() || ln_std_dev <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+
+        let shape = internal::positive_root(
+            |k| {
+                let (s0, s1, s2) = ln_data.iter().fold((0.0, 0.0, 0.0), |(s0, s1, s2), &y| {
+                    let w = (k * y).exp();
+                    (s0 + w, s1 + w * y, s2 + w * y * y)
+                });
+                let r = s1 / s0;
+                (r - 1.0 / k - ln_mean, s2 / s0 - r * r + 1.0 / (k * k))
+            },
+            f64::consts::PI / (6f64.sqrt() * ln_std_dev),
+        )?;
+        let scale = max * ln_data.iter().map(|y| (shape * y).exp()).mean().powf(1.0 / shape);
+        Weibull::new(shape, scale)
+    }
+
+    /// Returns the method of moments estimate of the weibull distribution
+    /// for the observations in `data`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mean of the observations is not positive, if
+    /// all observations are equal or if the equation for the shape fails to
+    /// converge
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Γ(1 + 2 / k) / Γ(1 + 1 / k)^2 = 1 + s^2 / m^2
+    /// λ = m / Γ(1 + 1 / k)
+    /// ```
+    ///
+    /// where `k` is the shape, `λ` is the scale and `m` and `s^2` are the mean
+    /// and the population variance of the observations
+    fn fit_mom(data: &[f64]) -> Result<Weibull> {
+        let mean = data.mean();
+        let cv2 = data.population_variance() / (mean * mean);
+        if mean.is_nan() || mean <= 0.0 || !cv2.is_finite() || cv2 <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let target = cv2.ln_1p();
+        let shape = internal::positive_root(
+            |k| {
+                let (g1, g2) = (1.0 + 1.0 / k, 1.0 + 2.0 / k);
+                (
+                    gamma::ln_gamma(g2) - 2.0 * gamma::ln_gamma(g1) - target,
+                    2.0 * (gamma::digamma(g1) - gamma::digamma(g2)) / (k * k),
+                )
+            },
+            cv2.sqrt().powf(-1.086),
+        )?;
+        Weibull::new(shape, mean / gamma::gamma(1.0 + 1.0 / shape))
+    }
+
+    /// Returns the observed Fisher information of `data` with respect to
+    /// `(k, λ)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// [ n / k^2 + Σ z_i^k * ln(z_i)^2,            (n - Σ z_i^k - k * Σ z_i^k * ln(z_i)) / λ ]
+    /// [ (n - Σ z_i^k - k * Σ z_i^k * ln(z_i)) / λ, k * ((k + 1) * Σ z_i^k - n) / λ^2       ]
+    /// ```
+    ///
+    /// where `z_i = x_i / λ`
+    fn observed_information(&self, data: &[f64]) -> Option<DMatrix<f64>> {
+        let (k, lambda) = (self.shape, self.scale);
+        let n = data.len() as f64;
+        let (s0, s1, s2) = data.iter().fold((0.0, 0.0, 0.0), |(s0, s1, s2), &x| {
+            let ln_z = (x / lambda).ln();
+            let w = (k * ln_z).exp();
+            (s0 + w, s1 + w * ln_z, s2 + w * ln_z * ln_z)
+        });
+        let cross = (n - s0 - k * s1) / lambda;
+        Some(DMatrix::from_row_slice(
+            2,
+            2,
+            &[
+                n / (k * k) + s2,
+                cross,
+                cross,
+                k * ((k + 1.0) * s0 - n) / (lambda * lambda),
+            ],
+        ))
+    }
+}
+
 #[rustfmt::skip]
 #[cfg(all(test, feature = "nightly"))]
 mod tests {
     use crate::statistics::*;
+    use crate::distribution::Fit;
     use crate::distribution::{ContinuousCDF, Continuous, Weibull};
     use crate::distribution::internal::*;
     use crate::consts::ACC;
@@ -522,4 +667,39 @@ mod tests {
     fn test_continuous() {
         test::check_continuous_distribution(&try_create(1.0, 0.2), 0.0, 10.0);
     }
+
+    #[test]
+    fn test_inverse_cdf() {
+        let inverse_cdf = |arg: f64| move |x: Weibull| x.inverse_cdf(arg);
+        test_almost(1.0, 1.0, 0.1053605156578263074, 1e-15, inverse_cdf(0.1));
+        test_almost(1.0, 1.0, 0.69314718055994530942, 1e-15, inverse_cdf(0.5));
+        test_almost(1.0, 1.0, 2.3025850929940459061, 1e-14, inverse_cdf(0.9));
+        test_almost(2.0, 3.0, 0.97377853792350381959, 1e-15, inverse_cdf(0.1));
+        test_almost(2.0, 3.0, 2.4976638334730932691, 1e-14, inverse_cdf(0.5));
+        test_almost(2.0, 3.0, 4.5522813881554392721, 1e-14, inverse_cdf(0.9));
+        test_almost(0.5, 10.0, 0.11100838259683062488, 1e-15, inverse_cdf(0.1));
+        test_almost(0.5, 10.0, 4.8045301391820142467, 1e-14, inverse_cdf(0.5));
+        test_almost(0.5, 10.0, 53.018981104783990331, 1e-13, inverse_cdf(0.9));
+    }
+
+    #[test]
+    fn test_f32() {
+        let d32 = Weibull::<f32>::new(1.5, 2.0).unwrap();
+        let d64 = Weibull::new(1.5, 2.0).unwrap();
+        test::check_f32_matches_f64(&d32, &d64, &[0.25, 1.0, 2.0, 4.0], 1e-4);
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+        let n = Weibull::fit_mle(&data).unwrap();
+        assert_almost_eq!(n.shape(), 1.5685864997353621, 1e-12);
+        assert_almost_eq!(n.scale(), 1.6581308072711686, 1e-12);
+        test::check_observed_information(n.observed_information(&data), &[7.6157615404083506, -2.6871572888485687, -2.6871572888485687, 8.9491001644077936], 1e-12);
+        let n = Weibull::fit_mom(&data).unwrap();
+        assert_almost_eq!(n.shape(), 1.521830532099125, 1e-12);
+        assert_almost_eq!(n.scale(), 1.6444538528043883, 1e-12);
+        assert!(Weibull::fit_mle(&[3.0, 3.0]).is_err());
+        assert!(Weibull::fit_mle(&[3.0, -1.0]).is_err());
+    }
 }
@@ -0,0 +1,264 @@
+use crate::distribution::empirical::NonNAN;
+use crate::distribution::{ContinuousCDF, Uniform};
+use crate::statistics::*;
+use crate::{Result, StatsError};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Implements the [Empirical
+/// Distribution](https://en.wikipedia.org/wiki/Empirical_distribution_function)
+/// of weighted data points, which places on each data point a probability
+/// proportional to its weight
+///
+/// # Remarks
+///
+/// The weights are frequency weights, so that with integer weights the
+/// distribution, including its variance, is the `Empirical` distribution of
+/// the data in which each point is repeated as often as its weight.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::{ContinuousCDF, WeightedEmpirical};
+/// use statrs::statistics::Distribution;
+///
+/// let empirical = WeightedEmpirical::from_vecs(vec![0.0, 5.0, 10.0], vec![1.0, 2.0, 1.0]).unwrap();
+/// assert_eq!(empirical.mean().unwrap(), 5.0);
+/// assert_eq!(empirical.cdf(5.0), 0.75);
+/// assert_eq!(empirical.inverse_cdf(0.3), 5.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedEmpirical {
+    stats: OnlineStats,
+    // keys are data points, values are their total weights
+    data: BTreeMap<NonNAN<f64>, f64>,
+}
+
+impl WeightedEmpirical {
+    /// Constructs a new weighted empirical distribution without data points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::WeightedEmpirical;
+    ///
+    /// let mut result = WeightedEmpirical::new();
+    /// assert!(result.is_ok());
+    /// ```
+    pub fn new() -> Result<WeightedEmpirical> {
+        Ok(WeightedEmpirical {
+            stats: OnlineStats::new(),
+            data: BTreeMap::new(),
+        })
+    }
+
+    /// Constructs a new weighted empirical distribution of the data points
+    /// `src` with the corresponding `weights`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `src` and `weights` have different lengths or a
+    /// weight is negative, infinite or `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::distribution::WeightedEmpirical;
+    ///
+    /// assert!(WeightedEmpirical::from_vecs(vec![1.0, 2.0], vec![0.5, 1.5]).is_ok());
+    /// assert!(WeightedEmpirical::from_vecs(vec![1.0, 2.0], vec![0.5]).is_err());
+    /// assert!(WeightedEmpirical::from_vecs(vec![1.0, 2.0], vec![0.5, -1.0]).is_err());
+    /// ```
+    pub fn from_vecs(src: Vec<f64>, weights: Vec<f64>) -> Result<WeightedEmpirical> {
+        if src.len() != weights.len() {
+            return Err(StatsError::ContainersMustBeSameLength);
+        }
+        let mut empirical = WeightedEmpirical::new()?;
+        for (elt, weight) in src.into_iter().zip(weights) {
+            empirical.add(elt, weight)?;
+        }
+        Ok(empirical)
+    }
+
+    /// Adds the data point `data_point` with the weight `weight`. Data
+    /// points that are `NaN` or have zero weight are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weight` is negative, infinite or `NaN`
+    pub fn add(&mut self, data_point: f64, weight: f64) -> Result<()> {
+        if !(weight >= 0.0 && weight.is_finite()) {
+            return Err(StatsError::ArgNotNegative("weight"));
+        }
+        if !data_point.is_nan() && weight > 0.0 {
+            self.stats.push_weighted(data_point, weight)?;
+            *self.data.entry(NonNAN(data_point)).or_insert(0.0) += weight;
+        }
+        Ok(())
+    }
+
+    /// Returns the total weight of the data points
+    pub fn total_weight(&self) -> f64 {
+        self.stats.sum_weights()
+    }
+}
+
+impl ::rand::distributions::Distribution<f64> for WeightedEmpirical {
+    fn sample<R: ?Sized + Rng>(&self, rng: &mut R) -> f64 {
+        let uniform = Uniform::new(0.0, 1.0).unwrap();
+        self.inverse_cdf(uniform.sample(rng))
+    }
+}
+
+/// Panics if number of samples is zero
+impl Max<f64> for WeightedEmpirical {
+    fn max(&self) -> f64 {
+        self.data.keys().rev().map(|key| key.0).next().unwrap()
+    }
+}
+
+/// Panics if number of samples is zero
+impl Min<f64> for WeightedEmpirical {
+    fn min(&self) -> f64 {
+        self.data.keys().map(|key| key.0).next().unwrap()
+    }
+}
+
+impl Distribution<f64> for WeightedEmpirical {
+    /// Returns the weighted mean of the data points, or `None` if there are
+    /// none
+    fn mean(&self) -> Option<f64> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(self.stats.mean())
+        }
+    }
+
+    /// Returns the weighted variance of the data points with the total
+    /// weight less one as a normalizer, or `None` if the total weight is
+    /// at most one
+    fn variance(&self) -> Option<f64> {
+        if self.data.is_empty() || self.total_weight() <= 1.0 {
+            None
+        } else {
+            Some(self.stats.variance())
+        }
+    }
+}
+
+impl ContinuousCDF<f64, f64> for WeightedEmpirical {
+    fn cdf(&self, x: f64) -> f64 {
+        let mut sum = 0.0;
+        for (keys, values) in &self.data {
+            if keys.0 > x {
+                break;
+            }
+            sum += values;
+        }
+        (sum / self.total_weight()).min(1.0)
+    }
+
+    fn sf(&self, x: f64) -> f64 {
+        let mut sum = 0.0;
+        for (keys, values) in self.data.iter().rev() {
+            if keys.0 <= x {
+                break;
+            }
+            sum += values;
+        }
+        (sum / self.total_weight()).min(1.0)
+    }
+
+    /// Returns the smallest data point at which the cumulative distribution
+    /// function is at least `p`
+    ///
+    /// # Panics
+    ///
+    /// If there are no data points or `p` is not in `[0, 1]`
+    fn inverse_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            panic!("p must be in [0, 1]");
+        }
+        let target = p * self.total_weight();
+        let mut sum = 0.0;
+        for (keys, values) in &self.data {
+            sum += values;
+            if sum >= target {
+                return keys.0;
+            }
+        }
+        self.max()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(all(test, feature = "nightly"))]
+mod tests {
+    use super::*;
+    use crate::distribution::Empirical;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_cdf_sf() {
+        let empirical = WeightedEmpirical::from_vecs(vec![5.0, 10.0, 2.0], vec![1.0, 1.0, 2.0]).unwrap();
+        assert_eq!(empirical.cdf(0.0), 0.0);
+        assert_eq!(empirical.cdf(2.0), 0.5);
+        assert_eq!(empirical.cdf(5.5), 0.75);
+        assert_eq!(empirical.cdf(10.0), 1.0);
+        assert_eq!(empirical.sf(0.0), 1.0);
+        assert_eq!(empirical.sf(5.0), 0.25);
+        assert_eq!(empirical.sf(10.0), 0.0);
+        assert_eq!(empirical.min(), 2.0);
+        assert_eq!(empirical.max(), 10.0);
+        assert_eq!(empirical.inverse_cdf(0.0), 2.0);
+        assert_eq!(empirical.inverse_cdf(0.5), 2.0);
+        assert_eq!(empirical.inverse_cdf(0.6), 5.0);
+        assert_eq!(empirical.inverse_cdf(1.0), 10.0);
+    }
+
+    #[test]
+    fn test_matches_empirical() {
+        let weighted = WeightedEmpirical::from_vecs(vec![5.0, 10.0, 2.0, 5.0], vec![1.0, 1.0, 2.0, 3.0]).unwrap();
+        let repeated = Empirical::from_vec(vec![5.0, 10.0, 2.0, 2.0, 5.0, 5.0, 5.0]);
+        assert_almost_eq!(weighted.mean().unwrap(), repeated.mean().unwrap(), 1e-14);
+        assert_almost_eq!(weighted.variance().unwrap(), repeated.variance().unwrap(), 1e-14);
+        for &x in &[0.0, 2.0, 3.0, 5.0, 7.0, 10.0, 11.0] {
+            assert_almost_eq!(weighted.cdf(x), repeated.cdf(x), 1e-15);
+            assert_almost_eq!(weighted.sf(x), repeated.sf(x), 1e-15);
+        }
+        assert_eq!(weighted.total_weight(), 7.0);
+    }
+
+    #[test]
+    fn test_variance_small_total_weight() {
+        let empirical = WeightedEmpirical::from_vecs(vec![1.0, 2.0, 3.0], vec![0.2, 0.3, 0.5]).unwrap();
+        assert_almost_eq!(empirical.mean().unwrap(), 2.3, 1e-15);
+        assert!(empirical.variance().is_none());
+    }
+
+    #[test]
+    fn test_add() {
+        let mut empirical = WeightedEmpirical::new().unwrap();
+        assert!(empirical.mean().is_none());
+        empirical.add(1.0, 0.0).unwrap();
+        empirical.add(f64::NAN, 1.0).unwrap();
+        assert!(empirical.mean().is_none());
+        assert!(empirical.add(1.0, -1.0).is_err());
+        assert!(empirical.add(1.0, f64::INFINITY).is_err());
+        empirical.add(1.0, 0.25).unwrap();
+        empirical.add(3.0, 0.75).unwrap();
+        assert_eq!(empirical.mean().unwrap(), 2.5);
+        assert_eq!(empirical.cdf(1.0), 0.25);
+    }
+
+    #[test]
+    fn test_sample() {
+        let empirical = WeightedEmpirical::from_vecs(vec![1.0, 2.0], vec![1.0, 3.0]).unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+        let n = 10_000;
+        let twos = (0..n).filter(|_| rand::distributions::Distribution::sample(&empirical, &mut rng) == 2.0).count();
+        assert!((twos as f64 / n as f64 - 0.75).abs() < 0.02);
+    }
+}
@@ -2,9 +2,9 @@
 //! function
 
 use crate::error::StatsError;
+use crate::function::float::cast;
 use crate::function::gamma;
-use crate::is_zero;
-use crate::prec;
+use crate::function::SpecialFloat;
 use crate::Result;
 use std::f64;
 
@@ -17,7 +17,7 @@ use std::f64;
 /// # Panics
 ///
 /// if `a <= 0.0` or `b <= 0.0`
-pub fn ln_beta(a: f64, b: f64) -> f64 {
+pub fn ln_beta<T: SpecialFloat>(a: T, b: T) -> T {
     checked_ln_beta(a, b).unwrap()
 }
 
@@ -30,10 +30,10 @@ pub fn ln_beta(a: f64, b: f64) -> f64 {
 /// # Errors
 ///
 /// if `a <= 0.0` or `b <= 0.0`
-pub fn checked_ln_beta(a: f64, b: f64) -> Result<f64> {
-    if a <= 0.0 {
+pub fn checked_ln_beta<T: SpecialFloat>(a: T, b: T) -> Result<T> {
+    if a <= T::zero() {
         Err(StatsError::ArgMustBePositive("a"))
-    } else if b <= 0.0 {
+    } else if b <= T::zero() {
         Err(StatsError::ArgMustBePositive("b"))
     } else {
         Ok(gamma::ln_gamma(a) + gamma::ln_gamma(b) - gamma::ln_gamma(a + b))
@@ -48,7 +48,7 @@ pub fn checked_ln_beta(a: f64, b: f64) -> Result<f64> {
 /// # Panics
 ///
 /// if `a <= 0.0` or `b <= 0.0`
-pub fn beta(a: f64, b: f64) -> f64 {
+pub fn beta<T: SpecialFloat>(a: T, b: T) -> T {
     checked_beta(a, b).unwrap()
 }
 
@@ -60,7 +60,7 @@ pub fn beta(a: f64, b: f64) -> f64 {
 /// # Errors
 ///
 /// if `a <= 0.0` or `b <= 0.0`
-pub fn checked_beta(a: f64, b: f64) -> Result<f64> {
+pub fn checked_beta<T: SpecialFloat>(a: T, b: T) -> Result<T> {
     checked_ln_beta(a, b).map(|x| x.exp())
 }
 
@@ -72,7 +72,7 @@ pub fn checked_beta(a: f64, b: f64) -> Result<f64> {
 /// # Panics
 ///
 /// If `a <= 0.0`, `b <= 0.0`, `x < 0.0`, or `x > 1.0`
-pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+pub fn beta_inc<T: SpecialFloat>(a: T, b: T, x: T) -> T {
     checked_beta_inc(a, b, x).unwrap()
 }
 
@@ -84,7 +84,7 @@ pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
 /// # Errors
 ///
 /// If `a <= 0.0`, `b <= 0.0`, `x < 0.0`, or `x > 1.0`
-pub fn checked_beta_inc(a: f64, b: f64, x: f64) -> Result<f64> {
+pub fn checked_beta_inc<T: SpecialFloat>(a: T, b: T, x: T) -> Result<T> {
     checked_beta_reg(a, b, x).and_then(|x| checked_beta(a, b).map(|y| x * y))
 }
 
@@ -97,7 +97,7 @@ pub fn checked_beta_inc(a: f64, b: f64, x: f64) -> Result<f64> {
 /// # Panics
 ///
 /// if `a <= 0.0`, `b <= 0.0`, `x < 0.0`, or `x > 1.0`
-pub fn beta_reg(a: f64, b: f64, x: f64) -> f64 {
+pub fn beta_reg<T: SpecialFloat>(a: T, b: T, x: T) -> T {
     checked_beta_reg(a, b, x).unwrap()
 }
 
@@ -110,85 +110,87 @@ pub fn beta_reg(a: f64, b: f64, x: f64) -> f64 {
 /// # Errors
 ///
 /// if `a <= 0.0`, `b <= 0.0`, `x < 0.0`, or `x > 1.0`
-pub fn checked_beta_reg(a: f64, b: f64, x: f64) -> Result<f64> {
-    if a <= 0.0 {
+pub fn checked_beta_reg<T: SpecialFloat>(a: T, b: T, x: T) -> Result<T> {
+    let one = T::one();
+    let two = one + one;
+    if a <= T::zero() {
         Err(StatsError::ArgMustBePositive("a"))
-    } else if b <= 0.0 {
+    } else if b <= T::zero() {
         Err(StatsError::ArgMustBePositive("b"))
-    } else if !(0.0..=1.0).contains(&x) {
+    } else if x < T::zero() || x > one {
         Err(StatsError::ArgIntervalIncl("x", 0.0, 1.0))
     } else {
-        let bt = if is_zero(x) || ulps_eq!(x, 1.0) {
-            0.0
+        let bt = if x == T::zero() || ulps_eq!(x, one) {
+            T::zero()
         } else {
             (gamma::ln_gamma(a + b) - gamma::ln_gamma(a) - gamma::ln_gamma(b)
                 + a * x.ln()
-                + b * (1.0 - x).ln())
+                + b * (one - x).ln())
             .exp()
         };
-        let symm_transform = x >= (a + 1.0) / (a + b + 2.0);
-        let eps = prec::F64_PREC;
-        let fpmin = f64::MIN_POSITIVE / eps;
+        let symm_transform = x >= (a + one) / (a + b + two);
+        let eps = T::epsilon() / two;
+        let fpmin = T::min_positive_value() / eps;
 
         let mut a = a;
         let mut b = b;
         let mut x = x;
         if symm_transform {
             let swap = a;
-            x = 1.0 - x;
+            x = one - x;
             a = b;
             b = swap;
         }
 
         let qab = a + b;
-        let qap = a + 1.0;
-        let qam = a - 1.0;
-        let mut c = 1.0;
-        let mut d = 1.0 - qab * x / qap;
+        let qap = a + one;
+        let qam = a - one;
+        let mut c = one;
+        let mut d = one - qab * x / qap;
 
         if d.abs() < fpmin {
             d = fpmin;
         }
-        d = 1.0 / d;
+        d = one / d;
         let mut h = d;
 
         for m in 1..141 {
-            let m = f64::from(m);
-            let m2 = m * 2.0;
+            let m = cast::<T>(f64::from(m));
+            let m2 = m * two;
             let mut aa = m * (b - m) * x / ((qam + m2) * (a + m2));
-            d = 1.0 + aa * d;
+            d = one + aa * d;
 
             if d.abs() < fpmin {
                 d = fpmin;
             }
 
-            c = 1.0 + aa / c;
+            c = one + aa / c;
             if c.abs() < fpmin {
                 c = fpmin;
             }
 
-            d = 1.0 / d;
+            d = one / d;
             h = h * d * c;
             aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
-            d = 1.0 + aa * d;
+            d = one + aa * d;
 
             if d.abs() < fpmin {
                 d = fpmin;
             }
 
-            c = 1.0 + aa / c;
+            c = one + aa / c;
 
             if c.abs() < fpmin {
                 c = fpmin;
             }
 
-            d = 1.0 / d;
+            d = one / d;
             let del = d * c;
             h *= del;
 
-            if (del - 1.0).abs() <= eps {
+            if (del - one).abs() <= eps {
                 return if symm_transform {
-                    Ok(1.0 - bt * h / a)
+                    Ok(one - bt * h / a)
                 } else {
                     Ok(bt * h / a)
                 };
@@ -196,7 +198,7 @@ pub fn checked_beta_reg(a: f64, b: f64, x: f64) -> Result<f64> {
         }
 
         if symm_transform {
-            Ok(1.0 - bt * h / a)
+            Ok(one - bt * h / a)
         } else {
             Ok(bt * h / a)
         }
@@ -233,7 +235,7 @@ pub fn checked_beta_reg(a: f64, b: f64, x: f64) -> Result<f64> {
 // > COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
 // > IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // > CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
-pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
+pub fn inv_beta_reg<T: SpecialFloat>(mut a: T, mut b: T, mut x: T) -> T {
     // Algorithm AS 64
     // http://www.jstor.org/stable/2346798
     //
@@ -268,37 +270,41 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
     // where
     //
     // f(x) = I(x, p, q) - α.
+    let c = cast::<T>;
+    let one = T::one();
+    let two = one + one;
     let ln_beta = ln_beta(a, b);
 
     // Remark AS R83
     // http://www.jstor.org/stable/2347779
     const SAE: i32 = -30;
     const FPU: f64 = 1e-30; // 10^SAE
+    let fpu = c(FPU);
 
-    debug_assert!((0.0..=1.0).contains(&x) && a > 0.0 && b > 0.0);
+    debug_assert!(x >= T::zero() && x <= one && a > T::zero() && b > T::zero());
 
-    if x == 0.0 {
-        return 0.0;
+    if x == T::zero() {
+        return T::zero();
     }
-    if x == 1.0 {
-        return 1.0;
+    if x == one {
+        return one;
     }
 
     let mut p;
     let mut q;
 
-    let flip = 0.5 < x;
+    let flip = c(0.5) < x;
     if flip {
         p = a;
         a = b;
         b = p;
-        x = 1.0 - x;
+        x = one - x;
     }
 
     p = (-(x * x).ln()).sqrt();
-    q = p - (2.30753 + 0.27061 * p) / (1.0 + (0.99229 + 0.04481 * p) * p);
+    q = p - (c(2.30753) + c(0.27061) * p) / (one + (c(0.99229) + c(0.04481) * p) * p);
 
-    if 1.0 < a && 1.0 < b {
+    if one < a && one < b {
         // Remark AS R19 and Algorithm AS 109
         // http://www.jstor.org/stable/2346887
         //
@@ -306,58 +312,66 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
         // improves the Fisher–Cochran formula, is generally better. For
         // other values of a and b en empirical investigation has shown that
         // the approximation given in AS 64 is adequate.
-        let r = (q * q - 3.0) / 6.0;
-        let s = 1.0 / (2.0 * a - 1.0);
-        let t = 1.0 / (2.0 * b - 1.0);
-        let h = 2.0 / (s + t);
-        let w = q * (h + r).sqrt() / h - (t - s) * (r + 5.0 / 6.0 - 2.0 / (3.0 * h));
-        p = a / (a + b * (2.0 * w).exp());
+        let three = c(3.0);
+        let r = (q * q - three) / c(6.0);
+        let s = one / (two * a - one);
+        let t = one / (two * b - one);
+        let h = two / (s + t);
+        let w = q * (h + r).sqrt() / h - (t - s) * (r + c(5.0) / c(6.0) - two / (three * h));
+        p = a / (a + b * (two * w).exp());
     } else {
-        let mut t = 1.0 / (9.0 * b);
-        t = 2.0 * b * (1.0 - t + q * t.sqrt()).powf(3.0);
-        if t <= 0.0 {
-            p = 1.0 - ((((1.0 - x) * b).ln() + ln_beta) / b).exp();
+        let mut t = one / (c(9.0) * b);
+        t = two * b * (one - t + q * t.sqrt()).powf(c(3.0));
+        if t <= T::zero() {
+            p = one - ((((one - x) * b).ln() + ln_beta) / b).exp();
         } else {
-            t = 2.0 * (2.0 * a + b - 1.0) / t;
-            if t <= 1.0 {
+            t = two * (two * a + b - one) / t;
+            if t <= one {
                 p = (((x * a).ln() + ln_beta) / a).exp();
             } else {
-                p = 1.0 - 2.0 / (t + 1.0);
+                p = one - two / (t + one);
             }
         }
     }
 
-    if p < 0.0001 {
-        p = 0.0001;
-    } else if 0.9999 < p {
-        p = 0.9999;
+    if p < c(0.0001) {
+        p = c(0.0001);
+    } else if c(0.9999) < p {
+        p = c(0.9999);
     }
 
     // Remark AS R83
     // http://www.jstor.org/stable/2347779
-    let e = (-5.0 / a / a - 1.0 / x.powf(0.2) - 13.0) as i32;
-    let acu = if e > SAE { f64::powi(10.0, e) } else { FPU };
+    let e = (-c(5.0) / a / a - one / x.powf(c(0.2)) - c(13.0))
+        .to_i32()
+        .unwrap_or(SAE);
+    let acu = if e > SAE {
+        c(10.0).powi(e)
+    } else {
+        fpu
+    }
+    .max(T::epsilon() * T::epsilon());
 
     let mut pnext;
-    let mut qprev = 0.0;
-    let mut sq = 1.0;
-    let mut prev = 1.0;
+    let mut qprev = T::zero();
+    let mut sq = one;
+    let mut prev = one;
 
     'outer: loop {
         // Remark AS R19 and Algorithm AS 109
         // http://www.jstor.org/stable/2346887
         q = beta_reg(a, b, p);
-        q = (q - x) * (ln_beta + (1.0 - a) * p.ln() + (1.0 - b) * (1.0 - p).ln()).exp();
+        q = (q - x) * (ln_beta + (one - a) * p.ln() + (one - b) * (one - p).ln()).exp();
 
         // Remark AS R83
         // http://www.jstor.org/stable/2347779
-        if q * qprev <= 0.0 {
-            prev = if sq > FPU { sq } else { FPU };
+        if q * qprev <= T::zero() {
+            prev = if sq > fpu { sq } else { fpu };
         }
 
         // Remark AS R19 and Algorithm AS 109
         // http://www.jstor.org/stable/2346887
-        let mut g = 1.0;
+        let mut g = one;
         loop {
             loop {
                 let adj = g * q;
@@ -365,11 +379,11 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
 
                 if sq < prev {
                     pnext = p - adj;
-                    if 0.0 <= pnext && pnext <= 1.0 {
+                    if T::zero() <= pnext && pnext <= one {
                         break;
                     }
                 }
-                g /= 3.0;
+                g /= c(3.0);
             }
 
             if prev <= acu || q * q <= acu {
@@ -377,11 +391,11 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
                 break 'outer;
             }
 
-            if pnext != 0.0 && pnext != 1.0 {
+            if pnext != T::zero() && pnext != one {
                 break;
             }
 
-            g /= 3.0;
+            g /= c(3.0);
         }
 
         if pnext == p {
@@ -393,7 +407,7 @@ pub fn inv_beta_reg(mut a: f64, mut b: f64, mut x: f64) -> f64 {
     }
 
     if flip {
-        1.0 - p
+        one - p
     } else {
         p
     }
@@ -560,6 +574,24 @@ mod tests {
         assert_eq!(super::beta_reg(2.5, 2.5, 1.0), 1.0);
     }
 
+    #[test]
+    fn test_beta_reg_f32() {
+        assert_almost_eq!(super::beta_reg(0.5f32, 0.5, 0.25) as f64, 1.0 / 3.0, 2e-7);
+        assert_almost_eq!(super::beta_reg(2.5f32, 3.0, 0.5) as f64, 0.59109707489812957118, 1e-6);
+        assert_almost_eq!(super::beta_reg(10.0f32, 3.0, 0.75) as f64, 0.39067500829696655273, 2e-6);
+        assert_eq!(super::beta_reg(2.5f32, 2.5, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_inv_beta_reg_f32() {
+        for &(a, b) in [(0.5f32, 0.5f32), (2.5, 3.0), (10.0, 3.0)].iter() {
+            for &p in [0.01f32, 0.25, 0.5, 0.9].iter() {
+                let x = super::inv_beta_reg(a, b, p);
+                assert_almost_eq!(super::beta_reg(a, b, x) as f64, p as f64, 2e-5);
+            }
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_beta_reg_a_lte_0() {
@@ -2,65 +2,68 @@
 //! related functions
 
 use crate::function::evaluate;
-use crate::is_zero;
+use crate::function::SpecialFloat;
 use std::f64;
 
 /// `erf` calculates the error function at `x`.
-pub fn erf(x: f64) -> f64 {
+pub fn erf<T: SpecialFloat>(x: T) -> T {
     if x.is_nan() {
-        f64::NAN
-    } else if x >= 0.0 && x.is_infinite() {
-        1.0
-    } else if x <= 0.0 && x.is_infinite() {
-        -1.0
-    } else if is_zero(x) {
-        0.0
+        T::nan()
+    } else if x >= T::zero() && x.is_infinite() {
+        T::one()
+    } else if x <= T::zero() && x.is_infinite() {
+        -T::one()
+    } else if x == T::zero() {
+        T::zero()
     } else {
-        erf_impl(x, false)
+        T::erf_impl(x, false)
     }
 }
 
 /// `erf_inv` calculates the inverse error function
 /// at `x`.
-pub fn erf_inv(x: f64) -> f64 {
-    if x == 0.0 {
-        0.0
-    } else if x >= 1.0 {
-        f64::INFINITY
-    } else if x <= -1.0 {
-        f64::NEG_INFINITY
-    } else if x < 0.0 {
-        erf_inv_impl(-x, 1.0 + x, -1.0)
+pub fn erf_inv<T: SpecialFloat>(x: T) -> T {
+    let one = T::one();
+    if x == T::zero() {
+        T::zero()
+    } else if x >= one {
+        T::infinity()
+    } else if x <= -one {
+        T::neg_infinity()
+    } else if x < T::zero() {
+        T::erf_inv_impl(-x, one + x, -one)
     } else {
-        erf_inv_impl(x, 1.0 - x, 1.0)
+        T::erf_inv_impl(x, one - x, one)
     }
 }
 
 /// `erfc` calculates the complementary error function
 /// at `x`.
-pub fn erfc(x: f64) -> f64 {
+pub fn erfc<T: SpecialFloat>(x: T) -> T {
     if x.is_nan() {
-        f64::NAN
-    } else if x == f64::INFINITY {
-        0.0
-    } else if x == f64::NEG_INFINITY {
-        2.0
+        T::nan()
+    } else if x == T::infinity() {
+        T::zero()
+    } else if x == T::neg_infinity() {
+        T::one() + T::one()
     } else {
-        erf_impl(x, true)
+        T::erf_impl(x, true)
     }
 }
 
 /// `erfc_inv` calculates the complementary inverse
 /// error function at `x`.
-pub fn erfc_inv(x: f64) -> f64 {
-    if x <= 0.0 {
-        f64::INFINITY
-    } else if x >= 2.0 {
-        f64::NEG_INFINITY
-    } else if x > 1.0 {
-        erf_inv_impl(-1.0 + x, 2.0 - x, -1.0)
+pub fn erfc_inv<T: SpecialFloat>(x: T) -> T {
+    let one = T::one();
+    let two = one + one;
+    if x <= T::zero() {
+        T::infinity()
+    } else if x >= two {
+        T::neg_infinity()
+    } else if x > one {
+        T::erf_inv_impl(-one + x, two - x, -one)
     } else {
-        erf_inv_impl(1.0 - x, x, 1.0)
+        T::erf_inv_impl(one - x, x, one)
     }
 }
 
@@ -570,7 +573,7 @@ const ERF_INV_IMPL_GD: &[f64] = &[
 
 /// `erf_impl` computes the error function at `z`.
 /// If `inv` is true, `1 - erf` is calculated as opposed to `erf`
-fn erf_impl(z: f64, inv: bool) -> f64 {
+pub(crate) fn erf_impl(z: f64, inv: bool) -> f64 {
     if z < 0.0 {
         if !inv {
             return -erf_impl(-z, false);
@@ -686,7 +689,7 @@ fn erf_impl(z: f64, inv: bool) -> f64 {
 // `erf_inv_impl` computes the inverse error function where
 // `p`,`q`, and `s` are the first, second, and third intermediate
 // parameters respectively
-fn erf_inv_impl(p: f64, q: f64, s: f64) -> f64 {
+pub(crate) fn erf_inv_impl(p: f64, q: f64, s: f64) -> f64 {
     let result = if p <= 0.5 {
         let y = 0.0891314744949340820313;
         let g = p * (p + 10.0);
@@ -736,6 +739,176 @@ fn erf_inv_impl(p: f64, q: f64, s: f64) -> f64 {
     s * result
 }
 
+// **********************************************************
+// ********** Coefficients for erf_impl_f32 polynomial ******
+// **********************************************************
+
+/// Polynomial coefficients of `erf(z) / z` in `z^2` for `erf_impl_f32`
+/// in the interval [0, 0.5].
+const ERF_IMPL_F32_A: &[f32] = &[
+    1.128379166,
+    -0.3761260901,
+    0.1128283143,
+    -0.02675773274,
+    0.004719082567,
+];
+
+/// Polynomial coefficients of `erfc(z) * exp(z^2)` in `z - 0.5` for
+/// `erf_impl_f32` in the interval [0.5, 1.25].
+const ERF_IMPL_F32_B: &[f32] = &[
+    0.6156903383,
+    -0.5126878199,
+    0.3593174885,
+    -0.2216995842,
+    0.1224722617,
+    -0.05879428227,
+    0.02151461782,
+    -0.004241011098,
+];
+
+/// Polynomial coefficients of `z * erfc(z) * exp(z^2)` in `1 / z - 0.25`
+/// for `erf_impl_f32` in the interval [1.25, 4].
+const ERF_IMPL_F32_C: &[f32] = &[
+    0.5479978287,
+    -0.1194463989,
+    -0.1678109859,
+    0.2199356224,
+    -0.03133743989,
+    -0.2627292833,
+    0.4256407464,
+    -0.3228515301,
+    0.1040074634,
+];
+
+/// Polynomial coefficients of `z * erfc(z) * exp(z^2)` in `1 / z`
+/// for `erf_impl_f32` in the interval [4, 10.5].
+const ERF_IMPL_F32_D: &[f32] = &[
+    0.5641876075,
+    5.413447025e-5,
+    -0.2824112322,
+    -0.004498572459,
+    0.4996182744,
+    -0.4443692802,
+];
+
+// **********************************************************
+// ********** Coefficients for erf_inv_impl_f32 polynomial **
+// **********************************************************
+
+/// Polynomial coefficients of `erf_inv(p) / p` in `w - 2.5` for
+/// `erf_inv_impl_f32` with `w = -ln((1 - p)(1 + p))` less than 5.
+const ERF_INV_IMPL_F32_A: &[f32] = &[
+    1.50140941,
+    0.246640727,
+    -0.00417768164,
+    -0.00125372503,
+    0.00021858087,
+    -4.39150654e-6,
+    -3.5233877e-6,
+    3.43273939e-7,
+    2.81022636e-8,
+];
+
+/// Polynomial coefficients of `erf_inv(p) / p` in `sqrt(w) - 3` for
+/// `erf_inv_impl_f32` with `w` between 5 and 16.
+const ERF_INV_IMPL_F32_B: &[f32] = &[
+    2.83297682,
+    1.00167406,
+    0.00943887047,
+    -0.0076224613,
+    0.00573950773,
+    -0.00367342844,
+    0.00134934322,
+    0.000100950558,
+    -0.000200214257,
+];
+
+/// Polynomial coefficients of `erf_inv(p)` in `sqrt(w) - 4` for
+/// `erf_inv_impl_f32` with `sqrt(w)` between 4 and 6.5.
+const ERF_INV_IMPL_F32_C: &[f32] = &[
+    3.839783512,
+    1.009497678,
+    0.001235419967,
+    -8.075760464e-4,
+    2.359881029e-4,
+    -4.204956238e-5,
+    3.484802021e-6,
+];
+
+/// Polynomial coefficients of `erf_inv(p)` in `sqrt(w) - 6.5` for
+/// `erf_inv_impl_f32` with `sqrt(w)` greater than 6.5.
+const ERF_INV_IMPL_F32_D: &[f32] = &[
+    6.364593142,
+    1.009112753,
+    -5.210248755e-4,
+    1.656270208e-6,
+    4.078664422e-6,
+    -3.410369928e-7,
+];
+
+/// `erf_impl_f32` computes the error function at `z` in single precision.
+/// If `inv` is true, `1 - erf` is calculated as opposed to `erf`
+pub(crate) fn erf_impl_f32(z: f32, inv: bool) -> f32 {
+    if z < 0.0 {
+        if !inv {
+            return -erf_impl_f32(-z, false);
+        }
+        if z < -0.5 {
+            return 2.0 - erf_impl_f32(-z, true);
+        }
+        return 1.0 + erf_impl_f32(-z, false);
+    }
+
+    let result = if z < 0.5 {
+        z * evaluate::polynomial(z * z, ERF_IMPL_F32_A)
+    } else if z < 10.5 {
+        // exp(-z^2) is evaluated as exp(-s^2) * exp((s - z)(s + z)) where
+        // s is z truncated to 12 significant bits, such that s^2 is exact
+        let s = f32::from_bits(z.to_bits() & 0xffff_f000);
+        let g = ((s - z) * (s + z)).exp();
+        let g = if z < 1.25 {
+            g * evaluate::polynomial(z - 0.5, ERF_IMPL_F32_B)
+        } else if z < 4.0 {
+            g * evaluate::polynomial(1.0 / z - 0.25, ERF_IMPL_F32_C) / z
+        } else {
+            g * evaluate::polynomial(1.0 / z, ERF_IMPL_F32_D) / z
+        };
+        (-s * s).exp() * g
+    } else {
+        0.0
+    };
+
+    if inv && z >= 0.5 {
+        result
+    } else if z >= 0.5 || inv {
+        1.0 - result
+    } else {
+        result
+    }
+}
+
+// `erf_inv_impl_f32` computes the inverse error function in single
+// precision where `p`,`q`, and `s` are the first, second, and third
+// intermediate parameters respectively. The approximations for `w < 16` are
+// from M. Giles, "Approximating the erfinv function", GPU Computing Gems
+// Jade Edition, 2011
+pub(crate) fn erf_inv_impl_f32(p: f32, q: f32, s: f32) -> f32 {
+    let w = -(q * (1.0 + p)).ln();
+    let result = if w < 5.0 {
+        p * evaluate::polynomial(w - 2.5, ERF_INV_IMPL_F32_A)
+    } else if w < 16.0 {
+        p * evaluate::polynomial(w.sqrt() - 3.0, ERF_INV_IMPL_F32_B)
+    } else {
+        let t = w.sqrt();
+        if t < 6.5 {
+            evaluate::polynomial(t - 4.0, ERF_INV_IMPL_F32_C)
+        } else {
+            evaluate::polynomial(t - 6.5, ERF_INV_IMPL_F32_D)
+        }
+    };
+    s * result
+}
+
 #[rustfmt::skip]
 #[cfg(test)]
 mod tests {
@@ -823,4 +996,54 @@ mod tests {
         assert_eq!(super::erfc_inv(1.5), -0.47693627620446987338141835364313055980896974905947083);
         assert_eq!(super::erfc_inv(2.0), f64::NEG_INFINITY);
     }
+
+    #[test]
+    fn test_erf_f32() {
+        assert!(super::erf(f32::NAN).is_nan());
+        assert_eq!(super::erf(0.0f32), 0.0);
+        assert_almost_eq!(super::erf(0.25f32) as f64, 0.27632639016823693299, 1e-7);
+        assert_almost_eq!(super::erf(-1.0f32) as f64, -0.84270079294971486934, 2e-7);
+        assert_almost_eq!(super::erf(1.0f32) as f64, 0.84270079294971486934, 2e-7);
+        assert_almost_eq!(super::erf(2.0f32) as f64, 0.99532226501895273416, 2e-7);
+        assert_eq!(super::erf(4.5f32), 1.0);
+        assert_eq!(super::erf(f32::INFINITY), 1.0);
+        assert_eq!(super::erf(f32::NEG_INFINITY), -1.0);
+    }
+
+    #[test]
+    fn test_erfc_f32() {
+        assert!(super::erfc(f32::NAN).is_nan());
+        assert_eq!(super::erfc(0.0f32), 1.0);
+        assert_almost_eq!(super::erfc(-1.0f32) as f64, 1.8427007929497148693, 2e-7);
+        assert_almost_eq!(super::erfc(0.25f32) as f64, 0.72367360983176306701, 1e-7);
+        assert_almost_eq!(super::erfc(1.0f32) as f64, 0.15729920705028513066, 2e-8);
+        assert_almost_eq!(super::erfc(2.0f32) as f64, 0.0046777349810472658379, 1e-9);
+        assert_almost_eq!(super::erfc(4.5f32) as f64, 1.9661604415428874763e-10, 1e-16);
+        assert_almost_eq!(super::erfc(9.0f32) as f64, 4.1370317465138102381e-37, 1e-43);
+        assert_eq!(super::erfc(11.0f32), 0.0);
+        assert_eq!(super::erfc(f32::INFINITY), 0.0);
+        assert_eq!(super::erfc(f32::NEG_INFINITY), 2.0);
+    }
+
+    #[test]
+    fn test_erf_inv_f32() {
+        assert_eq!(super::erf_inv(0.0f32), 0.0);
+        assert_almost_eq!(super::erf_inv(0.125f32) as f64, 0.11123545184094995915, 1e-7);
+        assert_almost_eq!(super::erf_inv(0.5f32) as f64, 0.47693627620446987338, 2e-7);
+        assert_almost_eq!(super::erf_inv(-0.875f32) as f64, -1.0847870400692831413, 4e-7);
+        assert_eq!(super::erf_inv(1.0f32), f32::INFINITY);
+        assert_eq!(super::erf_inv(-1.0f32), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_erfc_inv_f32() {
+        assert_eq!(super::erfc_inv(0.0f32), f32::INFINITY);
+        assert_almost_eq!(super::erfc_inv(1e-40f32) as f64, 9.4487897667208582625, 2e-6);
+        assert_almost_eq!(super::erfc_inv(1e-30f32) as f64, 8.1486162231698646074, 2e-6);
+        assert_almost_eq!(super::erfc_inv(1e-10f32) as f64, 4.5728249673894852787, 1e-6);
+        assert_almost_eq!(super::erfc_inv(0.5f32) as f64, 0.47693627620446987338, 2e-7);
+        assert_eq!(super::erfc_inv(1.0f32), 0.0);
+        assert_almost_eq!(super::erfc_inv(1.5f32) as f64, -0.47693627620446987338, 2e-7);
+        assert_eq!(super::erfc_inv(2.0f32), f32::NEG_INFINITY);
+    }
 }
@@ -1,6 +1,8 @@
 //! Provides functions that don't have a numerical solution and must
 //! be solved computationally (e.g. evaluation of a polynomial)
 
+use ::num_traits::float::Float;
+
 /// evaluates a polynomial at `z` where `coeff` are the coeffecients
 /// to a polynomial of order `k` where `k` is the length of `coeff` and the
 /// coeffecient
@@ -10,10 +12,10 @@
 /// # Remarks
 ///
 /// Returns 0 for a 0 length coefficient slice
-pub fn polynomial(z: f64, coeff: &[f64]) -> f64 {
+pub fn polynomial<T: Float>(z: T, coeff: &[T]) -> T {
     let n = coeff.len();
     if n == 0 {
-        return 0.0;
+        return T::zero();
     }
 
     let mut sum = *coeff.last().unwrap();
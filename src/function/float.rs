@@ -0,0 +1,85 @@
+//! Provides the floating point abstraction that the special functions
+//! are generic over
+
+use crate::function::{erf, gamma};
+use ::approx::UlpsEq;
+use ::num_traits::float::{Float, FloatConst};
+use ::num_traits::NumAssignOps;
+
+/// The `SpecialFloat` trait is implemented by the floating point types for
+/// which the special functions (and the distributions built on them) can be
+/// evaluated, i.e. `f32` and `f64`.
+///
+/// # Remarks
+///
+/// The `f64` implementations are accurate to (nearly) double precision.
+/// The `f32` implementations use approximations with fewer terms that are
+/// tuned to single precision and are evaluated entirely in `f32`
+/// arithmetic.
+///
+/// This trait is sealed and cannot be implemented outside of `statrs`.
+pub trait SpecialFloat:
+    Float + FloatConst + NumAssignOps + UlpsEq + 'static + private::Sealed
+{
+    /// Auxiliary variable of the Lanczos approximation used by `ln_gamma`
+    #[doc(hidden)]
+    const GAMMA_R: Self;
+
+    /// Coefficients of the Lanczos approximation used by `ln_gamma`
+    #[doc(hidden)]
+    const GAMMA_DK: &'static [Self];
+
+    /// Whether this is the single precision type, which selects the
+    /// thresholds tuned to `f32`
+    #[doc(hidden)]
+    const SINGLE_PRECISION: bool;
+
+    /// Computes `erf(z)`, or `erfc(z)` if `inv` is true
+    #[doc(hidden)]
+    fn erf_impl(z: Self, inv: bool) -> Self;
+
+    /// Computes `s * erf_inv(p)` where `q = 1 - p`
+    #[doc(hidden)]
+    fn erf_inv_impl(p: Self, q: Self, s: Self) -> Self;
+}
+
+impl SpecialFloat for f64 {
+    const GAMMA_R: f64 = gamma::GAMMA_R;
+    const GAMMA_DK: &'static [f64] = gamma::GAMMA_DK;
+    const SINGLE_PRECISION: bool = false;
+
+    fn erf_impl(z: f64, inv: bool) -> f64 {
+        erf::erf_impl(z, inv)
+    }
+
+    fn erf_inv_impl(p: f64, q: f64, s: f64) -> f64 {
+        erf::erf_inv_impl(p, q, s)
+    }
+}
+
+impl SpecialFloat for f32 {
+    const GAMMA_R: f32 = gamma::GAMMA_R_F32;
+    const GAMMA_DK: &'static [f32] = gamma::GAMMA_DK_F32;
+    const SINGLE_PRECISION: bool = true;
+
+    fn erf_impl(z: f32, inv: bool) -> f32 {
+        erf::erf_impl_f32(z, inv)
+    }
+
+    fn erf_inv_impl(p: f32, q: f32, s: f32) -> f32 {
+        erf::erf_inv_impl_f32(p, q, s)
+    }
+}
+
+/// Converts the constant `x` to the float type `T`
+#[inline(always)]
+pub(crate) fn cast<T: Float>(x: f64) -> T {
+    T::from(x).unwrap()
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+}
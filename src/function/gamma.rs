@@ -3,16 +3,18 @@
 
 use crate::consts;
 use crate::error::StatsError;
-use crate::is_zero;
+use crate::function::erf;
+use crate::function::float::cast;
+use crate::function::SpecialFloat;
 use crate::prec;
 use crate::Result;
 use std::f64;
 
 /// Auxiliary variable when evaluating the `gamma_ln` function
-const GAMMA_R: f64 = 10.900511;
+pub(crate) const GAMMA_R: f64 = 10.900511;
 
 /// Polynomial coefficients for approximating the `gamma_ln` function
-const GAMMA_DK: &[f64] = &[
+pub(crate) const GAMMA_DK: &[f64] = &[
     2.48574089138753565546e-5,
     1.05142378581721974210,
     -3.45687097222016235469,
@@ -26,34 +28,56 @@ const GAMMA_DK: &[f64] = &[
     -2.71994908488607703910e-9,
 ];
 
+/// Auxiliary variable when evaluating the `gamma_ln` function
+/// in single precision
+pub(crate) const GAMMA_R_F32: f32 = 4.4;
+
+/// Polynomial coefficients for approximating the `gamma_ln` function in
+/// single precision. These were fitted for the same Lanczos form as
+/// `GAMMA_DK` and have a relative error below `1.1e-9` for `x >= 0.5`
+pub(crate) const GAMMA_DK_F32: &[f32] = &[
+    0.016542148449506654299,
+    0.64384712192929201166,
+    -0.59231525257263594655,
+    0.10977784948580455442,
+    -0.0018709797629471670599,
+];
+
 /// Computes the logarithm of the gamma function
 /// with an accuracy of 16 floating point digits.
 /// The implementation is derived from
 /// "An Analysis of the Lanczos Gamma Approximation",
 /// Glendon Ralph Pugh, 2004 p. 116
-pub fn ln_gamma(x: f64) -> f64 {
-    if x < 0.5 {
-        let s = GAMMA_DK
+///
+/// # Remarks
+///
+/// For `f32` a shorter Lanczos series accurate to single precision is used
+pub fn ln_gamma<T: SpecialFloat>(x: T) -> T {
+    let half = cast::<T>(0.5);
+    if x < half {
+        let s = T::GAMMA_DK
             .iter()
             .enumerate()
             .skip(1)
-            .fold(GAMMA_DK[0], |s, t| s + t.1 / (t.0 as f64 - x));
+            .fold(T::GAMMA_DK[0], |s, t| s + *t.1 / (cast::<T>(t.0 as f64) - x));
 
-        consts::LN_PI
-            - (f64::consts::PI * x).sin().ln()
+        cast::<T>(consts::LN_PI)
+            - (T::PI() * x).sin().ln()
             - s.ln()
-            - consts::LN_2_SQRT_E_OVER_PI
-            - (0.5 - x) * ((0.5 - x + GAMMA_R) / f64::consts::E).ln()
+            - cast(consts::LN_2_SQRT_E_OVER_PI)
+            - (half - x) * ((half - x + T::GAMMA_R) / T::E()).ln()
     } else {
-        let s = GAMMA_DK
+        let s = T::GAMMA_DK
             .iter()
             .enumerate()
             .skip(1)
-            .fold(GAMMA_DK[0], |s, t| s + t.1 / (x + t.0 as f64 - 1.0));
+            .fold(T::GAMMA_DK[0], |s, t| {
+                s + *t.1 / (x + cast(t.0 as f64) - T::one())
+            });
 
         s.ln()
-            + consts::LN_2_SQRT_E_OVER_PI
-            + (x - 0.5) * ((x - 0.5 + GAMMA_R) / f64::consts::E).ln()
+            + cast(consts::LN_2_SQRT_E_OVER_PI)
+            + (x - half) * ((x - half + T::GAMMA_R) / T::E()).ln()
     }
 }
 
@@ -61,27 +85,35 @@ pub fn ln_gamma(x: f64) -> f64 {
 /// of 16 floating point digits. The implementation
 /// is derived from "An Analysis of the Lanczos Gamma Approximation",
 /// Glendon Ralph Pugh, 2004 p. 116
-pub fn gamma(x: f64) -> f64 {
-    if x < 0.5 {
-        let s = GAMMA_DK
+///
+/// # Remarks
+///
+/// For `f32` a shorter Lanczos series accurate to single precision is used
+pub fn gamma<T: SpecialFloat>(x: T) -> T {
+    let half = cast::<T>(0.5);
+    if x < half {
+        let s = T::GAMMA_DK
             .iter()
             .enumerate()
             .skip(1)
-            .fold(GAMMA_DK[0], |s, t| s + t.1 / (t.0 as f64 - x));
+            .fold(T::GAMMA_DK[0], |s, t| s + *t.1 / (cast::<T>(t.0 as f64) - x));
 
-        f64::consts::PI
-            / ((f64::consts::PI * x).sin()
+        T::PI()
+            / ((T::PI() * x).sin()
                 * s
-                * consts::TWO_SQRT_E_OVER_PI
-                * ((0.5 - x + GAMMA_R) / f64::consts::E).powf(0.5 - x))
+                * cast(consts::TWO_SQRT_E_OVER_PI)
+                * ((half - x + T::GAMMA_R) / T::E()).powf(half - x))
     } else {
-        let s = GAMMA_DK
+        let s = T::GAMMA_DK
             .iter()
             .enumerate()
             .skip(1)
-            .fold(GAMMA_DK[0], |s, t| s + t.1 / (x + t.0 as f64 - 1.0));
+            .fold(T::GAMMA_DK[0], |s, t| {
+                s + *t.1 / (x + cast(t.0 as f64) - T::one())
+            });
 
-        s * consts::TWO_SQRT_E_OVER_PI * ((x - 0.5 + GAMMA_R) / f64::consts::E).powf(x - 0.5)
+        s * cast(consts::TWO_SQRT_E_OVER_PI)
+            * ((x - half + T::GAMMA_R) / T::E()).powf(x - half)
     }
 }
 
@@ -93,7 +125,7 @@ pub fn gamma(x: f64) -> f64 {
 /// # Panics
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn gamma_ui(a: f64, x: f64) -> f64 {
+pub fn gamma_ui<T: SpecialFloat>(a: T, x: T) -> T {
     checked_gamma_ui(a, x).unwrap()
 }
 
@@ -105,7 +137,7 @@ pub fn gamma_ui(a: f64, x: f64) -> f64 {
 /// # Errors
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn checked_gamma_ui(a: f64, x: f64) -> Result<f64> {
+pub fn checked_gamma_ui<T: SpecialFloat>(a: T, x: T) -> Result<T> {
     checked_gamma_ur(a, x).map(|x| x * gamma(a))
 }
 
@@ -118,7 +150,7 @@ pub fn checked_gamma_ui(a: f64, x: f64) -> Result<f64> {
 /// # Panics
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn gamma_li(a: f64, x: f64) -> f64 {
+pub fn gamma_li<T: SpecialFloat>(a: T, x: T) -> T {
     checked_gamma_li(a, x).unwrap()
 }
 
@@ -131,7 +163,7 @@ pub fn gamma_li(a: f64, x: f64) -> f64 {
 /// # Errors
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn checked_gamma_li(a: f64, x: f64) -> Result<f64> {
+pub fn checked_gamma_li<T: SpecialFloat>(a: T, x: T) -> Result<T> {
     checked_gamma_lr(a, x).map(|x| x * gamma(a))
 }
 
@@ -142,12 +174,12 @@ pub fn checked_gamma_li(a: f64, x: f64) -> Result<f64> {
 ///
 /// # Remarks
 ///
-/// Returns `f64::NAN` if either argument is `f64::NAN`
+/// Returns `NAN` if either argument is `NAN`
 ///
 /// # Panics
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn gamma_ur(a: f64, x: f64) -> f64 {
+pub fn gamma_ur<T: SpecialFloat>(a: T, x: T) -> T {
     checked_gamma_ur(a, x).unwrap()
 }
 
@@ -158,48 +190,50 @@ pub fn gamma_ur(a: f64, x: f64) -> f64 {
 ///
 /// # Remarks
 ///
-/// Returns `f64::NAN` if either argument is `f64::NAN`
+/// Returns `NAN` if either argument is `NAN`
 ///
 /// # Errors
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn checked_gamma_ur(a: f64, x: f64) -> Result<f64> {
+pub fn checked_gamma_ur<T: SpecialFloat>(a: T, x: T) -> Result<T> {
     if a.is_nan() || x.is_nan() {
-        return Ok(f64::NAN);
+        return Ok(T::nan());
     }
-    if a <= 0.0 || a == f64::INFINITY {
+    if a <= T::zero() || a == T::infinity() {
         return Err(StatsError::ArgIntervalExcl("a", 0.0, f64::INFINITY));
     }
-    if x <= 0.0 || x == f64::INFINITY {
+    if x <= T::zero() || x == T::infinity() {
         return Err(StatsError::ArgIntervalExcl("x", 0.0, f64::INFINITY));
     }
 
-    let eps = 0.000000000000001;
-    let big = 4503599627370496.0;
-    let big_inv = 2.22044604925031308085e-16;
+    let eps = cast::<T>(0.000000000000001).max(T::epsilon());
+    let big = cast::<T>(4503599627370496.0);
+    let big_inv = cast::<T>(2.22044604925031308085e-16);
+    let one = T::one();
+    let two = one + one;
 
-    if x < 1.0 || x <= a {
-        return Ok(1.0 - gamma_lr(a, x));
+    if x < one || x <= a {
+        return Ok(one - gamma_lr(a, x));
     }
 
     let mut ax = a * x.ln() - x - ln_gamma(a);
-    if ax < -709.78271289338399 {
-        return if a < x { Ok(0.0) } else { Ok(1.0) };
+    if ax < -T::max_value().ln() {
+        return if a < x { Ok(T::zero()) } else { Ok(one) };
     }
 
     ax = ax.exp();
-    let mut y = 1.0 - a;
-    let mut z = x + y + 1.0;
-    let mut c = 0.0;
-    let mut pkm2 = 1.0;
+    let mut y = one - a;
+    let mut z = x + y + one;
+    let mut c = T::zero();
+    let mut pkm2 = one;
     let mut qkm2 = x;
-    let mut pkm1 = x + 1.0;
+    let mut pkm1 = x + one;
     let mut qkm1 = z * x;
     let mut ans = pkm1 / qkm1;
     loop {
-        y += 1.0;
-        z += 2.0;
-        c += 1.0;
+        y += one;
+        z += two;
+        c += one;
         let yc = y * c;
         let pk = pkm1 * z - pkm2 * yc;
         let qk = qkm1 * z - qkm2 * yc;
@@ -216,7 +250,7 @@ pub fn checked_gamma_ur(a: f64, x: f64) -> Result<f64> {
             qkm1 *= big_inv;
         }
 
-        if !is_zero(qk) {
+        if qk != T::zero() {
             let r = pk / qk;
             let t = ((ans - r) / r).abs();
             ans = r;
@@ -236,12 +270,12 @@ pub fn checked_gamma_ur(a: f64, x: f64) -> Result<f64> {
 ///
 /// # Remarks
 ///
-/// Returns `f64::NAN` if either argument is `f64::NAN`
+/// Returns `NAN` if either argument is `NAN`
 ///
 /// # Panics
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn gamma_lr(a: f64, x: f64) -> f64 {
+pub fn gamma_lr<T: SpecialFloat>(a: T, x: T) -> T {
     checked_gamma_lr(a, x).unwrap()
 }
 
@@ -252,46 +286,44 @@ pub fn gamma_lr(a: f64, x: f64) -> f64 {
 ///
 /// # Remarks
 ///
-/// Returns `f64::NAN` if either argument is `f64::NAN`
+/// Returns `NAN` if either argument is `NAN`
 ///
 /// # Errors
 ///
 /// if `a` or `x` are not in `(0, +inf)`
-pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
+pub fn checked_gamma_lr<T: SpecialFloat>(a: T, x: T) -> Result<T> {
     if a.is_nan() || x.is_nan() {
-        return Ok(f64::NAN);
+        return Ok(T::nan());
     }
-    if a <= 0.0 || a == f64::INFINITY {
+    if a <= T::zero() || a == T::infinity() {
         return Err(StatsError::ArgIntervalExcl("a", 0.0, f64::INFINITY));
     }
-    if x <= 0.0 || x == f64::INFINITY {
+    if x <= T::zero() || x == T::infinity() {
         return Err(StatsError::ArgIntervalExcl("x", 0.0, f64::INFINITY));
     }
 
-    let eps = 0.000000000000001;
-    let big = 4503599627370496.0;
-    let big_inv = 2.22044604925031308085e-16;
+    let eps = cast::<T>(0.000000000000001).max(T::epsilon());
+    let big = cast::<T>(4503599627370496.0);
+    let big_inv = cast::<T>(2.22044604925031308085e-16);
+    let one = T::one();
+    let two = one + one;
 
-    if prec::almost_eq(a, 0.0, prec::DEFAULT_F64_ACC) {
-        return Ok(1.0);
+    if a.abs() < cast(prec::DEFAULT_F64_ACC) {
+        return Ok(one);
     }
-    if prec::almost_eq(x, 0.0, prec::DEFAULT_F64_ACC) {
-        return Ok(0.0);
-    }
-
     let ax = a * x.ln() - x - ln_gamma(a);
-    if ax < -709.78271289338399 {
+    if ax < -T::max_value().ln() {
         if a < x {
-            return Ok(1.0);
+            return Ok(one);
         }
-        return Ok(0.0);
+        return Ok(T::zero());
     }
-    if x <= 1.0 || x <= a {
+    if x <= one || x <= a {
         let mut r2 = a;
-        let mut c2 = 1.0;
-        let mut ans2 = 1.0;
+        let mut c2 = one;
+        let mut ans2 = one;
         loop {
-            r2 += 1.0;
+            r2 += one;
             c2 *= x / r2;
             ans2 += c2;
 
@@ -302,21 +334,21 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
         return Ok(ax.exp() * ans2 / a);
     }
 
-    let mut y = 1.0 - a;
-    let mut z = x + y + 1.0;
+    let mut y = one - a;
+    let mut z = x + y + one;
     let mut c = 0;
 
-    let mut p3 = 1.0;
+    let mut p3 = one;
     let mut q3 = x;
-    let mut p2 = x + 1.0;
+    let mut p2 = x + one;
     let mut q2 = z * x;
     let mut ans = p2 / q2;
 
     loop {
-        y += 1.0;
-        z += 2.0;
+        y += one;
+        z += two;
         c += 1;
-        let yc = y * f64::from(c);
+        let yc = y * cast(f64::from(c));
 
         let p = p2 * z - p3 * yc;
         let q = q2 * z - q3 * yc;
@@ -333,7 +365,7 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
             q2 *= big_inv;
         }
 
-        if q != 0.0 {
+        if q != T::zero() {
             let nextans = p / q;
             let error = ((ans - nextans) / nextans).abs();
             ans = nextans;
@@ -343,47 +375,342 @@ pub fn checked_gamma_lr(a: f64, x: f64) -> Result<f64> {
             }
         }
     }
-    Ok(1.0 - ax.exp() * ans)
+    Ok(one - ax.exp() * ans)
+}
+
+/// Computes the inverse of the lower incomplete regularized gamma function,
+/// i.e. the `x` such that `P(a,x) = p` for `a > 0` and `0 <= p <= 1`.
+///
+/// # Remarks
+///
+/// Returns `NAN` if either argument is `NAN`
+///
+/// # Panics
+///
+/// if `a` is not in `(0, +inf)` or `p` is not in `[0, 1]`
+pub fn inv_gamma_lr<T: SpecialFloat>(a: T, p: T) -> T {
+    checked_inv_gamma_lr(a, p).unwrap()
+}
+
+/// Computes the inverse of the lower incomplete regularized gamma function,
+/// i.e. the `x` such that `P(a,x) = p` for `a > 0` and `0 <= p <= 1`.
+///
+/// # Remarks
+///
+/// Returns `NAN` if either argument is `NAN`
+///
+/// # Errors
+///
+/// if `a` is not in `(0, +inf)` or `p` is not in `[0, 1]`
+pub fn checked_inv_gamma_lr<T: SpecialFloat>(a: T, p: T) -> Result<T> {
+    if a.is_nan() || p.is_nan() {
+        return Ok(T::nan());
+    }
+    if a <= T::zero() || a == T::infinity() {
+        return Err(StatsError::ArgIntervalExcl("a", 0.0, f64::INFINITY));
+    }
+    if p < T::zero() || p > T::one() {
+        return Err(StatsError::ArgIntervalIncl("p", 0.0, 1.0));
+    }
+    Ok(inv_gamma_impl(a, p, T::one() - p))
+}
+
+/// Computes the inverse of the upper incomplete regularized gamma function,
+/// i.e. the `x` such that `Q(a,x) = q` for `a > 0` and `0 <= q <= 1`.
+///
+/// # Remarks
+///
+/// Returns `NAN` if either argument is `NAN`
+///
+/// # Panics
+///
+/// if `a` is not in `(0, +inf)` or `q` is not in `[0, 1]`
+pub fn inv_gamma_ur<T: SpecialFloat>(a: T, q: T) -> T {
+    checked_inv_gamma_ur(a, q).unwrap()
+}
+
+/// Computes the inverse of the upper incomplete regularized gamma function,
+/// i.e. the `x` such that `Q(a,x) = q` for `a > 0` and `0 <= q <= 1`.
+///
+/// # Remarks
+///
+/// Returns `NAN` if either argument is `NAN`
+///
+/// # Errors
+///
+/// if `a` is not in `(0, +inf)` or `q` is not in `[0, 1]`
+pub fn checked_inv_gamma_ur<T: SpecialFloat>(a: T, q: T) -> Result<T> {
+    if a.is_nan() || q.is_nan() {
+        return Ok(T::nan());
+    }
+    if a <= T::zero() || a == T::infinity() {
+        return Err(StatsError::ArgIntervalExcl("a", 0.0, f64::INFINITY));
+    }
+    if q < T::zero() || q > T::one() {
+        return Err(StatsError::ArgIntervalIncl("q", 0.0, 1.0));
+    }
+    Ok(inv_gamma_impl(a, T::one() - q, q))
+}
+
+// Solves `P(a,x) = p` (equivalently `Q(a,x) = q`) for `x`, where exactly one
+// of `p` and `q` is expected to be accurate when it is close to `1`. The
+// initial guess follows DiDonato and Morris, "Computation of the Incomplete
+// Gamma Function Ratios and their Inverse", ACM TOMS 12(4), 1986, as
+// refined in Boost.Math, and is polished by Halley iterations on whichever
+// of `P` and `Q` is smaller so that both tails keep full relative accuracy.
+fn inv_gamma_impl<T: SpecialFloat>(a: T, p: T, q: T) -> T {
+    if p == T::zero() {
+        return T::zero();
+    }
+    if q == T::zero() {
+        return T::infinity();
+    }
+
+    let one = T::one();
+    let half = cast::<T>(0.5);
+    let ln_gamma_a = ln_gamma(a);
+    let mut x = inv_gamma_guess(a, p, q, ln_gamma_a);
+    if x == T::zero() {
+        // the quantile underflows
+        return T::zero();
+    }
+    if !x.is_finite() || x < T::zero() {
+        x = a;
+    }
+
+    // f(x) = P(a,x) - p = q - Q(a,x) is increasing in x
+    let use_lower = p < q;
+    let mut lo = T::zero();
+    let mut hi = T::infinity();
+    for _ in 0..200 {
+        let f = if use_lower {
+            gamma_lr(a, x) - p
+        } else {
+            q - gamma_ur(a, x)
+        };
+        if f == T::zero() {
+            break;
+        }
+        if f < T::zero() {
+            lo = x;
+        } else {
+            hi = x;
+        }
+
+        let ln_dfdx = (a - one) * x.ln() - x - ln_gamma_a;
+        let newton = f / ln_dfdx.exp();
+        let correction = one - half * newton * ((a - one) / x - one);
+        let step = if correction > T::zero() && correction.is_finite() {
+            newton / correction
+        } else {
+            newton
+        };
+
+        let mut next = x - step;
+        if !(next > lo && next < hi) {
+            next = if hi.is_infinite() {
+                cast::<T>(2.0) * x
+            } else {
+                half * (lo + hi)
+            };
+        }
+        if (next - x).abs() <= cast::<T>(4.0) * T::epsilon() * next {
+            x = next;
+            break;
+        }
+        if next == lo || next == hi {
+            break;
+        }
+        x = next;
+    }
+    x
+}
+
+// Initial approximation of DiDonato and Morris (1986) for the solution of
+// `P(a,x) = p`, `Q(a,x) = q`.
+fn inv_gamma_guess<T: SpecialFloat>(a: T, p: T, q: T, ln_gamma_a: T) -> T {
+    let one = T::one();
+    let half = cast::<T>(0.5);
+    let euler = cast::<T>(consts::EULER_MASCHERONI);
+    if a == one {
+        return if q < half { -q.ln() } else { -(-p).ln_1p() };
+    }
+
+    if a < one {
+        let g = gamma(a);
+        let b = q * g;
+        if b > cast(0.6) || (b >= cast(0.45) && a >= cast(0.3)) {
+            // DiDonato and Morris Eq 21
+            let u = if b * q > cast(1e-8) && q > cast(1e-5) {
+                (((p * a).ln() + ln_gamma_a) / a).exp()
+            } else {
+                (-q / a - euler).exp()
+            };
+            u / (one - u / (a + one))
+        } else if a < cast(0.3) && b >= cast(0.35) {
+            // DiDonato and Morris Eq 22
+            let t = (-euler - b).exp();
+            let u = t * t.exp();
+            t * u.exp()
+        } else if b > cast(0.15) || a >= cast(0.3) {
+            // DiDonato and Morris Eq 23
+            let y = -b.ln();
+            let u = y - (one - a) * y.ln();
+            y - (one - a) * u.ln() - (one + (one - a) / (one + u)).ln()
+        } else if b > cast(0.1) {
+            // DiDonato and Morris Eq 24
+            let two = cast::<T>(2.0);
+            let three = cast::<T>(3.0);
+            let y = -b.ln();
+            let u = y - (one - a) * y.ln();
+            y - (one - a) * u.ln()
+                - ((u * u + two * (three - a) * u + (two - a) * (three - a))
+                    / (u * u + (cast::<T>(5.0) - a) * u + two))
+                    .ln()
+        } else {
+            // DiDonato and Morris Eq 25
+            inv_gamma_guess_eq25(a, -b.ln())
+        }
+    } else {
+        // DiDonato and Morris Eq 31
+        let two = cast::<T>(2.0);
+        let s = if p < half {
+            -T::SQRT_2() * erf::erfc_inv(two * p)
+        } else {
+            T::SQRT_2() * erf::erfc_inv(two * q)
+        };
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let s4 = s2 * s2;
+        let s5 = s4 * s;
+        let ra = a.sqrt();
+        let w = a + s * ra + (s2 - one) / cast(3.0) + (s3 - cast::<T>(7.0) * s) / (cast::<T>(36.0) * ra)
+            - (cast::<T>(3.0) * s4 + cast::<T>(7.0) * s2 - cast(16.0)) / (cast::<T>(810.0) * a)
+            + (cast::<T>(9.0) * s5 + cast::<T>(256.0) * s3 - cast::<T>(433.0) * s)
+                / (cast::<T>(38880.0) * a * ra);
+
+        if a >= cast(500.0) && (one - w / a).abs() < cast(1e-6) {
+            w
+        } else if p > half {
+            if w < cast::<T>(3.0) * a {
+                w
+            } else {
+                let d = (a * (a - one)).max(two);
+                let lb = q.ln() + ln_gamma_a;
+                if lb < -d * cast(2.3) {
+                    // DiDonato and Morris Eq 25
+                    inv_gamma_guess_eq25(a, -lb)
+                } else {
+                    // DiDonato and Morris Eq 33
+                    let u = -lb + (a - one) * w.ln() - (one + (one - a) / (one + w)).ln();
+                    -lb + (a - one) * u.ln() - (one + (one - a) / (one + u)).ln()
+                }
+            }
+        } else {
+            let ap1 = a + one;
+            let ap2 = a + two;
+            let v = p.ln() + ln_gamma(ap1);
+            let mut z = w;
+            if w < cast::<T>(0.15) * ap1 {
+                // DiDonato and Morris Eq 35
+                z = ((v + w) / a).exp();
+                let mut s = (z / ap1 * (one + z / ap2)).ln_1p();
+                z = ((v + z - s) / a).exp();
+                s = (z / ap1 * (one + z / ap2)).ln_1p();
+                z = ((v + z - s) / a).exp();
+                s = (z / ap1 * (one + z / ap2 * (one + z / (a + cast(3.0))))).ln_1p();
+                z = ((v + z - s) / a).exp();
+            }
+            if z <= cast::<T>(0.01) * ap1 || z > cast::<T>(0.7) * ap1 {
+                z
+            } else {
+                // DiDonato and Morris Eq 36
+                let mut sn = one;
+                let mut term = one;
+                for i in 1..100 {
+                    term *= z / (a + cast(i as f64));
+                    sn += term;
+                    if term < cast(1e-4) {
+                        break;
+                    }
+                }
+                let ls = sn.ln();
+                let zn = ((v + z - ls) / a).exp();
+                zn * (one - (a * zn.ln() - zn - v + ls) / (a - zn))
+            }
+        }
+    }
+}
+
+// DiDonato and Morris Eq 25, an asymptotic expansion of the solution of
+// `Q(a,x) = q` for small `q` in terms of `y = -ln(q * Gamma(a))`.
+fn inv_gamma_guess_eq25<T: SpecialFloat>(a: T, y: T) -> T {
+    let c = cast::<T>;
+    let one = T::one();
+    let c1 = (a - one) * y.ln();
+    let c1_2 = c1 * c1;
+    let c1_3 = c1_2 * c1;
+    let c1_4 = c1_2 * c1_2;
+    let a_2 = a * a;
+    let a_3 = a_2 * a;
+
+    let c2 = (a - one) * (one + c1);
+    let c3 = (a - one) * (-(c1_2 / c(2.0)) + (a - c(2.0)) * c1 + (c(3.0) * a - c(5.0)) / c(2.0));
+    let c4 = (a - one)
+        * ((c1_3 / c(3.0)) - (c(3.0) * a - c(5.0)) * c1_2 / c(2.0)
+            + (a_2 - c(6.0) * a + c(7.0)) * c1
+            + (c(11.0) * a_2 - c(46.0) * a + c(47.0)) / c(6.0));
+    let c5 = (a - one)
+        * (-(c1_4 / c(4.0)) + (c(11.0) * a - c(17.0)) * c1_3 / c(6.0)
+            + (-c(3.0) * a_2 + c(13.0) * a - c(13.0)) * c1_2
+            + (c(2.0) * a_3 - c(25.0) * a_2 + c(72.0) * a - c(61.0)) * c1 / c(2.0)
+            + (c(25.0) * a_3 - c(195.0) * a_2 + c(477.0) * a - c(379.0)) / c(12.0));
+
+    let y_2 = y * y;
+    let y_3 = y_2 * y;
+    let y_4 = y_2 * y_2;
+    y + c1 + (c2 / y) + (c3 / y_2) + (c4 / y_3) + (c5 / y_4)
 }
 
 /// Computes the Digamma function which is defined as the derivative of
 /// the log of the gamma function. The implementation is based on
 /// "Algorithm AS 103", Jose Bernardo, Applied Statistics, Volume 25, Number 3
 /// 1976, pages 315 - 317
-pub fn digamma(x: f64) -> f64 {
-    let c = 12.0;
-    let d1 = -0.57721566490153286;
-    let d2 = 1.6449340668482264365;
-    let s = 1e-6;
-    let s3 = 1.0 / 12.0;
-    let s4 = 1.0 / 120.0;
-    let s5 = 1.0 / 252.0;
-    let s6 = 1.0 / 240.0;
-    let s7 = 1.0 / 132.0;
-
-    if x == f64::NEG_INFINITY || x.is_nan() {
-        return f64::NAN;
-    }
-    if x <= 0.0 && ulps_eq!(x.floor(), x) {
-        return f64::NEG_INFINITY;
-    }
-    if x < 0.0 {
-        return digamma(1.0 - x) + f64::consts::PI / (-f64::consts::PI * x).tan();
+pub fn digamma<T: SpecialFloat>(x: T) -> T {
+    let one = T::one();
+    let c = cast::<T>(12.0);
+    let d1 = cast::<T>(-0.57721566490153286);
+    let d2 = cast::<T>(1.6449340668482264365);
+    let s = cast::<T>(1e-6);
+    let s3 = one / cast(12.0);
+    let s4 = one / cast(120.0);
+    let s5 = one / cast(252.0);
+    let s6 = one / cast(240.0);
+    let s7 = one / cast(132.0);
+
+    if x == T::neg_infinity() || x.is_nan() {
+        return T::nan();
+    }
+    if x <= T::zero() && ulps_eq!(x.floor(), x) {
+        return T::neg_infinity();
+    }
+    if x < T::zero() {
+        return digamma(one - x) + T::PI() / (-T::PI() * x).tan();
     }
     if x <= s {
-        return d1 - 1.0 / x + d2 * x;
+        return d1 - one / x + d2 * x;
     }
 
-    let mut result = 0.0;
+    let mut result = T::zero();
     let mut z = x;
     while z < c {
-        result -= 1.0 / z;
-        z += 1.0;
+        result -= one / z;
+        z += one;
     }
 
     if z >= c {
-        let mut r = 1.0 / z;
-        result += z.ln() - 0.5 * r;
+        let mut r = one / z;
+        result += z.ln() - cast::<T>(0.5) * r;
         r *= r;
 
         result -= r * (s3 - r * (s4 - r * (s5 - r * (s6 - r * s7))));
@@ -391,21 +718,61 @@ pub fn digamma(x: f64) -> f64 {
     result
 }
 
-pub fn inv_digamma(x: f64) -> f64 {
+/// Computes the Trigamma function which is defined as the derivative of
+/// the digamma function. Small arguments are shifted with the recurrence
+/// `ψ1(x) = ψ1(x + 1) + 1 / x^2` before the asymptotic expansion is applied
+/// and negative arguments use the reflection formula
+/// `ψ1(1 - x) + ψ1(x) = π^2 / sin^2(πx)`
+pub fn trigamma<T: SpecialFloat>(x: T) -> T {
+    let one = T::one();
+
+    if x == T::neg_infinity() || x.is_nan() {
+        return T::nan();
+    }
+    if x == T::infinity() {
+        return T::zero();
+    }
+    if x <= T::zero() && ulps_eq!(x.floor(), x) {
+        return T::infinity();
+    }
+    if x < T::zero() {
+        let s = (T::PI() * x).sin();
+        return -trigamma(one - x) + T::PI() * T::PI() / (s * s);
+    }
+
+    let mut result = T::zero();
+    let mut z = x;
+    while z < cast(20.0) {
+        result += one / (z * z);
+        z += one;
+    }
+
+    let r = one / z;
+    let r2 = r * r;
+    result
+        + r
+        + r2 / cast(2.0)
+        + r * r2
+            * (one / cast(6.0)
+                - r2 * (one / cast(30.0)
+                    - r2 * (one / cast(42.0) - r2 * (one / cast(30.0) - r2 * cast(5.0 / 66.0)))))
+}
+
+pub fn inv_digamma<T: SpecialFloat>(x: T) -> T {
     if x.is_nan() {
-        return f64::NAN;
+        return T::nan();
     }
-    if x == f64::NEG_INFINITY {
-        return 0.0;
+    if x == T::neg_infinity() {
+        return T::zero();
     }
-    if x == f64::INFINITY {
-        return f64::INFINITY;
+    if x == T::infinity() {
+        return T::infinity();
     }
     let mut y = x.exp();
-    let mut i = 1.0;
-    while i > 1e-15 {
+    let mut i = T::one();
+    while i > cast(1e-15) {
         y += i * signum(x - digamma(y));
-        i /= 2.0;
+        i /= cast(2.0);
     }
     y
 }
@@ -413,9 +780,9 @@ pub fn inv_digamma(x: f64) -> f64 {
 // modified signum that returns 0.0 if x == 0.0. Used
 // by inv_digamma, may consider extracting into a public
 // method
-fn signum(x: f64) -> f64 {
-    if x == 0.0 {
-        0.0
+fn signum<T: SpecialFloat>(x: T) -> T {
+    if x == T::zero() {
+        T::zero()
     } else {
         x.signum()
     }
@@ -536,6 +903,9 @@ mod tests {
         assert_eq!(super::gamma_lr(1000.0, 10000.0), 1.0);
         assert_eq!(super::gamma_lr(1e+50, 1e+48), 0.0);
         assert_eq!(super::gamma_lr(1e+50, 1e+52), 1.0);
+        assert_almost_eq!(super::gamma_lr(0.1, 1e-20), 0.01051137006111777535452644610692418819111, 1e-16);
+        assert_almost_eq!(super::gamma_lr(1.5, 1e-20), 7.522527780636749873716865443333051373156e-31, 1e-44);
+        assert_almost_eq!(super::gamma_lr(2.5, 1e-20), 3.009011112254699784448891642390185036386e-51, 1e-64);
     }
 
     #[test]
@@ -782,6 +1152,161 @@ mod tests {
         assert!(super::checked_gamma_ui(1.0, f64::INFINITY).is_err());
     }
 
+    #[test]
+    fn test_inv_gamma_lr() {
+        assert!(super::inv_gamma_lr(f64::NAN, 0.5).is_nan());
+        assert_eq!(super::inv_gamma_lr(1.5, 0.0), 0.0);
+        assert_eq!(super::inv_gamma_lr(1.5, 1.0), f64::INFINITY);
+        assert_almost_eq!(super::inv_gamma_lr(0.1, 0.3), 0.000003586086018410947205325, 1e-18);
+        assert_almost_eq!(super::inv_gamma_lr(0.5, 0.1), 0.00789538704671561331527, 1e-16);
+        assert_almost_eq!(super::inv_gamma_lr(0.5, 0.5), 0.2274682115597863759713, 1e-15);
+        assert_almost_eq!(super::inv_gamma_lr(1.0, 0.5), consts::LN_2, 1e-15);
+        assert_almost_eq!(super::inv_gamma_lr(1.5, 0.01), 0.05741590094955851957561, 1e-15);
+        assert_almost_eq!(super::inv_gamma_lr(1.5, 0.99), 5.672433365072185004782, 1e-13);
+        assert_almost_eq!(super::inv_gamma_lr(2.5, 0.5), 2.175730095547763658579, 1e-14);
+        assert_almost_eq!(super::inv_gamma_lr(5.5, 0.1), 2.788892394899925630868, 1e-14);
+        assert_almost_eq!(super::inv_gamma_lr(10.0, 0.9), 14.20599029215281713117, 1e-13);
+        assert_almost_eq!(super::inv_gamma_lr(100.0, 0.5), 99.66686491931548874369, 1e-11);
+        assert_almost_eq!(super::inv_gamma_lr(0.01, 0.5), 4.465535018910355121413e-31, 1e-42);
+        assert_almost_eq!(super::inv_gamma_lr(0.1, 1e-20), 6.073048362407882531571e-201, 1e-212);
+        assert_almost_eq!(super::inv_gamma_lr(3.0, 1e-100), 8.434326653017492484663e-34, 1e-45);
+        assert_almost_eq!(super::inv_gamma_lr(1000.0, 0.5), 999.6666864269651820591, 1e-9);
+    }
+
+    #[test]
+    fn test_inv_gamma_ur() {
+        assert!(super::inv_gamma_ur(f64::NAN, 0.5).is_nan());
+        assert_eq!(super::inv_gamma_ur(1.5, 0.0), f64::INFINITY);
+        assert_eq!(super::inv_gamma_ur(1.5, 1.0), 0.0);
+        assert_almost_eq!(super::inv_gamma_ur(0.01, 0.5), 4.465535018910355121413e-31, 1e-42);
+        assert_almost_eq!(super::inv_gamma_ur(0.5, 1e-20), 43.58086671345491144267, 1e-12);
+        assert_almost_eq!(super::inv_gamma_ur(1.0, 0.5), consts::LN_2, 1e-15);
+        assert_almost_eq!(super::inv_gamma_ur(1.5, 0.01), 5.672433365072185943137, 1e-13);
+        assert_almost_eq!(super::inv_gamma_ur(10.0, 0.1), 14.20599029215281649955, 1e-13);
+        assert_almost_eq!(super::inv_gamma_ur(50.0, 1e-300), 878.3937665919384152864, 1e-10);
+        assert_almost_eq!(super::inv_gamma_ur(1000.0, 1e-10), 1214.499556323812585224, 1e-9);
+    }
+
+    #[test]
+    fn test_inv_gamma_lr_ur_roundtrip() {
+        for &a in [0.05, 0.5, 1.0, 2.5, 10.0, 150.0].iter() {
+            for &p in [1e-10, 0.01, 0.25, 0.5, 0.75, 0.99].iter() {
+                let x = super::inv_gamma_lr(a, p);
+                assert_almost_eq!(super::gamma_lr(a, x), p, 1e-12 * p.max(1e-3));
+                let x = super::inv_gamma_ur(a, p);
+                assert_almost_eq!(super::gamma_ur(a, x), p, 1e-12 * p.max(1e-3));
+            }
+        }
+    }
+
+    #[test]
+    fn test_gamma_f32() {
+        assert!(super::gamma(f32::NAN).is_nan());
+        assert_almost_eq!(super::gamma(0.1f32) as f64, 9.5135076986687312858, 1e-5);
+        assert_almost_eq!(super::gamma(0.5f32) as f64, 1.7724538509055160273, 2e-7);
+        assert_almost_eq!(super::gamma(1.5f32) as f64, 0.88622692545275801365, 1e-7);
+        assert_almost_eq!(super::gamma(2.5f32) as f64, 1.3293403881791370205, 2e-7);
+        assert_almost_eq!(super::gamma(10.0f32) as f64, 362880.0, 0.5);
+        assert_almost_eq!(super::gamma(30.5f32) as f64 / 4.8226969334909086011e+31, 1.0, 5e-6);
+        assert_eq!(super::gamma(40.0f32), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_ln_gamma_f32() {
+        assert!(super::ln_gamma(f32::NAN).is_nan());
+        assert_almost_eq!(super::ln_gamma(0.1f32) as f64, 2.252712651734205902, 4e-7);
+        assert_almost_eq!(super::ln_gamma(0.5f32) as f64, 0.57236494292470008707, 1e-7);
+        assert_almost_eq!(super::ln_gamma(1.5f32) as f64, -0.12078223763524522235, 5e-7);
+        assert_almost_eq!(super::ln_gamma(10.0f32) as f64, 12.801827480081469611, 2e-6);
+        assert_almost_eq!(super::ln_gamma(30.5f32) as f64, 72.953471184169408324, 1e-5);
+        assert_almost_eq!(super::ln_gamma(1000.0f32) as f64, 5905.2204232091812118, 1e-3);
+    }
+
+    #[test]
+    fn test_gamma_lr_ur_f32() {
+        assert!(super::gamma_lr(f32::NAN, 1.0).is_nan());
+        assert_almost_eq!(super::gamma_lr(0.5f32, 0.5) as f64, 0.68268949213708589717, 4e-7);
+        assert_almost_eq!(super::gamma_ur(0.5f32, 0.5) as f64, 0.31731050786291410283, 4e-7);
+        assert_almost_eq!(super::gamma_lr(2.5f32, 1.0) as f64, 0.15085496391539036377, 2e-7);
+        assert_almost_eq!(super::gamma_ur(2.5f32, 1.0) as f64, 0.84914503608460963623, 2e-7);
+        assert_almost_eq!(super::gamma_lr(10.0f32, 12.0) as f64, 0.75760783832948765132, 4e-6);
+        assert_almost_eq!(super::gamma_ur(10.0f32, 12.0) as f64, 0.24239216167051234868, 4e-6);
+        assert_almost_eq!(super::gamma_ur(1.0f32, 50.0) as f64, 1.928749847963917783e-22, 1e-27);
+    }
+
+    #[test]
+    fn test_inv_gamma_lr_ur_f32() {
+        assert_eq!(super::inv_gamma_lr(1.5f32, 0.0), 0.0);
+        assert_eq!(super::inv_gamma_ur(1.5f32, 0.0), f32::INFINITY);
+        assert_almost_eq!(super::inv_gamma_lr(1.0f32, 0.5) as f64, consts::LN_2, 2e-7);
+        for &a in [0.5f32, 1.0, 2.5, 10.0].iter() {
+            for &p in [1e-6f32, 0.01, 0.5, 0.99].iter() {
+                let x = super::inv_gamma_lr(a, p);
+                assert_almost_eq!(super::gamma_lr(a, x) as f64, p as f64, 5e-5 * p as f64);
+                let x = super::inv_gamma_ur(a, p);
+                assert_almost_eq!(super::gamma_ur(a, x) as f64, p as f64, 5e-5 * p as f64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_trigamma() {
+        assert!(super::trigamma(f64::NAN).is_nan());
+        assert!(super::trigamma(f64::NEG_INFINITY).is_nan());
+        assert_eq!(super::trigamma(f64::INFINITY), 0.0);
+        assert_eq!(super::trigamma(0.0), f64::INFINITY);
+        assert_eq!(super::trigamma(-2.0), f64::INFINITY);
+        assert_almost_eq!(super::trigamma(1e-8), 10000000000000001.645, 1e1);
+        assert_almost_eq!(super::trigamma(0.1), 101.43329915079275882, 1e-12);
+        assert_almost_eq!(super::trigamma(0.5), 4.9348022005446793094, 1e-14);
+        assert_almost_eq!(super::trigamma(1.0), 1.6449340668482264365, 1e-15);
+        assert_almost_eq!(super::trigamma(2.5), 0.49035775610023486497, 1e-15);
+        assert_almost_eq!(super::trigamma(10.0), 0.10516633568168574612, 1e-15);
+        assert_almost_eq!(super::trigamma(100.0), 0.010050166663333571395, 1e-16);
+        assert_almost_eq!(super::trigamma(1e6), 1.0000005000001666667e-6, 1e-20);
+        assert_almost_eq!(super::trigamma(-1.5), 9.3792466449891237539, 1e-13);
+        assert_almost_eq!(super::trigamma(-0.25), 18.541879647671606498, 1e-13);
+        assert_almost_eq!(super::trigamma(2.5f32) as f64, 0.49035775610023486497, 1e-7);
+    }
+
+    #[test]
+    fn test_digamma_f32() {
+        assert!(super::digamma(f32::NAN).is_nan());
+        assert_almost_eq!(super::digamma(-1.5f32) as f64, 0.70315664064524318723, 1e-6);
+        assert_almost_eq!(super::digamma(0.125f32) as f64, -8.3884926632958548678, 4e-6);
+        assert_almost_eq!(super::digamma(2.5f32) as f64, 0.70315664064524318723, 2e-7);
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_lr_a_lower_bound() {
+        assert!(super::checked_inv_gamma_lr(0.0, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_lr_a_upper_bound() {
+        assert!(super::checked_inv_gamma_lr(f64::INFINITY, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_lr_p_lower_bound() {
+        assert!(super::checked_inv_gamma_lr(1.0, -0.1).is_err());
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_lr_p_upper_bound() {
+        assert!(super::checked_inv_gamma_lr(1.0, 1.1).is_err());
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_ur_a_lower_bound() {
+        assert!(super::checked_inv_gamma_ur(-1.0, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_checked_inv_gamma_ur_q_upper_bound() {
+        assert!(super::checked_inv_gamma_ur(1.0, 1.1).is_err());
+    }
+
     // TODO: precision testing could be more accurate
     #[test]
     fn test_digamma() {
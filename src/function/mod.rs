@@ -1,11 +1,14 @@
 //! Provides a host of special statistical functions (e.g. the beta function or
 //! the error function)
 
+pub use self::float::SpecialFloat;
+
 pub mod beta;
 pub mod erf;
 pub mod evaluate;
 pub mod exponential;
 pub mod factorial;
+pub(crate) mod float;
 pub mod gamma;
 pub mod harmonic;
 pub mod logistic;
@@ -57,14 +57,16 @@ pub mod euclid;
 pub mod function;
 pub mod generate;
 pub mod prec;
+pub mod resampling;
 pub mod statistics;
+pub mod stats_tests;
 
 mod error;
 
 // function to silence clippy on the special case when comparing to zero.
 #[inline(always)]
-pub(crate) fn is_zero(x: f64) -> bool {
-    ulps_eq!(x, 0.0, max_ulps = 0)
+pub(crate) fn is_zero<T: function::SpecialFloat>(x: T) -> bool {
+    ulps_eq!(x, T::zero(), max_ulps = 0)
 }
 
 // #[cfg(test)]
@@ -0,0 +1,802 @@
+//! Provides bootstrap and jackknife estimates and permutation tests for
+//! arbitrary statistics. The randomized methods draw from a caller-supplied
+//! random number generator, so results are reproducible with a seeded one.
+
+use crate::distribution::{ContinuousCDF, Normal};
+use crate::statistics::{Data, QuantileDefinition, QuantileStatistics, Statistics};
+use crate::stats_tests::{check_level, p_value, Alternative, ConfidenceInterval};
+use crate::{Result, StatsError};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Enumeration of the methods for bootstrap confidence intervals
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BootstrapInterval {
+    /// The quantiles of the bootstrap replicates
+    Percentile,
+    /// The basic, or reverse percentile, interval, the quantiles of the
+    /// replicates reflected about the estimate
+    Basic,
+    /// The bootstrap-t interval from the quantiles of the replicates
+    /// standardized by their standard errors, which requires a statistic
+    /// that returns its standard error
+    Studentized,
+    /// The bias-corrected and accelerated percentile interval of Efron
+    /// (1987), with the acceleration estimated by the jackknife, which
+    /// requires a bootstrap from `bootstrap_bca` or `bootstrap_columns_bca`
+    BCa,
+}
+
+/// The result of a bootstrap
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapResult {
+    /// The statistic of the original sample
+    pub estimate: f64,
+    /// The statistic of each resample
+    pub replicates: Vec<f64>,
+    /// The standard error of the estimate, if the statistic returns one
+    std_err: Option<f64>,
+    /// The standard error of each replicate, if the statistic returns one
+    replicate_std_errs: Option<Vec<f64>>,
+    /// The statistic of the original sample leaving out each observation,
+    /// if the bootstrap computed it for the BCa interval
+    jackknife: Option<Vec<f64>>,
+}
+
+impl BootstrapResult {
+    /// Returns the bootstrap estimate of the bias of the statistic, the mean
+    /// of the replicates minus the estimate
+    pub fn bias(&self) -> f64 {
+        self.replicates.iter().mean() - self.estimate
+    }
+
+    /// Returns the bootstrap estimate of the standard error of the
+    /// statistic, the standard deviation of the replicates
+    pub fn std_err(&self) -> f64 {
+        self.replicates.iter().std_dev()
+    }
+
+    /// Returns the two-sided confidence interval for the statistic at the
+    /// confidence `level` by the `method`
+    ///
+    /// # Remarks
+    ///
+    /// The quantiles of the replicates are those of
+    /// `QuantileDefinition::Linear`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`, the `Studentized`
+    /// method is requested for a statistic without standard errors, or the
+    /// `BCa` method is requested for a bootstrap without the jackknife or
+    /// with all replicates on one side of the estimate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    /// use statrs::resampling::{bootstrap_bca, BootstrapInterval};
+    /// use statrs::statistics::Statistics;
+    ///
+    /// let data = [2.3, 4.1, 3.3, 5.0, 2.8, 3.9, 4.4, 3.1, 3.6, 4.7];
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let result = bootstrap_bca(&data, |x| x.mean(), 2000, &mut rng).unwrap();
+    /// let ci = result.confidence_interval(BootstrapInterval::BCa, 0.95).unwrap();
+    /// assert!(ci.contains(result.estimate));
+    /// ```
+    pub fn confidence_interval(
+        &self,
+        method: BootstrapInterval,
+        level: f64,
+    ) -> Result<ConfidenceInterval> {
+        check_level(level)?;
+        let alpha = (1.0 - level) / 2.0;
+        let quantiles = |values: &[f64], taus: &[f64]| {
+            Data::new(values.to_vec()).quantiles_with(taus, QuantileDefinition::Linear)
+        };
+        let (lower, upper) = match method {
+            BootstrapInterval::Percentile => {
+                let q = quantiles(&self.replicates, &[alpha, 1.0 - alpha]);
+                (q[0], q[1])
+            }
+            BootstrapInterval::Basic => {
+                let q = quantiles(&self.replicates, &[alpha, 1.0 - alpha]);
+                (2.0 * self.estimate - q[1], 2.0 * self.estimate - q[0])
+            }
+            BootstrapInterval::Studentized => {
+                let (std_err, replicate_std_errs) =
+                    match (self.std_err, self.replicate_std_errs.as_ref()) {
+                        (Some(std_err), Some(replicate_std_errs)) => (std_err, replicate_std_errs),
+                        _ => {
+                            return Err(StatsError::SpecialCase(
+                                "the studentized interval requires standard errors",
+                            ))
+                        }
+                    };
+                let t: Vec<f64> = self
+                    .replicates
+                    .iter()
+                    .zip(replicate_std_errs)
+                    .map(|(replicate, se)| (replicate - self.estimate) / se)
+                    .collect();
+                let q = quantiles(&t, &[alpha, 1.0 - alpha]);
+                (
+                    self.estimate - q[1] * std_err,
+                    self.estimate - q[0] * std_err,
+                )
+            }
+            BootstrapInterval::BCa => {
+                let jackknife = match self.jackknife.as_ref() {
+                    Some(jackknife) => jackknife,
+                    None => {
+                        return Err(StatsError::SpecialCase(
+                            "the BCa interval requires the jackknife",
+                        ))
+                    }
+                };
+                let normal = Normal::new(0.0, 1.0).unwrap();
+                let below = self
+                    .replicates
+                    .iter()
+                    .filter(|&&x| x < self.estimate)
+                    .count();
+                let z0 = normal.inverse_cdf(below as f64 / self.replicates.len() as f64);
+                if !z0.is_finite() {
+                    return Err(StatsError::SpecialCase(
+                        "the bootstrap replicates lie on one side of the estimate",
+                    ));
+                }
+                let mean = jackknife.iter().mean();
+                let (sum_2, sum_3) = jackknife.iter().fold((0.0, 0.0), |(s2, s3), x| {
+                    let d = mean - x;
+                    (s2 + d * d, s3 + d * d * d)
+                });
+                let acceleration = if sum_2 > 0.0 {
+                    sum_3 / (6.0 * sum_2.powf(1.5))
+                } else {
+                    0.0
+                };
+                let adjust = |z: f64| normal.cdf(z0 + (z0 + z) / (1.0 - acceleration * (z0 + z)));
+                let z = normal.inverse_cdf(alpha);
+                let q = quantiles(&self.replicates, &[adjust(z), adjust(-z)]);
+                (q[0], q[1])
+            }
+        };
+        Ok(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        })
+    }
+}
+
+/// The result of a jackknife
+#[derive(Debug, Clone, PartialEq)]
+pub struct JackknifeResult {
+    /// The statistic of the original sample
+    pub estimate: f64,
+    /// The jackknife estimate of the bias of the statistic
+    pub bias: f64,
+    /// The jackknife estimate of the standard error of the statistic
+    pub std_err: f64,
+    /// The statistic of the sample leaving out each observation in turn
+    pub replicates: Vec<f64>,
+}
+
+impl JackknifeResult {
+    /// Returns the bias-corrected estimate, the estimate minus the bias
+    pub fn corrected_estimate(&self) -> f64 {
+        self.estimate - self.bias
+    }
+}
+
+/// The result of a permutation test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PermutationTestResult {
+    /// The statistic of the original samples
+    pub statistic: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The number of random permutations
+    pub permutations: usize,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+/// Checks that `columns` are non-empty and have the same, non-zero length,
+/// and returns it
+fn rows(columns: &[&[f64]]) -> Result<usize> {
+    let n = match columns.first() {
+        Some(column) if !column.is_empty() => column.len(),
+        _ => return Err(StatsError::BadParams),
+    };
+    if columns.iter().any(|column| column.len() != n) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    Ok(n)
+}
+
+/// Returns the statistic of `columns` with each row left out in turn
+fn leave_one_out<F>(columns: &[&[f64]], statistic: F) -> Vec<f64>
+where
+    F: Fn(&[&[f64]]) -> f64,
+{
+    let n = columns[0].len();
+    let mut buffers: Vec<Vec<f64>> = vec![Vec::with_capacity(n - 1); columns.len()];
+    (0..n)
+        .map(|i| {
+            for (buffer, column) in buffers.iter_mut().zip(columns) {
+                buffer.clear();
+                buffer.extend_from_slice(&column[..i]);
+                buffer.extend_from_slice(&column[i + 1..]);
+            }
+            let slices: Vec<&[f64]> = buffers.iter().map(|b| b.as_slice()).collect();
+            statistic(&slices)
+        })
+        .collect()
+}
+
+/// Resamples the rows of `columns` with replacement `resamples` times and
+/// evaluates the statistic, which returns an estimate and its standard
+/// error, on the original and each resampled data, and with `bca` on the
+/// original data leaving out each row in turn
+fn run_bootstrap<F, R>(
+    columns: &[&[f64]],
+    statistic: F,
+    resamples: usize,
+    studentized: bool,
+    bca: bool,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[&[f64]]) -> (f64, f64),
+    R: Rng + ?Sized,
+{
+    let n = rows(columns)?;
+    if resamples == 0 {
+        return Err(StatsError::ArgMustBePositive("resamples"));
+    }
+    let (estimate, std_err) = statistic(columns);
+    let mut buffers: Vec<Vec<f64>> = vec![vec![0.0; n]; columns.len()];
+    let mut replicates = Vec::with_capacity(resamples);
+    let mut replicate_std_errs = Vec::with_capacity(if studentized { resamples } else { 0 });
+    for _ in 0..resamples {
+        for i in 0..n {
+            let j = rng.gen_range(0..n);
+            for (buffer, column) in buffers.iter_mut().zip(columns) {
+                buffer[i] = column[j];
+            }
+        }
+        let slices: Vec<&[f64]> = buffers.iter().map(|b| b.as_slice()).collect();
+        let (replicate, replicate_std_err) = statistic(&slices);
+        replicates.push(replicate);
+        if studentized {
+            replicate_std_errs.push(replicate_std_err);
+        }
+    }
+    let jackknife = if !bca {
+        None
+    } else if n > 1 {
+        Some(leave_one_out(columns, |x| statistic(x).0))
+    } else {
+        Some(Vec::new())
+    };
+    Ok(BootstrapResult {
+        estimate,
+        replicates,
+        std_err: if studentized { Some(std_err) } else { None },
+        replicate_std_errs: if studentized {
+            Some(replicate_std_errs)
+        } else {
+            None
+        },
+        jackknife,
+    })
+}
+
+/// Bootstraps the `statistic` of `data` from `resamples` resamples drawn
+/// with replacement using `rng`
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or `resamples` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::resampling::{bootstrap, BootstrapInterval};
+/// use statrs::statistics::{Data, OrderStatistics};
+///
+/// let data = [2.3, 4.1, 3.3, 5.0, 2.8, 3.9, 4.4, 3.1, 3.6, 4.7];
+/// let median = |x: &[f64]| Data::new(x.to_vec()).median();
+/// let result = bootstrap(&data, median, 1000, &mut StdRng::seed_from_u64(1)).unwrap();
+/// assert_eq!(result.estimate, 3.75);
+/// assert_eq!(result.replicates.len(), 1000);
+/// let ci = result.confidence_interval(BootstrapInterval::Percentile, 0.9).unwrap();
+/// assert!(ci.contains(3.75));
+/// ```
+pub fn bootstrap<F, R>(
+    data: &[f64],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    run_bootstrap(
+        &[data],
+        |x| (statistic(x[0]), f64::NAN),
+        resamples,
+        false,
+        false,
+        rng,
+    )
+}
+
+/// Bootstraps the `statistic` of `data`, which returns an estimate and its
+/// standard error, from `resamples` resamples drawn with replacement using
+/// `rng`, which allows studentized intervals
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or `resamples` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::resampling::{bootstrap_studentized, BootstrapInterval};
+/// use statrs::statistics::Statistics;
+///
+/// let data = [2.3, 4.1, 3.3, 5.0, 2.8, 3.9, 4.4, 3.1, 3.6, 4.7];
+/// let mean = |x: &[f64]| (x.mean(), x.std_dev() / (x.len() as f64).sqrt());
+/// let result = bootstrap_studentized(&data, mean, 1000, &mut StdRng::seed_from_u64(1)).unwrap();
+/// let ci = result.confidence_interval(BootstrapInterval::Studentized, 0.95).unwrap();
+/// assert!(ci.lower < 3.72 && 3.72 < ci.upper);
+/// ```
+pub fn bootstrap_studentized<F, R>(
+    data: &[f64],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[f64]) -> (f64, f64),
+    R: Rng + ?Sized,
+{
+    run_bootstrap(&[data], |x| statistic(x[0]), resamples, true, false, rng)
+}
+
+/// Bootstraps the `statistic` of the paired or multi-column data `columns`
+/// from `resamples` resamples of its rows drawn with replacement using
+/// `rng`
+///
+/// # Errors
+///
+/// Returns an error if there are no columns, they have different lengths or
+/// are empty, or `resamples` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::resampling::bootstrap_columns;
+/// use statrs::statistics::Statistics;
+///
+/// let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+/// let y = [2.1, 3.9, 6.2, 8.1, 9.8, 12.2];
+/// let ratio = |c: &[&[f64]]| c[1].mean() / c[0].mean();
+/// let result = bootstrap_columns(&[&x, &y], ratio, 500, &mut StdRng::seed_from_u64(1)).unwrap();
+/// assert!((result.estimate - 2.0142857).abs() < 1e-7);
+/// assert!(result.std_err() < 0.1);
+/// ```
+pub fn bootstrap_columns<F, R>(
+    columns: &[&[f64]],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[&[f64]]) -> f64,
+    R: Rng + ?Sized,
+{
+    run_bootstrap(
+        columns,
+        |x| (statistic(x), f64::NAN),
+        resamples,
+        false,
+        false,
+        rng,
+    )
+}
+
+/// Bootstraps the `statistic` of the paired or multi-column data
+/// `columns`, which returns an estimate and its standard error, from
+/// `resamples` resamples of its rows drawn with replacement using `rng`,
+/// which allows studentized intervals
+///
+/// # Errors
+///
+/// Returns an error if there are no columns, they have different lengths or
+/// are empty, or `resamples` is zero
+pub fn bootstrap_columns_studentized<F, R>(
+    columns: &[&[f64]],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[&[f64]]) -> (f64, f64),
+    R: Rng + ?Sized,
+{
+    run_bootstrap(columns, statistic, resamples, true, false, rng)
+}
+
+/// Bootstraps the `statistic` of `data` like `bootstrap`, and also computes
+/// its jackknife for the `BCa` interval
+///
+/// # Remarks
+///
+/// The jackknife evaluates the statistic once more for each observation,
+/// so for a statistic linear in the size of the data it adds `O(n^2)` work
+/// to the `O(resamples * n)` of the bootstrap. The replicates are those of
+/// `bootstrap` with the same `rng`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or `resamples` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::resampling::{bootstrap_bca, BootstrapInterval};
+/// use statrs::statistics::Statistics;
+///
+/// let data = [2.3, 4.1, 3.3, 5.0, 2.8, 3.9, 4.4, 3.1, 3.6, 4.7];
+/// let variance = |x: &[f64]| x.variance();
+/// let result = bootstrap_bca(&data, variance, 2000, &mut StdRng::seed_from_u64(1)).unwrap();
+/// let ci = result.confidence_interval(BootstrapInterval::BCa, 0.95).unwrap();
+/// assert!(ci.contains(result.estimate));
+/// ```
+pub fn bootstrap_bca<F, R>(
+    data: &[f64],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    run_bootstrap(
+        &[data],
+        |x| (statistic(x[0]), f64::NAN),
+        resamples,
+        false,
+        true,
+        rng,
+    )
+}
+
+/// Bootstraps the `statistic` of the paired or multi-column data `columns`
+/// like `bootstrap_columns`, and also computes its jackknife, leaving out
+/// one row at a time, for the `BCa` interval
+///
+/// # Errors
+///
+/// Returns an error if there are no columns, they have different lengths or
+/// are empty, or `resamples` is zero
+pub fn bootstrap_columns_bca<F, R>(
+    columns: &[&[f64]],
+    statistic: F,
+    resamples: usize,
+    rng: &mut R,
+) -> Result<BootstrapResult>
+where
+    F: Fn(&[&[f64]]) -> f64,
+    R: Rng + ?Sized,
+{
+    run_bootstrap(
+        columns,
+        |x| (statistic(x), f64::NAN),
+        resamples,
+        false,
+        true,
+        rng,
+    )
+}
+
+/// Returns the jackknife estimates of the bias and standard error of the
+/// `statistic` of `data`
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations
+///
+/// # Formula
+///
+/// ```ignore
+/// bias = (n - 1) * (θ_(.) - θ)
+/// se = sqrt((n - 1) / n * Σ (θ_(i) - θ_(.))^2)
+/// ```
+///
+/// where `θ` is the statistic of the sample, `θ_(i)` that of the sample
+/// without the `i`th observation and `θ_(.)` their mean
+///
+/// # Examples
+///
+/// ```
+/// use statrs::resampling::jackknife;
+/// use statrs::statistics::Statistics;
+///
+/// let data = [2.0, 4.0, 4.0, 5.0, 7.0, 9.0];
+/// let result = jackknife(&data, |x| x.population_variance()).unwrap();
+/// assert!((result.corrected_estimate() - data.variance()).abs() < 1e-12);
+/// ```
+pub fn jackknife<F>(data: &[f64], statistic: F) -> Result<JackknifeResult>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    jackknife_columns(&[data], |x| statistic(x[0]))
+}
+
+/// Returns the jackknife estimates of the bias and standard error of the
+/// `statistic` of the paired or multi-column data `columns`, leaving out
+/// one row at a time
+///
+/// # Errors
+///
+/// Returns an error if there are no columns, they have different lengths,
+/// or fewer than two rows
+pub fn jackknife_columns<F>(columns: &[&[f64]], statistic: F) -> Result<JackknifeResult>
+where
+    F: Fn(&[&[f64]]) -> f64,
+{
+    let n = rows(columns)?;
+    if n < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let estimate = statistic(columns);
+    let replicates = leave_one_out(columns, &statistic);
+    let n = n as f64;
+    let mean = replicates.iter().mean();
+    let sum_squares: f64 = replicates.iter().map(|x| (x - mean) * (x - mean)).sum();
+    Ok(JackknifeResult {
+        estimate,
+        bias: (n - 1.0) * (mean - estimate),
+        std_err: ((n - 1.0) / n * sum_squares).sqrt(),
+        replicates,
+    })
+}
+
+/// Performs a two-sample permutation test of the null hypothesis that `x`
+/// and `y` come from the same distribution, comparing the `statistic` of
+/// the samples with its value for `permutations` random reassignments of
+/// the pooled observations to samples of the same sizes drawn using `rng`
+///
+/// # Remarks
+///
+/// The one-sided p-values count the original assignment among the
+/// permutations, `(1 + #{T* >= T}) / (1 + B)` for the `Greater`
+/// alternative, so they are never zero. The two-sided p-value is twice the
+/// smaller one-sided one, at most one.
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` is empty or `permutations` is zero
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use statrs::resampling::permutation_test;
+/// use statrs::statistics::Statistics;
+/// use statrs::stats_tests::Alternative;
+///
+/// let x = [5.2, 6.1, 5.8, 6.5, 7.0, 6.3];
+/// let y = [4.1, 4.8, 5.0, 4.4, 5.3, 4.9];
+/// let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+/// let mut rng = StdRng::seed_from_u64(3);
+/// let result = permutation_test(&x, &y, difference, 5000, Alternative::Greater, &mut rng).unwrap();
+/// assert!(result.p_value < 0.01);
+/// ```
+pub fn permutation_test<F, R>(
+    x: &[f64],
+    y: &[f64],
+    statistic: F,
+    permutations: usize,
+    alternative: Alternative,
+    rng: &mut R,
+) -> Result<PermutationTestResult>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    R: Rng + ?Sized,
+{
+    if x.is_empty() || y.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    if permutations == 0 {
+        return Err(StatsError::ArgMustBePositive("permutations"));
+    }
+    let observed = statistic(x, y);
+    let mut pooled: Vec<f64> = x.iter().chain(y).cloned().collect();
+    let (mut less, mut greater) = (1.0, 1.0);
+    for _ in 0..permutations {
+        pooled.shuffle(rng);
+        let (x, y) = pooled.split_at(x.len());
+        let permuted = statistic(x, y);
+        if permuted <= observed {
+            less += 1.0;
+        }
+        if permuted >= observed {
+            greater += 1.0;
+        }
+    }
+    let total = permutations as f64 + 1.0;
+    Ok(PermutationTestResult {
+        statistic: observed,
+        p_value: p_value(less / total, greater / total, alternative),
+        permutations,
+        alternative,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::StudentsT;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const DATA: [f64; 12] = [2.3, 4.1, 3.3, 5.0, 2.8, 3.9, 4.4, 3.1, 3.6, 4.7, 2.6, 3.8];
+
+    fn mean(x: &[f64]) -> f64 {
+        x.mean()
+    }
+
+    #[test]
+    fn test_bootstrap_reproducible() {
+        let a = bootstrap(&DATA, mean, 200, &mut StdRng::seed_from_u64(5)).unwrap();
+        let b = bootstrap(&DATA, mean, 200, &mut StdRng::seed_from_u64(5)).unwrap();
+        let c = bootstrap(&DATA, mean, 200, &mut StdRng::seed_from_u64(6)).unwrap();
+        assert_eq!(a, b);
+        assert_ne!(a.replicates, c.replicates);
+        assert_eq!(a.estimate, DATA.mean());
+    }
+
+    #[test]
+    fn test_bootstrap_mean_intervals() {
+        let result = bootstrap_bca(&DATA, mean, 20_000, &mut StdRng::seed_from_u64(7)).unwrap();
+        let n = DATA.len() as f64;
+        // the bootstrap standard error of the mean tends to the plug-in one
+        let se = DATA.population_std_dev() / n.sqrt();
+        assert_almost_eq!(result.std_err(), se, 0.005);
+        assert_almost_eq!(result.bias(), 0.0, 0.005);
+        let half_width = 1.959963984540054 * se;
+        for &method in &[BootstrapInterval::Percentile, BootstrapInterval::Basic, BootstrapInterval::BCa] {
+            let ci = result.confidence_interval(method, 0.95).unwrap();
+            assert_almost_eq!(ci.lower, DATA.mean() - half_width, 0.02);
+            assert_almost_eq!(ci.upper, DATA.mean() + half_width, 0.02);
+        }
+        let percentile = result.confidence_interval(BootstrapInterval::Percentile, 0.9).unwrap();
+        let basic = result.confidence_interval(BootstrapInterval::Basic, 0.9).unwrap();
+        assert_almost_eq!(basic.lower, 2.0 * result.estimate - percentile.upper, 1e-14);
+        assert_almost_eq!(basic.upper, 2.0 * result.estimate - percentile.lower, 1e-14);
+        assert!(result.confidence_interval(BootstrapInterval::Studentized, 0.95).is_err());
+        assert!(result.confidence_interval(BootstrapInterval::Percentile, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_studentized() {
+        let statistic = |x: &[f64]| (x.mean(), x.std_dev() / (x.len() as f64).sqrt());
+        let result = bootstrap_studentized(&DATA, statistic, 20_000, &mut StdRng::seed_from_u64(8)).unwrap();
+        // close to the t interval for the mean of a symmetric sample
+        let t = StudentsT::new(0.0, 1.0, DATA.len() as f64 - 1.0).unwrap().inverse_cdf(0.975);
+        let half_width = t * statistic(&DATA).1;
+        let ci = result.confidence_interval(BootstrapInterval::Studentized, 0.95).unwrap();
+        assert_almost_eq!(ci.lower, DATA.mean() - half_width, 0.06);
+        assert_almost_eq!(ci.upper, DATA.mean() + half_width, 0.06);
+    }
+
+    #[test]
+    fn test_bootstrap_bca_skewed() {
+        // the BCa interval of the variance of a sample shifts to the right of
+        // the percentile one
+        let result = bootstrap_bca(&DATA, |x| x.variance(), 5000, &mut StdRng::seed_from_u64(9)).unwrap();
+        let percentile = result.confidence_interval(BootstrapInterval::Percentile, 0.95).unwrap();
+        let bca = result.confidence_interval(BootstrapInterval::BCa, 0.95).unwrap();
+        assert!(bca.lower > percentile.lower && bca.upper > percentile.upper);
+        // the jackknife leaves the replicates unchanged and is only computed
+        // on request
+        let plain = bootstrap(&DATA, |x| x.variance(), 5000, &mut StdRng::seed_from_u64(9)).unwrap();
+        assert_eq!(plain.replicates, result.replicates);
+        assert!(plain.confidence_interval(BootstrapInterval::BCa, 0.95).is_err());
+
+        let constant = bootstrap_bca(&[1.0, 1.0, 1.0], mean, 100, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert!(constant.confidence_interval(BootstrapInterval::BCa, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_bootstrap_columns() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let y = [2.1, 3.9, 6.2, 8.1, 9.8, 12.2];
+        let difference = |c: &[&[f64]]| c[1].mean() - c[0].mean();
+        let paired = bootstrap_columns(&[&x, &y], difference, 300, &mut StdRng::seed_from_u64(2)).unwrap();
+        let d: Vec<f64> = x.iter().zip(&y).map(|(x, y)| y - x).collect();
+        let single = bootstrap(&d, mean, 300, &mut StdRng::seed_from_u64(2)).unwrap();
+        for (a, b) in paired.replicates.iter().zip(&single.replicates) {
+            assert_almost_eq!(*a, *b, 1e-12);
+        }
+        let bca = bootstrap_columns_bca(&[&x, &y], difference, 300, &mut StdRng::seed_from_u64(2)).unwrap();
+        let plain = bootstrap_columns(&[&x, &y], difference, 300, &mut StdRng::seed_from_u64(2)).unwrap();
+        assert_eq!(bca.replicates, plain.replicates);
+        assert!(bca.confidence_interval(BootstrapInterval::BCa, 0.9).unwrap().contains(bca.estimate));
+        assert!(plain.confidence_interval(BootstrapInterval::BCa, 0.9).is_err());
+        assert!(bootstrap_columns(&[&x, &y[..5]], difference, 10, &mut StdRng::seed_from_u64(2)).is_err());
+        assert!(bootstrap_columns(&[], difference, 10, &mut StdRng::seed_from_u64(2)).is_err());
+        assert!(bootstrap(&[], mean, 10, &mut StdRng::seed_from_u64(2)).is_err());
+        assert!(bootstrap(&x, mean, 0, &mut StdRng::seed_from_u64(2)).is_err());
+    }
+
+    #[test]
+    fn test_jackknife() {
+        let result = jackknife(&DATA, mean).unwrap();
+        let n = DATA.len() as f64;
+        assert_almost_eq!(result.bias, 0.0, 1e-14);
+        assert_almost_eq!(result.std_err, DATA.std_dev() / n.sqrt(), 1e-14);
+        assert_eq!(result.replicates.len(), DATA.len());
+        assert_almost_eq!(result.replicates[0], (DATA.iter().sum::<f64>() - DATA[0]) / (n - 1.0), 1e-14);
+
+        let result = jackknife(&DATA, |x| x.population_variance()).unwrap();
+        assert_almost_eq!(result.corrected_estimate(), DATA.variance(), 1e-13);
+
+        let x = [1.0, 2.0, 3.0, 4.0];
+        let y = [2.0, 1.0, 4.0, 3.0];
+        let result = jackknife_columns(&[&x, &y], |c| c[0].covariance(c[1])).unwrap();
+        assert_almost_eq!(result.estimate, x.covariance(y), 1e-15);
+        assert!(jackknife(&[1.0], mean).is_err());
+    }
+
+    #[test]
+    fn test_permutation_test() {
+        let x = [5.2, 6.1, 5.8, 4.3];
+        let y = [4.1, 5.9, 5.0, 4.4];
+        let difference = |x: &[f64], y: &[f64]| x.mean() - y.mean();
+        // the exact p-value over all 70 assignments
+        let pooled: Vec<f64> = x.iter().chain(&y).cloned().collect();
+        let observed = difference(&x, &y);
+        let (mut count, mut total) = (0, 0);
+        for mask in 0u32..256 {
+            if mask.count_ones() != 4 {
+                continue;
+            }
+            let (a, b): (Vec<_>, Vec<_>) = (0..8).partition(|i| mask & (1 << i) != 0);
+            let a: Vec<f64> = a.iter().map(|&i| pooled[i]).collect();
+            let b: Vec<f64> = b.iter().map(|&i| pooled[i]).collect();
+            total += 1;
+            if difference(&a, &b) >= observed - 1e-12 {
+                count += 1;
+            }
+        }
+        let exact = count as f64 / total as f64;
+        let result = permutation_test(&x, &y, difference, 20_000, Alternative::Greater, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert_almost_eq!(result.p_value, exact, 0.01);
+        assert_eq!(result.statistic, observed);
+        let two_sided = permutation_test(&x, &y, difference, 20_000, Alternative::TwoSided, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert_almost_eq!(two_sided.p_value, 2.0 * result.p_value, 1e-12);
+        let less = permutation_test(&x, &y, difference, 20_000, Alternative::Less, &mut StdRng::seed_from_u64(4)).unwrap();
+        assert!(less.p_value > 0.5);
+        assert!(permutation_test(&x, &[], difference, 10, Alternative::Less, &mut StdRng::seed_from_u64(4)).is_err());
+    }
+}
@@ -381,6 +381,30 @@ mod tests {
         assert!(data.quadratic_mean().is_nan());
         assert!(data.variance().is_nan());
         assert!(data.population_variance().is_nan());
+        assert!(data.skewness().is_nan());
+        assert!(data.kurtosis().is_nan());
+    }
+
+    #[test]
+    fn test_skewness_kurtosis() {
+        let data = testing::load_data("nist/lew.txt");
+        let n = data.len() as f64;
+        let mean = (&data).mean();
+        let m2 = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        let m3 = data.iter().map(|x| (x - mean).powi(3)).sum::<f64>() / n;
+        let m4 = data.iter().map(|x| (x - mean).powi(4)).sum::<f64>() / n;
+        assert_almost_eq!((&data).skewness(), m3 / m2.powf(1.5), 1e-12);
+        assert_almost_eq!((&data).kurtosis(), m4 / (m2 * m2) - 3.0, 1e-12);
+
+        let shifted = data.iter().map(|x| x + 1e9).collect::<Vec<f64>>();
+        assert_almost_eq!((&shifted).skewness(), (&data).skewness(), 1e-6);
+        assert_almost_eq!((&shifted).kurtosis(), (&data).kurtosis(), 1e-6);
+
+        let symmetric = [-2.0, -1.0, 0.0, 1.0, 2.0];
+        assert_eq!(symmetric.skewness(), 0.0);
+        assert_almost_eq!(symmetric.kurtosis(), 1.7 - 3.0, 1e-15);
+        assert!([1.0, 1.0, 1.0].skewness().is_nan());
+        assert!([1.0, 1.0, 1.0].kurtosis().is_nan());
     }
 
     // TODO: test github issue 137 (Math.NET)
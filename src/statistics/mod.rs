@@ -1,14 +1,22 @@
 //! Provides traits for statistical computation
 
 pub use self::iter_statistics::*;
+pub use self::online_statistics::*;
 pub use self::order_statistics::*;
+pub use self::robust_statistics::*;
 pub use self::slice_statistics::*;
 pub use self::statistics::*;
+pub use self::streaming_quantiles::*;
 pub use self::traits::*;
+pub use self::weighted_statistics::*;
 
 mod iter_statistics;
+mod online_statistics;
 mod order_statistics;
+mod robust_statistics;
 // TODO: fix later
 mod slice_statistics;
 mod statistics;
+mod streaming_quantiles;
 mod traits;
+mod weighted_statistics;
@@ -0,0 +1,574 @@
+use crate::{Result, StatsError};
+use std::f64;
+use std::iter::FromIterator;
+
+/// Accumulates the count, mean, variance, skewness, kurtosis, minimum and
+/// maximum of a stream of samples in constant memory
+///
+/// # Remarks
+///
+/// The central moments are updated with the numerically stable formulas of
+/// Welford and Pébay, so that the accumulator does not suffer from the
+/// cancellation of the naive sums of powers. Two accumulators over disjoint
+/// samples can be merged, which allows the statistics of a data set to be
+/// computed in parallel.
+///
+/// Samples can carry non-negative frequency weights, in which case the
+/// statistics are those of the data set in which each sample is repeated
+/// `weight` times.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::OnlineStats;
+///
+/// let mut stats = OnlineStats::new();
+/// for &x in &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+///     stats.push(x);
+/// }
+/// assert_eq!(stats.count(), 8);
+/// assert_eq!(stats.mean(), 5.0);
+/// assert!((stats.population_variance() - 4.0).abs() < 1e-14);
+/// assert_eq!(stats.min(), 2.0);
+/// assert_eq!(stats.max(), 9.0);
+///
+/// let left: OnlineStats = [2.0, 4.0, 4.0, 4.0].iter().copied().collect();
+/// let mut right: OnlineStats = [5.0, 5.0, 7.0, 9.0].iter().copied().collect();
+/// right.merge(&left);
+/// assert_eq!(right.count(), 8);
+/// assert!((right.variance() - stats.variance()).abs() < 1e-14);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct OnlineStats {
+    count: u64,
+    weight: f64,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for OnlineStats {
+    fn default() -> Self {
+        OnlineStats {
+            count: 0,
+            weight: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl OnlineStats {
+    /// Constructs an empty accumulator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::OnlineStats;
+    ///
+    /// let stats = OnlineStats::new();
+    /// assert_eq!(stats.count(), 0);
+    /// assert!(stats.mean().is_nan());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the sample `x`
+    pub fn push(&mut self, x: f64) {
+        self.update(x, 1.0);
+    }
+
+    /// Adds the sample `x` with the frequency weight `weight`. A sample with
+    /// zero weight is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `weight` is negative, infinite or `NaN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::OnlineStats;
+    ///
+    /// let mut weighted = OnlineStats::new();
+    /// weighted.push_weighted(1.0, 3.0).unwrap();
+    /// weighted.push_weighted(5.0, 1.0).unwrap();
+    ///
+    /// let repeated: OnlineStats = [1.0, 1.0, 1.0, 5.0].iter().copied().collect();
+    /// assert_eq!(weighted.mean(), repeated.mean());
+    /// assert_eq!(weighted.variance(), repeated.variance());
+    /// assert!(weighted.push_weighted(1.0, -1.0).is_err());
+    /// ```
+    pub fn push_weighted(&mut self, x: f64, weight: f64) -> Result<()> {
+        if !(weight >= 0.0 && weight.is_finite()) {
+            return Err(StatsError::ArgNotNegative("weight"));
+        }
+        if weight > 0.0 {
+            self.update(x, weight);
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, x: f64, weight: f64) {
+        let w_a = self.weight;
+        let w = w_a + weight;
+        let delta = x - self.mean;
+        let delta_w = delta / w;
+        let delta_w2 = delta_w * delta_w;
+        let term = delta * delta_w * w_a * weight;
+        self.mean += delta_w * weight;
+        self.m4 += term * delta_w2 * (w_a * w_a - w_a * weight + weight * weight)
+            + 6.0 * delta_w2 * weight * weight * self.m2
+            - 4.0 * delta_w * weight * self.m3;
+        self.m3 += term * delta_w * (w_a - weight) - 3.0 * delta_w * weight * self.m2;
+        self.m2 += term;
+        self.weight = w;
+        self.count += 1;
+        if x < self.min || x.is_nan() {
+            self.min = x;
+        }
+        if x > self.max || x.is_nan() {
+            self.max = x;
+        }
+    }
+
+    /// Merges the samples accumulated by `other` into `self`, so that the
+    /// statistics become those of the union of both sets of samples
+    pub fn merge(&mut self, other: &OnlineStats) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+        let (w_a, w_b) = (self.weight, other.weight);
+        let w = w_a + w_b;
+        let delta = other.mean - self.mean;
+        let delta_w = delta / w;
+        let delta_w2 = delta_w * delta_w;
+        let term = delta * delta_w * w_a * w_b;
+        self.mean += delta_w * w_b;
+        self.m4 += other.m4
+            + term * delta_w2 * (w_a * w_a - w_a * w_b + w_b * w_b)
+            + 6.0 * delta_w2 * (w_a * w_a * other.m2 + w_b * w_b * self.m2)
+            + 4.0 * delta_w * (w_a * other.m3 - w_b * self.m3);
+        self.m3 += other.m3
+            + term * delta_w * (w_a - w_b)
+            + 3.0 * delta_w * (w_a * other.m2 - w_b * self.m2);
+        self.m2 += other.m2 + term;
+        self.weight = w;
+        self.count += other.count;
+        if other.min < self.min || other.min.is_nan() {
+            self.min = other.min;
+        }
+        if other.max > self.max || other.max.is_nan() {
+            self.max = other.max;
+        }
+    }
+
+    /// Multiplies the weights of all the accumulated samples by `factor`
+    fn scale_weights(&mut self, factor: f64) {
+        self.weight *= factor;
+        self.m2 *= factor;
+        self.m3 *= factor;
+        self.m4 *= factor;
+    }
+
+    /// Returns the number of samples with non-zero weight
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the weights of the samples, which is the number of
+    /// samples if they are unweighted
+    pub fn sum_weights(&self) -> f64 {
+        self.weight
+    }
+
+    /// Returns the mean of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn mean(&self) -> f64 {
+        if self.count > 0 {
+            self.mean
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the unbiased estimate of the variance of the samples, using
+    /// the sum of the weights less one as a normalizer
+    ///
+    /// Returns `f64::NAN` if the sum of the weights is at most one or any
+    /// sample is `f64::NAN`
+    pub fn variance(&self) -> f64 {
+        if self.weight > 1.0 {
+            self.m2 / (self.weight - 1.0)
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the population variance of the samples, using the sum of the
+    /// weights as a normalizer
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn population_variance(&self) -> f64 {
+        if self.count > 0 {
+            self.m2 / self.weight
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the unbiased estimate of the standard deviation of the samples
+    ///
+    /// Returns `f64::NAN` if the sum of the weights is at most one or any
+    /// sample is `f64::NAN`
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Returns the population standard deviation of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn population_std_dev(&self) -> f64 {
+        self.population_variance().sqrt()
+    }
+
+    /// Returns the sample skewness `m3 / m2^(3/2)` of the samples, as
+    /// computed by `Statistics::skewness`
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or all samples are equal
+    pub fn skewness(&self) -> f64 {
+        if self.count > 0 && self.m2 > 0.0 {
+            self.weight.sqrt() * self.m3 / self.m2.powf(1.5)
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the sample excess kurtosis `m4 / m2^2 - 3` of the samples, as
+    /// computed by `Statistics::kurtosis`
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or all samples are equal
+    pub fn kurtosis(&self) -> f64 {
+        if self.count > 0 && self.m2 > 0.0 {
+            self.weight * self.m4 / (self.m2 * self.m2) - 3.0
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the minimum of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn min(&self) -> f64 {
+        if self.count > 0 {
+            self.min
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the maximum of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn max(&self) -> f64 {
+        if self.count > 0 {
+            self.max
+        } else {
+            f64::NAN
+        }
+    }
+}
+
+impl Extend<f64> for OnlineStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl FromIterator<f64> for OnlineStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = OnlineStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// Accumulates the exponentially weighted mean, variance, skewness and
+/// kurtosis of a stream of samples in constant memory
+///
+/// # Remarks
+///
+/// Each new sample has weight one, and the weights of all the previous
+/// samples are multiplied by the `decay` factor, so that the sample pushed
+/// `k` steps ago has weight `decay^k`. The statistics are those of the
+/// samples with these weights, which for a long stream approach the
+/// exponentially weighted moving averages with smoothing factor
+/// `1 - decay`, without their bias towards the first sample.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::ExponentialOnlineStats;
+///
+/// let mut stats = ExponentialOnlineStats::new(0.5).unwrap();
+/// stats.push(1.0);
+/// stats.push(4.0);
+/// assert_eq!(stats.sum_weights(), 1.5);
+/// assert_eq!(stats.mean(), 3.0);
+/// assert_eq!(stats.variance(), 2.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ExponentialOnlineStats {
+    decay: f64,
+    stats: OnlineStats,
+}
+
+impl ExponentialOnlineStats {
+    /// Constructs an empty accumulator whose sample weights are multiplied
+    /// by `decay` for every new sample
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `decay` is not in `(0, 1]`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::ExponentialOnlineStats;
+    ///
+    /// assert!(ExponentialOnlineStats::new(0.9).is_ok());
+    /// assert!(ExponentialOnlineStats::new(0.0).is_err());
+    /// assert!(ExponentialOnlineStats::new(1.5).is_err());
+    /// ```
+    pub fn new(decay: f64) -> Result<Self> {
+        if decay > 0.0 && decay <= 1.0 {
+            Ok(ExponentialOnlineStats {
+                decay,
+                stats: OnlineStats::new(),
+            })
+        } else {
+            Err(StatsError::ArgIntervalExclMin("decay", 0.0, 1.0))
+        }
+    }
+
+    /// Returns the factor by which the sample weights decay
+    pub fn decay(&self) -> f64 {
+        self.decay
+    }
+
+    /// Adds the sample `x` with weight one, after decaying the weights of
+    /// the previous samples
+    pub fn push(&mut self, x: f64) {
+        self.stats.scale_weights(self.decay);
+        self.stats.update(x, 1.0);
+    }
+
+    /// Returns the number of samples
+    pub fn count(&self) -> u64 {
+        self.stats.count()
+    }
+
+    /// Returns the sum of the current weights of the samples
+    pub fn sum_weights(&self) -> f64 {
+        self.stats.sum_weights()
+    }
+
+    /// Returns the exponentially weighted mean of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn mean(&self) -> f64 {
+        self.stats.mean()
+    }
+
+    /// Returns the exponentially weighted variance of the samples, using the
+    /// sum of the weights as a normalizer
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn variance(&self) -> f64 {
+        self.stats.population_variance()
+    }
+
+    /// Returns the exponentially weighted standard deviation of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn std_dev(&self) -> f64 {
+        self.stats.population_std_dev()
+    }
+
+    /// Returns the exponentially weighted skewness of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or all samples are equal
+    pub fn skewness(&self) -> f64 {
+        self.stats.skewness()
+    }
+
+    /// Returns the exponentially weighted excess kurtosis of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or all samples are equal
+    pub fn kurtosis(&self) -> f64 {
+        self.stats.kurtosis()
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::Statistics;
+    use crate::testing;
+
+    fn check(stats: &OnlineStats, data: &[f64], prec: f64) {
+        assert_eq!(stats.count(), data.len() as u64);
+        assert_almost_eq!(stats.mean(), data.mean(), prec);
+        assert_almost_eq!(stats.variance(), data.variance(), prec);
+        assert_almost_eq!(stats.population_variance(), data.population_variance(), prec);
+        assert_almost_eq!(stats.skewness(), data.skewness(), prec);
+        assert_almost_eq!(stats.kurtosis(), data.kurtosis(), prec);
+        assert_eq!(stats.min(), Statistics::min(data));
+        assert_eq!(stats.max(), Statistics::max(data));
+    }
+
+    #[test]
+    fn test_push() {
+        let data = testing::load_data("nist/lew.txt");
+        let stats: OnlineStats = data.iter().copied().collect();
+        check(&stats, &data, 1e-10);
+        assert_almost_eq!(stats.std_dev(), (&data).std_dev(), 1e-10);
+        assert_almost_eq!(stats.population_std_dev(), (&data).population_std_dev(), 1e-10);
+
+        let shifted = data.iter().map(|x| x + 1e9).collect::<Vec<f64>>();
+        let stats: OnlineStats = shifted.iter().copied().collect();
+        assert_almost_eq!(stats.variance(), (&data).variance(), 1e-3);
+        assert_almost_eq!(stats.skewness(), (&data).skewness(), 1e-6);
+        assert_almost_eq!(stats.kurtosis(), (&data).kurtosis(), 1e-6);
+    }
+
+    #[test]
+    fn test_merge() {
+        let data = testing::load_data("nist/lew.txt");
+        for &split in &[0, 1, 17, 100, data.len()] {
+            let mut left: OnlineStats = data[..split].iter().copied().collect();
+            let right: OnlineStats = data[split..].iter().copied().collect();
+            left.merge(&right);
+            check(&left, &data, 1e-10);
+        }
+        let mut merged = OnlineStats::new();
+        for chunk in data.chunks(7) {
+            merged.merge(&chunk.iter().copied().collect());
+        }
+        check(&merged, &data, 1e-10);
+    }
+
+    #[test]
+    fn test_push_weighted() {
+        let values = [1.5, -2.0, 3.25, 7.0, 0.5];
+        let weights = [2.0, 1.0, 3.0, 0.0, 4.0];
+        let mut weighted = OnlineStats::new();
+        let mut repeated = vec![];
+        for (&x, &w) in values.iter().zip(&weights) {
+            weighted.push_weighted(x, w).unwrap();
+            for _ in 0..w as usize {
+                repeated.push(x);
+            }
+        }
+        assert_eq!(weighted.count(), 4);
+        assert_eq!(weighted.sum_weights(), 10.0);
+        assert_almost_eq!(weighted.mean(), repeated.clone().mean(), 1e-14);
+        assert_almost_eq!(weighted.variance(), repeated.clone().variance(), 1e-14);
+        assert_almost_eq!(weighted.skewness(), repeated.clone().skewness(), 1e-14);
+        assert_almost_eq!(weighted.kurtosis(), repeated.clone().kurtosis(), 1e-14);
+        assert_eq!(weighted.max(), 3.25);
+
+        let mut half = OnlineStats::new();
+        for (&x, &w) in values.iter().zip(&weights) {
+            half.push_weighted(x, w / 2.0).unwrap();
+        }
+        assert_almost_eq!(half.population_variance(), weighted.population_variance(), 1e-14);
+        assert_almost_eq!(half.skewness(), weighted.skewness(), 1e-14);
+        assert_almost_eq!(half.kurtosis(), weighted.kurtosis(), 1e-14);
+    }
+
+    #[test]
+    fn test_empty_and_nan() {
+        let stats = OnlineStats::new();
+        assert_eq!(stats.count(), 0);
+        assert!(stats.mean().is_nan());
+        assert!(stats.variance().is_nan());
+        assert!(stats.population_variance().is_nan());
+        assert!(stats.skewness().is_nan());
+        assert!(stats.kurtosis().is_nan());
+        assert!(stats.min().is_nan());
+        assert!(stats.max().is_nan());
+
+        let single: OnlineStats = vec![3.0].into_iter().collect();
+        assert_eq!(single.mean(), 3.0);
+        assert!(single.variance().is_nan());
+        assert_eq!(single.population_variance(), 0.0);
+        assert!(single.skewness().is_nan());
+
+        let nan: OnlineStats = vec![1.0, f64::NAN, 2.0].into_iter().collect();
+        assert!(nan.mean().is_nan());
+        assert!(nan.variance().is_nan());
+        assert!(nan.min().is_nan());
+        assert!(nan.max().is_nan());
+
+        let mut stats = OnlineStats::new();
+        assert!(stats.push_weighted(1.0, -1.0).is_err());
+        assert!(stats.push_weighted(1.0, f64::NAN).is_err());
+        assert!(stats.push_weighted(1.0, f64::INFINITY).is_err());
+        stats.push_weighted(1.0, 0.0).unwrap();
+        assert_eq!(stats, OnlineStats::new());
+    }
+
+    #[test]
+    fn test_exponential() {
+        let data = testing::load_data("nist/lew.txt");
+        let decay = 0.9;
+        let mut stats = ExponentialOnlineStats::new(decay).unwrap();
+        for &x in &data {
+            stats.push(x);
+        }
+        let n = data.len();
+        let weights: Vec<f64> = (0..n).map(|i| decay.powi((n - 1 - i) as i32)).collect();
+        let total: f64 = weights.iter().sum();
+        let mean = data.iter().zip(&weights).map(|(x, w)| w * x).sum::<f64>() / total;
+        let moment = |k: i32| {
+            data.iter().zip(&weights).map(|(x, w)| w * (x - mean).powi(k)).sum::<f64>() / total
+        };
+        assert_eq!(stats.count(), n as u64);
+        assert_almost_eq!(stats.sum_weights(), total, 1e-12);
+        assert_almost_eq!(stats.mean(), mean, 1e-10);
+        assert_almost_eq!(stats.variance(), moment(2), 1e-8);
+        assert_almost_eq!(stats.std_dev(), moment(2).sqrt(), 1e-10);
+        assert_almost_eq!(stats.skewness(), moment(3) / moment(2).powf(1.5), 1e-10);
+        assert_almost_eq!(stats.kurtosis(), moment(4) / moment(2).powi(2) - 3.0, 1e-10);
+
+        let mut ordinary = ExponentialOnlineStats::new(1.0).unwrap();
+        for &x in &data {
+            ordinary.push(x);
+        }
+        assert_almost_eq!(ordinary.variance(), (&data).population_variance(), 1e-10);
+        assert!(ExponentialOnlineStats::new(0.0).is_err());
+        assert!(ExponentialOnlineStats::new(f64::NAN).is_err());
+        assert!(ExponentialOnlineStats::new(1.0 + 1e-12).is_err());
+    }
+}
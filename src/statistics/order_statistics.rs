@@ -1,4 +1,97 @@
 use super::RankTieBreaker;
+use core::ops::Sub;
+
+/// Enumeration of the definitions of the sample quantile, the nine of
+/// [Hyndman and Fan (1996)](https://doi.org/10.2307/2684934) and the nearest
+/// rank
+///
+/// # Remarks
+///
+/// With the data sorted as `x_1 <= ... <= x_n`, types 4 to 9 interpolate
+/// linearly between `x_j` and `x_(j+1)` at the position `h = n * tau + m`,
+/// where `j = floor(h)`, and `x_0 = x_1` and `x_(n+1) = x_n`. Types 1 to 3
+/// return one of the two or, for type 2, their average. The numbering and
+/// results are those of the `type` argument of R's `quantile`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum QuantileDefinition {
+    /// Type 1, the inverse of the empirical distribution function,
+    /// `x_(ceil(n * tau))`
+    InvertedCdf,
+    /// Type 2, the inverse of the empirical distribution function with
+    /// averaging at discontinuities
+    AveragedInvertedCdf,
+    /// Type 3, the observation closest to `n * tau`, taking the even order
+    /// statistic on ties, as in SAS
+    ClosestObservation,
+    /// Type 4, the linear interpolation of the empirical distribution
+    /// function, `m = 0`
+    InterpolatedInvertedCdf,
+    /// Type 5, the piecewise linear function with knots midway through the
+    /// steps of the empirical distribution function, `m = 1 / 2`
+    Hazen,
+    /// Type 6, `m = tau`, as in Minitab and SPSS
+    Weibull,
+    /// Type 7, `m = 1 - tau`, the default of R, numpy and Excel
+    Linear,
+    /// Type 8, `m = (tau + 1) / 3`, approximately median-unbiased regardless
+    /// of the distribution and used by `OrderStatistics::quantile`
+    MedianUnbiased,
+    /// Type 9, `m = tau / 4 + 3 / 8`, approximately unbiased for the
+    /// expected order statistics of a normal distribution
+    NormalUnbiased,
+    /// The observation whose zero-based index is nearest to
+    /// `(n - 1) * tau`, the position of `Linear`, taking the even index on
+    /// ties as numpy's `nearest` method
+    NearestRank,
+}
+
+impl QuantileDefinition {
+    /// Returns the one-based index `j`, which may be outside `1..=n`, and the
+    /// weight `gamma` of the `tau` quantile `(1 - gamma) * x_j + gamma *
+    /// x_(j+1)` of `n` sorted observations
+    pub(crate) fn position(self, tau: f64, n: usize) -> (i64, f64) {
+        const FUZZ: f64 = 4.0 * f64::EPSILON;
+        let n = n as f64;
+        let (alpha, beta) = match self {
+            QuantileDefinition::InvertedCdf
+            | QuantileDefinition::AveragedInvertedCdf
+            | QuantileDefinition::ClosestObservation => {
+                let nppm = match self {
+                    QuantileDefinition::ClosestObservation => n * tau - 0.5,
+                    _ => n * tau,
+                };
+                let j = (nppm + FUZZ).floor();
+                let gamma = match self {
+                    QuantileDefinition::InvertedCdf if nppm > j => 1.0,
+                    QuantileDefinition::InvertedCdf => 0.0,
+                    QuantileDefinition::AveragedInvertedCdf if nppm > j => 1.0,
+                    QuantileDefinition::AveragedInvertedCdf => 0.5,
+                    _ if nppm != j || j.rem_euclid(2.0) == 1.0 => 1.0,
+                    _ => 0.0,
+                };
+                return (j as i64, gamma);
+            }
+            QuantileDefinition::NearestRank => {
+                let index = (n - 1.0) * tau;
+                let mut rounded = index.round();
+                if index - index.floor() == 0.5 && rounded.rem_euclid(2.0) == 1.0 {
+                    rounded -= 1.0;
+                }
+                return (rounded as i64 + 1, 0.0);
+            }
+            QuantileDefinition::InterpolatedInvertedCdf => (0.0, 1.0),
+            QuantileDefinition::Hazen => (0.5, 0.5),
+            QuantileDefinition::Weibull => (0.0, 0.0),
+            QuantileDefinition::Linear => (1.0, 1.0),
+            QuantileDefinition::MedianUnbiased => (1.0 / 3.0, 1.0 / 3.0),
+            QuantileDefinition::NormalUnbiased => (3.0 / 8.0, 3.0 / 8.0),
+        };
+        let nppm = alpha + tau * (n + 1.0 - alpha - beta);
+        let j = (nppm + FUZZ).floor();
+        let gamma = nppm - j;
+        (j as i64, if gamma.abs() < FUZZ { 0.0 } else { gamma })
+    }
+}
 
 /// The `OrderStatistics` trait provides statistical utilities
 /// having to do with ordering. All the algorithms are in-place thus requiring
@@ -217,3 +310,162 @@ pub trait OrderStatistics<T> {
     /// ```
     fn ranks(&mut self, tie_breaker: RankTieBreaker) -> Vec<T>;
 }
+
+/// The `QuantileStatistics` trait provides estimates of the sample
+/// quantiles under each of the definitions of `QuantileDefinition`. Like
+/// `OrderStatistics`, the algorithms are in-place thus requiring a mutable
+/// borrow.
+pub trait QuantileStatistics<T> {
+    /// Estimates the tau-th quantile from the data with the quantile
+    /// `definition`
+    ///
+    /// # Remarks
+    ///
+    /// No sorting is assumed. Tau must be between `0` and `1` inclusive.
+    /// Returns `f64::NAN` if data is empty, an entry is `f64::NAN` or tau is
+    /// outside the inclusive range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(x.quantile_with(0.25, QuantileDefinition::Linear), 1.75);
+    /// assert_eq!(x.quantile_with(0.25, QuantileDefinition::Hazen), 1.5);
+    /// assert_eq!(x.quantile_with(0.25, QuantileDefinition::InvertedCdf), 1.0);
+    /// assert_eq!(x.quantile_with(0.5, QuantileDefinition::NearestRank), 3.0);
+    /// assert!(x.quantile_with(1.5, QuantileDefinition::Linear).is_nan());
+    /// ```
+    fn quantile_with(&mut self, tau: f64, definition: QuantileDefinition) -> T;
+
+    /// Estimates the quantiles of the data at each of the `taus` with the
+    /// `MedianUnbiased` definition of `quantile`
+    ///
+    /// # Remarks
+    ///
+    /// No sorting is assumed. The quantile at a tau outside `[0, 1]`, or at
+    /// any tau if data is empty or an entry is `f64::NAN`, is `f64::NAN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics};
+    ///
+    /// let mut x = Data::new([2.0, 1.0, 3.0, 4.0]);
+    /// let q = x.quantiles(&[0.0, 0.5, 1.0]);
+    /// assert_eq!(q[0], 1.0);
+    /// assert!((q[1] - 2.5).abs() < 1e-15);
+    /// assert_eq!(q[2], 4.0);
+    /// ```
+    fn quantiles(&mut self, taus: &[f64]) -> Vec<T> {
+        self.quantiles_with(taus, QuantileDefinition::MedianUnbiased)
+    }
+
+    /// Estimates the quantiles of the data at each of the `taus` with the
+    /// quantile `definition`
+    ///
+    /// # Remarks
+    ///
+    /// No sorting is assumed. The quantile at a tau outside `[0, 1]`, or at
+    /// any tau if data is empty or an entry is `f64::NAN`, is `f64::NAN`.
+    /// `Data` computes all of them with a single partial sort of the data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let mut x = Data::new([5.0, 1.0, 4.0, 2.0, 3.0]);
+    /// let q = x.quantiles_with(&[0.25, 0.5, 0.75], QuantileDefinition::Linear);
+    /// assert_eq!(q, [2.0, 3.0, 4.0]);
+    /// ```
+    fn quantiles_with(&mut self, taus: &[f64], definition: QuantileDefinition) -> Vec<T> {
+        taus.iter().map(|&tau| self.quantile_with(tau, definition)).collect()
+    }
+
+    /// Estimates the p-Percentile value from the data with the quantile
+    /// `definition`
+    ///
+    /// # Remarks
+    ///
+    /// Use `quantile_with` for non-integer percentiles. `p` must be between
+    /// `0` and `100` inclusive.
+    /// Returns `f64::NAN` if data is empty, an entry is `f64::NAN` or `p` is
+    /// outside the inclusive range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let y = [1.0, 5.0, 3.0, 4.0, 10.0, 9.0, 6.0, 7.0, 8.0, 2.0];
+    /// let mut y = Data::new(y);
+    /// assert_eq!(y.percentile_with(75, QuantileDefinition::Linear), 7.75);
+    /// assert_eq!(y.percentile_with(75, QuantileDefinition::InvertedCdf), 8.0);
+    /// ```
+    fn percentile_with(&mut self, p: usize, definition: QuantileDefinition) -> T {
+        self.quantile_with(p as f64 / 100.0, definition)
+    }
+
+    /// Estimates the first quartile value from the data with the quantile
+    /// `definition`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let mut y = Data::new([2.0, 1.0, 3.0, 4.0]);
+    /// assert_eq!(y.lower_quartile_with(QuantileDefinition::Linear), 1.75);
+    /// ```
+    fn lower_quartile_with(&mut self, definition: QuantileDefinition) -> T {
+        self.quantile_with(0.25, definition)
+    }
+
+    /// Estimates the third quartile value from the data with the quantile
+    /// `definition`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let mut y = Data::new([2.0, 1.0, 3.0, 4.0]);
+    /// assert_eq!(y.upper_quartile_with(QuantileDefinition::Linear), 3.25);
+    /// ```
+    fn upper_quartile_with(&mut self, definition: QuantileDefinition) -> T {
+        self.quantile_with(0.75, definition)
+    }
+
+    /// Estimates the inter-quartile range from the data with the quantile
+    /// `definition`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, QuantileStatistics, QuantileDefinition};
+    ///
+    /// let mut y = Data::new([2.0, 1.0, 3.0, 4.0]);
+    /// assert_eq!(y.interquartile_range_with(QuantileDefinition::Linear), 1.5);
+    /// assert_eq!(y.interquartile_range_with(QuantileDefinition::Weibull), 2.5);
+    /// ```
+    fn interquartile_range_with(&mut self, definition: QuantileDefinition) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.upper_quartile_with(definition) - self.lower_quartile_with(definition)
+    }
+}
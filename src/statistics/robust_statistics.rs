@@ -0,0 +1,300 @@
+/// The scale factor `1 / Φ^-1(3 / 4)` which makes the median absolute
+/// deviation a consistent estimator of the standard deviation of a normal
+/// distribution
+pub(crate) const MAD_NORMAL_CONSISTENCY: f64 = 1.482602218505602;
+
+/// A location and scale estimate, as returned by the M-estimators of
+/// `RobustStatistics`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MEstimate<T> {
+    /// The estimated location
+    pub location: T,
+    /// The estimated scale
+    pub scale: T,
+}
+
+/// The `RobustStatistics` trait provides estimators of location and scale
+/// that are insensitive to outliers. Like `OrderStatistics`, the algorithms
+/// reorder the data in-place thus requiring a mutable borrow.
+pub trait RobustStatistics<T> {
+    /// Returns the median absolute deviation from the median of the data,
+    /// scaled to be a consistent estimator of the standard deviation of a
+    /// normal distribution
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1.4826 * median(|x_i - median(x)|)
+    /// ```
+    ///
+    /// where `1.4826` is `1 / Φ^-1(3 / 4)`, as the `mad` function of R
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.median_absolute_deviation_unscaled(), 1.0);
+    /// assert_eq!(x.median_absolute_deviation(), 1.482602218505602);
+    /// ```
+    fn median_absolute_deviation(&mut self) -> T;
+
+    /// Returns the median absolute deviation from the median of the data
+    /// without scaling
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// median(|x_i - median(x)|)
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 4.0, 8.0]);
+    /// assert_eq!(x.median_absolute_deviation_unscaled(), 1.5);
+    /// ```
+    fn median_absolute_deviation_unscaled(&mut self) -> T;
+
+    /// Returns the mean of the data after removing the `floor(n *
+    /// proportion)` smallest and largest entries
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty, an entry is `f64::NAN` or
+    /// `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.trimmed_mean(0.0), 22.0);
+    /// assert_eq!(x.trimmed_mean(0.2), 3.0);
+    /// assert!(x.trimmed_mean(0.5).is_nan());
+    /// ```
+    fn trimmed_mean(&mut self, proportion: f64) -> T;
+
+    /// Returns the unbiased sample variance of the data after removing the
+    /// `floor(n * proportion)` smallest and largest entries
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if fewer than two entries remain, an entry is
+    /// `f64::NAN` or `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.trimmed_variance(0.2), 1.0);
+    /// ```
+    fn trimmed_variance(&mut self, proportion: f64) -> T;
+
+    /// Returns the mean of the data after replacing the `floor(n *
+    /// proportion)` smallest and largest entries with the nearest remaining
+    /// ones
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty, an entry is `f64::NAN` or
+    /// `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.winsorized_mean(0.2), 3.0);
+    /// ```
+    fn winsorized_mean(&mut self, proportion: f64) -> T;
+
+    /// Returns the unbiased sample variance of the data after replacing the
+    /// `floor(n * proportion)` smallest and largest entries with the nearest
+    /// remaining ones
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data has fewer than two entries, an entry is
+    /// `f64::NAN` or `proportion` is not in `[0, 0.5)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.winsorized_variance(0.2), 1.0);
+    /// ```
+    fn winsorized_variance(&mut self, proportion: f64) -> T;
+
+    /// Returns the Hodges-Lehmann estimator of location, the median of the
+    /// averages of all pairs of entries
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data is empty or an entry is `f64::NAN`. The
+    /// `n * (n + 1) / 2` pairwise averages are held in memory.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// median((x_i + x_j) / 2 for i <= j)
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert_eq!(x.hodges_lehmann(), 3.0);
+    /// ```
+    fn hodges_lehmann(&mut self) -> T;
+
+    /// Returns the `Qn` estimator of scale of Rousseeuw and Croux (1993),
+    /// scaled to be a consistent estimator of the standard deviation of a
+    /// normal distribution
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data has fewer than two entries or an entry is
+    /// `f64::NAN`. The `n * (n - 1) / 2` pairwise distances are held in
+    /// memory.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 2.2219 * c_n * {|x_i - x_j|; i < j}_(k)
+    /// ```
+    ///
+    /// where `k = h * (h - 1) / 2` with `h = floor(n / 2) + 1`, and `c_n` is
+    /// the small sample correction of Croux and Rousseeuw (1992)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert!((x.qn_scale() - 2.2219 * 0.844).abs() < 1e-12);
+    /// ```
+    fn qn_scale(&mut self) -> T;
+
+    /// Returns the `Sn` estimator of scale of Rousseeuw and Croux (1993),
+    /// scaled to be a consistent estimator of the standard deviation of a
+    /// normal distribution
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if data has fewer than two entries or an entry is
+    /// `f64::NAN`. The `n` distances from each entry are held in memory in
+    /// turn.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// 1.1926 * c_n * lomed_i(himed_j(|x_i - x_j|))
+    /// ```
+    ///
+    /// where `lomed` and `himed` are the low and high medians, the order
+    /// statistics `floor((n + 1) / 2)` and `floor(n / 2) + 1`, and `c_n` is
+    /// the small sample correction of Croux and Rousseeuw (1992)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// assert!((x.sn_scale() - 1.1926 * 1.351 * 2.0).abs() < 1e-12);
+    /// ```
+    fn sn_scale(&mut self) -> T;
+
+    /// Returns Huber's M-estimates of location and scale with the tuning
+    /// constant `k`, Huber's proposal 2
+    ///
+    /// # Remarks
+    ///
+    /// Starting from the median and the scaled median absolute deviation,
+    /// the location is updated as the weighted mean with the weights
+    /// `min(1, k / |z_i|)` and the scale from the mean square of the
+    /// clipped residuals until both change by less than `1e-12` times the
+    /// scale. The estimates are those of `hubers` in R's MASS package.
+    ///
+    /// Returns `f64::NAN` estimates if data has fewer than two entries, an
+    /// entry is `f64::NAN` or `k` is not positive. If the median absolute
+    /// deviation is zero, returns the median and zero scale.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// Σ ψ(z_i) = 0
+    /// Σ ψ(z_i)^2 = (n - 1) * β
+    /// ```
+    ///
+    /// where `z_i = (x_i - μ) / σ`, `ψ(z) = max(-k, min(k, z))` and `β =
+    /// E[ψ(Z)^2]` for a standard normal `Z`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([2.1, 3.4, 1.9, 5.6, 2.8, 3.1, 40.0, 2.5, 3.3, 2.9, -7.0, 3.0]);
+    /// let huber = x.huber(1.5);
+    /// assert!((huber.location - 2.9725865).abs() < 1e-7);
+    /// assert!((huber.scale - 1.1688523).abs() < 1e-7);
+    /// ```
+    fn huber(&mut self, k: f64) -> MEstimate<T>;
+
+    /// Returns Tukey's biweight M-estimate of location and the biweight
+    /// midvariance estimate of scale with the tuning constant `c`
+    ///
+    /// # Remarks
+    ///
+    /// The scale of the residuals is fixed at the scaled median absolute
+    /// deviation `s`. Starting from the median, the location is updated as
+    /// the weighted mean with the weights `(1 - u_i^2)^2`, where
+    /// `u_i = (x_i - μ) / (c * s)`, and zero for `|u_i| >= 1`, until it
+    /// changes by less than `1e-12` times `s`. The usual choice `c = 4.685`
+    /// gives 95% efficiency at the normal distribution.
+    ///
+    /// Returns `f64::NAN` estimates if data is empty, an entry is
+    /// `f64::NAN` or `c` is not positive. If the median absolute deviation
+    /// is zero, returns the median and zero scale.
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// σ = sqrt(n * Σ (x_i - μ)^2 * (1 - u_i^2)^4) / |Σ (1 - u_i^2) * (1 - 5 * u_i^2)|
+    /// ```
+    ///
+    /// where the sums are over `|u_i| < 1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::{Data, RobustStatistics};
+    ///
+    /// let mut x = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// let biweight = x.biweight(4.685);
+    /// assert!((biweight.location - 2.5).abs() < 1e-12);
+    /// ```
+    fn biweight(&mut self, c: f64) -> MEstimate<T>;
+}
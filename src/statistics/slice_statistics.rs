@@ -1,7 +1,14 @@
+use crate::consts;
+use crate::function::erf;
 use crate::statistics::*;
 use core::ops::{Index, IndexMut};
 use rand::prelude::SliceRandom;
 
+/// The maximum number of iterations of the M-estimators
+const MAX_M_ITERATIONS: usize = 1000;
+/// The convergence tolerance of the M-estimators relative to the scale
+const M_TOLERANCE: f64 = 1e-12;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Data<D>(D);
 
@@ -100,6 +107,51 @@ impl<D: AsMut<[f64]> + AsRef<[f64]>> Data<D> {
             }
         }
     }
+    fn has_nan(&self) -> bool {
+        self.iter().any(|x| x.is_nan())
+    }
+
+    /// Returns the smallest and largest entries remaining after removing the
+    /// `floor(n * proportion)` smallest and largest ones, and how many
+    /// entries equal to each remain, or `None` if data is empty, an entry is
+    /// `f64::NAN` or `proportion` is not in `[0, 0.5)`
+    fn trimmed(&mut self, proportion: f64) -> Option<(f64, f64, usize, usize)> {
+        let n = self.len();
+        if n == 0 || !(0.0..0.5).contains(&proportion) || self.has_nan() {
+            return None;
+        }
+        let k = (n as f64 * proportion).floor() as usize;
+        let lower = self.select_inplace(k);
+        let upper = self.select_inplace(n - k - 1);
+        if lower == upper {
+            return Some((lower, upper, n - 2 * k, 0));
+        }
+        let count_lower = self.iter().filter(|&&x| x <= lower).count() - k;
+        let count_upper = self.iter().filter(|&&x| x >= upper).count() - k;
+        Some((lower, upper, count_lower, count_upper))
+    }
+
+    /// Returns the mean and unbiased variance of the entries remaining after
+    /// removing the `floor(n * proportion)` smallest and largest ones
+    fn trimmed_moments(&mut self, proportion: f64) -> (f64, f64) {
+        let (lower, upper, count_lower, count_upper) = match self.trimmed(proportion) {
+            Some(bounds) => bounds,
+            None => return (f64::NAN, f64::NAN),
+        };
+        let middle = self.iter().filter(|&&x| x > lower && x < upper);
+        let count = (middle.clone().count() + count_lower + count_upper) as f64;
+        let mean =
+            (middle.clone().sum::<f64>() + count_lower as f64 * lower + count_upper as f64 * upper)
+                / count;
+        let sum_squares = middle.map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            + count_lower as f64 * (lower - mean) * (lower - mean)
+            + count_upper as f64 * (upper - mean) * (upper - mean);
+        if count > 1.0 {
+            (mean, sum_squares / (count - 1.0))
+        } else {
+            (mean, f64::NAN)
+        }
+    }
 }
 
 impl<D: AsRef<[f64]>> ::rand::distributions::Distribution<f64> for Data<D> {
@@ -205,6 +257,44 @@ impl<D: AsMut<[f64]> + AsRef<[f64]>> OrderStatistics<f64> for Data<D> {
     }
 }
 
+impl<D: AsMut<[f64]> + AsRef<[f64]>> QuantileStatistics<f64> for Data<D> {
+    fn quantile_with(&mut self, tau: f64, definition: QuantileDefinition) -> f64 {
+        self.quantiles_with(&[tau], definition)[0]
+    }
+
+    fn quantiles_with(&mut self, taus: &[f64], definition: QuantileDefinition) -> Vec<f64> {
+        if self.has_nan() {
+            return vec![f64::NAN; taus.len()];
+        }
+        let n = self.len();
+        let indices: Vec<_> = taus
+            .iter()
+            .map(|&tau| {
+                if (0.0..=1.0).contains(&tau) && n > 0 {
+                    Some(quantile_indices(n, tau, definition))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut ranks: Vec<usize> = indices
+            .iter()
+            .flatten()
+            .flat_map(|&(lower, upper, _)| vec![lower, upper])
+            .collect();
+        ranks.sort_unstable();
+        ranks.dedup();
+        select_many(self.0.as_mut(), &ranks, 0);
+        indices
+            .into_iter()
+            .map(|index| match index {
+                Some((lower, upper, gamma)) => interpolate(self[lower], self[upper], gamma),
+                None => f64::NAN,
+            })
+            .collect()
+    }
+}
+
 impl<D: AsMut<[f64]> + AsRef<[f64]>> Min<f64> for Data<D> {
     /// Returns the minimum value in the data
     ///
@@ -358,6 +448,244 @@ impl<D: AsMut<[f64]> + AsRef<[f64]> + Clone> Median<f64> for Data<D> {
     }
 }
 
+impl<D: AsMut<[f64]> + AsRef<[f64]>> RobustStatistics<f64> for Data<D> {
+    fn median_absolute_deviation(&mut self) -> f64 {
+        MAD_NORMAL_CONSISTENCY * self.median_absolute_deviation_unscaled()
+    }
+
+    fn median_absolute_deviation_unscaled(&mut self) -> f64 {
+        if self.has_nan() {
+            return f64::NAN;
+        }
+        let median = OrderStatistics::median(self);
+        let mut deviations: Vec<_> = self.iter().map(|x| (x - median).abs()).collect();
+        OrderStatistics::median(&mut Data::new(deviations.as_mut_slice()))
+    }
+
+    fn trimmed_mean(&mut self, proportion: f64) -> f64 {
+        self.trimmed_moments(proportion).0
+    }
+
+    fn trimmed_variance(&mut self, proportion: f64) -> f64 {
+        self.trimmed_moments(proportion).1
+    }
+
+    fn winsorized_mean(&mut self, proportion: f64) -> f64 {
+        match self.trimmed(proportion) {
+            Some((lower, upper, ..)) => self.iter().map(|x| x.clamp(lower, upper)).mean(),
+            None => f64::NAN,
+        }
+    }
+
+    fn winsorized_variance(&mut self, proportion: f64) -> f64 {
+        match self.trimmed(proportion) {
+            Some((lower, upper, ..)) => self.iter().map(|x| x.clamp(lower, upper)).variance(),
+            None => f64::NAN,
+        }
+    }
+
+    fn hodges_lehmann(&mut self) -> f64 {
+        if self.has_nan() {
+            return f64::NAN;
+        }
+        let data = self.0.as_ref();
+        let mut averages: Vec<_> = data
+            .iter()
+            .enumerate()
+            .flat_map(|(i, x)| data[i..].iter().map(move |y| (x + y) / 2.0))
+            .collect();
+        OrderStatistics::median(&mut Data::new(averages.as_mut_slice()))
+    }
+
+    fn qn_scale(&mut self) -> f64 {
+        let n = self.len();
+        if n < 2 || self.has_nan() {
+            return f64::NAN;
+        }
+        let data = self.0.as_ref();
+        let mut distances: Vec<_> = data
+            .iter()
+            .enumerate()
+            .flat_map(|(i, x)| data[i + 1..].iter().map(move |y| (x - y).abs()))
+            .collect();
+        let h = n / 2 + 1;
+        let k = h * (h - 1) / 2;
+        let correction = match n {
+            2 => 0.399,
+            3 => 0.994,
+            4 => 0.512,
+            5 => 0.844,
+            6 => 0.611,
+            7 => 0.857,
+            8 => 0.669,
+            9 => 0.872,
+            _ if n % 2 == 1 => n as f64 / (n as f64 + 1.4),
+            _ => n as f64 / (n as f64 + 3.8),
+        };
+        2.2219 * correction * Data::new(distances.as_mut_slice()).select_inplace(k - 1)
+    }
+
+    fn sn_scale(&mut self) -> f64 {
+        let n = self.len();
+        if n < 2 || self.has_nan() {
+            return f64::NAN;
+        }
+        let data = self.0.as_ref();
+        let mut distances = vec![0.0; n];
+        let mut inner: Vec<_> = data
+            .iter()
+            .map(|x| {
+                for (distance, y) in distances.iter_mut().zip(data) {
+                    *distance = (x - y).abs();
+                }
+                Data::new(distances.as_mut_slice()).select_inplace(n / 2)
+            })
+            .collect();
+        let correction = match n {
+            2 => 0.743,
+            3 => 1.851,
+            4 => 0.954,
+            5 => 1.351,
+            6 => 0.993,
+            7 => 1.198,
+            8 => 1.005,
+            9 => 1.131,
+            _ if n % 2 == 1 => n as f64 / (n as f64 - 0.9),
+            _ => 1.0,
+        };
+        1.1926 * correction * Data::new(inner.as_mut_slice()).select_inplace((n - 1) / 2)
+    }
+
+    fn huber(&mut self, k: f64) -> MEstimate<f64> {
+        let n = self.len();
+        if n < 2 || k.is_nan() || k <= 0.0 {
+            return MEstimate {
+                location: f64::NAN,
+                scale: f64::NAN,
+            };
+        }
+        let mut scale = self.median_absolute_deviation();
+        let mut location = OrderStatistics::median(self);
+        if scale.is_nan() || scale == 0.0 {
+            return MEstimate { location, scale };
+        }
+
+        // E[ψ(Z)^2] for a standard normal Z
+        let theta = 1.0 - erf::erfc(k / std::f64::consts::SQRT_2);
+        let density = (-k * k / 2.0).exp() / consts::SQRT_2PI;
+        let beta = theta + k * k * (1.0 - theta) - 2.0 * k * density;
+
+        for _ in 0..MAX_M_ITERATIONS {
+            let (mut sum_w, mut sum_wx, mut sum_psi_2) = (0.0, 0.0, 0.0);
+            for x in self.iter() {
+                let z = (x - location) / scale;
+                let psi = z.clamp(-k, k);
+                let w = if z == 0.0 { 1.0 } else { psi / z };
+                sum_w += w;
+                sum_wx += w * x;
+                sum_psi_2 += psi * psi;
+            }
+            let next_location = sum_wx / sum_w;
+            let next_scale = scale * (sum_psi_2 / ((n - 1) as f64 * beta)).sqrt();
+            let converged = (next_location - location).abs() < M_TOLERANCE * scale
+                && (next_scale - scale).abs() < M_TOLERANCE * scale;
+            location = next_location;
+            scale = next_scale;
+            if converged {
+                break;
+            }
+        }
+        MEstimate { location, scale }
+    }
+
+    fn biweight(&mut self, c: f64) -> MEstimate<f64> {
+        if self.is_empty() || c.is_nan() || c <= 0.0 {
+            return MEstimate {
+                location: f64::NAN,
+                scale: f64::NAN,
+            };
+        }
+        let mad = self.median_absolute_deviation();
+        let mut location = OrderStatistics::median(self);
+        if mad.is_nan() || mad == 0.0 {
+            return MEstimate {
+                location,
+                scale: mad,
+            };
+        }
+
+        let u = |x: f64, location: f64| (x - location) / (c * mad);
+        for _ in 0..MAX_M_ITERATIONS {
+            let (mut sum_w, mut sum_wx) = (0.0, 0.0);
+            for &x in self.iter() {
+                let u = u(x, location);
+                if u.abs() < 1.0 {
+                    let w = (1.0 - u * u) * (1.0 - u * u);
+                    sum_w += w;
+                    sum_wx += w * x;
+                }
+            }
+            let next_location = sum_wx / sum_w;
+            let converged = (next_location - location).abs() < M_TOLERANCE * mad;
+            location = next_location;
+            if converged {
+                break;
+            }
+        }
+
+        let (mut numerator, mut denominator) = (0.0, 0.0);
+        for &x in self.iter() {
+            let u = u(x, location);
+            if u.abs() < 1.0 {
+                let v = 1.0 - u * u;
+                numerator += (x - location) * (x - location) * v.powi(4);
+                denominator += v * (1.0 - 5.0 * u * u);
+            }
+        }
+        let scale = (self.len() as f64 * numerator).sqrt() / denominator.abs();
+        MEstimate { location, scale }
+    }
+}
+
+/// Returns the zero-based indices of the order statistics of `n`
+/// observations between which the `tau` quantile interpolates, and the
+/// weight of the upper one
+fn quantile_indices(n: usize, tau: f64, definition: QuantileDefinition) -> (usize, usize, f64) {
+    let (j, gamma) = definition.position(tau, n);
+    let last = n as i64 - 1;
+    (
+        (j - 1).clamp(0, last) as usize,
+        j.clamp(0, last) as usize,
+        gamma,
+    )
+}
+
+/// Returns `(1 - gamma) * a + gamma * b`, which is `a` or `b` exactly if
+/// `gamma` is `0` or `1` or they are equal
+fn interpolate(a: f64, b: f64, gamma: f64) -> f64 {
+    if gamma == 0.0 || a == b {
+        a
+    } else if gamma == 1.0 {
+        b
+    } else {
+        (1.0 - gamma) * a + gamma * b
+    }
+}
+
+/// Partially sorts `data` so that the element at each of the ascending
+/// `ranks`, offset by `offset`, is the one at that position in sorted order
+fn select_many(data: &mut [f64], ranks: &[usize], offset: usize) {
+    if ranks.is_empty() {
+        return;
+    }
+    let mid = ranks.len() / 2;
+    let rank = ranks[mid] - offset;
+    data.select_nth_unstable_by(rank, |a, b| a.total_cmp(b));
+    let (lower, upper) = data.split_at_mut(rank);
+    select_many(lower, &ranks[..mid], offset);
+    select_many(&mut upper[1..], &ranks[mid + 1..], offset + rank + 1);
+}
+
 fn handle_rank_ties(
     ranks: &mut [f64],
     index: &[(usize, &f64)],
@@ -411,6 +739,175 @@ mod tests {
         assert_almost_eq!(data.quantile(0.325), -37.0 / 240.0, 1e-15);
     }
 
+    #[test]
+    fn test_quantile_definitions() {
+        let data = [0.5, 3.2, -1.0, 7.7, 2.2, 2.2, 10.0, 4.1];
+        let taus = [0.0, 0.1, 0.25, 0.5, 0.6, 0.75, 0.9, 1.0];
+        let expected: [(QuantileDefinition, [f64; 8]); 10] = [
+            (
+                QuantileDefinition::InvertedCdf,
+                [-1.0, -1.0, 0.5, 2.2, 3.2, 4.1, 10.0, 10.0],
+            ),
+            (
+                QuantileDefinition::AveragedInvertedCdf,
+                [-1.0, -1.0, 1.35, 2.7, 3.2, 5.9, 10.0, 10.0],
+            ),
+            (
+                QuantileDefinition::ClosestObservation,
+                [-1.0, -1.0, 0.5, 2.2, 3.2, 4.1, 7.7, 10.0],
+            ),
+            (
+                QuantileDefinition::InterpolatedInvertedCdf,
+                [-1.0, -1.0, 0.5, 2.2, 3.0, 4.1, 8.16, 10.0],
+            ),
+            (
+                QuantileDefinition::Hazen,
+                [-1.0, -0.55, 1.35, 2.7, 3.47, 5.9, 9.31, 10.0],
+            ),
+            (
+                QuantileDefinition::Weibull,
+                [-1.0, -1.0, 0.925, 2.7, 3.56, 6.8, 10.0, 10.0],
+            ),
+            (
+                QuantileDefinition::Linear,
+                [-1.0, 0.05, 1.775, 2.7, 3.38, 5.0, 8.39, 10.0],
+            ),
+            (
+                QuantileDefinition::MedianUnbiased,
+                [
+                    -1.0,
+                    -0.75,
+                    1.2083333333333333,
+                    2.7,
+                    3.5,
+                    6.2,
+                    9.616666666666667,
+                    10.0,
+                ],
+            ),
+            (
+                QuantileDefinition::NormalUnbiased,
+                [-1.0, -0.7, 1.24375, 2.7, 3.4925, 6.125, 9.54, 10.0],
+            ),
+            (
+                QuantileDefinition::NearestRank,
+                [-1.0, 0.5, 2.2, 3.2, 3.2, 4.1, 7.7, 10.0],
+            ),
+        ];
+        for (definition, values) in expected.iter() {
+            for (&tau, &value) in taus.iter().zip(values.iter()) {
+                let mut x = Data::new(data);
+                assert_almost_eq!(x.quantile_with(tau, *definition), value, 1e-14);
+            }
+            let mut x = Data::new(data);
+            let all = x.quantiles_with(&taus, *definition);
+            for (&q, &value) in all.iter().zip(values.iter()) {
+                assert_almost_eq!(q, value, 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantile_with_edge_cases() {
+        let mut empty = Data::new([]);
+        assert!(empty
+            .quantile_with(0.5, QuantileDefinition::Linear)
+            .is_nan());
+        assert!(empty.quantiles(&[0.5])[0].is_nan());
+        let mut single = Data::new([3.0]);
+        for &tau in &[0.0, 0.3, 0.5, 1.0] {
+            assert_eq!(
+                single.quantile_with(tau, QuantileDefinition::InvertedCdf),
+                3.0
+            );
+            assert_eq!(
+                single.quantile_with(tau, QuantileDefinition::ClosestObservation),
+                3.0
+            );
+            assert_eq!(single.quantile_with(tau, QuantileDefinition::Hazen), 3.0);
+            assert_eq!(
+                single.quantile_with(tau, QuantileDefinition::NearestRank),
+                3.0
+            );
+        }
+        let mut data = Data::new([1.0, f64::INFINITY, 2.0, f64::INFINITY]);
+        assert_eq!(
+            data.quantile_with(0.9, QuantileDefinition::Linear),
+            f64::INFINITY
+        );
+        assert!(data
+            .quantile_with(f64::NAN, QuantileDefinition::Linear)
+            .is_nan());
+        assert!(data
+            .percentile_with(101, QuantileDefinition::Linear)
+            .is_nan());
+        let q = data.quantiles_with(&[-0.5, 0.0, 1.5], QuantileDefinition::Linear);
+        assert!(q[0].is_nan() && q[2].is_nan());
+        assert_eq!(q[1], 1.0);
+    }
+
+    #[test]
+    fn test_quantile_with_nan() {
+        let data = [f64::NAN, 1.0, 2.0, 3.0];
+        for &tau in &[0.0, 0.5, 1.0] {
+            assert!(Data::new(data)
+                .quantile_with(tau, QuantileDefinition::Linear)
+                .is_nan());
+        }
+        let q = Data::new(data).quantiles_with(&[0.0, 0.5], QuantileDefinition::Linear);
+        assert!(q.iter().all(|x| x.is_nan()));
+        assert!(Data::new(data)
+            .interquartile_range_with(QuantileDefinition::Linear)
+            .is_nan());
+    }
+
+    #[test]
+    fn test_quantiles_match_quantile() {
+        let data: Vec<f64> = (0..1000)
+            .map(|i| ((i * 7919) % 1000) as f64 / 7.0)
+            .collect();
+        let taus: Vec<f64> = (0..=40).map(|i| i as f64 / 40.0).rev().collect();
+        let all = Data::new(data.clone()).quantiles(&taus);
+        for (&tau, &q) in taus.iter().zip(all.iter()) {
+            assert_almost_eq!(q, Data::new(data.clone()).quantile(tau), 1e-12);
+        }
+        let all =
+            Data::new(data.clone()).quantiles_with(&taus, QuantileDefinition::AveragedInvertedCdf);
+        for (&tau, &q) in taus.iter().zip(all.iter()) {
+            let mut x = Data::new(data.clone());
+            assert_eq!(
+                q,
+                x.quantile_with(tau, QuantileDefinition::AveragedInvertedCdf)
+            );
+        }
+    }
+
+    #[test]
+    fn test_quartiles_with() {
+        let mut data = Data::new([-1.0, 5.0, 0.0, -3.0, 10.0, -0.5, 4.0, 0.2, 1.0, 6.0]);
+        assert_almost_eq!(
+            data.lower_quartile_with(QuantileDefinition::Linear),
+            -0.375,
+            1e-15
+        );
+        assert_almost_eq!(
+            data.upper_quartile_with(QuantileDefinition::Linear),
+            4.75,
+            1e-15
+        );
+        assert_almost_eq!(
+            data.interquartile_range_with(QuantileDefinition::Linear),
+            5.125,
+            1e-15
+        );
+        assert_eq!(data.percentile_with(50, QuantileDefinition::Linear), 0.6);
+        assert_almost_eq!(
+            data.interquartile_range_with(QuantileDefinition::MedianUnbiased),
+            data.interquartile_range(),
+            1e-14
+        );
+    }
+
     #[test]
     fn test_ranks() {
         let sorted_distinct = [1.0, 2.0, 4.0, 7.0, 8.0, 9.0, 10.0, 12.0];
@@ -488,6 +985,94 @@ mod tests {
         );
     }
 
+    const OUTLIERS: [f64; 12] = [2.1, 3.4, 1.9, 5.6, 2.8, 3.1, 40.0, 2.5, 3.3, 2.9, -7.0, 3.0];
+
+    #[test]
+    fn test_median_absolute_deviation() {
+        let mut data = Data::new(OUTLIERS);
+        assert_almost_eq!(data.median_absolute_deviation_unscaled(), 0.45, 1e-15);
+        assert_almost_eq!(data.median_absolute_deviation(), 0.6671709983275208, 1e-15);
+        assert!(Data::new([]).median_absolute_deviation().is_nan());
+        assert!(Data::new([1.0, f64::NAN])
+            .median_absolute_deviation()
+            .is_nan());
+        assert_eq!(Data::new([2.0, 2.0, 5.0]).median_absolute_deviation(), 0.0);
+    }
+
+    #[test]
+    fn test_trimmed_and_winsorized() {
+        let mut data = Data::new(OUTLIERS);
+        assert_almost_eq!(data.trimmed_mean(0.1), 3.06, 1e-14);
+        assert_almost_eq!(data.trimmed_variance(0.1), 1.0337777777777777, 1e-14);
+        assert_almost_eq!(data.trimmed_mean(0.25), 2.933333333333333, 1e-14);
+        assert_almost_eq!(data.trimmed_variance(0.25), 0.07466666666666666, 1e-14);
+        assert_almost_eq!(data.winsorized_mean(0.1), 3.175, 1e-14);
+        assert_almost_eq!(data.winsorized_variance(0.1), 1.5402272727272723, 1e-14);
+        assert_almost_eq!(data.winsorized_mean(0.25), 2.9166666666666665, 1e-14);
+        assert_almost_eq!(data.winsorized_variance(0.25), 0.12151515151515145, 1e-14);
+        assert_almost_eq!(data.trimmed_mean(0.0), OUTLIERS.mean(), 1e-14);
+        assert_almost_eq!(data.winsorized_variance(0.0), OUTLIERS.variance(), 1e-13);
+        assert!(data.trimmed_mean(-0.1).is_nan());
+        assert!(data.winsorized_mean(0.5).is_nan());
+
+        let mut ties = Data::new([1.0, 2.0, 2.0, 2.0, 2.0, 3.0, 9.0]);
+        assert_almost_eq!(ties.trimmed_mean(0.2), 2.2, 1e-15);
+        assert_eq!(ties.trimmed_mean(0.3), 2.0);
+        assert_eq!(ties.trimmed_variance(0.3), 0.0);
+        assert_almost_eq!(ties.trimmed_mean(0.0), 3.0, 1e-15);
+        assert!(Data::new([1.0, 2.0, 3.0]).trimmed_variance(0.4).is_nan());
+    }
+
+    #[test]
+    fn test_hodges_lehmann() {
+        assert_eq!(Data::new(OUTLIERS).hodges_lehmann(), 2.95);
+        assert_eq!(Data::new([5.0]).hodges_lehmann(), 5.0);
+        assert!(Data::new([]).hodges_lehmann().is_nan());
+    }
+
+    #[test]
+    fn test_qn_sn_scale() {
+        let mut data = Data::new(OUTLIERS);
+        assert_almost_eq!(data.qn_scale(), 1.0125113924050635, 1e-14);
+        assert_almost_eq!(data.sn_scale(), 0.95408, 1e-14);
+        let mut data = Data::new([2.1, 3.4, 1.9, 5.6, 2.8, 3.1, 40.0]);
+        assert_almost_eq!(data.qn_scale(), 1.71375147, 1e-14);
+        assert_almost_eq!(data.sn_scale(), 1.71448176, 1e-14);
+        assert!(Data::new([1.0]).qn_scale().is_nan());
+        assert!(Data::new([1.0]).sn_scale().is_nan());
+    }
+
+    #[test]
+    fn test_huber() {
+        let huber = Data::new(OUTLIERS).huber(1.5);
+        assert_almost_eq!(huber.location, 2.972586496913166, 1e-10);
+        assert_almost_eq!(huber.scale, 1.1688523148123486, 1e-10);
+        let huber = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]).huber(1.5);
+        assert_almost_eq!(huber.location, 4.027470541352259, 1e-9);
+        assert_almost_eq!(huber.scale, 4.073254776939358, 1e-9);
+        let huber = Data::new([2.0, 2.0, 2.0, 7.0]).huber(1.5);
+        assert_eq!(
+            huber,
+            MEstimate {
+                location: 2.0,
+                scale: 0.0
+            }
+        );
+        assert!(Data::new([1.0]).huber(1.5).location.is_nan());
+        assert!(Data::new(OUTLIERS).huber(0.0).scale.is_nan());
+    }
+
+    #[test]
+    fn test_biweight() {
+        let biweight = Data::new(OUTLIERS).biweight(4.685);
+        assert_almost_eq!(biweight.location, 2.8070353177441905, 1e-10);
+        assert_almost_eq!(biweight.scale, 0.6398211814492039, 1e-10);
+        let biweight = Data::new([1.0, 2.0, 3.0, 4.0, 100.0]).biweight(4.685);
+        assert_almost_eq!(biweight.location, 2.5, 1e-10);
+        assert_almost_eq!(biweight.scale, 1.348886154260532, 1e-10);
+        assert!(Data::new([]).biweight(4.685).location.is_nan());
+    }
+
     #[test]
     fn test_median_short() {
         let even = [-1.0, 5.0, 0.0, -3.0, 10.0, -0.5, 4.0, 0.2, 1.0, 6.0];
@@ -1,3 +1,5 @@
+use std::borrow::Borrow;
+
 /// Enumeration of possible tie-breaking strategies
 /// when computing ranks
 #[derive(Debug, Copy, Clone)]
@@ -424,4 +426,117 @@ pub trait Statistics<T> {
     /// # }
     /// ```
     fn quadratic_mean(self) -> T;
+
+    /// Estimates the skewness of the data
+    ///
+    /// # Remarks
+    ///
+    /// This is the moment estimator `g1 = m3 / m2^(3/2)`, where `m2` and
+    /// `m3` are the second and third central moments of the data with `N`
+    /// used as a normalizer. It is biased for small samples.
+    ///
+    /// Returns `f64::NAN` if data is empty, any entry is `f64::NAN`, or all
+    /// entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use]
+    /// extern crate statrs;
+    ///
+    /// use std::f64;
+    /// use statrs::statistics::Statistics;
+    ///
+    /// # fn main() {
+    /// let x = &[];
+    /// assert!(x.skewness().is_nan());
+    ///
+    /// let y = &[0.0, f64::NAN, 3.0, -2.0];
+    /// assert!(y.skewness().is_nan());
+    ///
+    /// let z = &[1.0, 2.0, 3.0, 10.0];
+    /// assert_almost_eq!(z.skewness(), 45.0 / 12.5f64.powf(1.5), 1e-14);
+    /// # }
+    /// ```
+    fn skewness(self) -> T
+    where
+        Self: Sized + IntoIterator,
+        Self::Item: Borrow<f64>,
+        T: From<f64>,
+    {
+        let (n, m2, m3, _) = central_moment_sums(self);
+        T::from(n.sqrt() * m3 / (m2 * m2.sqrt()))
+    }
+
+    /// Estimates the excess kurtosis of the data
+    ///
+    /// # Remarks
+    ///
+    /// This is the moment estimator `g2 = m4 / m2^2 - 3`, where `m2` and
+    /// `m4` are the second and fourth central moments of the data with `N`
+    /// used as a normalizer. It is biased for small samples.
+    ///
+    /// Returns `f64::NAN` if data is empty, any entry is `f64::NAN`, or all
+    /// entries are equal
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[macro_use]
+    /// extern crate statrs;
+    ///
+    /// use std::f64;
+    /// use statrs::statistics::Statistics;
+    ///
+    /// # fn main() {
+    /// let x = &[];
+    /// assert!(x.kurtosis().is_nan());
+    ///
+    /// let y = &[0.0, f64::NAN, 3.0, -2.0];
+    /// assert!(y.kurtosis().is_nan());
+    ///
+    /// let z = &[1.0, 2.0, 3.0, 10.0];
+    /// assert_almost_eq!(z.kurtosis(), 348.5 / 156.25 - 3.0, 1e-14);
+    /// # }
+    /// ```
+    fn kurtosis(self) -> T
+    where
+        Self: Sized + IntoIterator,
+        Self::Item: Borrow<f64>,
+        T: From<f64>,
+    {
+        let (n, m2, _, m4) = central_moment_sums(self);
+        T::from(n * m4 / (m2 * m2) - 3.0)
+    }
+}
+
+/// Returns the number of entries and the sums of the second, third and
+/// fourth powers of their deviations from the mean, accumulated in a single
+/// pass with the update formulas of Terriberry (2007). The sums are `NaN`
+/// if the data is empty.
+fn central_moment_sums<T>(data: T) -> (f64, f64, f64, f64)
+where
+    T: IntoIterator,
+    T::Item: Borrow<f64>,
+{
+    let mut n = 0.0;
+    let mut mean = 0.0;
+    let (mut m2, mut m3, mut m4) = (0.0, 0.0, 0.0);
+    for x in data {
+        let n1 = n;
+        n += 1.0;
+        let delta = *x.borrow() - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * n1;
+        mean += delta_n;
+        m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * m2 - 4.0 * delta_n * m3;
+        m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * m2;
+        m2 += term;
+    }
+    if n > 0.0 {
+        (n, m2, m3, m4)
+    } else {
+        (n, f64::NAN, f64::NAN, f64::NAN)
+    }
 }
@@ -0,0 +1,627 @@
+use crate::statistics::{Data, OrderStatistics};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A cluster of samples summarized by their mean and total weight
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Estimates quantiles of a stream of samples with a
+/// [t-digest](https://arxiv.org/abs/1902.04023)
+///
+/// # Remarks
+///
+/// The digest clusters the samples into centroids whose sizes are bounded by
+/// the `k1` scale function, so that the centroids are small near the tails
+/// and the extreme quantiles are estimated accurately. The number of
+/// centroids is bounded by the `compression` parameter, typically `100`,
+/// independently of the number of samples. Digests of disjoint streams can
+/// be merged.
+///
+/// The estimates interpolate linearly between the centroids and the exact
+/// minimum and maximum. While every centroid holds a single sample, they are
+/// the quantiles `x_(h)` of the sorted samples at `h = n * tau + 1 / 2`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::TDigest;
+///
+/// let mut digest = TDigest::new(100.0).unwrap();
+/// for i in 1..=10 {
+///     digest.push(i as f64);
+/// }
+/// assert_eq!(digest.median(), 5.5);
+/// assert_eq!(digest.quantile(0.0), 1.0);
+/// assert_eq!(digest.percentile(100), 10.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<Centroid>,
+    count: u64,
+    min: f64,
+    max: f64,
+    has_nan: bool,
+}
+
+impl TDigest {
+    /// Constructs an empty t-digest with the `compression` parameter, which
+    /// bounds the number of centroids and trades memory for accuracy
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `compression` is not finite or is less than `1`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::TDigest;
+    ///
+    /// assert!(TDigest::new(100.0).is_ok());
+    /// assert!(TDigest::new(0.5).is_err());
+    /// ```
+    pub fn new(compression: f64) -> Result<TDigest> {
+        if compression >= 1.0 && compression.is_finite() {
+            Ok(TDigest {
+                compression,
+                centroids: Vec::new(),
+                buffer: Vec::new(),
+                count: 0,
+                min: f64::INFINITY,
+                max: f64::NEG_INFINITY,
+                has_nan: false,
+            })
+        } else {
+            Err(StatsError::ArgGte("compression", 1.0))
+        }
+    }
+
+    /// Returns the compression parameter of the digest
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Returns the number of samples
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the exact minimum of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn min(&self) -> f64 {
+        if self.count == 0 || self.has_nan {
+            f64::NAN
+        } else {
+            self.min
+        }
+    }
+
+    /// Returns the exact maximum of the samples
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn max(&self) -> f64 {
+        if self.count == 0 || self.has_nan {
+            f64::NAN
+        } else {
+            self.max
+        }
+    }
+
+    /// Adds the sample `x`
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        if x.is_nan() {
+            self.has_nan = true;
+            return;
+        }
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        self.buffer.push(Centroid {
+            mean: x,
+            weight: 1.0,
+        });
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+    }
+
+    /// Merges the samples summarized by `other` into `self`, so that the
+    /// digest summarizes the union of both sets of samples
+    pub fn merge(&mut self, other: &TDigest) {
+        self.count += other.count;
+        self.has_nan |= other.has_nan;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.buffer.extend_from_slice(&other.centroids);
+        self.buffer.extend_from_slice(&other.buffer);
+        self.compress();
+    }
+
+    fn buffer_capacity(&self) -> usize {
+        (5.0 * self.compression).ceil() as usize
+    }
+
+    /// Returns the value of the `k1` scale function at the quantile `q`
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / (2.0 * f64::consts::PI) * (2.0 * q - 1.0).asin()
+    }
+
+    /// Returns the quantile at which the `k1` scale function equals `k`
+    fn scale_inverse(&self, k: f64) -> f64 {
+        let x = 2.0 * f64::consts::PI * k / self.compression;
+        if x >= f64::consts::FRAC_PI_2 {
+            1.0
+        } else {
+            (x.sin() + 1.0) / 2.0
+        }
+    }
+
+    /// Merges the buffered samples into the centroids
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut sorted = std::mem::take(&mut self.buffer);
+        sorted.extend_from_slice(&self.centroids);
+        sorted.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+        let total: f64 = sorted.iter().map(|c| c.weight).sum();
+
+        let mut centroids = Vec::with_capacity(self.centroids.len() + 1);
+        let mut weight_so_far = 0.0;
+        let mut q_limit = self.scale_inverse(self.scale(0.0) + 1.0);
+        let mut current = sorted[0];
+        for c in sorted.into_iter().skip(1) {
+            let weight = current.weight + c.weight;
+            if (weight_so_far + weight) / total <= q_limit {
+                current.mean += (c.mean - current.mean) * c.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                centroids.push(current);
+                q_limit = self.scale_inverse(self.scale(weight_so_far / total) + 1.0);
+                current = c;
+            }
+        }
+        centroids.push(current);
+        self.centroids = centroids;
+        self.buffer.reserve(self.buffer_capacity());
+    }
+
+    /// Returns the points `(x, w)` of the piecewise linear interpolation of
+    /// the cumulative weight `w` of the samples up to `x`
+    fn knots(&self) -> Vec<(f64, f64)> {
+        let mut knots = Vec::with_capacity(self.centroids.len() + 2);
+        knots.push((self.min, 0.0));
+        let mut cumulative = 0.0;
+        for c in &self.centroids {
+            knots.push((c.mean, cumulative + c.weight / 2.0));
+            cumulative += c.weight;
+        }
+        knots.push((self.max, cumulative));
+        knots
+    }
+
+    /// Estimates the `tau` quantile of the samples
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or `tau` is outside `[0, 1]`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::TDigest;
+    ///
+    /// let mut digest = TDigest::new(100.0).unwrap();
+    /// for i in 0..100_000 {
+    ///     digest.push(i as f64);
+    /// }
+    /// assert!((digest.quantile(0.999) - 99_900.0).abs() < 10.0);
+    /// assert!((digest.quantile(0.5) - 50_000.0).abs() < 500.0);
+    /// ```
+    pub fn quantile(&mut self, tau: f64) -> f64 {
+        if !(0.0..=1.0).contains(&tau) || self.count == 0 || self.has_nan {
+            return f64::NAN;
+        }
+        self.compress();
+        let knots = self.knots();
+        let target = tau * knots[knots.len() - 1].1;
+        for pair in knots.windows(2) {
+            let ((x0, w0), (x1, w1)) = (pair[0], pair[1]);
+            if target <= w1 {
+                return if w1 > w0 {
+                    x0 + (x1 - x0) * (target - w0) / (w1 - w0)
+                } else {
+                    x0
+                };
+            }
+        }
+        self.max
+    }
+
+    /// Estimates the median of the samples
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn median(&mut self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// Estimates the `p`-percentile of the samples
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there are no samples, any sample is `f64::NAN`,
+    /// or `p` is greater than `100`
+    pub fn percentile(&mut self, p: usize) -> f64 {
+        self.quantile(p as f64 / 100.0)
+    }
+
+    /// Estimates the fraction of the samples that are at most `x`
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    pub fn cdf(&mut self, x: f64) -> f64 {
+        if self.count == 0 || self.has_nan {
+            return f64::NAN;
+        }
+        if x < self.min {
+            return 0.0;
+        }
+        if x >= self.max {
+            return 1.0;
+        }
+        self.compress();
+        let knots = self.knots();
+        let total = knots[knots.len() - 1].1;
+        for pair in knots.windows(2) {
+            let ((x0, w0), (x1, w1)) = (pair[0], pair[1]);
+            if x < x1 {
+                return (w0 + (w1 - w0) * (x - x0) / (x1 - x0)) / total;
+            }
+        }
+        1.0
+    }
+}
+
+impl Extend<f64> for TDigest {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+/// Estimates a single quantile of a stream of samples with the
+/// [P² algorithm](https://doi.org/10.1145/4372.4378) of Jain and Chlamtac
+///
+/// # Remarks
+///
+/// The estimator keeps five markers whose heights approximate the minimum,
+/// the `tau / 2`, `tau` and `(1 + tau) / 2` quantiles and the maximum, and
+/// adjusts them with piecewise-parabolic interpolation as samples arrive.
+/// It uses constant memory and time per sample, but cannot be merged and
+/// estimates only the quantile it was constructed for.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::P2Quantile;
+///
+/// let mut p2 = P2Quantile::new_median();
+/// for i in 0..10_000 {
+///     p2.push((i * 7919 % 10_000) as f64);
+/// }
+/// assert!((p2.quantile() - 5_000.0).abs() < 50.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct P2Quantile {
+    tau: f64,
+    heights: [f64; 5],
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+    count: u64,
+    has_nan: bool,
+}
+
+impl P2Quantile {
+    /// Constructs an estimator of the `tau` quantile
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `tau` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::P2Quantile;
+    ///
+    /// assert!(P2Quantile::new(0.99).is_ok());
+    /// assert!(P2Quantile::new(1.0).is_err());
+    /// ```
+    pub fn new(tau: f64) -> Result<P2Quantile> {
+        if tau > 0.0 && tau < 1.0 {
+            Ok(P2Quantile {
+                tau,
+                heights: [0.0; 5],
+                positions: [0.0, 1.0, 2.0, 3.0, 4.0],
+                desired: [0.0, 2.0 * tau, 4.0 * tau, 2.0 + 2.0 * tau, 4.0],
+                increments: [0.0, tau / 2.0, tau, (1.0 + tau) / 2.0, 1.0],
+                count: 0,
+                has_nan: false,
+            })
+        } else {
+            Err(StatsError::ArgIntervalExcl("tau", 0.0, 1.0))
+        }
+    }
+
+    /// Constructs an estimator of the median
+    pub fn new_median() -> P2Quantile {
+        P2Quantile::new(0.5).unwrap()
+    }
+
+    /// Constructs an estimator of the `p`-percentile
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `p` is `0` or at least `100`
+    pub fn new_percentile(p: usize) -> Result<P2Quantile> {
+        P2Quantile::new(p as f64 / 100.0)
+    }
+
+    /// Returns the quantile estimated by the estimator
+    pub fn tau(&self) -> f64 {
+        self.tau
+    }
+
+    /// Returns the number of samples
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds the sample `x`
+    pub fn push(&mut self, x: f64) {
+        if x.is_nan() {
+            self.has_nan = true;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 && !self.has_nan {
+                self.heights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            }
+            return;
+        }
+        self.count += 1;
+        if self.has_nan {
+            return;
+        }
+
+        let q = &mut self.heights;
+        let k = if x < q[0] {
+            q[0] = x;
+            0
+        } else if x >= q[4] {
+            q[4] = x;
+            3
+        } else {
+            (1..4).find(|&i| x < q[i]).unwrap_or(4) - 1
+        };
+        for n in &mut self.positions[k + 1..] {
+            *n += 1.0;
+        }
+        for (d, inc) in self.desired.iter_mut().zip(&self.increments) {
+            *d += inc;
+        }
+
+        let n = &mut self.positions;
+        for i in 1..4 {
+            let d = self.desired[i] - n[i];
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+                n[i] += d;
+            }
+        }
+    }
+
+    /// Returns the estimate of the quantile. While fewer than five samples
+    /// have been pushed, this is the quantile of the samples computed by
+    /// `OrderStatistics::quantile`.
+    ///
+    /// # Remarks
+    ///
+    /// Returns `f64::NAN` if there are no samples or any sample is `f64::NAN`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::statistics::P2Quantile;
+    ///
+    /// let mut p2 = P2Quantile::new(0.9).unwrap();
+    /// assert!(p2.quantile().is_nan());
+    /// p2.push(2.0);
+    /// assert_eq!(p2.quantile(), 2.0);
+    /// ```
+    pub fn quantile(&self) -> f64 {
+        if self.count == 0 || self.has_nan {
+            f64::NAN
+        } else if self.count < 5 {
+            Data::new(self.heights[..self.count as usize].to_vec()).quantile(self.tau)
+        } else {
+            self.heights[2]
+        }
+    }
+}
+
+impl Extend<f64> for P2Quantile {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Normal;
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn normal_samples(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        (0..n).map(|_| normal.sample(&mut rng)).collect()
+    }
+
+    /// Returns the fraction of the sorted `data` that is below `x`
+    fn rank(sorted: &[f64], x: f64) -> f64 {
+        sorted.iter().filter(|&&y| y < x).count() as f64 / sorted.len() as f64
+    }
+
+    #[test]
+    fn test_t_digest_accuracy() {
+        let data = normal_samples(100_000, 1);
+        let mut digest = TDigest::new(100.0).unwrap();
+        digest.extend(data.iter().copied());
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for &(tau, tol) in &[
+            (0.0001, 1e-4), (0.001, 2e-4), (0.01, 5e-4), (0.1, 1e-3), (0.5, 1e-3),
+            (0.9, 1e-3), (0.99, 5e-4), (0.999, 2e-4), (0.9999, 1e-4),
+        ] {
+            assert!((rank(&sorted, digest.quantile(tau)) - tau).abs() < tol, "tau = {}", tau);
+        }
+        assert_eq!(digest.quantile(0.0), sorted[0]);
+        assert_eq!(digest.quantile(1.0), sorted[sorted.len() - 1]);
+        assert!(digest.centroids.len() <= 200);
+        assert_almost_eq!(digest.cdf(0.0), 0.5, 5e-3);
+        let x = digest.quantile(0.3);
+        assert_almost_eq!(digest.cdf(x), 0.3, 1e-9);
+        assert_eq!(digest.cdf(-100.0), 0.0);
+        assert_eq!(digest.cdf(100.0), 1.0);
+    }
+
+    #[test]
+    fn test_t_digest_merge() {
+        let data = normal_samples(50_000, 2);
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut merged = TDigest::new(100.0).unwrap();
+        for chunk in data.chunks(4_999) {
+            let mut digest = TDigest::new(100.0).unwrap();
+            digest.extend(chunk.iter().copied());
+            merged.merge(&digest);
+        }
+        assert_eq!(merged.count(), 50_000);
+        assert_eq!(merged.min(), sorted[0]);
+        assert_eq!(merged.max(), sorted[sorted.len() - 1]);
+        for &(tau, tol) in &[(0.001, 5e-4), (0.1, 1e-3), (0.5, 2e-3), (0.9, 1e-3), (0.999, 5e-4)] {
+            assert!((rank(&sorted, merged.quantile(tau)) - tau).abs() < tol, "tau = {}", tau);
+        }
+    }
+
+    #[test]
+    fn test_t_digest_exact() {
+        let data = [3.0, -1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let mut digest = TDigest::new(100.0).unwrap();
+        digest.extend(data.iter().copied());
+        assert_eq!(digest.median(), 3.5);
+        assert_eq!(digest.quantile(0.25), 1.5);
+        assert_eq!(digest.percentile(0), -1.0);
+        assert_eq!(digest.percentile(100), 9.0);
+        assert_eq!(digest.quantile(1.0 / 16.0), -1.0);
+        assert_eq!(digest.cdf(3.5), 0.5);
+
+        let mut single = TDigest::new(100.0).unwrap();
+        single.push(7.0);
+        assert_eq!(single.median(), 7.0);
+        assert_eq!(single.quantile(0.1), 7.0);
+        assert_eq!(single.cdf(7.0), 1.0);
+    }
+
+    #[test]
+    fn test_t_digest_nan() {
+        let mut digest = TDigest::new(100.0).unwrap();
+        assert!(digest.median().is_nan());
+        assert!(digest.min().is_nan());
+        assert!(digest.cdf(0.0).is_nan());
+        digest.push(1.0);
+        assert!(digest.quantile(-0.1).is_nan());
+        assert!(digest.quantile(1.1).is_nan());
+        assert!(digest.percentile(101).is_nan());
+        digest.push(f64::NAN);
+        assert!(digest.median().is_nan());
+        assert!(digest.max().is_nan());
+        assert!(TDigest::new(f64::NAN).is_err());
+        assert!(TDigest::new(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_p2_paper() {
+        let data = [
+            0.02, 0.15, 0.74, 3.39, 0.83, 22.37, 10.15, 15.43, 38.62, 15.92, 34.60, 10.28, 1.47,
+            0.40, 0.05, 11.39, 0.27, 0.42, 0.09, 11.37,
+        ];
+        let mut p2 = P2Quantile::new_median();
+        p2.extend(data.iter().copied());
+        assert_eq!(p2.count(), 20);
+        assert_almost_eq!(p2.quantile(), 4.440634353260338, 1e-12);
+        assert_eq!(p2.heights[0], 0.02);
+        assert_almost_eq!(p2.heights[1], 0.4938954475308638, 1e-12);
+        assert_almost_eq!(p2.heights[3], 17.203904274140214, 1e-12);
+        assert_eq!(p2.heights[4], 38.62);
+    }
+
+    #[test]
+    fn test_p2_accuracy() {
+        let data = normal_samples(100_000, 3);
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for &p in &[1, 10, 25, 50, 75, 90, 99] {
+            let mut p2 = P2Quantile::new_percentile(p).unwrap();
+            p2.extend(data.iter().copied());
+            let tau = p as f64 / 100.0;
+            assert!((rank(&sorted, p2.quantile()) - tau).abs() < 5e-3, "p = {}", p);
+        }
+    }
+
+    #[test]
+    fn test_p2_small() {
+        let data = [5.0, 1.0, 4.0, 2.0];
+        let mut p2 = P2Quantile::new(0.3).unwrap();
+        for (i, &x) in data.iter().enumerate() {
+            p2.push(x);
+            assert_eq!(p2.quantile(), Data::new(data[..=i].to_vec()).quantile(0.3));
+        }
+        p2.push(f64::NAN);
+        assert!(p2.quantile().is_nan());
+        p2.push(3.0);
+        assert!(p2.quantile().is_nan());
+        assert!(P2Quantile::new(0.0).is_err());
+        assert!(P2Quantile::new(f64::NAN).is_err());
+        assert!(P2Quantile::new_percentile(100).is_err());
+    }
+}
@@ -0,0 +1,428 @@
+use crate::error::StatsError;
+use std::f64;
+
+/// Enumeration of the interpretations of observation weights, which
+/// determine the normalizer of the weighted variance and covariance
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WeightKind {
+    /// Each weight is the number of times its observation occurred, so that
+    /// the sum of the weights is the sample size. The normalizer is
+    /// `V1 - 1`, where `V1` is the sum of the weights.
+    Frequency,
+    /// Each weight is the reliability of its observation, such as an inverse
+    /// variance, and only their ratios matter. The normalizer is
+    /// `V1 - V2 / V1`, where `V2` is the sum of the squared weights, which
+    /// makes the estimate unbiased.
+    Reliability,
+    /// Each weight is inversely proportional to the variance of its
+    /// observation, as Stata's analytic weights, which are rescaled to sum
+    /// to the number `n` of observations with non-zero weight. The
+    /// normalizer is `V1 * (n - 1) / n`.
+    Analytic,
+}
+
+/// Returns the sum of the weights, or `None` if a weight is negative or not
+/// finite or all are zero
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+fn check_weights(data: &[f64], weights: &[f64]) -> Option<f64> {
+    if data.len() != weights.len() {
+        panic!("{}", StatsError::ContainersMustBeSameLength);
+    }
+    if weights.iter().any(|w| !(*w >= 0.0 && w.is_finite())) {
+        return None;
+    }
+    let total: f64 = weights.iter().sum();
+    if total > 0.0 {
+        Some(total)
+    } else {
+        None
+    }
+}
+
+fn mean_with_total(data: &[f64], weights: &[f64], total: f64) -> f64 {
+    data.iter()
+        .zip(weights)
+        .filter(|(_, &w)| w > 0.0)
+        .map(|(x, w)| w * x)
+        .sum::<f64>()
+        / total
+}
+
+/// Returns the normalizer of the weighted sum of squared deviations or
+/// products of deviations for the `kind` of weights
+fn normalizer(weights: &[f64], total: f64, kind: WeightKind) -> f64 {
+    match kind {
+        WeightKind::Frequency => total - 1.0,
+        WeightKind::Reliability => total - weights.iter().map(|w| w * w).sum::<f64>() / total,
+        WeightKind::Analytic => {
+            let n = weights.iter().filter(|&&w| w > 0.0).count() as f64;
+            total * (n - 1.0) / n
+        }
+    }
+}
+
+/// Evaluates the weighted mean `Σ w_i * x_i / Σ w_i` of `data` with the
+/// non-negative `weights`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if data is empty, an entry is `f64::NAN`, or a weight
+/// is negative, infinite or `f64::NAN`, or all weights are zero
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::weighted_mean;
+///
+/// assert_eq!(weighted_mean(&[1.0, 2.0, 4.0], &[1.0, 1.0, 2.0]), 2.75);
+/// assert!(weighted_mean(&[1.0, 2.0], &[1.0, -1.0]).is_nan());
+/// ```
+pub fn weighted_mean(data: &[f64], weights: &[f64]) -> f64 {
+    match check_weights(data, weights) {
+        Some(total) => mean_with_total(data, weights, total),
+        None => f64::NAN,
+    }
+}
+
+/// Estimates the variance of the population from `data` with the
+/// non-negative `weights` interpreted as `kind`
+///
+/// # Remarks
+///
+/// With integer weights, the `Frequency` estimate equals the unbiased
+/// variance of the data in which each entry is repeated as often as its
+/// weight, and with equal weights, all three estimates equal the unbiased
+/// variance of the data.
+///
+/// Returns `f64::NAN` if a weight is negative, infinite or `f64::NAN`, an
+/// entry is `f64::NAN`, or the normalizer is not positive, such as when
+/// fewer than two entries have non-zero weight
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+///
+/// # Formula
+///
+/// ```ignore
+/// Σ w_i * (x_i - x̄_w)^2 / d
+/// ```
+///
+/// where `x̄_w` is the weighted mean and `d` is the normalizer of the
+/// `WeightKind`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::{weighted_variance, Statistics, WeightKind};
+///
+/// let x = [1.0, 2.0, 4.0];
+/// let w = [1.0, 1.0, 2.0];
+/// let repeated = [1.0, 2.0, 4.0, 4.0];
+/// let frequency = weighted_variance(&x, &w, WeightKind::Frequency);
+/// assert!((frequency - repeated.variance()).abs() < 1e-15);
+/// assert_eq!(weighted_variance(&x, &w, WeightKind::Reliability), 6.75 / 2.5);
+/// assert_eq!(weighted_variance(&x, &w, WeightKind::Analytic), 6.75 / 4.0 * 1.5);
+/// ```
+pub fn weighted_variance(data: &[f64], weights: &[f64], kind: WeightKind) -> f64 {
+    weighted_covariance(data, data, weights, kind)
+}
+
+/// Estimates the standard deviation of the population from `data` with the
+/// non-negative `weights` interpreted as `kind`, as the square root of
+/// `weighted_variance`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if a weight is negative, infinite or `f64::NAN`, an
+/// entry is `f64::NAN`, or the normalizer is not positive
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+pub fn weighted_std_dev(data: &[f64], weights: &[f64], kind: WeightKind) -> f64 {
+    weighted_variance(data, weights, kind).sqrt()
+}
+
+/// Estimates the covariance of the population from the paired entries of
+/// `x` and `y` with the non-negative `weights` interpreted as `kind`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if a weight is negative, infinite or `f64::NAN`, an
+/// entry is `f64::NAN`, or the normalizer is not positive
+///
+/// # Panics
+///
+/// If `x`, `y` and `weights` do not all have the same length
+///
+/// # Formula
+///
+/// ```ignore
+/// Σ w_i * (x_i - x̄_w) * (y_i - ȳ_w) / d
+/// ```
+///
+/// where `x̄_w` and `ȳ_w` are the weighted means and `d` is the normalizer
+/// of the `WeightKind`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::{weighted_covariance, WeightKind};
+///
+/// let x = [1.0, 2.0, 4.0];
+/// let y = [2.0, 1.0, 0.0];
+/// let w = [1.0, 1.0, 2.0];
+/// let cov = weighted_covariance(&x, &y, &w, WeightKind::Frequency);
+/// assert!((cov - -4.25 / 3.0).abs() < 1e-15);
+/// ```
+pub fn weighted_covariance(x: &[f64], y: &[f64], weights: &[f64], kind: WeightKind) -> f64 {
+    if x.len() != y.len() {
+        panic!("{}", StatsError::ContainersMustBeSameLength);
+    }
+    let total = match check_weights(x, weights) {
+        Some(total) => total,
+        None => return f64::NAN,
+    };
+    let d = normalizer(weights, total, kind);
+    if d <= 0.0 {
+        return f64::NAN;
+    }
+    let mean_x = mean_with_total(x, weights, total);
+    let mean_y = mean_with_total(y, weights, total);
+    let comoment: f64 = x
+        .iter()
+        .zip(y)
+        .zip(weights)
+        .filter(|(_, &w)| w > 0.0)
+        .map(|((x, y), w)| w * (x - mean_x) * (y - mean_y))
+        .sum();
+    comoment / d
+}
+
+/// Estimates the tau-th quantile of `data` with the non-negative `weights`
+/// as the inverse of the weighted empirical distribution function, with
+/// averaging where it is flat at tau
+///
+/// # Remarks
+///
+/// The quantile is the smallest entry at which the cumulative weight of the
+/// sorted entries reaches `tau * Σ w_i`, or the average of that entry and
+/// the next if the cumulative weight equals it. Entries with zero weight are
+/// ignored. With equal weights this is
+/// `QuantileDefinition::AveragedInvertedCdf`.
+///
+/// Returns `f64::NAN` if data is empty, an entry is `f64::NAN`, tau is
+/// outside `[0, 1]`, or a weight is negative, infinite or `f64::NAN`, or
+/// all weights are zero
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::weighted_quantile;
+///
+/// let x = [3.0, 1.0, 2.0, 4.0];
+/// assert_eq!(weighted_quantile(&x, &[1.0, 1.0, 1.0, 1.0], 0.5), 2.5);
+/// assert_eq!(weighted_quantile(&x, &[1.0, 5.0, 1.0, 1.0], 0.5), 1.0);
+/// assert_eq!(weighted_quantile(&x, &[1.0, 1.0, 1.0, 1.0], 0.9), 4.0);
+/// ```
+pub fn weighted_quantile(data: &[f64], weights: &[f64], tau: f64) -> f64 {
+    weighted_quantiles(data, weights, &[tau])[0]
+}
+
+/// Estimates the weighted median of `data` with the non-negative `weights`,
+/// the 0.5 quantile of `weighted_quantile`
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if data is empty, an entry is `f64::NAN`, or a weight
+/// is negative, infinite or `f64::NAN`, or all weights are zero
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+pub fn weighted_median(data: &[f64], weights: &[f64]) -> f64 {
+    weighted_quantile(data, weights, 0.5)
+}
+
+/// Estimates the quantiles of `data` with the non-negative `weights` at each
+/// of the `taus`, as computed by `weighted_quantile`, sorting the data once
+///
+/// # Remarks
+///
+/// The quantile at a tau outside `[0, 1]` is `f64::NAN`, as are all
+/// quantiles if data is empty, an entry is `f64::NAN`, or a weight is
+/// negative, infinite or `f64::NAN`, or all weights are zero
+///
+/// # Panics
+///
+/// If `data` and `weights` have different lengths
+///
+/// # Examples
+///
+/// ```
+/// use statrs::statistics::weighted_quantiles;
+///
+/// let x = [10.0, 20.0, 30.0];
+/// let w = [0.2, 0.3, 0.5];
+/// assert_eq!(weighted_quantiles(&x, &w, &[0.1, 0.5, 0.9]), [10.0, 25.0, 30.0]);
+/// ```
+pub fn weighted_quantiles(data: &[f64], weights: &[f64], taus: &[f64]) -> Vec<f64> {
+    let total = check_weights(data, weights);
+    let total = match total {
+        Some(total) if !data.iter().any(|x| x.is_nan()) => total,
+        _ => return vec![f64::NAN; taus.len()],
+    };
+    let mut sorted: Vec<(f64, f64)> = data
+        .iter()
+        .zip(weights)
+        .filter(|(_, &w)| w > 0.0)
+        .map(|(&x, &w)| (x, w))
+        .collect();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    let cumulative: Vec<f64> = sorted
+        .iter()
+        .scan(0.0, |sum, &(_, w)| {
+            *sum += w;
+            Some(*sum)
+        })
+        .collect();
+    let fuzz = 8.0 * f64::EPSILON * total;
+    taus.iter()
+        .map(|&tau| {
+            if !(0.0..=1.0).contains(&tau) {
+                return f64::NAN;
+            }
+            let target = tau * total;
+            let k = cumulative
+                .iter()
+                .position(|&s| s >= target - fuzz)
+                .unwrap_or(sorted.len() - 1);
+            if (cumulative[k] - target).abs() <= fuzz && k + 1 < sorted.len() && tau > 0.0 {
+                (sorted[k].0 + sorted[k + 1].0) / 2.0
+            } else {
+                sorted[k].0
+            }
+        })
+        .collect()
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::{Data, QuantileDefinition, QuantileStatistics, Statistics};
+
+    #[test]
+    fn test_weighted_mean() {
+        assert_eq!(weighted_mean(&[1.0, 2.0, 3.0], &[0.0, 1.0, 1.0]), 2.5);
+        assert_almost_eq!(weighted_mean(&[1.0, 2.0, 3.0], &[0.1, 0.1, 0.1]), 2.0, 1e-15);
+        assert!(weighted_mean(&[], &[]).is_nan());
+        assert!(weighted_mean(&[1.0, 2.0], &[0.0, 0.0]).is_nan());
+        assert!(weighted_mean(&[1.0, 2.0], &[1.0, f64::NAN]).is_nan());
+        assert!(weighted_mean(&[1.0, 2.0], &[1.0, f64::INFINITY]).is_nan());
+        assert!(weighted_mean(&[1.0, f64::NAN], &[1.0, 1.0]).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_weighted_mean_length_mismatch() {
+        weighted_mean(&[1.0, 2.0], &[1.0]);
+    }
+
+    #[test]
+    fn test_weighted_variance() {
+        let x = [2.5, -1.0, 4.0, 7.5, 0.0];
+        let w = [3.0, 1.0, 2.0, 1.0, 4.0];
+        let repeated = [2.5, 2.5, 2.5, -1.0, 4.0, 4.0, 7.5, 0.0, 0.0, 0.0, 0.0];
+        assert_almost_eq!(weighted_variance(&x, &w, WeightKind::Frequency), repeated.variance(), 1e-14);
+        let mean = weighted_mean(&x, &w);
+        let ss: f64 = x.iter().zip(&w).map(|(x, w)| w * (x - mean) * (x - mean)).sum();
+        assert_almost_eq!(weighted_variance(&x, &w, WeightKind::Reliability), ss / (11.0 - 31.0 / 11.0), 1e-14);
+        assert_almost_eq!(weighted_variance(&x, &w, WeightKind::Analytic), ss / 11.0 * 5.0 / 4.0, 1e-14);
+        assert_almost_eq!(weighted_std_dev(&x, &w, WeightKind::Analytic), (ss / 11.0 * 5.0 / 4.0).sqrt(), 1e-14);
+
+        let equal = [0.5; 5];
+        for &kind in &[WeightKind::Reliability, WeightKind::Analytic] {
+            assert_almost_eq!(weighted_variance(&x, &equal, kind), x.variance(), 1e-14);
+        }
+        let ones = [1.0; 5];
+        assert_almost_eq!(weighted_variance(&x, &ones, WeightKind::Frequency), x.variance(), 1e-14);
+        let scaled: Vec<f64> = w.iter().map(|w| 10.0 * w).collect();
+        for &kind in &[WeightKind::Reliability, WeightKind::Analytic] {
+            assert_almost_eq!(weighted_variance(&x, &scaled, kind), weighted_variance(&x, &w, kind), 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_weighted_variance_degenerate() {
+        assert!(weighted_variance(&[1.0], &[1.0], WeightKind::Frequency).is_nan());
+        assert!(weighted_variance(&[1.0, 2.0], &[0.5, 0.5], WeightKind::Frequency).is_nan());
+        assert!(weighted_variance(&[1.0, 2.0], &[1.0, 0.0], WeightKind::Reliability).is_nan());
+        assert!(weighted_variance(&[1.0, 2.0], &[1.0, 0.0], WeightKind::Analytic).is_nan());
+        assert_eq!(weighted_variance(&[1.0, 2.0, 3.0], &[2.0, 0.0, 2.0], WeightKind::Analytic), 2.0);
+        assert!(weighted_variance(&[1.0, 2.0], &[1.0, -1.0], WeightKind::Analytic).is_nan());
+    }
+
+    #[test]
+    fn test_weighted_covariance() {
+        let x = [2.5, -1.0, 4.0, 7.5, 0.0];
+        let y = [1.0, 0.5, -2.0, 3.0, 1.5];
+        let w = [3.0, 1.0, 2.0, 1.0, 4.0];
+        let rx = [2.5, 2.5, 2.5, -1.0, 4.0, 4.0, 7.5, 0.0, 0.0, 0.0, 0.0];
+        let ry = [1.0, 1.0, 1.0, 0.5, -2.0, -2.0, 3.0, 1.5, 1.5, 1.5, 1.5];
+        assert_almost_eq!(weighted_covariance(&x, &y, &w, WeightKind::Frequency), rx.covariance(ry), 1e-14);
+        assert_eq!(weighted_covariance(&x, &x, &w, WeightKind::Reliability), weighted_variance(&x, &w, WeightKind::Reliability));
+        assert!(weighted_covariance(&x, &[f64::NAN; 5], &w, WeightKind::Frequency).is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_weighted_covariance_length_mismatch() {
+        weighted_covariance(&[1.0, 2.0], &[1.0], &[1.0, 1.0], WeightKind::Frequency);
+    }
+
+    #[test]
+    fn test_weighted_quantiles() {
+        let x = [0.5, 3.2, -1.0, 7.7, 2.2, 2.2, 10.0, 4.1];
+        let taus = [0.0, 0.1, 0.25, 0.5, 0.6, 0.75, 0.9, 1.0];
+        let ones = [1.0; 8];
+        for (&tau, &q) in taus.iter().zip(weighted_quantiles(&x, &ones, &taus).iter()) {
+            let mut data = Data::new(x);
+            assert_eq!(q, data.quantile_with(tau, QuantileDefinition::AveragedInvertedCdf));
+        }
+
+        let x = [3.0, 1.0, 4.0, 1.0, 5.0];
+        let w = [2.0, 1.0, 0.0, 1.0, 4.0];
+        let repeated = [3.0, 3.0, 1.0, 1.0, 5.0, 5.0, 5.0, 5.0];
+        for &tau in &taus {
+            let mut data = Data::new(repeated);
+            assert_eq!(weighted_quantile(&x, &w, tau), data.quantile_with(tau, QuantileDefinition::AveragedInvertedCdf));
+        }
+        assert_eq!(weighted_median(&x, &w), 4.0);
+        assert_eq!(weighted_median(&[1.0, 2.0, 3.0], &[0.1, 0.7, 0.2]), 2.0);
+        assert_eq!(weighted_median(&[1.0, 2.0, 3.0], &[0.25, 0.25, 0.5]), 2.5);
+    }
+
+    #[test]
+    fn test_weighted_quantiles_invalid() {
+        assert!(weighted_median(&[], &[]).is_nan());
+        assert!(weighted_median(&[1.0, f64::NAN], &[1.0, 1.0]).is_nan());
+        assert!(weighted_median(&[1.0, 2.0], &[0.0, 0.0]).is_nan());
+        assert!(weighted_median(&[1.0, 2.0], &[1.0, -1.0]).is_nan());
+        let q = weighted_quantiles(&[1.0, 2.0], &[1.0, 1.0], &[-0.1, 0.5, f64::NAN]);
+        assert!(q[0].is_nan() && q[2].is_nan());
+        assert_eq!(q[1], 1.5);
+    }
+}
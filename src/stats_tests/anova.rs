@@ -0,0 +1,373 @@
+use crate::distribution::{ContinuousCDF, FisherSnedecor};
+use crate::statistics::{Data, Median};
+use crate::{Result, StatsError};
+
+/// The result of an analysis of variance
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AnovaResult {
+    /// The F statistic
+    pub statistic: f64,
+    /// The numerator degrees of freedom of the F distribution of the
+    /// statistic under the null hypothesis
+    pub df_between: f64,
+    /// The denominator degrees of freedom of the F distribution of the
+    /// statistic under the null hypothesis
+    pub df_within: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The sum of squared deviations of the group means from the grand mean,
+    /// weighted by the group sizes
+    pub ss_between: f64,
+    /// The sum of squared deviations of the observations from their group
+    /// means
+    pub ss_within: f64,
+    /// The mean square between the groups, `ss_between / (k - 1)`
+    pub ms_between: f64,
+    /// The mean square within the groups, `ss_within / (N - k)`
+    pub ms_within: f64,
+    /// The effect size eta-squared, `ss_between / (ss_between + ss_within)`
+    pub eta_squared: f64,
+}
+
+impl AnovaResult {
+    /// Returns the total sum of squared deviations from the grand mean
+    pub fn ss_total(&self) -> f64 {
+        self.ss_between + self.ss_within
+    }
+}
+
+/// The sums of squares of a one-way layout
+struct SumsOfSquares {
+    between: f64,
+    within: f64,
+    df_between: f64,
+    df_within: f64,
+}
+
+fn check_groups(groups: &[&[f64]]) -> Result<()> {
+    if groups.len() < 2
+        || groups.iter().any(|g| g.is_empty())
+        || groups.iter().any(|g| g.iter().any(|x| x.is_nan()))
+    {
+        return Err(StatsError::BadParams);
+    }
+    Ok(())
+}
+
+/// Returns the mean of the non-empty `data`
+fn mean(data: &[f64]) -> f64 {
+    data.iter().sum::<f64>() / data.len() as f64
+}
+
+/// Returns the sum of squared deviations of `data` from its mean
+fn sum_of_squares(data: &[f64], mean: f64) -> f64 {
+    data.iter().map(|x| (x - mean) * (x - mean)).sum()
+}
+
+fn sums_of_squares<G: AsRef<[f64]>>(groups: &[G]) -> Result<SumsOfSquares> {
+    let total: usize = groups.iter().map(|g| g.as_ref().len()).sum();
+    if total <= groups.len() {
+        return Err(StatsError::BadParams);
+    }
+    let grand_mean = groups.iter().flat_map(|g| g.as_ref()).sum::<f64>() / total as f64;
+    let (mut between, mut within) = (0.0, 0.0);
+    for g in groups {
+        let g = g.as_ref();
+        let m = mean(g);
+        between += g.len() as f64 * (m - grand_mean) * (m - grand_mean);
+        within += sum_of_squares(g, m);
+    }
+    if between + within == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok(SumsOfSquares {
+        between,
+        within,
+        df_between: (groups.len() - 1) as f64,
+        df_within: (total - groups.len()) as f64,
+    })
+}
+
+fn anova_result(ss: SumsOfSquares, statistic: f64, df_within: f64) -> Result<AnovaResult> {
+    let f = FisherSnedecor::new(ss.df_between, df_within)?;
+    Ok(AnovaResult {
+        statistic,
+        df_between: ss.df_between,
+        df_within,
+        p_value: f.sf(statistic),
+        ss_between: ss.between,
+        ss_within: ss.within,
+        ms_between: ss.between / ss.df_between,
+        ms_within: ss.within / ss.df_within,
+        eta_squared: ss.between / (ss.between + ss.within),
+    })
+}
+
+/// Performs a one-way analysis of variance of the null hypothesis that the
+/// `groups` are drawn from normal distributions with equal means, assuming
+/// they have equal variances
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, a group is empty,
+/// the data contains `NaN`, there are no more observations than groups, or
+/// all observations are equal
+///
+/// # Formula
+///
+/// ```ignore
+/// F = (SS_b / (k - 1)) / (SS_w / (N - k))
+/// SS_b = Σ n_i * (x̄_i - x̄)^2
+/// SS_w = Σ Σ (x_ij - x̄_i)^2
+/// ```
+///
+/// where `x̄_i` and `n_i` are the mean and size of group `i`, `x̄` the grand
+/// mean, `k` the number of groups and `N` the total number of observations.
+/// The statistic has `k - 1` and `N - k` degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::one_way_anova;
+///
+/// let a = [6.0, 8.0, 4.0, 5.0, 3.0, 4.0];
+/// let b = [8.0, 12.0, 9.0, 11.0, 6.0, 8.0];
+/// let c = [13.0, 9.0, 11.0, 8.0, 7.0, 12.0];
+/// let t = one_way_anova(&[&a, &b, &c]).unwrap();
+/// assert_eq!((t.df_between, t.df_within), (2.0, 15.0));
+/// assert_eq!(t.ss_between, 84.0);
+/// assert_eq!(t.ss_within, 68.0);
+/// assert!((t.statistic - 9.264705882352942).abs() < 1e-12);
+/// ```
+pub fn one_way_anova(groups: &[&[f64]]) -> Result<AnovaResult> {
+    check_groups(groups)?;
+    let ss = sums_of_squares(groups)?;
+    let statistic = (ss.between / ss.df_between) / (ss.within / ss.df_within);
+    let df_within = ss.df_within;
+    anova_result(ss, statistic, df_within)
+}
+
+/// Performs Welch's one-way analysis of variance of the null hypothesis that
+/// the `groups` are drawn from normal distributions with equal means,
+/// without assuming they have equal variances. The sums of squares and
+/// eta-squared of the result are those of the ordinary one-way analysis of
+/// variance.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, a group has fewer
+/// than two observations or zero variance, or the data contains `NaN`
+///
+/// # Formula
+///
+/// ```ignore
+/// F = Σ w_i * (x̄_i - x̄_w)^2 / (k - 1) / (1 + 2 * (k - 2) / (k^2 - 1) * Λ)
+/// Λ = Σ (1 - w_i / W)^2 / (n_i - 1)
+/// ```
+///
+/// where `w_i = n_i / s_i^2` are the weights of the groups, `W = Σ w_i`, and
+/// `x̄_w = Σ w_i * x̄_i / W` the weighted grand mean. The statistic has
+/// `k - 1` and `(k^2 - 1) / (3 * Λ)` degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::welch_anova;
+///
+/// let a = [6.0, 8.0, 4.0, 5.0, 3.0, 4.0];
+/// let b = [8.0, 12.0, 9.0, 11.0, 6.0, 8.0];
+/// let c = [13.0, 9.0, 11.0, 8.0, 7.0, 12.0];
+/// let t = welch_anova(&[&a, &b, &c]).unwrap();
+/// assert_eq!(t.df_between, 2.0);
+/// assert!(t.df_within < 15.0 && t.p_value < 0.01);
+/// ```
+pub fn welch_anova(groups: &[&[f64]]) -> Result<AnovaResult> {
+    check_groups(groups)?;
+    if groups.iter().any(|g| g.len() < 2) {
+        return Err(StatsError::BadParams);
+    }
+    let mut means = Vec::with_capacity(groups.len());
+    let mut weights = Vec::with_capacity(groups.len());
+    for g in groups {
+        let m = mean(g);
+        let var = sum_of_squares(g, m) / (g.len() - 1) as f64;
+        if var == 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        means.push(m);
+        weights.push(g.len() as f64 / var);
+    }
+    let total_weight: f64 = weights.iter().sum();
+    let weighted_mean = weights.iter().zip(&means).map(|(w, m)| w * m).sum::<f64>() / total_weight;
+    let k = groups.len() as f64;
+    let a = weights
+        .iter()
+        .zip(&means)
+        .map(|(w, m)| w * (m - weighted_mean) * (m - weighted_mean))
+        .sum::<f64>()
+        / (k - 1.0);
+    let lambda: f64 = weights
+        .iter()
+        .zip(groups)
+        .map(|(w, g)| (1.0 - w / total_weight).powi(2) / (g.len() - 1) as f64)
+        .sum();
+    let statistic = a / (1.0 + 2.0 * (k - 2.0) / (k * k - 1.0) * lambda);
+    let df_within = (k * k - 1.0) / (3.0 * lambda);
+    anova_result(sums_of_squares(groups)?, statistic, df_within)
+}
+
+/// Performs the one-way analysis of variance of the absolute deviations of
+/// the observations from the `center` of their group
+fn deviation_anova<F: Fn(&[f64]) -> f64>(groups: &[&[f64]], center: F) -> Result<AnovaResult> {
+    check_groups(groups)?;
+    let deviations: Vec<Vec<f64>> = groups
+        .iter()
+        .map(|g| {
+            let c = center(g);
+            g.iter().map(|x| (x - c).abs()).collect()
+        })
+        .collect();
+    let ss = sums_of_squares(&deviations)?;
+    let statistic = (ss.between / ss.df_between) / (ss.within / ss.df_within);
+    let df_within = ss.df_within;
+    anova_result(ss, statistic, df_within)
+}
+
+/// Performs Levene's test of the null hypothesis that the `groups` are drawn
+/// from distributions with equal variances. The result is the one-way
+/// analysis of variance of the absolute deviations of the observations from
+/// their group means.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, a group is empty,
+/// the data contains `NaN`, there are no more observations than groups, or
+/// all absolute deviations are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::levene_test;
+///
+/// let a = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+/// let b = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+/// let t = levene_test(&[&a, &b]).unwrap();
+/// assert_eq!((t.df_between, t.df_within), (1.0, 18.0));
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn levene_test(groups: &[&[f64]]) -> Result<AnovaResult> {
+    deviation_anova(groups, mean)
+}
+
+/// Performs the Brown-Forsythe test of the null hypothesis that the `groups`
+/// are drawn from distributions with equal variances. The result is the
+/// one-way analysis of variance of the absolute deviations of the
+/// observations from their group medians, which is more robust than
+/// Levene's test for skewed distributions.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, a group is empty,
+/// the data contains `NaN`, there are no more observations than groups, or
+/// all absolute deviations are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::brown_forsythe_test;
+///
+/// let a = [8.88, 9.12, 9.04, 8.98, 9.00, 9.08, 9.01, 8.85, 9.06, 8.99];
+/// let b = [8.88, 8.95, 9.29, 9.44, 9.15, 9.58, 8.36, 9.18, 8.67, 9.05];
+/// let t = brown_forsythe_test(&[&a, &b]).unwrap();
+/// assert_eq!((t.df_between, t.df_within), (1.0, 18.0));
+/// assert!(t.p_value < 0.05);
+/// ```
+pub fn brown_forsythe_test(groups: &[&[f64]]) -> Result<AnovaResult> {
+    deviation_anova(groups, |g| Data::new(g.to_vec()).median())
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const G1: [f64; 7] = [4.2, 5.1, 3.9, 6.3, 5.5, 4.8, 5.0];
+    const G2: [f64; 5] = [6.1, 7.4, 5.9, 8.2, 6.6];
+    const G3: [f64; 8] = [3.1, 9.8, 5.2, 7.7, 2.9, 6.4, 8.8, 4.1];
+    const G4: [f64; 4] = [5.5, 5.9, 6.2, 5.1];
+
+    #[test]
+    fn test_one_way_anova() {
+        let t = one_way_anova(&[&G1, &G2, &G3, &G4]).unwrap();
+        assert_eq!((t.df_between, t.df_within), (3.0, 20.0));
+        assert_almost_eq!(t.statistic, 1.2653253356066888, 1e-13);
+        assert_almost_eq!(t.p_value, 0.31311982870272319, 1e-13);
+        assert_almost_eq!(t.ss_between, 10.585797619047619, 1e-12);
+        assert_almost_eq!(t.ss_within, 55.773785714285724, 1e-12);
+        assert_almost_eq!(t.ms_between, 10.585797619047619 / 3.0, 1e-12);
+        assert_almost_eq!(t.ms_within, 55.773785714285724 / 20.0, 1e-12);
+        assert_almost_eq!(t.ss_total(), 66.359583333333343, 1e-12);
+        assert_almost_eq!(t.eta_squared, 0.15952176139915914, 1e-14);
+    }
+
+    #[test]
+    fn test_one_way_anova_constant_groups() {
+        let t = one_way_anova(&[&[1.0, 1.0], &[2.0, 2.0]]).unwrap();
+        assert_eq!(t.statistic, f64::INFINITY);
+        assert_eq!(t.p_value, 0.0);
+        assert_eq!(t.eta_squared, 1.0);
+    }
+
+    #[test]
+    fn test_one_way_anova_bad_params() {
+        assert!(one_way_anova(&[&G1]).is_err());
+        assert!(one_way_anova(&[&G1, &[]]).is_err());
+        assert!(one_way_anova(&[&G1, &[1.0, f64::NAN]]).is_err());
+        assert!(one_way_anova(&[&[1.0], &[2.0]]).is_err());
+        assert!(one_way_anova(&[&[1.0, 1.0], &[1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_welch_anova() {
+        let t = welch_anova(&[&G1, &G2, &G3, &G4]).unwrap();
+        assert_eq!(t.df_between, 3.0);
+        assert_almost_eq!(t.df_within, 10.542186791688776, 1e-12);
+        assert_almost_eq!(t.statistic, 3.8331564963171317, 1e-13);
+        assert_almost_eq!(t.p_value, 0.04383431960562523, 1e-13);
+        assert_almost_eq!(t.eta_squared, 0.15952176139915914, 1e-14);
+    }
+
+    #[test]
+    fn test_welch_anova_bad_params() {
+        assert!(welch_anova(&[&G1]).is_err());
+        assert!(welch_anova(&[&G1, &[1.0]]).is_err());
+        assert!(welch_anova(&[&G1, &[1.0, 1.0]]).is_err());
+        assert!(welch_anova(&[&G1, &[1.0, f64::NAN]]).is_err());
+    }
+
+    #[test]
+    fn test_levene() {
+        let t = levene_test(&[&G1, &G2, &G3, &G4]).unwrap();
+        assert_eq!((t.df_between, t.df_within), (3.0, 20.0));
+        assert_almost_eq!(t.statistic, 7.6143759044428016, 1e-12);
+        assert_almost_eq!(t.p_value, 0.0013789183099913115, 1e-14);
+        assert_almost_eq!(t.eta_squared, 0.53318067406693926, 1e-14);
+    }
+
+    #[test]
+    fn test_brown_forsythe() {
+        let t = brown_forsythe_test(&[&G1, &G2, &G3, &G4]).unwrap();
+        assert_eq!((t.df_between, t.df_within), (3.0, 20.0));
+        assert_almost_eq!(t.statistic, 7.1346951283397632, 1e-12);
+        assert_almost_eq!(t.p_value, 0.0019153673739680748, 1e-14);
+        assert_almost_eq!(t.eta_squared, 0.51695587973943402, 1e-14);
+    }
+
+    #[test]
+    fn test_variance_tests_bad_params() {
+        assert!(levene_test(&[&G1]).is_err());
+        assert!(levene_test(&[&G1, &[]]).is_err());
+        assert!(brown_forsythe_test(&[&G1, &[f64::NAN]]).is_err());
+        assert!(brown_forsythe_test(&[&[1.0, 3.0], &[2.0, 4.0]]).is_err());
+    }
+}
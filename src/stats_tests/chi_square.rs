@@ -0,0 +1,461 @@
+use crate::distribution::{Categorical, ChiSquared, ContinuousCDF, Discrete};
+use crate::statistics::Max;
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+
+/// The result of a chi-square goodness-of-fit test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChiSquareTestResult {
+    /// The test statistic
+    pub statistic: f64,
+    /// The degrees of freedom of the chi-square distribution of the
+    /// statistic under the null hypothesis
+    pub df: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+}
+
+/// The result of a chi-square test of independence on a contingency table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContingencyTestResult {
+    /// The test statistic
+    pub statistic: f64,
+    /// The degrees of freedom of the chi-square distribution of the
+    /// statistic under the null hypothesis
+    pub df: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The expected frequencies under independence, i.e.
+    /// `row_sum * column_sum / total`
+    pub expected: DMatrix<f64>,
+    /// The Pearson residuals `(observed - expected) / sqrt(expected)`
+    pub residuals: DMatrix<f64>,
+    /// The adjusted standardized residuals, i.e. the Pearson residuals
+    /// divided by their standard error
+    /// `sqrt((1 - row_sum / total) * (1 - column_sum / total))`. These are
+    /// approximately standard normal under independence.
+    pub standardized_residuals: DMatrix<f64>,
+}
+
+/// The `ContingencyTable` trait is implemented by the representations of a
+/// table of counts accepted by the contingency table tests, i.e.
+/// `DMatrix<u64>`, nested slices and vectors, and nested arrays
+pub trait ContingencyTable {
+    /// Returns the counts as a matrix
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rows have different lengths
+    fn to_matrix(&self) -> Result<DMatrix<f64>>;
+}
+
+impl ContingencyTable for DMatrix<u64> {
+    fn to_matrix(&self) -> Result<DMatrix<f64>> {
+        Ok(self.map(|x| x as f64))
+    }
+}
+
+fn rows_to_matrix<R: AsRef<[u64]>>(rows: &[R]) -> Result<DMatrix<f64>> {
+    let ncols = rows.first().map_or(0, |r| r.as_ref().len());
+    if rows.iter().any(|r| r.as_ref().len() != ncols) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    Ok(DMatrix::from_fn(rows.len(), ncols, |i, j| rows[i].as_ref()[j] as f64))
+}
+
+impl ContingencyTable for [&[u64]] {
+    fn to_matrix(&self) -> Result<DMatrix<f64>> {
+        rows_to_matrix(self)
+    }
+}
+
+impl ContingencyTable for [Vec<u64>] {
+    fn to_matrix(&self) -> Result<DMatrix<f64>> {
+        rows_to_matrix(self)
+    }
+}
+
+impl ContingencyTable for Vec<Vec<u64>> {
+    fn to_matrix(&self) -> Result<DMatrix<f64>> {
+        rows_to_matrix(self)
+    }
+}
+
+impl<const R: usize, const C: usize> ContingencyTable for [[u64; C]; R] {
+    fn to_matrix(&self) -> Result<DMatrix<f64>> {
+        rows_to_matrix(self)
+    }
+}
+
+/// Computes the Pearson statistic `Σ (o - e)^2 / e` or, if
+/// `likelihood_ratio` is true, the G statistic `2 * Σ o * ln(o / e)`
+fn statistic<'a, I: Iterator<Item = (f64, &'a f64)>>(pairs: I, likelihood_ratio: bool) -> f64 {
+    pairs
+        .map(|(o, &e)| {
+            if !likelihood_ratio {
+                (o - e) * (o - e) / e
+            } else if o > 0.0 {
+                2.0 * o * (o / e).ln()
+            } else {
+                0.0
+            }
+        })
+        .sum()
+}
+
+fn goodness_of_fit(
+    observed: &[u64],
+    expected: &[f64],
+    ddof: usize,
+    likelihood_ratio: bool,
+) -> Result<ChiSquareTestResult> {
+    if observed.len() != expected.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if expected.iter().any(|&e| e.is_nan() || e <= 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    let total = observed.iter().sum::<u64>() as f64;
+    if (expected.iter().sum::<f64>() - total).abs() > 1e-8 * total {
+        return Err(StatsError::ContainerExpectedSum("expected", total));
+    }
+    if observed.len() < ddof + 2 {
+        return Err(StatsError::BadParams);
+    }
+    let df = (observed.len() - 1 - ddof) as f64;
+    let statistic = statistic(observed.iter().map(|&o| o as f64).zip(expected), likelihood_ratio);
+    Ok(ChiSquareTestResult {
+        statistic,
+        df,
+        p_value: ChiSquared::new(df)?.sf(statistic),
+    })
+}
+
+/// Performs Pearson's chi-square goodness-of-fit test of the null hypothesis
+/// that the `observed` counts follow the `expected` counts. `ddof` is the
+/// number of parameters that were estimated from the data to compute the
+/// expected counts.
+///
+/// # Errors
+///
+/// Returns an error if `observed` and `expected` have different lengths,
+/// an expected count is not positive, the expected counts do not sum to the
+/// observed total, or there are not more than `ddof + 1` categories
+///
+/// # Formula
+///
+/// ```ignore
+/// χ^2 = Σ (o_i - e_i)^2 / e_i
+/// ```
+///
+/// where `o_i` and `e_i` are the observed and expected counts. The statistic
+/// has `k - 1 - ddof` degrees of freedom where `k` is the number of
+/// categories.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::chi_square_goodness_of_fit;
+/// use statrs::prec;
+///
+/// let t = chi_square_goodness_of_fit(&[20, 12, 16, 16, 16, 16], &[16.0; 6], 0).unwrap();
+/// assert_eq!(t.statistic, 2.0);
+/// assert_eq!(t.df, 5.0);
+/// assert!(prec::almost_eq(t.p_value, 0.8491450360846096, 1e-12));
+/// ```
+pub fn chi_square_goodness_of_fit(
+    observed: &[u64],
+    expected: &[f64],
+    ddof: usize,
+) -> Result<ChiSquareTestResult> {
+    goodness_of_fit(observed, expected, ddof, false)
+}
+
+/// Performs Pearson's chi-square goodness-of-fit test of the null hypothesis
+/// that the `observed` counts of the categories `0, 1, ..., k - 1` are drawn
+/// from the categorical distribution `dist`. `ddof` is the number of
+/// parameters that were estimated from the data to construct `dist`.
+///
+/// # Errors
+///
+/// Returns an error if the number of categories of `dist` differs from the
+/// length of `observed`, a category has probability zero, or there are not
+/// more than `ddof + 1` categories
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Categorical;
+/// use statrs::stats_tests::chi_square_goodness_of_fit_categorical;
+///
+/// let dist = Categorical::new(&[1.0, 2.0, 1.0]).unwrap();
+/// let t = chi_square_goodness_of_fit_categorical(&[10, 20, 10], &dist, 0).unwrap();
+/// assert_eq!(t.statistic, 0.0);
+/// assert_eq!(t.p_value, 1.0);
+/// ```
+pub fn chi_square_goodness_of_fit_categorical(
+    observed: &[u64],
+    dist: &Categorical,
+    ddof: usize,
+) -> Result<ChiSquareTestResult> {
+    goodness_of_fit(observed, &categorical_expected(observed, dist)?, ddof, false)
+}
+
+/// Performs the G-test (likelihood-ratio test) of goodness of fit of the
+/// null hypothesis that the `observed` counts follow the `expected` counts.
+/// `ddof` is the number of parameters that were estimated from the data to
+/// compute the expected counts.
+///
+/// # Errors
+///
+/// Returns an error if `observed` and `expected` have different lengths,
+/// an expected count is not positive, the expected counts do not sum to the
+/// observed total, or there are not more than `ddof + 1` categories
+///
+/// # Formula
+///
+/// ```ignore
+/// G = 2 * Σ o_i * ln(o_i / e_i)
+/// ```
+///
+/// where `o_i` and `e_i` are the observed and expected counts. The statistic
+/// has `k - 1 - ddof` degrees of freedom where `k` is the number of
+/// categories.
+pub fn g_test_goodness_of_fit(
+    observed: &[u64],
+    expected: &[f64],
+    ddof: usize,
+) -> Result<ChiSquareTestResult> {
+    goodness_of_fit(observed, expected, ddof, true)
+}
+
+fn categorical_expected(observed: &[u64], dist: &Categorical) -> Result<Vec<f64>> {
+    if dist.max() + 1 != observed.len() as u64 {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let total = observed.iter().sum::<u64>() as f64;
+    Ok((0..observed.len() as u64).map(|i| total * dist.pmf(i)).collect())
+}
+
+fn independence<T: ContingencyTable + ?Sized>(
+    table: &T,
+    correction: bool,
+    likelihood_ratio: bool,
+) -> Result<ContingencyTestResult> {
+    let observed = table.to_matrix()?;
+    let (nrows, ncols) = observed.shape();
+    if nrows < 2 || ncols < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let row_sums = observed.column_sum();
+    let col_sums = observed.row_sum();
+    let total = row_sums.sum();
+    if row_sums.iter().chain(col_sums.iter()).any(|&s| s == 0.0) {
+        return Err(StatsError::BadParams);
+    }
+    let expected = DMatrix::from_fn(nrows, ncols, |i, j| row_sums[i] * col_sums[j] / total);
+    let df = ((nrows - 1) * (ncols - 1)) as f64;
+
+    // Yates' correction moves each observed count 0.5 towards its expected
+    // count, but never past it
+    let adjusted = if correction && df == 1.0 {
+        observed.zip_map(&expected, |o, e| {
+            let d = o - e;
+            o - d.signum() * d.abs().min(0.5)
+        })
+    } else {
+        observed.clone()
+    };
+    let statistic = statistic(adjusted.iter().copied().zip(expected.iter()), likelihood_ratio);
+
+    let residuals = observed.zip_map(&expected, |o, e| (o - e) / e.sqrt());
+    let standardized_residuals = DMatrix::from_fn(nrows, ncols, |i, j| {
+        residuals[(i, j)] / ((1.0 - row_sums[i] / total) * (1.0 - col_sums[j] / total)).sqrt()
+    });
+    Ok(ContingencyTestResult {
+        statistic,
+        df,
+        p_value: ChiSquared::new(df)?.sf(statistic),
+        expected,
+        residuals,
+        standardized_residuals,
+    })
+}
+
+/// Performs Pearson's chi-square test of the null hypothesis that the row
+/// and column variables of the contingency `table` are independent. If
+/// `correction` is true and the table is 2×2, Yates' continuity correction
+/// is applied.
+///
+/// # Errors
+///
+/// Returns an error if the rows of `table` have different lengths, the table
+/// has fewer than two rows or columns, or a row or column sums to zero
+///
+/// # Formula
+///
+/// ```ignore
+/// e_ij = r_i * c_j / n
+/// χ^2 = Σ (o_ij - e_ij)^2 / e_ij
+/// ```
+///
+/// where `o_ij` are the observed counts, `r_i` and `c_j` the row and column
+/// sums and `n` the total count. With Yates' correction `|o_ij - e_ij|` is
+/// reduced by `0.5` (but not below zero). The statistic has
+/// `(r - 1) * (c - 1)` degrees of freedom for an r×c table.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::chi_square_independence;
+/// use statrs::prec;
+///
+/// let t = chi_square_independence(&[[12, 5], [7, 9]], false).unwrap();
+/// assert_eq!(t.df, 1.0);
+/// assert_eq!(t.expected[(0, 0)], 17.0 * 19.0 / 33.0);
+/// assert!(prec::almost_eq(t.statistic, 2.4305755196815568, 1e-14));
+///
+/// let corrected = chi_square_independence(&vec![vec![12, 5], vec![7, 9]], true).unwrap();
+/// assert!(corrected.p_value > t.p_value);
+/// ```
+pub fn chi_square_independence<T: ContingencyTable + ?Sized>(
+    table: &T,
+    correction: bool,
+) -> Result<ContingencyTestResult> {
+    independence(table, correction, false)
+}
+
+/// Performs the G-test (likelihood-ratio test) of the null hypothesis that
+/// the row and column variables of the contingency `table` are independent.
+/// If `correction` is true and the table is 2×2, the observed counts are
+/// adjusted as by Yates' continuity correction.
+///
+/// # Errors
+///
+/// Returns an error if the rows of `table` have different lengths, the table
+/// has fewer than two rows or columns, or a row or column sums to zero
+///
+/// # Formula
+///
+/// ```ignore
+/// e_ij = r_i * c_j / n
+/// G = 2 * Σ o_ij * ln(o_ij / e_ij)
+/// ```
+///
+/// where `o_ij` are the observed counts, `r_i` and `c_j` the row and column
+/// sums and `n` the total count. The statistic has `(r - 1) * (c - 1)`
+/// degrees of freedom for an r×c table.
+pub fn g_test_independence<T: ContingencyTable + ?Sized>(
+    table: &T,
+    correction: bool,
+) -> Result<ContingencyTestResult> {
+    independence(table, correction, true)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OBSERVED: [u64; 6] = [43, 52, 54, 40, 38, 53];
+
+    fn check_matrix(actual: &DMatrix<f64>, expected: &[f64]) {
+        for (i, &e) in expected.iter().enumerate() {
+            assert_almost_eq!(actual[(i / actual.ncols(), i % actual.ncols())], e, 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_goodness_of_fit() {
+        let t = chi_square_goodness_of_fit(&OBSERVED, &[280.0 / 6.0; 6], 0).unwrap();
+        assert_almost_eq!(t.statistic, 5.4714285714285717, 1e-13);
+        assert_eq!(t.df, 5.0);
+        assert_almost_eq!(t.p_value, 0.36108901446669041, 1e-13);
+
+        let t = chi_square_goodness_of_fit(&OBSERVED, &[280.0 / 6.0; 6], 2).unwrap();
+        assert_eq!(t.df, 3.0);
+        assert_almost_eq!(t.p_value, 0.14035752729356502, 1e-13);
+
+        let t = g_test_goodness_of_fit(&OBSERVED, &[280.0 / 6.0; 6], 0).unwrap();
+        assert_almost_eq!(t.statistic, 5.5238060088326323, 1e-13);
+        assert_almost_eq!(t.p_value, 0.35534252672665614, 1e-13);
+    }
+
+    #[test]
+    fn test_goodness_of_fit_categorical() {
+        let dist = Categorical::new(&[0.1, 0.2, 0.3, 0.15, 0.15, 0.1]).unwrap();
+        let t = chi_square_goodness_of_fit_categorical(&OBSERVED, &dist, 0).unwrap();
+        assert_almost_eq!(t.statistic, 41.833333333333333, 1e-12);
+        assert_almost_eq!(t.p_value, 6.3660019369285179e-8, 1e-18);
+        assert!(chi_square_goodness_of_fit_categorical(&OBSERVED[..5], &dist, 0).is_err());
+        let dist = Categorical::new(&[0.5, 0.0, 0.5]).unwrap();
+        assert!(chi_square_goodness_of_fit_categorical(&[1, 2, 3], &dist, 0).is_err());
+    }
+
+    #[test]
+    fn test_goodness_of_fit_bad_params() {
+        assert!(chi_square_goodness_of_fit(&[1, 2], &[1.5, 1.5, 0.0], 0).is_err());
+        assert!(chi_square_goodness_of_fit(&[1, 2], &[1.0, 1.0], 0).is_err());
+        assert!(chi_square_goodness_of_fit(&[1, 2], &[3.0, 0.0], 0).is_err());
+        assert!(chi_square_goodness_of_fit(&[1, 2], &[1.5, 1.5], 1).is_err());
+        assert!(chi_square_goodness_of_fit(&[3], &[3.0], 0).is_err());
+    }
+
+    #[test]
+    fn test_independence_2x2() {
+        let t = chi_square_independence(&[[12, 5], [7, 9]], false).unwrap();
+        assert_almost_eq!(t.statistic, 2.4305755196815568, 1e-13);
+        assert_eq!(t.df, 1.0);
+        assert_almost_eq!(t.p_value, 0.11898920553214525, 1e-13);
+        check_matrix(&t.expected, &[9.7878787878787879, 7.2121212121212121, 9.2121212121212121, 6.7878787878787879]);
+        check_matrix(&t.residuals, &[0.70707361093761704, -0.8237158288773264, -0.72883479574567046, 0.84906684198860467]);
+        check_matrix(&t.standardized_residuals, &[1.5590303139071917, -1.5590303139071917, -1.5590303139071917, 1.5590303139071917]);
+
+        let t = chi_square_independence(&[[12, 5], [7, 9]], true).unwrap();
+        assert_almost_eq!(t.statistic, 1.4559963788146838, 1e-13);
+        assert_almost_eq!(t.p_value, 0.22756821457580981, 1e-13);
+
+        let t = g_test_independence(&[[12, 5], [7, 9]], false).unwrap();
+        assert_almost_eq!(t.statistic, 2.4600430077307169, 1e-13);
+        assert_almost_eq!(t.p_value, 0.116775584678496, 1e-13);
+
+        let t = g_test_independence(&[[12, 5], [7, 9]], true).unwrap();
+        assert_almost_eq!(t.statistic, 1.4660007253363233, 1e-13);
+        assert_almost_eq!(t.p_value, 0.22597775072188469, 1e-13);
+    }
+
+    #[test]
+    fn test_independence_3x3() {
+        let rows = vec![vec![20, 15, 5], vec![30, 22, 18], vec![10, 12, 25]];
+        let matrix = DMatrix::from_row_slice(3, 3, &[20, 15, 5, 30, 22, 18, 10, 12, 25]);
+        let slices: Vec<&[u64]> = rows.iter().map(|r| r.as_slice()).collect();
+        let t = chi_square_independence(&rows, true).unwrap();
+        assert_eq!(t, chi_square_independence(&matrix, true).unwrap());
+        assert_eq!(t, chi_square_independence(&slices[..], false).unwrap());
+        assert_eq!(t, chi_square_independence(&rows[..], false).unwrap());
+        assert_almost_eq!(t.statistic, 19.049236147726568, 1e-13);
+        assert_eq!(t.df, 4.0);
+        assert_almost_eq!(t.p_value, 0.0007686298970933934, 1e-15);
+        check_matrix(&t.expected, &[
+            15.286624203821656, 12.484076433121019, 12.229299363057325,
+            26.751592356687898, 21.847133757961783, 21.401273885350318,
+            17.961783439490446, 14.668789808917197, 14.369426751592357,
+        ]);
+        check_matrix(&t.standardized_residuals, &[
+            1.7766305554737414, 0.99452127376610583, -2.8740140938674726,
+            1.0733710152822851, 0.052971528390800802, -1.1853553638237219,
+            -2.8553087442549496, -1.0037109447959297, 4.0209347320642254,
+        ]);
+
+        let t = g_test_independence(&matrix, false).unwrap();
+        assert_almost_eq!(t.statistic, 19.422107303474094, 1e-13);
+        assert_almost_eq!(t.p_value, 0.00064919502090717766, 1e-15);
+    }
+
+    #[test]
+    fn test_independence_bad_table() {
+        assert!(chi_square_independence(&vec![vec![1, 2], vec![3]], false).is_err());
+        assert!(chi_square_independence(&[[1, 2, 3]], false).is_err());
+        assert!(chi_square_independence(&[[1, 0], [3, 0]], false).is_err());
+    }
+}
@@ -0,0 +1,495 @@
+use crate::distribution::{
+    Beta, ChiSquared, ContinuousCDF, FisherSnedecor, Gamma, Normal, StudentsT,
+};
+use crate::statistics::Statistics;
+use crate::stats_tests::check_level;
+use crate::stats_tests::exact::{clopper_pearson, garwood};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// A confidence interval, which is one-sided if it belongs to a test with
+/// the `Less` or `Greater` alternative
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ConfidenceInterval {
+    /// The lower bound of the interval
+    pub lower: f64,
+    /// The upper bound of the interval
+    pub upper: f64,
+    /// The confidence level of the interval
+    pub level: f64,
+}
+
+impl ConfidenceInterval {
+    /// Returns the width of the interval, `upper - lower`
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Returns whether `x` lies in the closed interval
+    pub fn contains(&self, x: f64) -> bool {
+        self.lower <= x && x <= self.upper
+    }
+}
+
+/// Enumeration of the methods for confidence intervals for a binomial
+/// proportion
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProportionInterval {
+    /// The Wald interval from the normal approximation with the estimated
+    /// standard error, clipped to `[0, 1]`, whose coverage is poor for small
+    /// samples or proportions near 0 or 1
+    Wald,
+    /// The Wilson score interval, which inverts the score test
+    Wilson,
+    /// The Agresti-Coull interval, the Wald interval after adding `z^2 / 2`
+    /// successes and failures, clipped to `[0, 1]`
+    AgrestiCoull,
+    /// The exact Clopper-Pearson interval, which inverts the binomial test
+    /// and is conservative
+    ClopperPearson,
+    /// The Jeffreys interval, the equal-tailed credible interval of the
+    /// posterior under the Jeffreys prior `Beta(1/2, 1/2)`, with the lower
+    /// bound 0 if there are no successes and the upper bound 1 if there are
+    /// no failures
+    Jeffreys,
+}
+
+/// Returns the `1 - α / 2` quantile of the standard normal distribution for
+/// the confidence `level`
+fn normal_quantile(level: f64) -> f64 {
+    Normal::new(0.0, 1.0)
+        .unwrap()
+        .inverse_cdf((1.0 + level) / 2.0)
+}
+
+/// Returns the unbiased sample variance of `data`
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or the
+/// variance is `NaN`
+fn sample_variance(data: &[f64]) -> Result<f64> {
+    let variance = data.variance();
+    if data.len() < 2 || variance.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    Ok(variance)
+}
+
+/// Returns the Wilson score interval for a proportion, which requires
+/// `successes <= trials` and `trials > 0`
+fn wilson(successes: u64, trials: u64, z: f64) -> (f64, f64) {
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = z * z;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let half_width = z / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    // the bounds are exact at the boundaries of the support
+    let lower = if successes == 0 {
+        0.0
+    } else {
+        center - half_width
+    };
+    let upper = if successes == trials {
+        1.0
+    } else {
+        center + half_width
+    };
+    (lower, upper)
+}
+
+/// Returns the confidence interval for the mean of the normal distribution
+/// from which `data` is sampled at the confidence `level`, based on the t
+/// distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or `level`
+/// is not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// x̄ ± t(1 - α / 2; n - 1) * s / sqrt(n)
+/// ```
+///
+/// where `x̄` and `s` are the sample mean and standard deviation, `n` the
+/// number of observations, `t(p; ν)` the `p` quantile of the t distribution
+/// with `ν` degrees of freedom and `α = 1 - level`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::mean_interval;
+///
+/// let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let ci = mean_interval(&data, 0.95).unwrap();
+/// assert!((ci.lower - 5.0280745).abs() < 1e-7);
+/// assert!((ci.upper - 5.9052588).abs() < 1e-7);
+/// ```
+pub fn mean_interval(data: &[f64], level: f64) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    let variance = sample_variance(data)?;
+    let n = data.len() as f64;
+    let t = StudentsT::new(0.0, 1.0, n - 1.0)?.inverse_cdf((1.0 + level) / 2.0);
+    let half_width = t * (variance / n).sqrt();
+    let mean = data.mean();
+    Ok(ConfidenceInterval {
+        lower: mean - half_width,
+        upper: mean + half_width,
+        level,
+    })
+}
+
+/// Returns the confidence interval for the variance of the normal
+/// distribution from which `data` is sampled at the confidence `level`,
+/// based on the chi-squared distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or `level`
+/// is not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// ((n - 1) * s^2 / χ^2(1 - α / 2; n - 1), (n - 1) * s^2 / χ^2(α / 2; n - 1))
+/// ```
+///
+/// where `s^2` is the unbiased sample variance, `n` the number of
+/// observations, `χ^2(p; ν)` the `p` quantile of the chi-squared
+/// distribution with `ν` degrees of freedom and `α = 1 - level`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::variance_interval;
+///
+/// let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let ci = variance_interval(&data, 0.95).unwrap();
+/// assert!((ci.lower - 0.0680564).abs() < 1e-7);
+/// assert!((ci.upper - 1.0506751).abs() < 1e-7);
+/// ```
+pub fn variance_interval(data: &[f64], level: f64) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    let variance = sample_variance(data)?;
+    let df = data.len() as f64 - 1.0;
+    let chi_squared = ChiSquared::new(df)?;
+    let alpha = (1.0 - level) / 2.0;
+    Ok(ConfidenceInterval {
+        lower: df * variance / chi_squared.inverse_cdf(1.0 - alpha),
+        upper: df * variance / chi_squared.inverse_cdf(alpha),
+        level,
+    })
+}
+
+/// Returns the confidence interval for the ratio of the variances of the
+/// normal distributions from which `x` and `y` are sampled at the
+/// confidence `level`, based on the F distribution
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` has fewer than two observations, `y` has
+/// zero variance, or `level` is not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// (F / F(1 - α / 2; n_x - 1, n_y - 1), F / F(α / 2; n_x - 1, n_y - 1))
+/// ```
+///
+/// where `F = s_x^2 / s_y^2` is the ratio of the unbiased sample
+/// variances, `n_x` and `n_y` the numbers of observations, `F(p; ν_1, ν_2)`
+/// the `p` quantile of the F distribution and `α = 1 - level`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::variance_ratio_interval;
+///
+/// let x = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let y = [4.8, 5.0, 4.6, 5.1, 4.7];
+/// let ci = variance_ratio_interval(&x, &y, 0.95).unwrap();
+/// assert!(ci.contains(1.0));
+/// ```
+pub fn variance_ratio_interval(x: &[f64], y: &[f64], level: f64) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    let (var_x, var_y) = (sample_variance(x)?, sample_variance(y)?);
+    if var_y == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let ratio = var_x / var_y;
+    let f = FisherSnedecor::new(x.len() as f64 - 1.0, y.len() as f64 - 1.0)?;
+    let alpha = (1.0 - level) / 2.0;
+    Ok(ConfidenceInterval {
+        lower: ratio / f.inverse_cdf(1.0 - alpha),
+        upper: ratio / f.inverse_cdf(alpha),
+        level,
+    })
+}
+
+/// Returns the confidence interval for the probability of success from
+/// `successes` in `trials` independent trials at the confidence `level` by
+/// the `method`
+///
+/// # Errors
+///
+/// Returns an error if `trials` is zero, `successes > trials`, or `level`
+/// is not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// Wald:           p ± z * sqrt(p * (1 - p) / n)
+/// Wilson:         (p + z^2 / 2n ± z * sqrt(p * (1 - p) / n + z^2 / 4n^2)) / (1 + z^2 / n)
+/// Agresti-Coull:  p̃ ± z * sqrt(p̃ * (1 - p̃) / ñ)
+/// Clopper-Pearson: (B(α / 2; x, n - x + 1), B(1 - α / 2; x + 1, n - x))
+/// Jeffreys:       (B(α / 2; x + 1/2, n - x + 1/2), B(1 - α / 2; x + 1/2, n - x + 1/2))
+/// ```
+///
+/// where `x` is the number of successes, `n` the number of trials,
+/// `p = x / n`, `ñ = n + z^2`, `p̃ = (x + z^2 / 2) / ñ`, `z` the
+/// `1 - α / 2` quantile of the standard normal distribution, `B(p; a, b)`
+/// the `p` quantile of the beta distribution and `α = 1 - level`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{proportion_interval, ProportionInterval};
+///
+/// let ci = proportion_interval(7, 20, ProportionInterval::Wilson, 0.95).unwrap();
+/// assert!((ci.lower - 0.1811918).abs() < 1e-7);
+/// assert!((ci.upper - 0.5671457).abs() < 1e-7);
+///
+/// let ci = proportion_interval(0, 20, ProportionInterval::Wald, 0.95).unwrap();
+/// assert_eq!((ci.lower, ci.upper), (0.0, 0.0));
+/// ```
+pub fn proportion_interval(
+    successes: u64,
+    trials: u64,
+    method: ProportionInterval,
+    level: f64,
+) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    if trials == 0 || successes > trials {
+        return Err(StatsError::BadParams);
+    }
+    let (x, n) = (successes as f64, trials as f64);
+    let alpha = (1.0 - level) / 2.0;
+    let z = normal_quantile(level);
+    let (lower, upper) = match method {
+        ProportionInterval::Wald => {
+            let p = x / n;
+            let half_width = z * (p * (1.0 - p) / n).sqrt();
+            ((p - half_width).max(0.0), (p + half_width).min(1.0))
+        }
+        ProportionInterval::Wilson => wilson(successes, trials, z),
+        ProportionInterval::AgrestiCoull => {
+            let n = n + z * z;
+            let p = (x + z * z / 2.0) / n;
+            let half_width = z * (p * (1.0 - p) / n).sqrt();
+            ((p - half_width).max(0.0), (p + half_width).min(1.0))
+        }
+        ProportionInterval::ClopperPearson => clopper_pearson(successes, trials, alpha)?,
+        ProportionInterval::Jeffreys => {
+            let beta = Beta::new(x + 0.5, n - x + 0.5)?;
+            let lower = if successes == 0 {
+                0.0
+            } else {
+                beta.inverse_cdf(alpha)
+            };
+            let upper = if successes == trials {
+                1.0
+            } else {
+                beta.inverse_cdf(1.0 - alpha)
+            };
+            (lower, upper)
+        }
+    };
+    Ok(ConfidenceInterval {
+        lower,
+        upper,
+        level,
+    })
+}
+
+/// Returns Newcombe's hybrid score confidence interval for the difference
+/// `p_1 - p_2` of the probabilities of success from `successes[i]` in
+/// `trials[i]` independent trials at the confidence `level`
+///
+/// # Errors
+///
+/// Returns an error if an entry of `trials` is zero, `successes[i] >
+/// trials[i]`, or `level` is not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// (d - sqrt((p_1 - l_1)^2 + (u_2 - p_2)^2), d + sqrt((u_1 - p_1)^2 + (p_2 - l_2)^2))
+/// ```
+///
+/// where `p_i` is the estimated probability of success, `d = p_1 - p_2` and
+/// `(l_i, u_i)` is the Wilson score interval for `p_i`, Newcombe's (1998)
+/// method 10
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::proportion_difference_interval;
+///
+/// let ci = proportion_difference_interval([56, 48], [70, 80], 0.95).unwrap();
+/// assert!((ci.lower - 0.0524).abs() < 1e-4);
+/// assert!((ci.upper - 0.3339).abs() < 1e-4);
+/// ```
+pub fn proportion_difference_interval(
+    successes: [u64; 2],
+    trials: [u64; 2],
+    level: f64,
+) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    if trials
+        .iter()
+        .zip(&successes)
+        .any(|(&n, &x)| n == 0 || x > n)
+    {
+        return Err(StatsError::BadParams);
+    }
+    let z = normal_quantile(level);
+    let p1 = successes[0] as f64 / trials[0] as f64;
+    let p2 = successes[1] as f64 / trials[1] as f64;
+    let (l1, u1) = wilson(successes[0], trials[0], z);
+    let (l2, u2) = wilson(successes[1], trials[1], z);
+    let d = p1 - p2;
+    Ok(ConfidenceInterval {
+        lower: d - ((p1 - l1).powi(2) + (u2 - p2).powi(2)).sqrt(),
+        upper: d + ((u1 - p1).powi(2) + (p2 - l2).powi(2)).sqrt(),
+        level,
+    })
+}
+
+/// Returns Garwood's exact confidence interval for the rate of a Poisson
+/// process from `events` counted over `exposure` at the confidence `level`
+///
+/// # Errors
+///
+/// Returns an error if `exposure` is not positive and finite or `level` is
+/// not in `(0, 1)`
+///
+/// # Formula
+///
+/// ```ignore
+/// (G(α / 2; x) / T, G(1 - α / 2; x + 1) / T)
+/// ```
+///
+/// where `G(p; a)` is the `p` quantile of the gamma distribution with shape
+/// `a` and rate 1, `x` the number of events, `T` the exposure and
+/// `α = 1 - level`. The lower bound is 0 if there are no events.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::poisson_rate_interval;
+///
+/// let ci = poisson_rate_interval(10, 2.0, 0.95).unwrap();
+/// assert!((ci.lower - 2.3976943).abs() < 1e-7);
+/// assert!((ci.upper - 9.1951780).abs() < 1e-7);
+/// ```
+pub fn poisson_rate_interval(events: u64, exposure: f64, level: f64) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    if exposure.is_nan() || exposure <= 0.0 || exposure.is_infinite() {
+        return Err(StatsError::ArgMustBePositive("exposure"));
+    }
+    let (lower, upper) = garwood(events, exposure, (1.0 - level) / 2.0)?;
+    Ok(ConfidenceInterval {
+        lower,
+        upper,
+        level,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 10] = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+    const Y: [f64; 8] = [1.2, 1.5, 3.4, 1.05, 2.2, 1.8, 6.5, 1.3];
+
+    fn check(ci: ConfidenceInterval, lower: f64, upper: f64, tol: f64) {
+        assert_almost_eq!(ci.lower, lower, tol);
+        assert_almost_eq!(ci.upper, upper, tol);
+    }
+
+    #[test]
+    fn test_mean_interval() {
+        // agrees with the interval of the one-sample t-test
+        let ci = mean_interval(&X, 0.95).unwrap();
+        check(ci, 0.73327647878628473, 2.2307235212137153, 1e-12);
+        assert_eq!(ci.level, 0.95);
+        assert!(ci.contains(X.mean()));
+        assert_almost_eq!(ci.width(), 2.2307235212137153 - 0.73327647878628473, 1e-12);
+        assert!(mean_interval(&[1.0], 0.95).is_err());
+        assert!(mean_interval(&X, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_variance_intervals() {
+        check(variance_interval(&X, 0.95).unwrap(), 0.51828209776623656, 3.6510140481851652, 1e-10);
+        check(variance_ratio_interval(&X, &Y, 0.95).unwrap(), 0.067697690093966743, 1.3704224188527302, 1e-10);
+        assert!(variance_interval(&[1.0, f64::NAN], 0.95).is_err());
+        assert!(variance_ratio_interval(&X, &[1.0, 1.0], 0.95).is_err());
+        assert!(variance_ratio_interval(&[1.0], &Y, 0.95).is_err());
+    }
+
+    #[test]
+    fn test_proportion_interval() {
+        let ci = |method| proportion_interval(7, 20, method, 0.95).unwrap();
+        check(ci(ProportionInterval::Wald), 0.14096269690460584, 0.55903730309539416, 1e-12);
+        check(ci(ProportionInterval::Wilson), 0.18119182410108205, 0.56714572331476377, 1e-12);
+        check(ci(ProportionInterval::AgrestiCoull), 0.17992636143822801, 0.56841118597761781, 1e-12);
+        check(ci(ProportionInterval::ClopperPearson), 0.15390920478454116, 0.59218853453282812, 1e-10);
+        check(ci(ProportionInterval::Jeffreys), 0.17227621363191202, 0.56776609384149617, 1e-10);
+    }
+
+    #[test]
+    fn test_proportion_interval_boundaries() {
+        for &method in &[
+            ProportionInterval::Wald,
+            ProportionInterval::Wilson,
+            ProportionInterval::AgrestiCoull,
+            ProportionInterval::ClopperPearson,
+            ProportionInterval::Jeffreys,
+        ] {
+            let ci = proportion_interval(0, 10, method, 0.9).unwrap();
+            assert_eq!(ci.lower, 0.0);
+            assert!(ci.upper < 1.0);
+            let ci = proportion_interval(10, 10, method, 0.9).unwrap();
+            assert_eq!(ci.upper, 1.0);
+            assert!(ci.lower > 0.0 || method == ProportionInterval::Wald);
+        }
+        assert!(proportion_interval(3, 2, ProportionInterval::Wilson, 0.95).is_err());
+        assert!(proportion_interval(0, 0, ProportionInterval::Wilson, 0.95).is_err());
+        assert!(proportion_interval(1, 2, ProportionInterval::Wilson, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_proportion_difference_interval() {
+        // Newcombe (1998), example (a)
+        let ci = proportion_difference_interval([56, 48], [70, 80], 0.95).unwrap();
+        check(ci, 0.052431472402364878, 0.33387265403690598, 1e-12);
+        let ci = proportion_difference_interval([48, 56], [80, 70], 0.95).unwrap();
+        check(ci, -0.33387265403690598, -0.052431472402364878, 1e-12);
+        assert!(proportion_difference_interval([2, 2], [1, 3], 0.95).is_err());
+        assert!(proportion_difference_interval([0, 0], [0, 3], 0.95).is_err());
+    }
+
+    #[test]
+    fn test_poisson_rate_interval() {
+        check(poisson_rate_interval(10, 2.0, 0.95).unwrap(), 2.3976943480662168, 9.1951780210088894, 1e-10);
+        let ci = poisson_rate_interval(0, 1.0, 0.95).unwrap();
+        assert_eq!(ci.lower, 0.0);
+        assert_almost_eq!(ci.upper, -(0.025f64.ln()), 1e-12);
+        assert!(poisson_rate_interval(1, 0.0, 0.95).is_err());
+        assert!(poisson_rate_interval(1, f64::INFINITY, 0.95).is_err());
+    }
+}
@@ -0,0 +1,632 @@
+use crate::distribution::{ContinuousCDF, Normal, StudentsT};
+use crate::stats_tests::rank_tests::ranks;
+use crate::stats_tests::{check_level, p_value, Alternative, ConfidenceInterval};
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+use std::f64;
+
+/// Samples with fewer observations than this have exact p-values for
+/// Kendall's tau if there are no ties
+const KENDALL_EXACT_MAX: usize = 50;
+
+/// Enumeration of the correlation coefficients
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CorrelationMethod {
+    /// Pearson's product-moment correlation coefficient `r`
+    Pearson,
+    /// Spearman's rank correlation coefficient `rho`
+    Spearman,
+    /// Kendall's rank correlation coefficient `tau-b`
+    Kendall,
+}
+
+/// The result of a test of the null hypothesis that two samples are
+/// uncorrelated
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CorrelationTestResult {
+    /// The correlation coefficient
+    pub coefficient: f64,
+    /// The test statistic, i.e. the t statistic for Pearson and Spearman
+    /// correlations and the standardized `S` statistic for Kendall's tau
+    pub statistic: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The standard error of the Fisher z-transformed coefficient
+    /// `atanh(coefficient)`, or `NaN` if there are too few observations to
+    /// estimate it
+    pub z_std_err: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+impl CorrelationTestResult {
+    /// Returns the confidence interval for the correlation coefficient at
+    /// the confidence `level`, obtained by back-transforming the normal
+    /// interval of the Fisher z-transformed coefficient. The interval is
+    /// one-sided for the `Less` and `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)` or if the standard
+    /// error of the transformed coefficient is unknown
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// tanh(atanh(r) ± z_α * se)
+    /// ```
+    ///
+    /// where `se` is `1 / sqrt(n - 3)` for Pearson's `r`,
+    /// `sqrt(1.06 / (n - 3))` for Spearman's `rho` and
+    /// `sqrt(0.437 / (n - 4))` for Kendall's `tau` (Fieller et al., 1957)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{pearson_correlation_test, Alternative};
+    ///
+    /// let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    /// let y = [2.1, 3.9, 6.2, 7.8, 9.7, 12.5, 13.9, 16.1];
+    /// let t = pearson_correlation_test(&x, &y, Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!(ci.contains(t.coefficient) && ci.upper < 1.0);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        check_level(level)?;
+        if !self.z_std_err.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let z = self.coefficient.atanh();
+        let (lower, upper) = match self.alternative {
+            Alternative::TwoSided => {
+                let half_width = normal.inverse_cdf((1.0 + level) / 2.0) * self.z_std_err;
+                ((z - half_width).tanh(), (z + half_width).tanh())
+            }
+            Alternative::Less => (
+                -1.0,
+                (z + normal.inverse_cdf(level) * self.z_std_err).tanh(),
+            ),
+            Alternative::Greater => ((z - normal.inverse_cdf(level) * self.z_std_err).tanh(), 1.0),
+        };
+        Ok(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        })
+    }
+}
+
+fn check_samples(x: &[f64], y: &[f64], min_len: usize) -> Result<()> {
+    if x.len() != y.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    if x.len() < min_len || x.iter().chain(y).any(|v| v.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    Ok(())
+}
+
+/// Returns the Pearson correlation coefficient of `x` and `y`, which have
+/// the same length and no `NaN`
+fn pearson(x: &[f64], y: &[f64]) -> Result<f64> {
+    let n = x.len() as f64;
+    let x_mean = x.iter().sum::<f64>() / n;
+    let y_mean = y.iter().sum::<f64>() / n;
+    let (mut sxx, mut syy, mut sxy) = (0.0, 0.0, 0.0);
+    for (xi, yi) in x.iter().zip(y) {
+        let (dx, dy) = (xi - x_mean, yi - y_mean);
+        sxx += dx * dx;
+        syy += dy * dy;
+        sxy += dx * dy;
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    Ok((sxy / (sxx * syy).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// Returns the result of the t-test of a Pearson or Spearman correlation
+/// coefficient `r` of `n` observations
+fn t_test_result(
+    r: f64,
+    n: usize,
+    z_variance: f64,
+    alternative: Alternative,
+) -> CorrelationTestResult {
+    let df = n as f64 - 2.0;
+    let statistic = r * (df / ((1.0 - r) * (1.0 + r))).sqrt();
+    let t = StudentsT::new(0.0, 1.0, df).unwrap();
+    CorrelationTestResult {
+        coefficient: r,
+        statistic,
+        p_value: p_value(t.cdf(statistic), t.sf(statistic), alternative),
+        z_std_err: if n > 3 {
+            (z_variance / (n as f64 - 3.0)).sqrt()
+        } else {
+            f64::NAN
+        },
+        alternative,
+    }
+}
+
+/// Computes Pearson's product-moment correlation coefficient of the paired
+/// observations `x` and `y` and tests the null hypothesis that they are
+/// uncorrelated, assuming they are drawn from a bivariate normal
+/// distribution
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` have different lengths, fewer than three
+/// observations, contain `NaN`, or if either has zero variance
+///
+/// # Formula
+///
+/// ```ignore
+/// r = Σ (x_i - x̄) * (y_i - ȳ) / sqrt(Σ (x_i - x̄)^2 * Σ (y_i - ȳ)^2)
+/// t = r * sqrt((n - 2) / (1 - r^2))
+/// ```
+///
+/// where `n` is the number of observations. The statistic has `n - 2`
+/// degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{pearson_correlation_test, Alternative};
+///
+/// let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+/// let y = [2.1, 3.9, 6.2, 7.8, 9.7, 12.5, 13.9, 16.1];
+/// let t = pearson_correlation_test(&x, &y, Alternative::TwoSided).unwrap();
+/// assert!(t.coefficient > 0.99);
+/// assert!(t.p_value < 1e-6);
+/// ```
+pub fn pearson_correlation_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<CorrelationTestResult> {
+    check_samples(x, y, 3)?;
+    Ok(t_test_result(pearson(x, y)?, x.len(), 1.0, alternative))
+}
+
+/// Computes Spearman's rank correlation coefficient of the paired
+/// observations `x` and `y` and tests the null hypothesis that they are
+/// independent
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` have different lengths, fewer than three
+/// observations, contain `NaN`, or if all observations of either are equal
+///
+/// # Remarks
+///
+/// The coefficient is Pearson's correlation coefficient of the ranks, with
+/// tied observations receiving their average rank. The p-value is computed
+/// from the t approximation of Pearson's correlation test.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{spearman_correlation_test, Alternative};
+///
+/// let x = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+/// let y = [1.0, 4.0, 9.0, 16.0, 25.0, 36.0, 49.0, 64.0];
+/// let t = spearman_correlation_test(&x, &y, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.coefficient, 1.0);
+/// assert_eq!(t.p_value, 0.0);
+/// ```
+pub fn spearman_correlation_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<CorrelationTestResult> {
+    check_samples(x, y, 3)?;
+    let rho = pearson(&ranks(x)?, &ranks(y)?)?;
+    Ok(t_test_result(rho, x.len(), 1.06, alternative))
+}
+
+/// Computes Kendall's rank correlation coefficient `tau-b` of the paired
+/// observations `x` and `y` and tests the null hypothesis that they are
+/// independent
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` have different lengths, fewer than two
+/// observations, contain `NaN`, or if all observations of either are equal
+///
+/// # Remarks
+///
+/// The coefficient is computed in `O(n log n)` time with Knight's
+/// algorithm. The p-value is exact if there are fewer than 50 observations
+/// and no ties, otherwise it is computed from the normal approximation of
+/// `S` with the variance corrected for ties.
+///
+/// # Formula
+///
+/// ```ignore
+/// S = n_c - n_d
+/// tau_b = S / sqrt((n_0 - n_1) * (n_0 - n_2))
+/// Var[S] = (v_0 - v_t - v_u) / 18
+///     + Σ t (t - 1) * Σ u (u - 1) / (2 * n * (n - 1))
+///     + Σ t (t - 1) (t - 2) * Σ u (u - 1) (u - 2) / (9 * n * (n - 1) * (n - 2))
+/// ```
+///
+/// where `n_c` and `n_d` are the numbers of concordant and discordant
+/// pairs, `n_0 = n * (n - 1) / 2`, `n_1` and `n_2` the numbers of pairs
+/// tied in `x` and in `y`, `t` and `u` the sizes of the groups of ties in
+/// `x` and in `y`, and `v_0 = n * (n - 1) * (2n + 5)`,
+/// `v_t = Σ t (t - 1) (2t + 5)` and `v_u = Σ u (u - 1) (2u + 5)`
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{kendall_tau_test, Alternative};
+///
+/// let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+/// let y = [3.0, 1.0, 2.0, 5.0, 4.0];
+/// let t = kendall_tau_test(&x, &y, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.coefficient, 0.4);
+/// assert!((t.p_value - 0.48333333333333).abs() < 1e-12);
+/// ```
+pub fn kendall_tau_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+) -> Result<CorrelationTestResult> {
+    check_samples(x, y, 2)?;
+    let n = x.len();
+    let mut pairs: Vec<(f64, f64)> = x.iter().copied().zip(y.iter().copied()).collect();
+    pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let x_ties = ties(pairs.iter().map(|p| p.0));
+    let joint_ties = ties(pairs.iter().copied());
+    let mut ys: Vec<f64> = pairs.iter().map(|p| p.1).collect();
+    let mut buffer = vec![0.0; n];
+    let discordant = merge_sort_swaps(&mut ys, &mut buffer);
+    let y_ties = ties(ys.iter().copied());
+
+    let n0 = (n * (n - 1) / 2) as f64;
+    if x_ties.pairs == n0 || y_ties.pairs == n0 {
+        return Err(StatsError::BadParams);
+    }
+    let s = n0 - x_ties.pairs - y_ties.pairs + joint_ties.pairs - 2.0 * discordant as f64;
+    let tau = s / ((n0 - x_ties.pairs) * (n0 - y_ties.pairs)).sqrt();
+
+    let nf = n as f64;
+    let mut var = (nf * (nf - 1.0) * (2.0 * nf + 5.0) - x_ties.v - y_ties.v) / 18.0
+        + 4.0 * x_ties.pairs * y_ties.pairs / (2.0 * nf * (nf - 1.0));
+    if n > 2 {
+        var += x_ties.triples * y_ties.triples / (9.0 * nf * (nf - 1.0) * (nf - 2.0));
+    }
+    let statistic = s / var.sqrt();
+
+    let p_value = if x_ties.pairs == 0.0 && y_ties.pairs == 0.0 && n < KENDALL_EXACT_MAX {
+        // S = n0 - 2 * discordant, so large values of S are small numbers
+        // of inversions
+        let pmf = inversions_pmf(n);
+        let cdf: f64 = pmf[discordant..].iter().sum();
+        let sf: f64 = pmf[..=discordant].iter().sum();
+        p_value(cdf.min(1.0), sf.min(1.0), alternative)
+    } else {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        p_value(normal.cdf(statistic), normal.sf(statistic), alternative)
+    };
+    Ok(CorrelationTestResult {
+        coefficient: tau.clamp(-1.0, 1.0),
+        statistic,
+        p_value,
+        z_std_err: if n > 4 {
+            (0.437 / (nf - 4.0)).sqrt()
+        } else {
+            f64::NAN
+        },
+        alternative,
+    })
+}
+
+/// Sums over the groups of `t` tied values
+struct Ties {
+    /// `Σ t (t - 1) / 2`, the number of tied pairs
+    pairs: f64,
+    /// `Σ t (t - 1) (t - 2)`
+    triples: f64,
+    /// `Σ t (t - 1) (2t + 5)`
+    v: f64,
+}
+
+/// Returns the sums over the groups of ties of the sorted `values`
+fn ties<T: PartialEq, I: Iterator<Item = T>>(values: I) -> Ties {
+    let mut result = Ties {
+        pairs: 0.0,
+        triples: 0.0,
+        v: 0.0,
+    };
+    let mut add = |t: f64| {
+        result.pairs += t * (t - 1.0) / 2.0;
+        result.triples += t * (t - 1.0) * (t - 2.0);
+        result.v += t * (t - 1.0) * (2.0 * t + 5.0);
+    };
+    let mut values = values.peekable();
+    while let Some(v) = values.next() {
+        let mut t = 1.0;
+        while values.peek() == Some(&v) {
+            values.next();
+            t += 1.0;
+        }
+        add(t);
+    }
+    result
+}
+
+/// Sorts `values` with a stable merge sort and returns the number of pairs
+/// that were out of order, i.e. the number of pairs `i < j` with
+/// `values[i] > values[j]`
+fn merge_sort_swaps(values: &mut [f64], buffer: &mut [f64]) -> usize {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let mid = n / 2;
+    let mut swaps = merge_sort_swaps(&mut values[..mid], &mut buffer[..mid])
+        + merge_sort_swaps(&mut values[mid..], &mut buffer[mid..]);
+    let (mut i, mut j) = (0, mid);
+    for slot in buffer[..n].iter_mut() {
+        if j == n || (i < mid && values[i] <= values[j]) {
+            *slot = values[i];
+            i += 1;
+        } else {
+            // values[j] precedes all remaining values of the left half
+            *slot = values[j];
+            swaps += mid - i;
+            j += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    swaps
+}
+
+/// Returns the probability mass function of the number of inversions of a
+/// uniformly random permutation of `n` elements on `0..=n * (n - 1) / 2`
+fn inversions_pmf(n: usize) -> Vec<f64> {
+    // inserting element i into a random permutation of i - 1 elements adds
+    // 0..i inversions with equal probability
+    let mut pmf = vec![1.0];
+    for i in 2..=n {
+        let mut next = vec![0.0; pmf.len() + i - 1];
+        let mut window = 0.0;
+        for (k, p) in next.iter_mut().enumerate() {
+            if k < pmf.len() {
+                window += pmf[k];
+            }
+            if k >= i {
+                window -= pmf[k - i];
+            }
+            *p = window / i as f64;
+        }
+        pmf = next;
+    }
+    pmf
+}
+
+/// Returns the matrix of the correlation coefficients between the columns
+/// of `data`, whose rows are the observations
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two rows, contains `NaN`, or
+/// if all observations of a column are equal
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{correlation_matrix, CorrelationMethod};
+/// use nalgebra::DMatrix;
+///
+/// let data = DMatrix::from_row_slice(4, 3, &[
+///     1.0, 2.0, 4.0,
+///     2.0, 1.0, 3.0,
+///     3.0, 4.0, 2.0,
+///     4.0, 3.0, 1.0,
+/// ]);
+/// let r = correlation_matrix(&data, CorrelationMethod::Spearman).unwrap();
+/// assert_eq!(r[(0, 0)], 1.0);
+/// assert_eq!(r[(0, 2)], -1.0);
+/// assert_eq!(r[(0, 1)], r[(1, 0)]);
+/// ```
+pub fn correlation_matrix(data: &DMatrix<f64>, method: CorrelationMethod) -> Result<DMatrix<f64>> {
+    if data.nrows() < 2 || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let columns: Vec<Vec<f64>> = data
+        .column_iter()
+        .map(|c| c.iter().copied().collect())
+        .collect();
+    let columns = match method {
+        CorrelationMethod::Spearman => columns.iter().map(|c| ranks(c)).collect::<Result<_>>()?,
+        _ => columns,
+    };
+    let k = columns.len();
+    let mut result = DMatrix::identity(k, k);
+    for i in 0..k {
+        for j in 0..i {
+            let r = match method {
+                CorrelationMethod::Pearson | CorrelationMethod::Spearman => {
+                    pearson(&columns[i], &columns[j])?
+                }
+                CorrelationMethod::Kendall => {
+                    kendall_tau_test(&columns[i], &columns[j], Alternative::TwoSided)?.coefficient
+                }
+            };
+            result[(i, j)] = r;
+            result[(j, i)] = r;
+        }
+    }
+    Ok(result)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 10] = [2.3, 4.1, 3.3, 5.9, 6.2, 1.7, 4.8, 5.1, 3.9, 7.4];
+    const Y: [f64; 10] = [1.9, 3.2, 3.9, 4.4, 6.8, 2.2, 3.7, 5.9, 3.1, 6.1];
+    const X_TIES: [f64; 10] = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 5.0, 6.0, 7.0];
+    const Y_TIES: [f64; 10] = [2.0, 1.0, 3.0, 3.0, 5.0, 4.0, 6.0, 6.0, 8.0, 7.0];
+
+    #[test]
+    fn test_pearson() {
+        let t = pearson_correlation_test(&X, &Y, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.coefficient, 0.86786377276571239, 1e-14);
+        assert_almost_eq!(t.statistic, 4.9409789383921319, 1e-12);
+        assert_almost_eq!(t.p_value, 0.0011336669231820682, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.52524945873302362, 1e-10);
+        assert_almost_eq!(ci.upper, 0.96835305324183902, 1e-10);
+
+        let t = pearson_correlation_test(&X, &Y, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.99943316653840897, 1e-14);
+        let ci = t.confidence_interval(0.9).unwrap();
+        assert_eq!(ci.lower, -1.0);
+        assert_almost_eq!(ci.upper, 0.94770362124343857, 1e-10);
+
+        let t = pearson_correlation_test(&X, &Y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.00056683346159103411, 1e-14);
+        let ci = t.confidence_interval(0.9).unwrap();
+        assert_almost_eq!(ci.lower, 0.68579723731356031, 1e-10);
+        assert_eq!(ci.upper, 1.0);
+    }
+
+    #[test]
+    fn test_pearson_perfect() {
+        let y: Vec<f64> = X.iter().map(|x| 3.0 - 2.0 * x).collect();
+        let t = pearson_correlation_test(&X, &y, Alternative::TwoSided).unwrap();
+        assert_eq!(t.coefficient, -1.0);
+        assert_eq!(t.p_value, 0.0);
+    }
+
+    #[test]
+    fn test_pearson_bad_params() {
+        assert!(pearson_correlation_test(&X, &Y[..9], Alternative::TwoSided).is_err());
+        assert!(pearson_correlation_test(&X[..2], &Y[..2], Alternative::TwoSided).is_err());
+        assert!(pearson_correlation_test(&[1.0, f64::NAN, 3.0], &[1.0, 2.0, 3.0], Alternative::TwoSided).is_err());
+        assert!(pearson_correlation_test(&[1.0, 1.0, 1.0], &[1.0, 2.0, 3.0], Alternative::TwoSided).is_err());
+        let t = pearson_correlation_test(&X[..3], &Y[..3], Alternative::TwoSided).unwrap();
+        assert!(t.z_std_err.is_nan());
+        assert!(t.confidence_interval(0.95).is_err());
+        assert!(t.confidence_interval(0.0).is_err());
+    }
+
+    #[test]
+    fn test_spearman_ties() {
+        let t = spearman_correlation_test(&X_TIES, &Y_TIES, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.coefficient, 0.92573652488001818, 1e-14);
+        assert_almost_eq!(t.statistic, 6.9238288631663896, 1e-12);
+        assert_almost_eq!(t.p_value, 0.00012157407536297894, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.69883780039490445, 1e-10);
+        assert_almost_eq!(ci.upper, 0.98336165335220944, 1e-10);
+    }
+
+    #[test]
+    fn test_spearman_bad_params() {
+        assert!(spearman_correlation_test(&X, &Y[..9], Alternative::TwoSided).is_err());
+        assert!(spearman_correlation_test(&[1.0, 2.0, f64::NAN], &[1.0, 2.0, 3.0], Alternative::TwoSided).is_err());
+        assert!(spearman_correlation_test(&[2.0, 2.0, 2.0], &[1.0, 2.0, 3.0], Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_kendall_exact() {
+        let t = kendall_tau_test(&X, &Y, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.coefficient, 0.73333333333333333, 1e-15);
+        assert_almost_eq!(t.statistic, 2.9516097302997224, 1e-13);
+        assert_almost_eq!(t.p_value, 2.0 * 803.0 / 725760.0, 1e-15);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.38588231095530971, 1e-10);
+        assert_almost_eq!(ci.upper, 0.89859034631073331, 1e-10);
+        let t = kendall_tau_test(&X, &Y, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 3627083.0 / 3628800.0, 1e-15);
+        let t = kendall_tau_test(&X, &Y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 803.0 / 725760.0, 1e-15);
+    }
+
+    #[test]
+    fn test_kendall_ties() {
+        let t = kendall_tau_test(&X_TIES, &Y_TIES, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.coefficient, 0.80975336399021957, 1e-14);
+        assert_almost_eq!(t.statistic, 3.123197023480582, 1e-13);
+        assert_almost_eq!(t.p_value, 0.0017889794722248034, 1e-12);
+        let t = kendall_tau_test(&X_TIES, &Y_TIES, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.00089448973611240172, 1e-12);
+    }
+
+    #[test]
+    fn test_kendall_matches_brute_force() {
+        let x: Vec<f64> = (0..200).map(|i| ((i * 37) % 23) as f64).collect();
+        let y: Vec<f64> = (0..200).map(|i| ((i * 11) % 17) as f64 + 0.1 * (i % 5) as f64).collect();
+        let (mut s, mut n1, mut n2) = (0.0, 0.0, 0.0);
+        for i in 0..200 {
+            for j in i + 1..200 {
+                let a = if x[i] == x[j] { 0.0 } else { (x[i] - x[j]).signum() };
+                let b = if y[i] == y[j] { 0.0 } else { (y[i] - y[j]).signum() };
+                s += a * b;
+                n1 += if a == 0.0 { 1.0 } else { 0.0 };
+                n2 += if b == 0.0 { 1.0 } else { 0.0 };
+            }
+        }
+        let n0: f64 = 200.0 * 199.0 / 2.0;
+        let t = kendall_tau_test(&x, &y, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.coefficient, s / ((n0 - n1) * (n0 - n2)).sqrt(), 1e-14);
+    }
+
+    #[test]
+    fn test_kendall_bad_params() {
+        assert!(kendall_tau_test(&X, &Y[..9], Alternative::TwoSided).is_err());
+        assert!(kendall_tau_test(&[1.0], &[1.0], Alternative::TwoSided).is_err());
+        assert!(kendall_tau_test(&[1.0, f64::NAN], &[1.0, 2.0], Alternative::TwoSided).is_err());
+        assert!(kendall_tau_test(&[1.0, 2.0, 3.0], &[4.0, 4.0, 4.0], Alternative::TwoSided).is_err());
+        let t = kendall_tau_test(&[1.0, 2.0], &[2.0, 1.0], Alternative::TwoSided).unwrap();
+        assert_eq!(t.coefficient, -1.0);
+        assert_eq!(t.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_inversions_pmf() {
+        let pmf = inversions_pmf(4);
+        let expected = [1.0, 3.0, 5.0, 6.0, 5.0, 3.0, 1.0];
+        assert_eq!(pmf.len(), expected.len());
+        for (p, e) in pmf.iter().zip(&expected) {
+            assert_almost_eq!(*p, e / 24.0, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_merge_sort_swaps() {
+        let mut values = [3.0, 1.0, 2.0, 2.0, 5.0, 0.0];
+        let mut buffer = [0.0; 6];
+        assert_eq!(merge_sort_swaps(&mut values, &mut buffer), 8);
+        assert_eq!(values, [0.0, 1.0, 2.0, 2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_correlation_matrix() {
+        let data = DMatrix::from_fn(10, 3, |i, j| match j {
+            0 => X[i],
+            1 => Y[i],
+            _ => X_TIES[i],
+        });
+        let r = correlation_matrix(&data, CorrelationMethod::Pearson).unwrap();
+        assert_eq!(r[(0, 0)], 1.0);
+        assert_almost_eq!(r[(0, 1)], 0.86786377276571239, 1e-14);
+        assert_eq!(r[(0, 1)], r[(1, 0)]);
+        let r = correlation_matrix(&data, CorrelationMethod::Kendall).unwrap();
+        assert_almost_eq!(r[(1, 0)], 0.73333333333333333, 1e-15);
+        let r = correlation_matrix(&data, CorrelationMethod::Spearman).unwrap();
+        let rho = spearman_correlation_test(&Y, &X_TIES, Alternative::TwoSided).unwrap().coefficient;
+        assert_almost_eq!(r[(1, 2)], rho, 1e-15);
+
+        assert!(correlation_matrix(&DMatrix::zeros(1, 3), CorrelationMethod::Pearson).is_err());
+        assert!(correlation_matrix(&DMatrix::zeros(4, 3), CorrelationMethod::Pearson).is_err());
+    }
+}
@@ -0,0 +1,859 @@
+use crate::distribution::internal::find_root;
+use crate::distribution::{
+    Beta, Binomial, ContinuousCDF, Discrete, DiscreteCDF, Gamma, Hypergeometric, Poisson,
+};
+use crate::stats_tests::{check_level, p_value, Alternative, ConfidenceInterval};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// Relative tolerance when comparing the probabilities of outcomes with the
+/// probability of the observed outcome for two-sided p-values, so that
+/// outcomes as likely as the observed one are counted despite rounding
+const RELATIVE_ERROR: f64 = 1.0 + 1e-7;
+
+/// The result of Fisher's exact test on a 2x2 contingency table
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FisherExactTestResult {
+    /// The contingency table
+    pub table: [[u64; 2]; 2],
+    /// The conditional maximum likelihood estimate of the odds ratio
+    pub odds_ratio: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+/// The distribution of the top left cell of a 2x2 table with fixed margins
+/// given the odds ratio, Fisher's noncentral hypergeometric distribution
+struct NoncentralHypergeometric {
+    /// The smallest value of the support
+    min: u64,
+    /// The natural logarithms of the probabilities of the support under
+    /// independence
+    ln_pmf: Vec<f64>,
+}
+
+impl NoncentralHypergeometric {
+    fn new(table: &[[u64; 2]; 2]) -> Result<Self> {
+        let row = table[0][0] + table[0][1];
+        let column = table[0][0] + table[1][0];
+        let total = row + table[1][0] + table[1][1];
+        if total == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let hypergeometric = Hypergeometric::new(total, column, row)?;
+        let min = (row + column).saturating_sub(total);
+        let max = row.min(column);
+        Ok(NoncentralHypergeometric {
+            min,
+            ln_pmf: (min..=max).map(|x| hypergeometric.ln_pmf(x)).collect(),
+        })
+    }
+
+    fn max(&self) -> u64 {
+        self.min + self.ln_pmf.len() as u64 - 1
+    }
+
+    /// Returns the probabilities of the support given the odds ratio, which
+    /// is finite
+    fn pmf(&self, odds_ratio: f64) -> Vec<f64> {
+        if odds_ratio == 0.0 {
+            let mut pmf = vec![0.0; self.ln_pmf.len()];
+            pmf[0] = 1.0;
+            return pmf;
+        }
+        let ln_odds = odds_ratio.ln();
+        let ln_weights: Vec<f64> = self
+            .ln_pmf
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p + ln_odds * (self.min + i as u64) as f64)
+            .collect();
+        let max = ln_weights.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let weights: Vec<f64> = ln_weights.iter().map(|w| (w - max).exp()).collect();
+        let sum: f64 = weights.iter().sum();
+        weights.iter().map(|w| w / sum).collect()
+    }
+
+    fn mean(&self, odds_ratio: f64) -> f64 {
+        self.pmf(odds_ratio)
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (self.min + i as u64) as f64 * p)
+            .sum()
+    }
+
+    fn cdf(&self, x: u64, odds_ratio: f64) -> f64 {
+        let pmf = self.pmf(odds_ratio);
+        pmf[..=(x - self.min) as usize].iter().sum::<f64>().min(1.0)
+    }
+
+    fn sf_inclusive(&self, x: u64, odds_ratio: f64) -> f64 {
+        let pmf = self.pmf(odds_ratio);
+        pmf[(x - self.min) as usize..].iter().sum::<f64>().min(1.0)
+    }
+}
+
+/// Returns the root of the function `f` of the odds ratio, which is
+/// monotonic and equal to `f1` at an odds ratio of 1, by searching in
+/// `(0, 1)` or, through the reciprocal, in `(1, inf)`
+fn odds_ratio_root<F: Fn(f64) -> f64>(f: F, f1: f64, increasing: bool) -> Result<f64> {
+    if f1 == 0.0 {
+        Ok(1.0)
+    } else if (f1 > 0.0) == increasing {
+        find_root(f, 0.0, 1.0)
+    } else {
+        Ok(1.0 / find_root(|t| f(1.0 / t), f64::EPSILON, 1.0)?)
+    }
+}
+
+impl FisherExactTestResult {
+    /// Returns the exact conditional confidence interval for the odds ratio
+    /// at the confidence `level`. The interval is one-sided for the `Less`
+    /// and `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{fisher_exact_test, Alternative};
+    ///
+    /// let t = fisher_exact_test([[3, 1], [1, 3]], Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!(ci.lower < t.odds_ratio && t.odds_ratio < ci.upper);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        check_level(level)?;
+        let dist = NoncentralHypergeometric::new(&self.table)?;
+        let x = self.table[0][0];
+        let lower = |alpha: f64| {
+            if x == dist.min {
+                return Ok(0.0);
+            }
+            let f = |t: f64| dist.sf_inclusive(x, t) - alpha;
+            odds_ratio_root(f, f(1.0), true)
+        };
+        let upper = |alpha: f64| {
+            if x == dist.max() {
+                return Ok(f64::INFINITY);
+            }
+            let f = |t: f64| dist.cdf(x, t) - alpha;
+            odds_ratio_root(f, f(1.0), false)
+        };
+        let (lower, upper) = match self.alternative {
+            Alternative::TwoSided => {
+                let alpha = (1.0 - level) / 2.0;
+                (lower(alpha)?, upper(alpha)?)
+            }
+            Alternative::Less => (0.0, upper(1.0 - level)?),
+            Alternative::Greater => (lower(1.0 - level)?, f64::INFINITY),
+        };
+        Ok(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        })
+    }
+}
+
+/// Performs Fisher's exact test of the null hypothesis that the rows and
+/// columns of the 2x2 contingency `table` are independent, i.e. that the
+/// odds ratio `table[0][0] * table[1][1] / (table[0][1] * table[1][0])` is
+/// 1. The `Greater` alternative is that the odds ratio is greater than 1.
+///
+/// # Errors
+///
+/// Returns an error if the table is empty
+///
+/// # Remarks
+///
+/// The test conditions on the margins of the table, under which the top
+/// left cell has a hypergeometric distribution. The two-sided p-value is
+/// the total probability of the tables that are at most as likely as the
+/// observed one. The odds ratio is the conditional maximum likelihood
+/// estimate, i.e. the odds ratio of Fisher's noncentral hypergeometric
+/// distribution whose mean is the observed top left cell, which differs
+/// from the sample odds ratio.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{fisher_exact_test, Alternative};
+///
+/// // Fisher's lady tasting tea
+/// let t = fisher_exact_test([[3, 1], [1, 3]], Alternative::TwoSided).unwrap();
+/// assert!((t.p_value - 34.0 / 70.0).abs() < 1e-14);
+/// assert!((t.odds_ratio - 6.40832).abs() < 1e-5);
+///
+/// let t = fisher_exact_test([[3, 1], [1, 3]], Alternative::Greater).unwrap();
+/// assert!((t.p_value - 17.0 / 70.0).abs() < 1e-14);
+/// ```
+pub fn fisher_exact_test(
+    table: [[u64; 2]; 2],
+    alternative: Alternative,
+) -> Result<FisherExactTestResult> {
+    let dist = NoncentralHypergeometric::new(&table)?;
+    let x = table[0][0];
+    let pmf = dist.pmf(1.0);
+    let i = (x - dist.min) as usize;
+    let p_value = match alternative {
+        Alternative::Less => pmf[..=i].iter().sum(),
+        Alternative::Greater => pmf[i..].iter().sum(),
+        Alternative::TwoSided => {
+            let threshold = pmf[i] * RELATIVE_ERROR;
+            pmf.iter().filter(|&&p| p <= threshold).sum()
+        }
+    };
+    let odds_ratio = if x == dist.min {
+        if x == dist.max() {
+            f64::NAN
+        } else {
+            0.0
+        }
+    } else if x == dist.max() {
+        f64::INFINITY
+    } else {
+        let f = |t: f64| dist.mean(t) - x as f64;
+        odds_ratio_root(f, f(1.0), true)?
+    };
+    Ok(FisherExactTestResult {
+        table,
+        odds_ratio,
+        p_value: f64::min(p_value, 1.0),
+        alternative,
+    })
+}
+
+/// Returns the two-sided p-value of the discrete statistic `x` with the
+/// probability mass function `pmf` on the support `0..=max`, i.e. the total
+/// probability of the outcomes that are at most as likely as `x`. `lower`
+/// and `upper` are the probabilities of the outcomes below and above a
+/// value including the value itself, and `mode` is a mode of `pmf` such
+/// that it is unimodal on each side.
+fn two_sided_p_value<P, L, U>(x: u64, mode: u64, max: u64, pmf: P, lower: L, upper: U) -> f64
+where
+    P: Fn(u64) -> f64,
+    L: Fn(u64) -> f64,
+    U: Fn(u64) -> f64,
+{
+    let threshold = pmf(x) * RELATIVE_ERROR;
+    if pmf(mode) <= threshold {
+        // every outcome is at most as likely as x
+        return 1.0;
+    }
+    let p = if x < mode {
+        // the outcomes above the mode at most as likely as x form a tail
+        // starting at the first such outcome
+        let mut hi = mode;
+        let mut step = 1;
+        while hi < max && pmf(hi) > threshold {
+            hi = (hi + step).min(max);
+            step *= 2;
+        }
+        let mut lo = mode;
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if pmf(mid) > threshold {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let tail = if pmf(hi) <= threshold { upper(hi) } else { 0.0 };
+        lower(x) + tail
+    } else {
+        let (mut lo, mut hi) = (0, mode);
+        if pmf(0) > threshold {
+            return upper(x).min(1.0);
+        }
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if pmf(mid) <= threshold {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lower(lo) + upper(x)
+    };
+    p.min(1.0)
+}
+
+/// Returns the two-sided Clopper-Pearson interval for the probability of
+/// success from `x` successes in `n` trials with the tail probability
+/// `alpha` on each side
+pub(crate) fn clopper_pearson(x: u64, n: u64, alpha: f64) -> Result<(f64, f64)> {
+    let lower = if x == 0 {
+        0.0
+    } else {
+        Beta::new(x as f64, (n - x + 1) as f64)?.inverse_cdf(alpha)
+    };
+    let upper = if x == n {
+        1.0
+    } else {
+        Beta::new((x + 1) as f64, (n - x) as f64)?.inverse_cdf(1.0 - alpha)
+    };
+    Ok((lower, upper))
+}
+
+/// Returns the Clopper-Pearson interval at the confidence `level`, which is
+/// one-sided for the `Less` and `Greater` alternatives
+fn clopper_pearson_interval(
+    x: u64,
+    n: u64,
+    level: f64,
+    alternative: Alternative,
+) -> Result<ConfidenceInterval> {
+    check_level(level)?;
+    let (lower, upper) = match alternative {
+        Alternative::TwoSided => clopper_pearson(x, n, (1.0 - level) / 2.0)?,
+        Alternative::Less => (0.0, clopper_pearson(x, n, 1.0 - level)?.1),
+        Alternative::Greater => (clopper_pearson(x, n, 1.0 - level)?.0, 1.0),
+    };
+    Ok(ConfidenceInterval {
+        lower,
+        upper,
+        level,
+    })
+}
+
+/// Returns the two-sided Garwood interval for the rate of a Poisson process
+/// from `events` counted over `exposure` with the tail probability `alpha`
+/// on each side
+pub(crate) fn garwood(events: u64, exposure: f64, alpha: f64) -> Result<(f64, f64)> {
+    let x = events as f64;
+    let lower = if events == 0 {
+        0.0
+    } else {
+        Gamma::new(x, 1.0)?.inverse_cdf(alpha) / exposure
+    };
+    let upper = Gamma::new(x + 1.0, 1.0)?.inverse_cdf(1.0 - alpha) / exposure;
+    Ok((lower, upper))
+}
+
+/// The result of an exact binomial test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BinomialTestResult {
+    /// The number of successes
+    pub successes: u64,
+    /// The number of trials
+    pub trials: u64,
+    /// The estimated probability of success, `successes / trials`
+    pub estimate: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+impl BinomialTestResult {
+    /// Returns the Clopper-Pearson confidence interval for the probability
+    /// of success at the confidence `level`. The interval is one-sided for
+    /// the `Less` and `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (B(α / 2; x, n - x + 1), B(1 - α / 2; x + 1, n - x))
+    /// ```
+    ///
+    /// where `B(p; a, b)` is the `p` quantile of the beta distribution with
+    /// shapes `a` and `b`, and `α = 1 - level`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{binomial_test, Alternative};
+    ///
+    /// let t = binomial_test(7, 20, 0.5, Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!((ci.lower - 0.1539092).abs() < 1e-7);
+    /// assert!((ci.upper - 0.5921885).abs() < 1e-7);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        clopper_pearson_interval(self.successes, self.trials, level, self.alternative)
+    }
+}
+
+/// Performs the exact binomial test of the null hypothesis that the
+/// probability of success is `p`, given `successes` in `trials`
+/// independent trials
+///
+/// # Errors
+///
+/// Returns an error if `trials` is zero, `successes > trials`, or `p` is
+/// not in `[0, 1]`
+///
+/// # Remarks
+///
+/// The two-sided p-value is the total probability of the numbers of
+/// successes that are at most as likely as the observed one.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{binomial_test, Alternative};
+///
+/// let t = binomial_test(7, 20, 0.5, Alternative::TwoSided).unwrap();
+/// assert!((t.p_value - 0.2631760).abs() < 1e-7);
+/// ```
+pub fn binomial_test(
+    successes: u64,
+    trials: u64,
+    p: f64,
+    alternative: Alternative,
+) -> Result<BinomialTestResult> {
+    if trials == 0 || successes > trials {
+        return Err(StatsError::BadParams);
+    }
+    let binomial = Binomial::new(p, trials)?;
+    let x = successes;
+    let lower = |x: u64| binomial.cdf(x);
+    let upper = |x: u64| if x == 0 { 1.0 } else { binomial.sf(x - 1) };
+    let p_value = match alternative {
+        Alternative::Less => lower(x),
+        Alternative::Greater => upper(x),
+        Alternative::TwoSided => {
+            // the mode is floor((n + 1) * p), both neighbours of the mean
+            // n * p are at least as likely as the others
+            let mode = (((trials + 1) as f64 * p).floor() as u64).min(trials);
+            two_sided_p_value(x, mode, trials, |k| binomial.pmf(k), lower, upper)
+        }
+    };
+    Ok(BinomialTestResult {
+        successes,
+        trials,
+        estimate: successes as f64 / trials as f64,
+        p_value,
+        alternative,
+    })
+}
+
+/// The result of an exact test for the rate of a Poisson process
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PoissonTestResult {
+    /// The number of events
+    pub events: u64,
+    /// The exposure, i.e. the time or size over which the events were
+    /// counted
+    pub exposure: f64,
+    /// The estimated rate, `events / exposure`
+    pub estimate: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+impl PoissonTestResult {
+    /// Returns the exact confidence interval for the rate at the confidence
+    /// `level`, obtained from the relation between the Poisson and gamma
+    /// distributions. The interval is one-sided for the `Less` and
+    /// `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    ///
+    /// # Formula
+    ///
+    /// ```ignore
+    /// (G(α / 2; x) / T, G(1 - α / 2; x + 1) / T)
+    /// ```
+    ///
+    /// where `G(p; a)` is the `p` quantile of the gamma distribution with
+    /// shape `a` and rate 1, `x` the number of events, `T` the exposure and
+    /// `α = 1 - level`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{poisson_test, Alternative};
+    ///
+    /// let t = poisson_test(10, 2.0, 3.0, Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!((ci.lower - 2.3977).abs() < 1e-4);
+    /// assert!((ci.upper - 9.1952).abs() < 1e-4);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        check_level(level)?;
+        let (events, exposure) = (self.events, self.exposure);
+        let (lower, upper) = match self.alternative {
+            Alternative::TwoSided => garwood(events, exposure, (1.0 - level) / 2.0)?,
+            Alternative::Less => (0.0, garwood(events, exposure, 1.0 - level)?.1),
+            Alternative::Greater => (garwood(events, exposure, 1.0 - level)?.0, f64::INFINITY),
+        };
+        Ok(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        })
+    }
+}
+
+/// Performs the exact test of the null hypothesis that `events` counted
+/// over `exposure` come from a Poisson process with `rate`, i.e. that the
+/// number of events is Poisson distributed with mean `rate * exposure`
+///
+/// # Errors
+///
+/// Returns an error if `exposure` is not finite and positive or `rate` is
+/// not finite and non-negative
+///
+/// # Remarks
+///
+/// The two-sided p-value is the total probability of the numbers of events
+/// that are at most as likely as the observed one.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{poisson_test, Alternative};
+///
+/// // 10 events in 2 years against an expected 3 per year
+/// let t = poisson_test(10, 2.0, 3.0, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.estimate, 5.0);
+/// assert!((t.p_value - 0.1012753).abs() < 1e-7);
+/// ```
+pub fn poisson_test(
+    events: u64,
+    exposure: f64,
+    rate: f64,
+    alternative: Alternative,
+) -> Result<PoissonTestResult> {
+    if !exposure.is_finite() || exposure <= 0.0 || !rate.is_finite() || rate < 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let mean = rate * exposure;
+    let x = events;
+    let p_value = if mean == 0.0 {
+        match (alternative, x) {
+            (Alternative::Less, _) | (_, 0) => 1.0,
+            _ => 0.0,
+        }
+    } else {
+        let poisson = Poisson::new(mean)?;
+        let lower = |x: u64| poisson.cdf(x);
+        let upper = |x: u64| if x == 0 { 1.0 } else { poisson.sf(x - 1) };
+        match alternative {
+            Alternative::Less => lower(x),
+            Alternative::Greater => upper(x),
+            Alternative::TwoSided => {
+                let mode = mean.floor() as u64;
+                two_sided_p_value(x, mode, u64::MAX, |k| poisson.pmf(k), lower, upper)
+            }
+        }
+    };
+    Ok(PoissonTestResult {
+        events,
+        exposure,
+        estimate: events as f64 / exposure,
+        p_value,
+        alternative,
+    })
+}
+
+/// The result of an exact test for the ratio of the rates of two Poisson
+/// processes
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PoissonRatioTestResult {
+    /// The numbers of events of the two samples
+    pub events: [u64; 2],
+    /// The exposures of the two samples
+    pub exposures: [f64; 2],
+    /// The estimated ratio of the rate of the first sample to that of the
+    /// second
+    pub estimate: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+impl PoissonRatioTestResult {
+    /// Returns the exact conditional confidence interval for the rate ratio
+    /// at the confidence `level`, obtained from the Clopper-Pearson interval
+    /// of the binomial distribution of the events of the first sample given
+    /// the total number of events. The interval is one-sided for the `Less`
+    /// and `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)` or there are no
+    /// events
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{poisson_ratio_test, Alternative};
+    ///
+    /// let t = poisson_ratio_test([30, 12], [1000.0, 1000.0], 1.0, Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!(1.0 < ci.lower && ci.contains(t.estimate));
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        let [x1, x2] = self.events;
+        if x1 + x2 == 0 {
+            return Err(StatsError::BadParams);
+        }
+        let ci = clopper_pearson_interval(x1, x1 + x2, level, self.alternative)?;
+        let scale = self.exposures[1] / self.exposures[0];
+        let ratio = |p: f64| {
+            if p == 1.0 {
+                f64::INFINITY
+            } else {
+                p / (1.0 - p) * scale
+            }
+        };
+        Ok(ConfidenceInterval {
+            lower: ratio(ci.lower),
+            upper: ratio(ci.upper),
+            level,
+        })
+    }
+}
+
+/// Performs the exact conditional test of the null hypothesis that the
+/// ratio of the rates of two Poisson processes, with `events[i]` counted
+/// over `exposures[i]`, is `ratio`. The `Greater` alternative is that the
+/// rate of the first process is more than `ratio` times that of the second.
+///
+/// # Errors
+///
+/// Returns an error if an exposure is not finite and positive or `ratio`
+/// is not finite and positive
+///
+/// # Remarks
+///
+/// Given the total number of events, the events of the first sample are
+/// binomially distributed with the probability of success
+/// `ratio * T_1 / (ratio * T_1 + T_2)` under the null hypothesis, and the
+/// result is that of the exact binomial test.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{poisson_ratio_test, Alternative};
+///
+/// // conversions in two arms with equal traffic
+/// let t = poisson_ratio_test([30, 12], [1000.0, 1000.0], 1.0, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.estimate, 2.5);
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn poisson_ratio_test(
+    events: [u64; 2],
+    exposures: [f64; 2],
+    ratio: f64,
+    alternative: Alternative,
+) -> Result<PoissonRatioTestResult> {
+    if exposures.iter().any(|t| !t.is_finite() || *t <= 0.0) || !ratio.is_finite() || ratio <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let [x1, x2] = events;
+    let [t1, t2] = exposures;
+    let p_value = if x1 + x2 == 0 {
+        1.0
+    } else {
+        let p = ratio * t1 / (ratio * t1 + t2);
+        binomial_test(x1, x1 + x2, p, alternative)?.p_value
+    };
+    Ok(PoissonRatioTestResult {
+        events,
+        exposures,
+        estimate: (x1 as f64 / t1) / (x2 as f64 / t2),
+        p_value,
+        alternative,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fisher_exact() {
+        let t = fisher_exact_test([[10, 3], [2, 15]], Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.0005367241191434358, 1e-16);
+        assert_almost_eq!(t.odds_ratio, 21.305317556750337, 1e-10);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 2.7533827882493237, 1e-10);
+        assert_almost_eq!(ci.upper, 301.46233797151626, 1e-8);
+
+        let t = fisher_exact_test([[10, 3], [2, 15]], Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.99998451901868615, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_eq!(ci.lower, 0.0);
+        assert_almost_eq!(ci.upper, 200.22044935876916, 1e-8);
+
+        let t = fisher_exact_test([[10, 3], [2, 15]], Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.00046518094336290501, 1e-16);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 3.5092700538433168, 1e-10);
+        assert_eq!(ci.upper, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_fisher_exact_large() {
+        let t = fisher_exact_test([[120, 880], [150, 850]], Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.057595850868926789, 1e-12);
+        assert_almost_eq!(t.odds_ratio, 0.77282672878543195, 1e-12);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.59157356771506895, 1e-12);
+        assert_almost_eq!(ci.upper, 1.0080386288748263, 1e-12);
+        let t = fisher_exact_test([[120, 880], [150, 850]], Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.028797925434463394, 1e-12);
+    }
+
+    #[test]
+    fn test_fisher_exact_boundary() {
+        let t = fisher_exact_test([[0, 5], [6, 4]], Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.043956043956043956, 1e-15);
+        assert_eq!(t.odds_ratio, 0.0);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_eq!(ci.lower, 0.0);
+        assert_almost_eq!(ci.upper, 1.2787375244879905, 1e-12);
+        let t = fisher_exact_test([[6, 4], [0, 5]], Alternative::TwoSided).unwrap();
+        assert_eq!(t.odds_ratio, f64::INFINITY);
+        assert_eq!(t.confidence_interval(0.95).unwrap().upper, f64::INFINITY);
+        let t = fisher_exact_test([[0, 5], [0, 4]], Alternative::TwoSided).unwrap();
+        assert_eq!(t.p_value, 1.0);
+        assert!(t.odds_ratio.is_nan());
+    }
+
+    #[test]
+    fn test_fisher_exact_bad_params() {
+        assert!(fisher_exact_test([[0, 0], [0, 0]], Alternative::TwoSided).is_err());
+        let t = fisher_exact_test([[3, 1], [1, 3]], Alternative::TwoSided).unwrap();
+        assert!(t.confidence_interval(0.0).is_err());
+        assert!(t.confidence_interval(1.0).is_err());
+    }
+
+    #[test]
+    fn test_binomial() {
+        let t = binomial_test(3, 40, 0.2, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.047869463388393024, 1e-14);
+        assert_eq!(t.estimate, 0.075);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.015742179851041532, 1e-14);
+        assert_almost_eq!(ci.upper, 0.20386474873289896, 1e-14);
+
+        let t = binomial_test(3, 40, 0.2, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.028462094459744501, 1e-15);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_eq!(ci.lower, 0.0);
+        assert_almost_eq!(ci.upper, 0.18258685001590121, 1e-14);
+
+        let t = binomial_test(3, 40, 0.2, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.99205786272518513, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.020753601475701205, 1e-14);
+        assert_eq!(ci.upper, 1.0);
+    }
+
+    #[test]
+    fn test_binomial_two_sided() {
+        let t = binomial_test(60, 100, 0.45, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.0033559979488459842, 1e-15);
+        let t = binomial_test(682, 925, 0.75, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.38249155957485168, 1e-12);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.70766826407903902, 1e-12);
+        assert_almost_eq!(ci.upper, 0.76540655824152524, 1e-12);
+        // the most likely outcomes
+        assert_eq!(binomial_test(1, 3, 0.5, Alternative::TwoSided).unwrap().p_value, 1.0);
+        assert_eq!(binomial_test(10, 20, 0.5, Alternative::TwoSided).unwrap().p_value, 1.0);
+        assert_eq!(binomial_test(0, 10, 0.0, Alternative::TwoSided).unwrap().p_value, 1.0);
+        assert_eq!(binomial_test(1, 10, 0.0, Alternative::TwoSided).unwrap().p_value, 0.0);
+    }
+
+    #[test]
+    fn test_binomial_bad_params() {
+        assert!(binomial_test(1, 0, 0.5, Alternative::TwoSided).is_err());
+        assert!(binomial_test(11, 10, 0.5, Alternative::TwoSided).is_err());
+        assert!(binomial_test(1, 10, 1.5, Alternative::TwoSided).is_err());
+        assert!(binomial_test(1, 10, f64::NAN, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_poisson() {
+        let t = poisson_test(10, 2.0, 3.0, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.10127528223154033, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 2.3976943480662168, 1e-12);
+        assert_almost_eq!(ci.upper, 9.1951780210088885, 1e-12);
+        let t = poisson_test(10, 2.0, 3.0, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.9573790764174619, 1e-14);
+        let t = poisson_test(10, 2.0, 3.0, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.083924016994875823, 1e-14);
+
+        let t = poisson_test(2, 1.0, 7.5, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.04182136609468108, 1e-14);
+        let t = poisson_test(2, 1.0, 7.5, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.020256715056664405, 1e-14);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_eq!(ci.lower, 0.0);
+        assert_almost_eq!(ci.upper, 6.2957936218719885, 1e-12);
+    }
+
+    #[test]
+    fn test_poisson_far_tail() {
+        let t = poisson_test(137, 24.19893, 1.0, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value / 2.8452272641144929e-56, 1.0, 1e-10);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 4.7531248048487506, 1e-12);
+        assert_almost_eq!(ci.upper, 6.6927093341760733, 1e-12);
+    }
+
+    #[test]
+    fn test_poisson_zero_rate() {
+        assert_eq!(poisson_test(0, 1.0, 0.0, Alternative::TwoSided).unwrap().p_value, 1.0);
+        assert_eq!(poisson_test(1, 1.0, 0.0, Alternative::TwoSided).unwrap().p_value, 0.0);
+        assert_eq!(poisson_test(1, 1.0, 0.0, Alternative::Less).unwrap().p_value, 1.0);
+        let t = poisson_test(0, 2.0, 1.0, Alternative::TwoSided).unwrap();
+        assert_eq!(t.confidence_interval(0.95).unwrap().lower, 0.0);
+    }
+
+    #[test]
+    fn test_poisson_bad_params() {
+        assert!(poisson_test(1, 0.0, 1.0, Alternative::TwoSided).is_err());
+        assert!(poisson_test(1, f64::INFINITY, 1.0, Alternative::TwoSided).is_err());
+        assert!(poisson_test(1, 1.0, -1.0, Alternative::TwoSided).is_err());
+        assert!(poisson_test(1, 1.0, f64::NAN, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_poisson_ratio() {
+        let t = poisson_ratio_test([30, 12], [1000.0, 1000.0], 1.0, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.0079158973348967265, 1e-15);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 1.2429631350621517, 1e-12);
+        assert_almost_eq!(ci.upper, 5.3616684835077608, 1e-12);
+
+        let t = poisson_ratio_test([11, 23], [800.0, 1083.0], 2.0, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.p_value, 0.001470506521579986, 1e-15);
+        assert_almost_eq!(t.estimate, 11.0 / 800.0 / (23.0 / 1083.0), 1e-15);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.28494125139725855, 1e-12);
+        assert_almost_eq!(ci.upper, 1.3825650441208016, 1e-12);
+        let t = poisson_ratio_test([11, 23], [800.0, 1083.0], 2.0, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.0011941401370268149, 1e-15);
+    }
+
+    #[test]
+    fn test_poisson_ratio_bad_params() {
+        assert!(poisson_ratio_test([1, 2], [0.0, 1.0], 1.0, Alternative::TwoSided).is_err());
+        assert!(poisson_ratio_test([1, 2], [1.0, 1.0], 0.0, Alternative::TwoSided).is_err());
+        let t = poisson_ratio_test([0, 0], [1.0, 1.0], 1.0, Alternative::TwoSided).unwrap();
+        assert_eq!(t.p_value, 1.0);
+        assert!(t.confidence_interval(0.95).is_err());
+    }
+}
@@ -0,0 +1,409 @@
+use crate::distribution::{ContinuousCDF, Kolmogorov};
+use crate::function::factorial;
+use crate::stats_tests::Alternative;
+use crate::{Result, StatsError};
+use nalgebra::DMatrix;
+
+/// The result of a Kolmogorov-Smirnov test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KsTestResult {
+    /// The Kolmogorov-Smirnov statistic, i.e. the (one-sided) maximal
+    /// distance between the compared distribution functions
+    pub statistic: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+}
+
+/// Largest sample size for which the exact two-sided one-sample p-value is
+/// computed by the Marsaglia-Tsang-Wang algorithm
+const KS_1SAMP_EXACT_MAX: usize = 1000;
+
+/// Largest product of the sample sizes for which the exact two-sample
+/// p-value is computed
+const KS_2SAMP_EXACT_MAX: usize = 10_000_000;
+
+fn sorted(data: &[f64]) -> Result<Vec<f64>> {
+    if data.is_empty() || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(data)
+}
+
+/// Performs the one-sample Kolmogorov-Smirnov test of the null hypothesis
+/// that `data` is drawn from the continuous distribution `dist`. For the
+/// `Greater` alternative the statistic is `D+`, the largest amount by which
+/// the empirical distribution function exceeds the cdf of `dist`, for
+/// `Less` it is `D-`, the largest amount by which it falls below, and for
+/// `TwoSided` it is `D = max(D+, D-)`.
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`
+///
+/// # Remarks
+///
+/// The one-sided p-values are computed exactly by the Birnbaum-Tingey
+/// formula. The two-sided p-value is computed exactly by the algorithm of
+/// Marsaglia, Tsang and Wang (2003), "Evaluating Kolmogorov's
+/// Distribution", for samples of up to 1000 observations and from the
+/// asymptotic Kolmogorov distribution of `sqrt(n) * D` otherwise. In the
+/// far tail, where `1 - P(D < d)` would lose precision, twice the one-sided
+/// p-value is used instead, which is exact for `d >= 0.5` and accurate to
+/// its cube otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Normal;
+/// use statrs::stats_tests::{ks_1samp, Alternative};
+///
+/// let data = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+/// let normal = Normal::new(0.0, 1.0).unwrap();
+/// let t = ks_1samp(&data, &normal, Alternative::TwoSided).unwrap();
+/// assert!(t.p_value > 0.05);
+/// ```
+pub fn ks_1samp<D: ContinuousCDF<f64, f64>>(
+    data: &[f64],
+    dist: &D,
+    alternative: Alternative,
+) -> Result<KsTestResult> {
+    let data = sorted(data)?;
+    let n = data.len() as f64;
+    let (mut d_plus, mut d_minus) = (0.0f64, 0.0f64);
+    for (i, &x) in data.iter().enumerate() {
+        let cdf = dist.cdf(x);
+        d_plus = d_plus.max((i + 1) as f64 / n - cdf);
+        d_minus = d_minus.max(cdf - i as f64 / n);
+    }
+    let (statistic, p_value) = match alternative {
+        Alternative::Greater => (d_plus, smirnov_sf(data.len(), d_plus)),
+        Alternative::Less => (d_minus, smirnov_sf(data.len(), d_minus)),
+        Alternative::TwoSided => {
+            let d = d_plus.max(d_minus);
+            (d, kolmogorov_smirnov_sf(data.len(), d))
+        }
+    };
+    Ok(KsTestResult { statistic, p_value })
+}
+
+/// Performs the two-sample Kolmogorov-Smirnov test of the null hypothesis
+/// that `x` and `y` are drawn from the same continuous distribution. For the
+/// `Greater` alternative the statistic is the largest amount by which the
+/// empirical distribution function of `x` exceeds that of `y`, for `Less`
+/// the largest amount by which it falls below, and for `TwoSided` the
+/// largest absolute difference.
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` is empty or contains `NaN`
+///
+/// # Remarks
+///
+/// The p-value is computed exactly by enumerating the lattice paths of the
+/// merged sample (Hodges, 1958) if the product of the sample sizes does not
+/// exceed `10^7`, and from the asymptotic distribution of
+/// `sqrt(n * m / (n + m)) * D` otherwise. The exact p-value assumes there are
+/// no ties between the samples, it is conservative otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{ks_2samp, Alternative};
+///
+/// let x = [0.61, 0.29, 0.06, 0.59, -1.73, -0.74, 0.51, -0.56, 0.39, 1.64];
+/// let y = [2.2, 1.8, 3.4, 2.05, 1.2, 2.5, 4.1, 1.3];
+/// let t = ks_2samp(&x, &y, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.statistic, 0.9);
+/// assert!(t.p_value < 0.001);
+/// ```
+pub fn ks_2samp(x: &[f64], y: &[f64], alternative: Alternative) -> Result<KsTestResult> {
+    let (x, y) = (sorted(x)?, sorted(y)?);
+    let (n, m) = (x.len(), y.len());
+
+    // the distance between the empirical distribution functions is tracked
+    // as the integer n * m * (F_x - F_y) to compare exactly with the lattice
+    // paths below
+    let (mut i, mut j) = (0, 0);
+    let (mut d_plus, mut d_minus) = (0i64, 0i64);
+    while i < n && j < m {
+        let t = if x[i] <= y[j] { x[i] } else { y[j] };
+        while i < n && x[i] == t {
+            i += 1;
+        }
+        while j < m && y[j] == t {
+            j += 1;
+        }
+        let diff = (i * m) as i64 - (j * n) as i64;
+        d_plus = d_plus.max(diff);
+        d_minus = d_minus.max(-diff);
+    }
+    let h = match alternative {
+        Alternative::Greater => d_plus,
+        Alternative::Less => d_minus,
+        Alternative::TwoSided => d_plus.max(d_minus),
+    };
+    let statistic = h as f64 / (n * m) as f64;
+
+    let p_value = if h == 0 {
+        1.0
+    } else if n * m <= KS_2SAMP_EXACT_MAX {
+        let outside = |i: usize, j: usize| {
+            let diff = (i * m) as i64 - (j * n) as i64;
+            match alternative {
+                Alternative::Greater => diff >= h,
+                Alternative::Less => -diff >= h,
+                Alternative::TwoSided => diff.abs() >= h,
+            }
+        };
+        ks_2samp_exact_sf(n, m, outside)
+    } else {
+        let en = (n * m) as f64 / (n + m) as f64;
+        match alternative {
+            Alternative::TwoSided => Kolmogorov::new().sf(en.sqrt() * statistic),
+            _ => (-2.0 * en * statistic * statistic).exp(),
+        }
+    };
+    Ok(KsTestResult { statistic, p_value })
+}
+
+/// Computes the probability that a uniformly random lattice path from
+/// `(0, 0)` to `(n, m)` visits a point for which `outside` is true. The
+/// path takes a step in `i` for every observation of the first sample and
+/// in `j` for every observation of the second sample in the merged order.
+fn ks_2samp_exact_sf<F: Fn(usize, usize) -> bool>(n: usize, m: usize, outside: F) -> f64 {
+    // q[j] is the probability of reaching (i, j) without visiting an
+    // outside point before
+    let mut q = vec![0.0; m + 1];
+    let mut hit = 0.0;
+    for i in 0..=n {
+        for j in 0..=m {
+            let mut p = if i == 0 && j == 0 { 1.0 } else { 0.0 };
+            if i > 0 {
+                let (rem_x, rem_y) = ((n - i + 1) as f64, (m - j) as f64);
+                p += q[j] * rem_x / (rem_x + rem_y);
+            }
+            if j > 0 {
+                let (rem_x, rem_y) = ((n - i) as f64, (m - j + 1) as f64);
+                p += q[j - 1] * rem_y / (rem_x + rem_y);
+            }
+            if outside(i, j) {
+                hit += p;
+                q[j] = 0.0;
+            } else {
+                q[j] = p;
+            }
+        }
+    }
+    hit.min(1.0)
+}
+
+/// Computes `P(D_n >= d)` for the two-sided one-sample Kolmogorov-Smirnov
+/// statistic `D_n` of a sample of size `n`
+fn kolmogorov_smirnov_sf(n: usize, d: f64) -> f64 {
+    let one_sided = smirnov_sf(n, d);
+    if d >= 0.5 || one_sided < 1e-5 {
+        (2.0 * one_sided).min(1.0)
+    } else if n <= KS_1SAMP_EXACT_MAX {
+        (1.0 - marsaglia_tsang_wang_cdf(n, d)).max(0.0)
+    } else {
+        Kolmogorov::new().sf((n as f64).sqrt() * d)
+    }
+}
+
+/// Computes `P(D+_n >= d)` for the one-sided one-sample Kolmogorov-Smirnov
+/// statistic `D+_n` of a sample of size `n` by the Birnbaum-Tingey formula
+///
+/// # Formula
+///
+/// ```ignore
+/// d * Σ_{j = 0}^{floor(n * (1 - d))} C(n, j) * (1 - d - j / n)^(n - j) * (d + j / n)^(j - 1)
+/// ```
+fn smirnov_sf(n: usize, d: f64) -> f64 {
+    if d <= 0.0 {
+        return 1.0;
+    }
+    if d >= 1.0 {
+        return 0.0;
+    }
+    let n_f = n as f64;
+    let j_max = (n_f * (1.0 - d)).floor() as usize;
+    let sum: f64 = (0..=j_max.min(n))
+        .map(|j| {
+            let j_f = j as f64;
+            let a = 1.0 - d - j_f / n_f;
+            if a <= 0.0 {
+                return 0.0;
+            }
+            (factorial::ln_binomial(n as u64, j as u64)
+                + (n_f - j_f) * a.ln()
+                + (j_f - 1.0) * (d + j_f / n_f).ln())
+            .exp()
+        })
+        .sum();
+    (d * sum).min(1.0)
+}
+
+/// Computes `P(D_n < d)` for the two-sided one-sample Kolmogorov-Smirnov
+/// statistic `D_n` of a sample of size `n` by the algorithm of Marsaglia,
+/// Tsang and Wang (2003), which evaluates Durbin's matrix formula with
+/// explicit tracking of a decimal exponent to avoid overflow.
+fn marsaglia_tsang_wang_cdf(n: usize, d: f64) -> f64 {
+    let nd = n as f64 * d;
+    let k = nd.floor() as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - nd;
+
+    let mut a = DMatrix::from_fn(m, m, |i, j| if i + 1 >= j { 1.0 } else { 0.0 });
+    for i in 0..m {
+        a[(i, 0)] -= h.powi(i as i32 + 1);
+        a[(m - 1, i)] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        a[(m - 1, 0)] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 > j {
+                for g in 1..=(i + 1 - j) {
+                    a[(i, j)] /= g as f64;
+                }
+            }
+        }
+    }
+
+    let (q, mut exponent) = matrix_power(&a, n, k - 1);
+    let mut s = q[(k - 1, k - 1)];
+    for i in 1..=n {
+        s = s * i as f64 / n as f64;
+        if s < 1e-140 {
+            s *= 1e140;
+            exponent -= 140;
+        }
+    }
+    s * 10f64.powi(exponent)
+}
+
+/// Computes `a^n` as `(b, e)` with `a^n = b * 10^e`, rescaling whenever the
+/// central element `(c, c)` grows too large
+fn matrix_power(a: &DMatrix<f64>, n: usize, c: usize) -> (DMatrix<f64>, i32) {
+    if n == 1 {
+        return (a.clone(), 0);
+    }
+    let (half, half_exponent) = matrix_power(a, n / 2, c);
+    let mut b = &half * &half;
+    let mut exponent = 2 * half_exponent;
+    if n % 2 == 1 {
+        b = a * b;
+    }
+    if b[(c, c)] > 1e140 {
+        b *= 1e-140;
+        exponent += 140;
+    }
+    (b, exponent)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::{Normal, Uniform};
+
+    const X: [f64; 9] = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73];
+    const Y: [f64; 8] = [1.2, 1.5, 3.4, 1.05, 2.2, 1.8, 6.5, 1.3];
+    const R: [f64; 10] = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8];
+
+    #[test]
+    fn test_marsaglia_tsang_wang_cdf() {
+        // the example of Marsaglia, Tsang and Wang (2003)
+        assert_almost_eq!(marsaglia_tsang_wang_cdf(10, 0.274), 0.62847961545650427530, 1e-14);
+        assert_almost_eq!(1.0 - marsaglia_tsang_wang_cdf(50, 0.4), 9.8635633644100785874e-8, 1e-15);
+    }
+
+    #[test]
+    fn test_smirnov_sf() {
+        assert_eq!(smirnov_sf(10, 0.0), 1.0);
+        assert_eq!(smirnov_sf(10, 1.0), 0.0);
+        assert_almost_eq!(smirnov_sf(1, 0.3), 0.7, 1e-15);
+        assert_almost_eq!(2.0 * smirnov_sf(50, 0.4), 9.8635633644100785874e-8, 1e-20);
+    }
+
+    #[test]
+    fn test_kolmogorov_smirnov_sf() {
+        // the tail switches to twice the one-sided p-value
+        assert_almost_eq!(kolmogorov_smirnov_sf(50, 0.4), 9.8635633644100785874e-8, 1e-20);
+        // the asymptotic distribution is used for large samples
+        assert_almost_eq!(kolmogorov_smirnov_sf(2000, 0.03), 0.054646330113863560772, 1e-14);
+    }
+
+    #[test]
+    fn test_ks_1samp() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let t = ks_1samp(&R, &normal, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.statistic, 0.24520070830044200604, 1e-10);
+        assert_almost_eq!(t.p_value, 0.50851140060229526641, 1e-10);
+
+        let t = ks_1samp(&R, &normal, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.statistic, 0.099516575857616222799, 1e-10);
+        assert_almost_eq!(t.p_value, 0.76627161758573132965, 1e-10);
+
+        let t = ks_1samp(&R, &normal, Alternative::Less).unwrap();
+        assert_almost_eq!(t.statistic, 0.24520070830044200604, 1e-10);
+        assert_almost_eq!(t.p_value, 0.25783805913507420015, 1e-10);
+
+        let t = ks_1samp(&R, &Normal::new(0.5, 2.0).unwrap(), Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.statistic, 0.13982783727702898147, 1e-10);
+        assert_almost_eq!(t.p_value, 0.97463488453027266582, 1e-10);
+    }
+
+    #[test]
+    fn test_ks_1samp_large() {
+        let uniform = Uniform::new(0.0, 1.0).unwrap();
+        let data: Vec<f64> = (0..200).map(|i| ((i as f64 + 0.5) / 200.0).powf(1.3)).collect();
+        let t = ks_1samp(&data, &uniform, Alternative::TwoSided).unwrap();
+        let d = (0..200).map(|i| (i as f64 + 1.0) / 200.0 - data[i]).fold(0.0, f64::max);
+        assert_almost_eq!(t.statistic, d, 1e-15);
+        assert_almost_eq!(t.p_value, 1.0 - marsaglia_tsang_wang_cdf(200, d), 1e-14);
+    }
+
+    #[test]
+    fn test_ks_1samp_bad_data() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        assert!(ks_1samp(&[], &normal, Alternative::TwoSided).is_err());
+        assert!(ks_1samp(&[0.0, f64::NAN], &normal, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_ks_2samp() {
+        // reference p-values by enumeration of all 24310 splits of the
+        // merged sample
+        let t = ks_2samp(&X, &Y, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.statistic, 1.0 / 3.0, 1e-15);
+        assert_almost_eq!(t.p_value, 2801.0 / 4862.0, 1e-14);
+
+        let t = ks_2samp(&X, &Y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.statistic, 1.0 / 3.0, 1e-15);
+        assert_almost_eq!(t.p_value, 553.0 / 1870.0, 1e-14);
+
+        let t = ks_2samp(&X, &Y, Alternative::Less).unwrap();
+        assert_eq!(t.statistic, 0.0);
+        assert_eq!(t.p_value, 1.0);
+
+        let t = ks_2samp(&Y, &X, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 553.0 / 1870.0, 1e-14);
+        assert!(ks_2samp(&X, &[], Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_ks_2samp_large() {
+        let x: Vec<f64> = (0..4000).map(|i| i as f64 / 4000.0).collect();
+        let y: Vec<f64> = (0..3000).map(|i| 0.05 + i as f64 / 3000.0).collect();
+        let t = ks_2samp(&x, &y, Alternative::TwoSided).unwrap();
+        assert_almost_eq!(t.statistic, 0.05, 1e-3);
+        let en = 4000.0 * 3000.0 / 7000.0;
+        assert_almost_eq!(t.p_value, Kolmogorov::new().sf(f64::sqrt(en) * t.statistic), 1e-15);
+        let t = ks_2samp(&x, &y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, (-2.0 * en * t.statistic * t.statistic).exp(), 1e-15);
+    }
+}
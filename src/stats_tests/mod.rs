@@ -0,0 +1,59 @@
+//! Provides hypothesis tests and confidence intervals for analyzing data
+//! sets
+use crate::{Result, StatsError};
+
+pub use self::anova::*;
+pub use self::chi_square::*;
+pub use self::confidence_interval::*;
+pub use self::correlation::*;
+pub use self::exact::*;
+pub use self::ks_test::*;
+pub use self::multiple_testing::*;
+pub use self::normality::*;
+pub use self::power::*;
+pub use self::rank_tests::*;
+pub use self::t_test::*;
+
+mod anova;
+mod chi_square;
+mod confidence_interval;
+mod correlation;
+mod exact;
+mod ks_test;
+mod multiple_testing;
+mod normality;
+mod power;
+mod rank_tests;
+mod t_test;
+
+/// Enumeration of the alternative hypotheses of a test
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Alternative {
+    /// The tested quantity differs from its value under the null hypothesis
+    TwoSided,
+    /// The tested quantity is less than its value under the null hypothesis
+    Less,
+    /// The tested quantity is greater than its value under the null
+    /// hypothesis
+    Greater,
+}
+
+/// Returns the p-value of a test whose statistic has the lower tail
+/// probability `cdf` and upper tail probability `sf` under the null
+/// hypothesis. Both tails are passed so that neither has to be computed as
+/// the complement of the other.
+pub(crate) fn p_value(cdf: f64, sf: f64, alternative: Alternative) -> f64 {
+    match alternative {
+        Alternative::TwoSided => (2.0 * cdf.min(sf)).min(1.0),
+        Alternative::Less => cdf,
+        Alternative::Greater => sf,
+    }
+}
+
+/// Checks that the confidence `level` is in `(0, 1)`
+pub(crate) fn check_level(level: f64) -> Result<()> {
+    if level.is_nan() || level <= 0.0 || level >= 1.0 {
+        return Err(StatsError::ArgIntervalExcl("level", 0.0, 1.0));
+    }
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use crate::statistics::{Data, OrderStatistics, RankTieBreaker};
+use crate::{Result, StatsError};
+
+/// Enumeration of the methods of adjusting a family of p-values for
+/// multiple comparisons
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PValueAdjustment {
+    /// Bonferroni's correction, controlling the family-wise error rate
+    Bonferroni,
+    /// Holm's step-down procedure, controlling the family-wise error rate
+    Holm,
+    /// Hochberg's step-up procedure, controlling the family-wise error rate
+    /// for independent or positively dependent p-values
+    Hochberg,
+    /// Hommel's procedure, controlling the family-wise error rate for
+    /// independent or positively dependent p-values
+    Hommel,
+    /// The Benjamini-Hochberg procedure, controlling the false discovery rate
+    /// for independent or positively dependent p-values
+    BenjaminiHochberg,
+    /// The Benjamini-Yekutieli procedure, controlling the false discovery
+    /// rate under arbitrary dependence
+    BenjaminiYekutieli,
+}
+
+/// The result of adjusting a family of p-values for multiple comparisons
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustedPValues {
+    /// The adjusted p-values, in the order of the original p-values
+    pub p_values: Vec<f64>,
+    /// Whether each hypothesis is rejected, that is whether its adjusted
+    /// p-value is at most the significance level
+    pub rejected: Vec<bool>,
+}
+
+/// The result of estimating Storey's q-values of a family of p-values
+#[derive(Debug, Clone, PartialEq)]
+pub struct QValues {
+    /// The q-values, in the order of the original p-values
+    pub q_values: Vec<f64>,
+    /// Whether each hypothesis is rejected, that is whether its q-value is at
+    /// most the significance level
+    pub rejected: Vec<bool>,
+    /// The estimated proportion of true null hypotheses
+    pub pi0: f64,
+}
+
+fn check_p_values(p_values: &[f64], alpha: f64) -> Result<()> {
+    if p_values.iter().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(StatsError::BadParams);
+    }
+    if !(0.0..=1.0).contains(&alpha) {
+        return Err(StatsError::ArgIntervalIncl("alpha", 0.0, 1.0));
+    }
+    Ok(())
+}
+
+/// Returns the indices of `p_values` in ascending order of the p-values,
+/// with ties in their original order
+fn ascending_order(p_values: &[f64]) -> Vec<usize> {
+    let ranks = Data::new(p_values.to_vec()).ranks(RankTieBreaker::First);
+    let mut order = vec![0; p_values.len()];
+    for (i, r) in ranks.into_iter().enumerate() {
+        order[r as usize - 1] = i;
+    }
+    order
+}
+
+/// Returns the adjusted p-values of the ascending p-values `sorted`
+fn adjust_sorted(sorted: &[f64], method: PValueAdjustment) -> Vec<f64> {
+    let n = sorted.len();
+    let nf = n as f64;
+    let mut adjusted: Vec<f64> = match method {
+        PValueAdjustment::Bonferroni => sorted.iter().map(|p| nf * p).collect(),
+        PValueAdjustment::Holm => sorted
+            .iter()
+            .enumerate()
+            .scan(0.0, |max: &mut f64, (i, p)| {
+                *max = max.max((n - i) as f64 * p);
+                Some(*max)
+            })
+            .collect(),
+        PValueAdjustment::Hochberg => step_up(sorted, |i| (n - i) as f64),
+        PValueAdjustment::BenjaminiHochberg => step_up(sorted, |i| nf / (i + 1) as f64),
+        PValueAdjustment::BenjaminiYekutieli => {
+            let harmonic: f64 = (1..=n).map(|k| 1.0 / k as f64).sum();
+            step_up(sorted, |i| harmonic * nf / (i + 1) as f64)
+        }
+        PValueAdjustment::Hommel => hommel(sorted),
+    };
+    for p in adjusted.iter_mut() {
+        *p = p.min(1.0);
+    }
+    adjusted
+}
+
+/// Returns the running minima, from the largest p-value down, of the
+/// ascending p-values `sorted` each multiplied by `factor` of its index
+fn step_up<F: Fn(usize) -> f64>(sorted: &[f64], factor: F) -> Vec<f64> {
+    let mut adjusted = vec![0.0; sorted.len()];
+    let mut min = f64::INFINITY;
+    for i in (0..sorted.len()).rev() {
+        min = min.min(factor(i) * sorted[i]);
+        adjusted[i] = min;
+    }
+    adjusted
+}
+
+/// Returns Hommel's adjusted p-values of the ascending p-values `sorted`,
+/// following the closed testing formulation of Wright (1992)
+fn hommel(sorted: &[f64]) -> Vec<f64> {
+    let n = sorted.len();
+    let initial = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, p)| n as f64 * p / (i + 1) as f64)
+        .fold(f64::INFINITY, f64::min);
+    let mut adjusted = vec![initial; n];
+    let mut q = vec![initial; n];
+    for m in (2..n).rev() {
+        let split = n - m + 1;
+        let q1 = (split..n)
+            .map(|i| m as f64 * sorted[i] / (i - split + 2) as f64)
+            .fold(f64::INFINITY, f64::min);
+        for i in 0..split {
+            q[i] = (m as f64 * sorted[i]).min(q1);
+        }
+        for i in split..n {
+            q[i] = q[split - 1];
+        }
+        for (a, q) in adjusted.iter_mut().zip(q.iter()) {
+            *a = a.max(*q);
+        }
+    }
+    for (a, p) in adjusted.iter_mut().zip(sorted) {
+        *a = a.max(*p);
+    }
+    adjusted
+}
+
+/// Returns the values of `sorted`, which are in the ascending order `order`
+/// of the original values, in the original order
+fn unsort(sorted: &[f64], order: &[usize]) -> Vec<f64> {
+    let mut values = vec![0.0; sorted.len()];
+    for (&i, &v) in order.iter().zip(sorted) {
+        values[i] = v;
+    }
+    values
+}
+
+/// Adjusts the family of `p_values` for multiple comparisons with `method`
+/// and rejects the hypotheses whose adjusted p-values are at most `alpha`.
+/// The adjusted p-values are those of R's `p.adjust`, so that rejecting at
+/// `alpha` is equivalent to applying the procedure at level `alpha`.
+///
+/// # Errors
+///
+/// Returns an error if a p-value is not in `[0, 1]` or `alpha` is not in
+/// `[0, 1]`
+///
+/// # Formula
+///
+/// With the `n` p-values sorted ascending as `p_(1) <= ... <= p_(n)`,
+///
+/// ```ignore
+/// Bonferroni: n * p_(i)
+/// Holm: max_{j <= i} (n - j + 1) * p_(j)
+/// Hochberg: min_{j >= i} (n - j + 1) * p_(j)
+/// Benjamini-Hochberg: min_{j >= i} n / j * p_(j)
+/// Benjamini-Yekutieli: min_{j >= i} H_n * n / j * p_(j)
+/// ```
+///
+/// where `H_n = Σ_{k=1}^n 1 / k`, all capped at `1`. Hommel's adjusted
+/// p-values are computed as in Wright (1992).
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{adjust_p_values, PValueAdjustment};
+///
+/// let p = [0.01, 0.04, 0.03, 0.005];
+/// let adjusted = adjust_p_values(&p, PValueAdjustment::Holm, 0.05).unwrap();
+/// for (a, b) in adjusted.p_values.iter().zip(&[0.03, 0.06, 0.06, 0.02]) {
+///     assert!((a - b).abs() < 1e-15);
+/// }
+/// assert_eq!(adjusted.rejected, [true, false, false, true]);
+///
+/// let adjusted = adjust_p_values(&p, PValueAdjustment::BenjaminiHochberg, 0.05).unwrap();
+/// for (a, b) in adjusted.p_values.iter().zip(&[0.02, 0.04, 0.04, 0.02]) {
+///     assert!((a - b).abs() < 1e-15);
+/// }
+/// assert!(adjusted.rejected.iter().all(|&r| r));
+/// ```
+pub fn adjust_p_values(
+    p_values: &[f64],
+    method: PValueAdjustment,
+    alpha: f64,
+) -> Result<AdjustedPValues> {
+    check_p_values(p_values, alpha)?;
+    let order = ascending_order(p_values);
+    let sorted: Vec<f64> = order.iter().map(|&i| p_values[i]).collect();
+    let p_values = unsort(&adjust_sorted(&sorted, method), &order);
+    let rejected = p_values.iter().map(|&p| p <= alpha).collect();
+    Ok(AdjustedPValues { p_values, rejected })
+}
+
+/// Estimates Storey's q-values of the family of `p_values`, the minimum
+/// false discovery rates at which each hypothesis is rejected, and rejects
+/// the hypotheses whose q-values are at most `alpha`. The proportion of true
+/// null hypotheses is estimated from the p-values above the tuning
+/// parameter `lambda`, commonly `0.5`.
+///
+/// # Errors
+///
+/// Returns an error if a p-value is not in `[0, 1]`, `alpha` is not in
+/// `[0, 1]`, `lambda` is not in `[0, 1)`, or no p-value is greater than
+/// `lambda`
+///
+/// # Formula
+///
+/// ```ignore
+/// π0 = min(1, #{p_i > λ} / (n * (1 - λ)))
+/// q_(i) = min(1, min_{j >= i} π0 * n / j * p_(j))
+/// ```
+///
+/// where `p_(1) <= ... <= p_(n)` are the sorted p-values, so that the
+/// q-values are the Benjamini-Hochberg adjusted p-values scaled by `π0`.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::storey_q_values;
+///
+/// let p = [0.001, 0.008, 0.039, 0.041, 0.042, 0.06, 0.074, 0.205, 0.6, 0.9];
+/// let q = storey_q_values(&p, 0.5, 0.05).unwrap();
+/// assert_eq!(q.pi0, 0.4);
+/// assert!((q.q_values[1] - 0.016).abs() < 1e-12);
+/// assert_eq!(q.rejected.iter().filter(|&&r| r).count(), 7);
+/// ```
+pub fn storey_q_values(p_values: &[f64], lambda: f64, alpha: f64) -> Result<QValues> {
+    check_p_values(p_values, alpha)?;
+    if !(0.0..1.0).contains(&lambda) {
+        return Err(StatsError::ArgIntervalExclMax("lambda", 0.0, 1.0));
+    }
+    let n = p_values.len() as f64;
+    let above = p_values.iter().filter(|&&p| p > lambda).count();
+    if above == 0 {
+        return Err(StatsError::BadParams);
+    }
+    let pi0 = (above as f64 / (n * (1.0 - lambda))).min(1.0);
+    let order = ascending_order(p_values);
+    let sorted: Vec<f64> = order.iter().map(|&i| p_values[i]).collect();
+    let mut q_values = step_up(&sorted, |i| pi0 * n / (i + 1) as f64);
+    for q in q_values.iter_mut() {
+        *q = q.min(1.0);
+    }
+    let q_values = unsort(&q_values, &order);
+    let rejected = q_values.iter().map(|&q| q <= alpha).collect();
+    Ok(QValues {
+        q_values,
+        rejected,
+        pi0,
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P_VALUES: [f64; 10] = [0.01, 0.04, 0.03, 0.005, 0.2, 0.02, 0.6, 0.011, 0.5, 0.03];
+
+    fn check(method: PValueAdjustment, expected: &[f64]) {
+        let adjusted = adjust_p_values(&P_VALUES, method, 0.05).unwrap();
+        for (&a, &e) in adjusted.p_values.iter().zip(expected) {
+            assert_almost_eq!(a, e, 1e-15);
+        }
+        for (&a, &r) in adjusted.p_values.iter().zip(&adjusted.rejected) {
+            assert_eq!(a <= 0.05, r);
+        }
+    }
+
+    #[test]
+    fn test_bonferroni() {
+        check(PValueAdjustment::Bonferroni, &[0.1, 0.4, 0.3, 0.05, 1.0, 0.2, 1.0, 0.11, 1.0, 0.3]);
+    }
+
+    #[test]
+    fn test_holm() {
+        check(PValueAdjustment::Holm, &[0.09, 0.18, 0.18, 0.05, 0.6, 0.14, 1.0, 0.09, 1.0, 0.18]);
+    }
+
+    #[test]
+    fn test_hochberg() {
+        check(PValueAdjustment::Hochberg, &[0.088, 0.16, 0.15, 0.05, 0.6, 0.14, 0.6, 0.088, 0.6, 0.15]);
+    }
+
+    #[test]
+    fn test_hommel() {
+        check(PValueAdjustment::Hommel, &[0.07, 0.16, 0.12, 0.045, 0.6, 0.1, 0.6, 0.07, 0.6, 0.12]);
+        let adjusted = adjust_p_values(&[0.02, 0.03], PValueAdjustment::Hommel, 0.05).unwrap();
+        assert_eq!(adjusted.p_values, [0.03, 0.03]);
+    }
+
+    #[test]
+    fn test_benjamini_hochberg() {
+        check(PValueAdjustment::BenjaminiHochberg, &[
+            0.03666666666666667, 0.05714285714285714, 0.05, 0.03666666666666667, 0.25,
+            0.05, 0.6, 0.03666666666666667, 0.5555555555555556, 0.05,
+        ]);
+    }
+
+    #[test]
+    fn test_benjamini_yekutieli() {
+        check(PValueAdjustment::BenjaminiYekutieli, &[
+            0.10739550264550264, 0.16736961451247165, 0.1464484126984127, 0.10739550264550264,
+            0.7322420634920634, 0.1464484126984127, 1.0, 0.10739550264550264, 1.0,
+            0.1464484126984127,
+        ]);
+    }
+
+    #[test]
+    fn test_single_and_empty() {
+        for &method in &[
+            PValueAdjustment::Bonferroni, PValueAdjustment::Holm, PValueAdjustment::Hochberg,
+            PValueAdjustment::Hommel, PValueAdjustment::BenjaminiHochberg,
+            PValueAdjustment::BenjaminiYekutieli,
+        ] {
+            assert_eq!(adjust_p_values(&[0.3], method, 0.05).unwrap().p_values, [0.3]);
+            assert!(adjust_p_values(&[], method, 0.05).unwrap().p_values.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_storey_q_values() {
+        let q = storey_q_values(&P_VALUES, 0.5, 0.1).unwrap();
+        assert_eq!(q.pi0, 0.2);
+        let bh = adjust_p_values(&P_VALUES, PValueAdjustment::BenjaminiHochberg, 0.05).unwrap();
+        for (&q, &p) in q.q_values.iter().zip(&bh.p_values) {
+            assert_almost_eq!(q, 0.2 * p, 1e-15);
+        }
+        assert_eq!(q.rejected.iter().filter(|&&r| r).count(), 8);
+        let q = storey_q_values(&P_VALUES, 0.0, 0.05).unwrap();
+        assert_eq!(q.pi0, 1.0);
+        assert_eq!(q.q_values, bh.p_values);
+    }
+
+    #[test]
+    fn test_bad_params() {
+        let method = PValueAdjustment::Holm;
+        assert!(adjust_p_values(&[0.1, f64::NAN], method, 0.05).is_err());
+        assert!(adjust_p_values(&[0.1, 1.5], method, 0.05).is_err());
+        assert!(adjust_p_values(&[0.1, -0.5], method, 0.05).is_err());
+        assert!(adjust_p_values(&[0.1, 0.2], method, 1.5).is_err());
+        assert!(adjust_p_values(&[0.1, 0.2], method, f64::NAN).is_err());
+        assert!(storey_q_values(&[0.1, 0.2], 1.0, 0.05).is_err());
+        assert!(storey_q_values(&[0.1, 0.2], 0.5, 0.05).is_err());
+        assert!(storey_q_values(&[0.1, 0.2], f64::NAN, 0.05).is_err());
+    }
+}
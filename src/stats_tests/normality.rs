@@ -0,0 +1,564 @@
+use crate::distribution::{ChiSquared, ContinuousCDF, Normal};
+use crate::function::evaluate::polynomial;
+use crate::statistics::Statistics;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The result of a test of normality or of another goodness-of-fit test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NormalityTestResult {
+    /// The test statistic
+    pub statistic: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+}
+
+/// Largest sample size for which the p-value of the Shapiro-Wilk test is
+/// accurate
+const SHAPIRO_WILK_MAX: usize = 5000;
+
+fn sorted(data: &[f64]) -> Result<Vec<f64>> {
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    Ok(data)
+}
+
+/// Performs the Shapiro-Wilk test of the null hypothesis that `data` is
+/// drawn from a normal distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than 3 or more than 5000
+/// observations, contains `NaN`, or if all observations are equal
+///
+/// # Remarks
+///
+/// The coefficients of the statistic and its p-value are computed with the
+/// approximations of Royston (1995), "Remark AS R94: A Remark on Algorithm
+/// AS 181: The W-test for Normality".
+///
+/// # Formula
+///
+/// ```ignore
+/// W = (Σ a_i * x_(i))^2 / Σ (x_i - x̄)^2
+/// ```
+///
+/// where `x_(i)` is the `i`th order statistic and `a_i` are the normalized
+/// coefficients of the best linear unbiased estimator of the standard
+/// deviation from the order statistics of a normal sample
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::shapiro_wilk_test;
+///
+/// // weights of 11 men in pounds, Shapiro and Wilk (1965)
+/// let data = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let t = shapiro_wilk_test(&data).unwrap();
+/// assert!((t.statistic - 0.79).abs() < 0.005);
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn shapiro_wilk_test(data: &[f64]) -> Result<NormalityTestResult> {
+    let n = data.len();
+    if !(3..=SHAPIRO_WILK_MAX).contains(&n) {
+        return Err(StatsError::BadParams);
+    }
+    let x = sorted(data)?;
+    let range = x[n - 1] - x[0];
+    if range == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let a = shapiro_wilk_coefficients(n);
+    // the statistic is invariant under location and scale changes, which
+    // are used to keep the sums of squares in range
+    let x: Vec<f64> = x.iter().map(|v| (v - x[0]) / range).collect();
+    let mean = x.iter().sum::<f64>() / n as f64;
+    let ssq: f64 = x.iter().map(|v| (v - mean) * (v - mean)).sum();
+    let numerator: f64 = a
+        .iter()
+        .enumerate()
+        .map(|(i, a)| a * (x[n - 1 - i] - x[i]))
+        .sum();
+    let w = (numerator * numerator / ssq).min(1.0);
+    Ok(NormalityTestResult {
+        statistic: w,
+        p_value: shapiro_wilk_sf(n, w),
+    })
+}
+
+/// Returns the first `n / 2` coefficients `a_n, a_(n-1), ...` of the
+/// Shapiro-Wilk statistic for a sample of size `n >= 3`, the others being
+/// their negatives
+fn shapiro_wilk_coefficients(n: usize) -> Vec<f64> {
+    const C1: [f64; 6] = [0.0, 0.221157, -0.147981, -2.07119, 4.434685, -2.706056];
+    const C2: [f64; 6] = [0.0, 0.042981, -0.293762, -1.752461, 5.682633, -3.582633];
+
+    let half = n / 2;
+    if n == 3 {
+        return vec![f64::consts::FRAC_1_SQRT_2];
+    }
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let nf = n as f64;
+    let m: Vec<f64> = (1..=half)
+        .map(|i| normal.inverse_cdf((i as f64 - 0.375) / (nf + 0.25)))
+        .collect();
+    let summ2 = 2.0 * m.iter().map(|m| m * m).sum::<f64>();
+    let ssumm2 = summ2.sqrt();
+    let rsn = 1.0 / nf.sqrt();
+    let a1 = polynomial(rsn, &C1) - m[0] / ssumm2;
+
+    let mut a = vec![0.0; half];
+    a[0] = a1;
+    let (first, fac) = if n > 5 {
+        let a2 = polynomial(rsn, &C2) - m[1] / ssumm2;
+        a[1] = a2;
+        let fac = ((summ2 - 2.0 * m[0] * m[0] - 2.0 * m[1] * m[1])
+            / (1.0 - 2.0 * a1 * a1 - 2.0 * a2 * a2))
+            .sqrt();
+        (2, fac)
+    } else {
+        (
+            1,
+            ((summ2 - 2.0 * m[0] * m[0]) / (1.0 - 2.0 * a1 * a1)).sqrt(),
+        )
+    };
+    for i in first..half {
+        a[i] = -m[i] / fac;
+    }
+    a
+}
+
+/// Returns the p-value of the Shapiro-Wilk statistic `w` of a sample of
+/// size `n`
+fn shapiro_wilk_sf(n: usize, w: f64) -> f64 {
+    const G: [f64; 2] = [-2.273, 0.459];
+    const C3: [f64; 4] = [0.544, -0.39978, 0.025054, -6.714e-4];
+    const C4: [f64; 4] = [1.3822, -0.77857, 0.062767, -0.0020322];
+    const C5: [f64; 4] = [-1.5861, -0.31082, -0.083751, 0.0038915];
+    const C6: [f64; 3] = [-0.4803, -0.082676, 0.0030302];
+
+    let nf = n as f64;
+    if n == 3 {
+        // exact, W is at least 3 / 4
+        let p = 6.0 / f64::consts::PI * (w.sqrt().asin() - f64::consts::FRAC_PI_3);
+        return p.max(0.0);
+    }
+    let mut y = (1.0 - w).ln();
+    let (mean, sd) = if n <= 11 {
+        let gamma = polynomial(nf, &G);
+        if y >= gamma {
+            return 0.0;
+        }
+        y = -(gamma - y).ln();
+        (polynomial(nf, &C3), polynomial(nf, &C4).exp())
+    } else {
+        let ln_n = nf.ln();
+        (polynomial(ln_n, &C5), polynomial(ln_n, &C6).exp())
+    };
+    Normal::new(mean, sd).unwrap().sf(y)
+}
+
+/// Performs the Anderson-Darling test of the null hypothesis that `data` is
+/// drawn from the continuous distribution `dist`, whose parameters are
+/// known in advance
+///
+/// # Errors
+///
+/// Returns an error if `data` is empty or contains `NaN`
+///
+/// # Remarks
+///
+/// The p-value is computed with the approximation of the finite sample
+/// distribution of `A^2` by Marsaglia and Marsaglia (2004), "Evaluating the
+/// Anderson-Darling Distribution", which is accurate to about `10^-5`. If
+/// the parameters of `dist` are estimated from `data`, the p-value is too
+/// large; use `anderson_darling_normality_test` to test for normality with
+/// unknown mean and variance.
+///
+/// # Formula
+///
+/// ```ignore
+/// A^2 = -n - Σ (2i - 1) / n * (ln F(x_(i)) + ln(1 - F(x_(n+1-i))))
+/// ```
+///
+/// where `F` is the cdf of `dist`, `x_(i)` the `i`th order statistic and `n`
+/// the number of observations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::distribution::Exp;
+/// use statrs::stats_tests::anderson_darling_test;
+///
+/// let data = [0.12, 0.45, 1.63, 0.08, 0.91, 2.71, 0.33, 0.57, 1.24, 0.19];
+/// let t = anderson_darling_test(&data, &Exp::new(1.0).unwrap()).unwrap();
+/// assert!(t.p_value > 0.5);
+/// ```
+pub fn anderson_darling_test<D: ContinuousCDF<f64, f64>>(
+    data: &[f64],
+    dist: &D,
+) -> Result<NormalityTestResult> {
+    if data.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let x = sorted(data)?;
+    let statistic = anderson_darling_statistic(&x, |v| (dist.cdf(v), dist.sf(v)));
+    Ok(NormalityTestResult {
+        statistic,
+        p_value: 1.0 - anderson_darling_cdf(x.len(), statistic),
+    })
+}
+
+/// Returns the Anderson-Darling statistic of the sorted `data` with the cdf
+/// and survival function `cdf_sf`
+fn anderson_darling_statistic<F: Fn(f64) -> (f64, f64)>(data: &[f64], cdf_sf: F) -> f64 {
+    let n = data.len();
+    let (cdf, sf): (Vec<f64>, Vec<f64>) = data.iter().map(|&x| cdf_sf(x)).unzip();
+    let s: f64 = (0..n)
+        .map(|i| (2 * i + 1) as f64 * (cdf[i].ln() + sf[n - 1 - i].ln()))
+        .sum();
+    -(n as f64) - s / n as f64
+}
+
+/// Returns `P(A^2 < z)` for a sample of size `n`, following Marsaglia and
+/// Marsaglia (2004)
+fn anderson_darling_cdf(n: usize, z: f64) -> f64 {
+    if z <= 0.0 {
+        return 0.0;
+    }
+    if z.is_infinite() {
+        return 1.0;
+    }
+    let x = if z < 2.0 {
+        (-1.2337141 / z).exp() / z.sqrt()
+            * polynomial(
+                z,
+                &[
+                    2.00012, 0.247105, -0.0649821, 0.0347962, -0.011672, 0.00168691,
+                ],
+            )
+    } else {
+        (-polynomial(
+            z,
+            &[1.0776, -2.30695, 0.43424, -0.082433, 0.008056, -0.0003146],
+        )
+        .exp())
+        .exp()
+    };
+    (x + anderson_darling_error(n as f64, x)).clamp(0.0, 1.0)
+}
+
+/// Returns the correction of the asymptotic cdf value `x` of `A^2` for a
+/// sample of size `n`
+fn anderson_darling_error(n: f64, x: f64) -> f64 {
+    if x > 0.8 {
+        return polynomial(
+            x,
+            &[
+                -130.2137, 745.2337, -1705.091, 1950.646, -1116.360, 255.7844,
+            ],
+        ) / n;
+    }
+    let c = 0.01265 + 0.1757 / n;
+    if x < c {
+        let t = x / c;
+        let t = t.sqrt() * (1.0 - t) * (49.0 * t - 102.0);
+        return t * (0.0037 / (n * n) + 0.00078 / n + 0.00006) / n;
+    }
+    let t = (x - c) / (0.8 - c);
+    let t = polynomial(
+        t,
+        &[-0.00022633, 6.54034, -14.6538, 14.458, -8.259, 1.91864],
+    );
+    t * (0.04213 + 0.01365 / n) / n
+}
+
+/// Performs the Anderson-Darling test of the null hypothesis that `data` is
+/// drawn from a normal distribution with unknown mean and variance
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than 8 observations, contains
+/// `NaN`, or if all observations are equal
+///
+/// # Remarks
+///
+/// The statistic is `A^2` of the normal distribution with the sample mean
+/// and standard deviation. The p-value is computed from the modified
+/// statistic `A^2 * (1 + 0.75 / n + 2.25 / n^2)` with the approximations of
+/// D'Agostino and Stephens (1986), "Goodness-of-Fit Techniques".
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::anderson_darling_normality_test;
+///
+/// let data = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let t = anderson_darling_normality_test(&data).unwrap();
+/// assert!(t.p_value < 0.05);
+/// ```
+pub fn anderson_darling_normality_test(data: &[f64]) -> Result<NormalityTestResult> {
+    let n = data.len();
+    if n < 8 {
+        return Err(StatsError::BadParams);
+    }
+    let x = sorted(data)?;
+    let sd = x.iter().std_dev();
+    if sd == 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let normal = Normal::new(x.iter().mean(), sd)?;
+    let statistic = anderson_darling_statistic(&x, |v| (normal.cdf(v), normal.sf(v)));
+    let nf = n as f64;
+    let a = statistic * (1.0 + 0.75 / nf + 2.25 / (nf * nf));
+    let p_value = if a >= 0.6 {
+        (1.2937 - 5.709 * a + 0.0186 * a * a).exp()
+    } else if a >= 0.34 {
+        (0.9177 - 4.279 * a - 1.38 * a * a).exp()
+    } else if a >= 0.2 {
+        1.0 - (-8.318 + 42.796 * a - 59.938 * a * a).exp()
+    } else {
+        1.0 - (-13.436 + 101.14 * a - 223.73 * a * a).exp()
+    };
+    Ok(NormalityTestResult {
+        statistic,
+        p_value: p_value.min(1.0),
+    })
+}
+
+/// Returns the z-score of the sample skewness `g1` of `n` observations,
+/// following D'Agostino (1970)
+fn skewness_z(n: f64, g1: f64) -> f64 {
+    let y = g1 * ((n + 1.0) * (n + 3.0) / (6.0 * (n - 2.0))).sqrt();
+    let beta2 = 3.0 * (n * n + 27.0 * n - 70.0) * (n + 1.0) * (n + 3.0)
+        / ((n - 2.0) * (n + 5.0) * (n + 7.0) * (n + 9.0));
+    let w2 = -1.0 + (2.0 * (beta2 - 1.0)).sqrt();
+    let delta = 1.0 / (0.5 * w2.ln()).sqrt();
+    let alpha = (2.0 / (w2 - 1.0)).sqrt();
+    delta * (y / alpha).asinh()
+}
+
+/// Returns the z-score of the sample kurtosis `b2 = g2 + 3` of `n`
+/// observations, following Anscombe and Glynn (1983)
+fn kurtosis_z(n: f64, b2: f64) -> f64 {
+    let mean = 3.0 * (n - 1.0) / (n + 1.0);
+    let var = 24.0 * n * (n - 2.0) * (n - 3.0) / ((n + 1.0) * (n + 1.0) * (n + 3.0) * (n + 5.0));
+    let x = (b2 - mean) / var.sqrt();
+    let sqrt_beta1 = 6.0 * (n * n - 5.0 * n + 2.0) / ((n + 7.0) * (n + 9.0))
+        * (6.0 * (n + 3.0) * (n + 5.0) / (n * (n - 2.0) * (n - 3.0))).sqrt();
+    let a = 6.0
+        + 8.0 / sqrt_beta1 * (2.0 / sqrt_beta1 + (1.0 + 4.0 / (sqrt_beta1 * sqrt_beta1)).sqrt());
+    let denom = 1.0 + x * (2.0 / (a - 4.0)).sqrt();
+    let term = ((1.0 - 2.0 / a) / denom).cbrt();
+    (1.0 - 2.0 / (9.0 * a) - term) / (2.0 / (9.0 * a)).sqrt()
+}
+
+/// Performs the D'Agostino-Pearson omnibus test of the null hypothesis that
+/// `data` is drawn from a normal distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than 8 observations, contains
+/// `NaN`, or if all observations are equal
+///
+/// # Remarks
+///
+/// The statistic combines the z-scores of the sample skewness (D'Agostino,
+/// 1970) and of the sample kurtosis (Anscombe and Glynn, 1983), and is
+/// approximately chi-squared distributed with 2 degrees of freedom. The
+/// approximation of the kurtosis is poor for fewer than 20 observations.
+///
+/// # Formula
+///
+/// ```ignore
+/// K^2 = Z(g1)^2 + Z(g2)^2
+/// ```
+///
+/// where `g1` and `g2` are the sample skewness and excess kurtosis
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::dagostino_pearson_test;
+///
+/// let data = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let t = dagostino_pearson_test(&data).unwrap();
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn dagostino_pearson_test(data: &[f64]) -> Result<NormalityTestResult> {
+    if data.len() < 8 || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let n = data.len() as f64;
+    let (g1, g2) = (data.skewness(), data.kurtosis());
+    if g1.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    let z_skew = skewness_z(n, g1);
+    let z_kurt = kurtosis_z(n, g2 + 3.0);
+    let statistic = z_skew * z_skew + z_kurt * z_kurt;
+    Ok(NormalityTestResult {
+        statistic,
+        p_value: ChiSquared::new(2.0)?.sf(statistic),
+    })
+}
+
+/// Performs the Jarque-Bera test of the null hypothesis that `data` is drawn
+/// from a normal distribution
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations, contains
+/// `NaN`, or if all observations are equal
+///
+/// # Remarks
+///
+/// The p-value is computed from the asymptotic chi-squared distribution
+/// with 2 degrees of freedom, which is only accurate for large samples.
+///
+/// # Formula
+///
+/// ```ignore
+/// JB = n / 6 * (g1^2 + g2^2 / 4)
+/// ```
+///
+/// where `g1` and `g2` are the sample skewness and excess kurtosis and `n`
+/// the number of observations
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::jarque_bera_test;
+///
+/// let data = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+/// let t = jarque_bera_test(&data).unwrap();
+/// assert!(t.p_value < 0.05);
+/// ```
+pub fn jarque_bera_test(data: &[f64]) -> Result<NormalityTestResult> {
+    if data.len() < 2 || data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    let (g1, g2) = (data.skewness(), data.kurtosis());
+    if g1.is_nan() {
+        return Err(StatsError::BadParams);
+    }
+    let statistic = data.len() as f64 / 6.0 * (g1 * g1 + g2 * g2 / 4.0);
+    Ok(NormalityTestResult {
+        statistic,
+        p_value: ChiSquared::new(2.0)?.sf(statistic),
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distribution::Exp;
+
+    const MEN: [f64; 11] = [148.0, 154.0, 158.0, 160.0, 161.0, 162.0, 166.0, 170.0, 182.0, 195.0, 236.0];
+    const DATA: [f64; 20] = [
+        6.3, 5.1, 7.2, 4.8, 5.9, 6.6, 5.5, 7.9, 4.4, 6.1, 5.8, 6.9, 5.2, 6.4, 8.8, 5.0, 6.0, 7.4, 5.7, 6.2,
+    ];
+
+    #[test]
+    fn test_shapiro_wilk() {
+        let t = shapiro_wilk_test(&MEN).unwrap();
+        assert_almost_eq!(t.statistic, 0.78881469483538743, 1e-12);
+        assert_almost_eq!(t.p_value, 0.006703814056502988, 1e-12);
+        let t = shapiro_wilk_test(&DATA).unwrap();
+        assert_almost_eq!(t.statistic, 0.96941567297514913, 1e-12);
+        assert_almost_eq!(t.p_value, 0.7424872668787581, 1e-10);
+        let data: Vec<f64> = (0..100).map(|i| (0.3 * (i as f64 * 1.7).sin()).exp() + 0.01 * i as f64).collect();
+        let t = shapiro_wilk_test(&data).unwrap();
+        assert_almost_eq!(t.statistic, 0.98793895357807426, 1e-12);
+        assert_almost_eq!(t.p_value, 0.50371689972545085, 1e-10);
+    }
+
+    #[test]
+    fn test_shapiro_wilk_small() {
+        let t = shapiro_wilk_test(&[1.0, 2.0, 4.0]).unwrap();
+        assert_almost_eq!(t.statistic, 0.96428571428571429, 1e-14);
+        assert_almost_eq!(t.p_value, 0.63688684502896981, 1e-12);
+        let t = shapiro_wilk_test(&[2.1, 3.4, 1.9, 5.6, 2.8]).unwrap();
+        assert_almost_eq!(t.statistic, 0.86863521718098519, 1e-12);
+        assert_almost_eq!(t.p_value, 0.26094132562460767, 1e-10);
+        let t = shapiro_wilk_test(&[1.0, 2.0, 3.0]).unwrap();
+        assert_almost_eq!(t.statistic, 1.0, 1e-15);
+        assert_almost_eq!(t.p_value, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn test_shapiro_wilk_bad_params() {
+        assert!(shapiro_wilk_test(&[1.0, 2.0]).is_err());
+        assert!(shapiro_wilk_test(&[1.0, 1.0, 1.0]).is_err());
+        assert!(shapiro_wilk_test(&[1.0, f64::NAN, 2.0]).is_err());
+        assert!(shapiro_wilk_test(&vec![1.0; 5001]).is_err());
+    }
+
+    #[test]
+    fn test_anderson_darling() {
+        let data = [0.12, 0.45, 1.63, 0.08, 0.91, 2.71, 0.33, 0.57, 1.24, 0.19];
+        let exp = Exp::new(1.0).unwrap();
+        let t = anderson_darling_test(&data, &exp).unwrap();
+        assert_almost_eq!(t.statistic, 0.2850326221127423, 1e-13);
+        assert_almost_eq!(t.p_value, 0.94823632078193769, 1e-12);
+        let scaled: Vec<f64> = DATA.iter().map(|x| x / 6.0).collect();
+        let t = anderson_darling_test(&scaled, &exp).unwrap();
+        assert_almost_eq!(t.statistic, 6.6033216329738611, 1e-12);
+        assert_almost_eq!(t.p_value, 0.00054756593440499621, 1e-12);
+        assert!(anderson_darling_test(&[], &exp).is_err());
+        assert!(anderson_darling_test(&[1.0, f64::NAN], &exp).is_err());
+    }
+
+    #[test]
+    fn test_anderson_darling_cdf() {
+        // the asymptotic distribution from the series of Anderson and
+        // Darling (1954)
+        assert_almost_eq!(anderson_darling_cdf(1_000_000_000, 1.0), 0.64273332678598091, 3e-5);
+        assert_almost_eq!(anderson_darling_cdf(1_000_000_000, 2.492), 0.94997781364039213, 3e-5);
+        assert_almost_eq!(anderson_darling_cdf(1_000_000_000, 3.878), 0.98999859011549954, 3e-5);
+        assert_eq!(anderson_darling_cdf(10, 0.0), 0.0);
+        assert_eq!(anderson_darling_cdf(10, f64::INFINITY), 1.0);
+    }
+
+    #[test]
+    fn test_anderson_darling_normality() {
+        let t = anderson_darling_normality_test(&MEN).unwrap();
+        assert_almost_eq!(t.statistic, 0.94677187959888729, 1e-9);
+        assert_almost_eq!(t.p_value, 0.010454024005147697, 1e-10);
+        let t = anderson_darling_normality_test(&DATA).unwrap();
+        assert_almost_eq!(t.statistic, 0.22782651119524581, 1e-9);
+        assert_almost_eq!(t.p_value, 0.78403921972977153, 1e-9);
+        assert!(anderson_darling_normality_test(&DATA[..7]).is_err());
+        assert!(anderson_darling_normality_test(&[1.0; 10]).is_err());
+    }
+
+    #[test]
+    fn test_dagostino_pearson() {
+        let t = dagostino_pearson_test(&MEN).unwrap();
+        assert_almost_eq!(t.statistic, 13.034263121192582, 1e-11);
+        assert_almost_eq!(t.p_value, 0.0014779023013100174, 1e-13);
+        let t = dagostino_pearson_test(&DATA).unwrap();
+        assert_almost_eq!(t.statistic, 2.3352846536076348, 1e-11);
+        assert_almost_eq!(t.p_value, 0.31109954836859581, 1e-12);
+        assert!(dagostino_pearson_test(&DATA[..7]).is_err());
+        assert!(dagostino_pearson_test(&[2.0; 10]).is_err());
+        assert!(dagostino_pearson_test(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_jarque_bera() {
+        let t = jarque_bera_test(&MEN).unwrap();
+        assert_almost_eq!(t.statistic, 6.9828482373446447, 1e-12);
+        assert_almost_eq!(t.p_value, 0.030457466224581905, 1e-13);
+        let t = jarque_bera_test(&DATA).unwrap();
+        assert_almost_eq!(t.statistic, 1.321451755717134, 1e-12);
+        assert_almost_eq!(t.p_value, 0.51647629968301994, 1e-13);
+        assert!(jarque_bera_test(&[1.0]).is_err());
+        assert!(jarque_bera_test(&[1.0, 1.0]).is_err());
+        assert!(jarque_bera_test(&[1.0, f64::NAN]).is_err());
+    }
+}
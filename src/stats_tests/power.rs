@@ -0,0 +1,415 @@
+use crate::distribution::internal::find_root;
+use crate::distribution::{
+    ChiSquared, ContinuousCDF, FisherSnedecor, NoncentralChiSquared, NoncentralF, NoncentralT,
+    Normal, StudentsT,
+};
+use crate::stats_tests::Alternative;
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The largest effect size considered when solving for Cohen's d, w or f
+const MAX_EFFECT_SIZE: f64 = 10.0;
+
+/// The largest sample size considered when solving for it
+const MAX_SAMPLE_SIZE: f64 = 1e9;
+
+/// The smallest and one minus the largest significance level considered
+/// when solving for it
+const MIN_ALPHA: f64 = 1e-10;
+
+/// Enumeration of the tests supported by `power_analysis`, which determine
+/// the effect size and the sample size `n`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PowerTest {
+    /// The one-sample t-test, with Cohen's `d = (μ - μ_0) / σ` as effect
+    /// size and the number of observations as `n`
+    OneSampleT(Alternative),
+    /// The two-sample t-test with equal group sizes, with Cohen's
+    /// `d = (μ_x - μ_y) / σ` as effect size and the number of observations
+    /// in each group as `n`
+    TwoSampleT(Alternative),
+    /// The paired t-test, with Cohen's `d` of the differences as effect
+    /// size and the number of pairs as `n`
+    PairedT(Alternative),
+    /// The two-proportion z-test with equal group sizes, with Cohen's `h`
+    /// (see `cohens_h`) as effect size and the number of observations in
+    /// each group as `n`
+    TwoProportionZ(Alternative),
+    /// The chi-square test with `df` degrees of freedom, with Cohen's
+    /// `w = sqrt(Σ (p_1i - p_0i)^2 / p_0i)` as effect size and the total
+    /// number of observations as `n`
+    ChiSquare {
+        /// The degrees of freedom of the test
+        df: u64,
+    },
+    /// The one-way ANOVA of `groups` groups of equal size, with Cohen's
+    /// `f = σ_μ / σ`, the standard deviation of the group means over the
+    /// common standard deviation, as effect size and the number of
+    /// observations in each group as `n`
+    Anova {
+        /// The number of groups
+        groups: u64,
+    },
+}
+
+impl PowerTest {
+    /// Returns the alternative hypothesis of the test, which is `Greater`
+    /// for the chi-square test and ANOVA
+    fn alternative(self) -> Alternative {
+        match self {
+            PowerTest::OneSampleT(alternative)
+            | PowerTest::TwoSampleT(alternative)
+            | PowerTest::PairedT(alternative)
+            | PowerTest::TwoProportionZ(alternative) => alternative,
+            PowerTest::ChiSquare { .. } | PowerTest::Anova { .. } => Alternative::Greater,
+        }
+    }
+
+    /// Returns the smallest sample size for which the test is defined,
+    /// exclusive
+    fn min_n(self) -> f64 {
+        match self {
+            PowerTest::OneSampleT(_)
+            | PowerTest::TwoSampleT(_)
+            | PowerTest::PairedT(_)
+            | PowerTest::Anova { .. } => 1.0,
+            PowerTest::TwoProportionZ(_) | PowerTest::ChiSquare { .. } => 0.0,
+        }
+    }
+
+    /// Returns the largest effect size considered when solving for it
+    fn max_effect_size(self) -> f64 {
+        match self {
+            PowerTest::TwoProportionZ(_) => f64::consts::PI,
+            _ => MAX_EFFECT_SIZE,
+        }
+    }
+
+    /// Returns the power of the test, which has valid parameters
+    fn power(self, effect_size: f64, n: f64, alpha: f64) -> Result<f64> {
+        let alternative = self.alternative();
+        match self {
+            PowerTest::OneSampleT(_) | PowerTest::PairedT(_) => {
+                t_power(n - 1.0, effect_size * n.sqrt(), alpha, alternative)
+            }
+            PowerTest::TwoSampleT(_) => t_power(
+                2.0 * (n - 1.0),
+                effect_size * (n / 2.0).sqrt(),
+                alpha,
+                alternative,
+            ),
+            PowerTest::TwoProportionZ(_) => {
+                let normal = Normal::new(0.0, 1.0).unwrap();
+                let shift = effect_size * (n / 2.0).sqrt();
+                Ok(match alternative {
+                    Alternative::TwoSided => {
+                        let z = normal.inverse_cdf(1.0 - alpha / 2.0);
+                        normal.cdf(shift - z) + normal.cdf(-shift - z)
+                    }
+                    Alternative::Less => normal.cdf(normal.inverse_cdf(alpha) - shift),
+                    Alternative::Greater => normal.sf(normal.inverse_cdf(1.0 - alpha) - shift),
+                })
+            }
+            PowerTest::ChiSquare { df } => {
+                let df = df as f64;
+                let critical = ChiSquared::new(df)?.inverse_cdf(1.0 - alpha);
+                let noncentral = NoncentralChiSquared::new(df, n * effect_size * effect_size)?;
+                Ok(noncentral.sf(critical))
+            }
+            PowerTest::Anova { groups } => {
+                let k = groups as f64;
+                let (df_1, df_2) = (k - 1.0, k * (n - 1.0));
+                let critical = FisherSnedecor::new(df_1, df_2)?.inverse_cdf(1.0 - alpha);
+                let noncentral = NoncentralF::new(df_1, df_2, k * n * effect_size * effect_size)?;
+                Ok(noncentral.sf(critical))
+            }
+        }
+    }
+}
+
+/// The result of a power analysis, with the parameter that was solved for
+/// filled in
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PowerAnalysis {
+    /// The test
+    pub test: PowerTest,
+    /// The effect size
+    pub effect_size: f64,
+    /// The sample size, which is not rounded
+    pub n: f64,
+    /// The significance level
+    pub alpha: f64,
+    /// The power, the probability of rejecting the null hypothesis
+    pub power: f64,
+}
+
+impl PowerAnalysis {
+    /// Returns the sample size rounded up to an integer, the smallest
+    /// number of observations that achieves the power
+    pub fn sample_size(&self) -> u64 {
+        // guard against a root found just above an integer due to rounding
+        (self.n - 1e-9).ceil() as u64
+    }
+}
+
+/// Returns Cohen's effect size `h` for the difference between the
+/// proportions `p1` and `p2`, the difference of their arcsine square root
+/// transforms
+///
+/// # Remarks
+///
+/// Returns `f64::NAN` if `p1` or `p2` is not in `[0, 1]`
+///
+/// # Formula
+///
+/// ```ignore
+/// h = 2 * asin(sqrt(p1)) - 2 * asin(sqrt(p2))
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::cohens_h;
+///
+/// assert!((cohens_h(0.65, 0.45) - 0.4048601).abs() < 1e-7);
+/// assert!(cohens_h(0.5, 1.5).is_nan());
+/// ```
+pub fn cohens_h(p1: f64, p2: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p1) || !(0.0..=1.0).contains(&p2) {
+        return f64::NAN;
+    }
+    2.0 * p1.sqrt().asin() - 2.0 * p2.sqrt().asin()
+}
+
+/// Computes the power of the `test`, or solves for the effect size, sample
+/// size or significance level that achieves a given power. Exactly one of
+/// `effect_size`, `n`, `alpha` and `power` must be `None`, and is solved
+/// for given the others.
+///
+/// # Errors
+///
+/// Returns an error if not exactly one parameter is `None`, `alpha` or
+/// `power` is not in `(0, 1)`, `n` is too small for the test, the effect
+/// size is not finite, the test has no degrees of freedom or groups to
+/// compare, or the parameter solved for has no solution in its search
+/// range. The effect size is searched for in `(0, 10]`, or `(0, π]` for
+/// Cohen's `h`, and is negative for the `Less` alternative; the sample size
+/// is searched for up to `10^9`.
+///
+/// # Remarks
+///
+/// The power of the t-tests is computed exactly from the noncentral t
+/// distribution, counting both rejection regions for the two-sided
+/// alternative, and that of the chi-square test and ANOVA from the
+/// noncentral chi-squared and F distributions with the noncentrality
+/// parameters `n * w^2` and `groups * n * f^2`. The power of the
+/// two-proportion z-test uses the normal approximation of the arcsine
+/// transformed proportions. The results are those of the `pwr` package of
+/// R.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{power_analysis, Alternative, PowerTest};
+///
+/// let test = PowerTest::TwoSampleT(Alternative::TwoSided);
+/// let power = power_analysis(test, Some(1.0), Some(20.0), Some(0.05), None).unwrap();
+/// assert!((power.power - 0.8689530).abs() < 1e-7);
+///
+/// let n = power_analysis(test, Some(0.5), None, Some(0.05), Some(0.8)).unwrap();
+/// assert!((n.n - 63.76561).abs() < 1e-5);
+/// assert_eq!(n.sample_size(), 64);
+/// ```
+pub fn power_analysis(
+    test: PowerTest,
+    effect_size: Option<f64>,
+    n: Option<f64>,
+    alpha: Option<f64>,
+    power: Option<f64>,
+) -> Result<PowerAnalysis> {
+    let unknowns = [
+        effect_size.is_none(),
+        n.is_none(),
+        alpha.is_none(),
+        power.is_none(),
+    ];
+    if unknowns.iter().filter(|&&unknown| unknown).count() != 1 {
+        return Err(StatsError::SpecialCase(
+            "exactly one of effect_size, n, alpha and power must be None",
+        ));
+    }
+    match test {
+        PowerTest::ChiSquare { df: 0 } => return Err(StatsError::ArgMustBePositive("df")),
+        PowerTest::Anova { groups } if groups < 2 => return Err(StatsError::ArgGte("groups", 2.0)),
+        _ => (),
+    }
+    if let Some(effect_size) = effect_size {
+        if !effect_size.is_finite() {
+            return Err(StatsError::BadParams);
+        }
+    }
+    if let Some(n) = n {
+        if n.is_nan() || n <= test.min_n() || n.is_infinite() {
+            return Err(StatsError::ArgGt("n", test.min_n()));
+        }
+    }
+    if let Some(alpha) = alpha {
+        if alpha.is_nan() || alpha <= 0.0 || alpha >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("alpha", 0.0, 1.0));
+        }
+    }
+    if let Some(power) = power {
+        if power.is_nan() || power <= 0.0 || power >= 1.0 {
+            return Err(StatsError::ArgIntervalExcl("power", 0.0, 1.0));
+        }
+    }
+
+    // the power is increasing in each of the other parameters, except in the
+    // effect size for the Less alternative
+    let (effect_size, n, alpha) = match (effect_size, n, alpha, power) {
+        (Some(effect_size), Some(n), Some(alpha), None) => (effect_size, n, alpha),
+        (None, Some(n), Some(alpha), Some(power)) => {
+            let f = |d: f64| test.power(d, n, alpha).map_or(f64::NAN, |p| p - power);
+            let max = test.max_effect_size();
+            let effect_size = match test.alternative() {
+                Alternative::Less => -increasing_root(|d| f(-d), 0.0, 0.5, max)?,
+                _ => increasing_root(f, 0.0, 0.5, max)?,
+            };
+            (effect_size, n, alpha)
+        }
+        (Some(effect_size), None, Some(alpha), Some(power)) => {
+            let f = |n: f64| {
+                test.power(effect_size, n, alpha)
+                    .map_or(f64::NAN, |p| p - power)
+            };
+            let min = test.min_n() + 1.0;
+            (
+                effect_size,
+                increasing_root(f, min, 8.0, MAX_SAMPLE_SIZE)?,
+                alpha,
+            )
+        }
+        (Some(effect_size), Some(n), None, Some(power)) => {
+            let f = |alpha: f64| {
+                test.power(effect_size, n, alpha)
+                    .map_or(f64::NAN, |p| p - power)
+            };
+            (effect_size, n, find_root(f, MIN_ALPHA, 1.0 - MIN_ALPHA)?)
+        }
+        _ => unreachable!(),
+    };
+    Ok(PowerAnalysis {
+        test,
+        effect_size,
+        n,
+        alpha,
+        power: test.power(effect_size, n, alpha)?,
+    })
+}
+
+/// Returns the root of the increasing function `f` in `[min, max]`,
+/// bracketing it by doubling the distance from `min` of the upper end,
+/// starting at `step`, so that `f` is not evaluated far beyond the root
+fn increasing_root<F: Fn(f64) -> f64>(f: F, min: f64, step: f64, max: f64) -> Result<f64> {
+    let (mut lower, mut upper) = (min, (min + step).min(max));
+    loop {
+        let value = f(upper);
+        if value.is_nan() {
+            return Err(StatsError::ComputationFailedToConverge);
+        }
+        if value >= 0.0 || upper >= max {
+            break;
+        }
+        lower = upper;
+        upper = (min + 2.0 * (upper - min)).min(max);
+    }
+    find_root(f, lower, upper)
+}
+
+/// Returns the power of a t-test with `df` degrees of freedom whose
+/// statistic has the noncentrality parameter `ncp` under the alternative
+fn t_power(df: f64, ncp: f64, alpha: f64, alternative: Alternative) -> Result<f64> {
+    let t = StudentsT::new(0.0, 1.0, df)?;
+    let noncentral = NoncentralT::new(df, ncp)?;
+    Ok(match alternative {
+        Alternative::TwoSided => {
+            let critical = t.inverse_cdf(1.0 - alpha / 2.0);
+            noncentral.sf(critical) + noncentral.cdf(-critical)
+        }
+        Alternative::Less => noncentral.cdf(t.inverse_cdf(alpha)),
+        Alternative::Greater => noncentral.sf(t.inverse_cdf(1.0 - alpha)),
+    })
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t_power() {
+        let two_sided = PowerTest::TwoSampleT(Alternative::TwoSided);
+        let analysis = power_analysis(two_sided, Some(1.0), Some(20.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(analysis.power, 0.86895302772398973, 1e-10);
+        let greater = power_analysis(PowerTest::OneSampleT(Alternative::Greater), Some(0.8), Some(10.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(greater.power, 0.75442475924934821, 1e-10);
+        let less = power_analysis(PowerTest::PairedT(Alternative::Less), Some(-0.8), Some(10.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(less.power, greater.power, 1e-12);
+        let n = power_analysis(two_sided, Some(0.5), None, Some(0.05), Some(0.8)).unwrap();
+        assert_almost_eq!(n.n, 63.765610190952317, 1e-7);
+        assert_eq!(n.sample_size(), 64);
+    }
+
+    #[test]
+    fn test_solve_round_trip() {
+        let tests = [
+            PowerTest::OneSampleT(Alternative::TwoSided),
+            PowerTest::TwoSampleT(Alternative::Greater),
+            PowerTest::PairedT(Alternative::Less),
+            PowerTest::TwoProportionZ(Alternative::TwoSided),
+            PowerTest::ChiSquare { df: 3 },
+            PowerTest::Anova { groups: 4 },
+        ];
+        for &test in &tests {
+            let effect_size = if test.alternative() == Alternative::Less { -0.4 } else { 0.4 };
+            let power = power_analysis(test, Some(effect_size), Some(30.0), Some(0.05), None).unwrap().power;
+            let d = power_analysis(test, None, Some(30.0), Some(0.05), Some(power)).unwrap();
+            assert_almost_eq!(d.effect_size, effect_size, 1e-8);
+            let n = power_analysis(test, Some(effect_size), None, Some(0.05), Some(power)).unwrap();
+            assert_almost_eq!(n.n, 30.0, 1e-6);
+            let alpha = power_analysis(test, Some(effect_size), Some(30.0), None, Some(power)).unwrap();
+            assert_almost_eq!(alpha.alpha, 0.05, 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_other_power() {
+        let h = cohens_h(0.65, 0.45);
+        assert_almost_eq!(h, 0.40486007517695730, 1e-15);
+        let z = power_analysis(PowerTest::TwoProportionZ(Alternative::TwoSided), Some(h), Some(50.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(z.power, 0.52568265606255757, 1e-12);
+        let z = power_analysis(PowerTest::TwoProportionZ(Alternative::TwoSided), Some(0.3), None, Some(0.05), Some(0.8)).unwrap();
+        assert_almost_eq!(z.n, 174.41912242947107, 1e-6);
+        let chi = power_analysis(PowerTest::ChiSquare { df: 3 }, Some(0.3), Some(100.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(chi.power, 0.71125359979504231, 1e-10);
+        let chi = power_analysis(PowerTest::ChiSquare { df: 3 }, Some(0.3), None, Some(0.05), Some(0.8)).unwrap();
+        assert_almost_eq!(chi.n, 121.13959211259187, 1e-6);
+        let anova = power_analysis(PowerTest::Anova { groups: 4 }, Some(0.25), Some(20.0), Some(0.05), None).unwrap();
+        assert_almost_eq!(anova.power, 0.42039009037704285, 1e-10);
+    }
+
+    #[test]
+    fn test_power_analysis_bad_params() {
+        let test = PowerTest::OneSampleT(Alternative::TwoSided);
+        assert!(power_analysis(test, None, None, Some(0.05), Some(0.8)).is_err());
+        assert!(power_analysis(test, Some(0.5), Some(10.0), Some(0.05), Some(0.8)).is_err());
+        assert!(power_analysis(test, Some(0.5), Some(1.0), Some(0.05), None).is_err());
+        assert!(power_analysis(test, Some(0.5), Some(10.0), Some(1.0), None).is_err());
+        assert!(power_analysis(test, Some(f64::NAN), Some(10.0), Some(0.05), None).is_err());
+        assert!(power_analysis(test, Some(0.5), Some(10.0), None, Some(0.0)).is_err());
+        assert!(power_analysis(PowerTest::ChiSquare { df: 0 }, Some(0.5), Some(10.0), Some(0.05), None).is_err());
+        assert!(power_analysis(PowerTest::Anova { groups: 1 }, Some(0.5), Some(10.0), Some(0.05), None).is_err());
+        // a power below the significance level is not achievable
+        assert!(power_analysis(test, None, Some(10.0), Some(0.05), Some(0.01)).is_err());
+    }
+}
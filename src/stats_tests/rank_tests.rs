@@ -0,0 +1,752 @@
+use crate::distribution::{ChiSquared, ContinuousCDF, Normal};
+use crate::function::factorial;
+use crate::statistics::{Data, OrderStatistics, RankTieBreaker};
+use crate::stats_tests::{p_value, Alternative, ChiSquareTestResult};
+use crate::{Result, StatsError};
+use std::collections::HashMap;
+
+/// The result of a two-sample or signed rank test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RankTestResult {
+    /// The test statistic
+    pub statistic: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+}
+
+/// Samples with fewer observations than this have exact p-values for the
+/// Mann-Whitney U and Wilcoxon signed-rank tests if there are no ties
+const RANK_SUM_EXACT_MAX: usize = 50;
+
+/// Largest number of equally likely rank arrangements for which the exact
+/// permutation distributions of the Kruskal-Wallis and Friedman statistics
+/// are computed
+const RANK_PERMUTATION_EXACT_MAX: f64 = 1e6;
+
+/// Relative tolerance when comparing a statistic with its permutation
+/// distribution, to count arrangements that equal the observed one up to
+/// rounding
+const STATISTIC_TOLERANCE: f64 = 1e-9;
+
+/// Returns the average ranks of `data`
+pub(super) fn ranks(data: &[f64]) -> Result<Vec<f64>> {
+    if data.iter().any(|x| x.is_nan()) {
+        return Err(StatsError::BadParams);
+    }
+    Ok(Data::new(data.to_vec()).ranks(RankTieBreaker::Average))
+}
+
+/// Returns `Σ (t^3 - t)` over the groups of `t` tied values in `ranks`,
+/// which are equal exactly if the values they rank are
+fn tie_sum(ranks: &[f64]) -> f64 {
+    let mut sorted = ranks.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut sum = 0.0;
+    let mut start = 0;
+    for i in 1..=sorted.len() {
+        if i == sorted.len() || sorted[i] != sorted[start] {
+            let t = (i - start) as f64;
+            sum += t * t * t - t;
+            start = i;
+        }
+    }
+    sum
+}
+
+/// Returns the p-value of the integer statistic `statistic` with the null
+/// probability mass function `pmf` on `0..pmf.len()`
+fn exact_p_value(pmf: &[f64], statistic: usize, alternative: Alternative) -> f64 {
+    let cdf: f64 = pmf[..=statistic].iter().sum();
+    let sf: f64 = pmf[statistic..].iter().sum();
+    p_value(cdf.min(1.0), sf.min(1.0), alternative)
+}
+
+/// Returns the p-value of a statistic with null mean `mean` and standard
+/// deviation `std_dev` from the normal approximation, optionally with a
+/// continuity correction of `0.5` towards the mean
+fn normal_p_value(
+    statistic: f64,
+    mean: f64,
+    std_dev: f64,
+    correction: bool,
+    alternative: Alternative,
+) -> f64 {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let c = if correction { 0.5 } else { 0.0 };
+    let sf = normal.sf((statistic - mean - c) / std_dev);
+    let cdf = normal.cdf((statistic - mean + c) / std_dev);
+    match alternative {
+        Alternative::TwoSided => (2.0 * cdf.min(sf)).min(1.0),
+        Alternative::Less => cdf,
+        Alternative::Greater => sf,
+    }
+}
+
+/// Performs the Mann-Whitney U test (Wilcoxon rank-sum test) of the null
+/// hypothesis that `x` and `y` are drawn from the same distribution. The
+/// `Greater` alternative is that `x` is stochastically greater than `y`.
+/// The statistic is `U` of `x`, i.e. the number of pairs `(x_i, y_j)` with
+/// `x_i > y_j` plus half the number of ties. If `correction` is true, the
+/// normal approximation is continuity corrected.
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` is empty, contains `NaN`, or if all
+/// observations are equal
+///
+/// # Remarks
+///
+/// The p-value is exact if both samples have fewer than 50 observations and
+/// there are no ties, otherwise it is computed from the normal
+/// approximation with the variance corrected for ties.
+///
+/// # Formula
+///
+/// ```ignore
+/// U = R_x - n * (n + 1) / 2
+/// E[U] = n * m / 2
+/// Var[U] = n * m / 12 * (N + 1 - Σ (t^3 - t) / (N * (N - 1)))
+/// ```
+///
+/// where `R_x` is the sum of the ranks of `x` in the merged sample,
+/// `n`, `m` and `N` the sizes of `x`, `y` and the merged sample, and `t`
+/// the sizes of the groups of ties
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{mann_whitney_u_test, Alternative};
+///
+/// let x = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+/// let y = [0.878, 0.647, 0.598, 2.05, 1.06, 1.29, 1.06, 3.14, 1.29];
+/// let t = mann_whitney_u_test(&x, &y, Alternative::Greater, true).unwrap();
+/// assert_eq!(t.statistic, 58.0);
+/// assert!((t.p_value - 0.06646).abs() < 1e-5);
+/// ```
+pub fn mann_whitney_u_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+    correction: bool,
+) -> Result<RankTestResult> {
+    if x.is_empty() || y.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let (n, m) = (x.len(), y.len());
+    let merged: Vec<f64> = x.iter().chain(y).copied().collect();
+    let ranks = ranks(&merged)?;
+    let rank_sum: f64 = ranks[..n].iter().sum();
+    let statistic = rank_sum - (n * (n + 1)) as f64 / 2.0;
+    let ties = tie_sum(&ranks);
+
+    let p_value = if ties == 0.0 && n < RANK_SUM_EXACT_MAX && m < RANK_SUM_EXACT_MAX {
+        exact_p_value(&mann_whitney_pmf(n, m), statistic as usize, alternative)
+    } else {
+        let total = (n + m) as f64;
+        let var = (n * m) as f64 / 12.0 * (total + 1.0 - ties / (total * (total - 1.0)));
+        if var <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let mean = (n * m) as f64 / 2.0;
+        normal_p_value(statistic, mean, var.sqrt(), correction, alternative)
+    };
+    Ok(RankTestResult { statistic, p_value })
+}
+
+/// Returns the null probability mass function of the Mann-Whitney `U`
+/// statistic for samples of size `n` and `m` without ties on `0..=n * m`
+fn mann_whitney_pmf(n: usize, m: usize) -> Vec<f64> {
+    // pmf[j] is the distribution for the sizes (i, j), built up by
+    // conditioning on whether the largest observation is from x or y:
+    // p_{i,j}(u) = i / (i + j) * p_{i-1,j}(u - j) + j / (i + j) * p_{i,j-1}(u)
+    let mut pmf: Vec<Vec<f64>> = vec![vec![1.0]; m + 1];
+    for i in 1..=n {
+        pmf[0] = vec![1.0];
+        for j in 1..=m {
+            let mut next = vec![0.0; i * j + 1];
+            let (a, b) = (i as f64 / (i + j) as f64, j as f64 / (i + j) as f64);
+            for (u, &p) in pmf[j].iter().enumerate() {
+                next[u + j] += a * p;
+            }
+            for (u, &p) in pmf[j - 1].iter().enumerate() {
+                next[u] += b * p;
+            }
+            pmf[j] = next;
+        }
+    }
+    pmf.pop().unwrap()
+}
+
+/// Performs the Wilcoxon signed-rank test of the null hypothesis that `data`
+/// is drawn from a distribution symmetric about `mu`. The `Greater`
+/// alternative is that the distribution is shifted above `mu`. The statistic
+/// is `W+`, the sum of the ranks of `|x_i - mu|` for which `x_i > mu`. If
+/// `correction` is true, the normal approximation is continuity corrected.
+///
+/// # Errors
+///
+/// Returns an error if `data` contains `NaN` or no observation differs from
+/// `mu`
+///
+/// # Remarks
+///
+/// Observations equal to `mu` are discarded. The p-value is exact if fewer
+/// than 50 observations remain and their absolute differences have no
+/// ties, otherwise it is computed from the normal approximation with the
+/// variance corrected for ties.
+///
+/// # Formula
+///
+/// ```ignore
+/// E[W+] = n * (n + 1) / 4
+/// Var[W+] = n * (n + 1) * (2n + 1) / 24 - Σ (t^3 - t) / 48
+/// ```
+///
+/// where `n` is the number of observations different from `mu` and `t` the
+/// sizes of the groups of ties
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{wilcoxon_signed_rank_test, Alternative};
+///
+/// let data = [1.83, 0.50, 1.62, 2.48, 1.68, 1.88, 1.55, 3.06, 1.30];
+/// let t = wilcoxon_signed_rank_test(&data, 1.0, Alternative::TwoSided, true).unwrap();
+/// assert_eq!(t.statistic, 43.0);
+/// assert_eq!(t.p_value, 3.0 / 256.0);
+/// ```
+pub fn wilcoxon_signed_rank_test(
+    data: &[f64],
+    mu: f64,
+    alternative: Alternative,
+    correction: bool,
+) -> Result<RankTestResult> {
+    let diff: Vec<f64> = data.iter().map(|x| x - mu).filter(|&d| d != 0.0).collect();
+    if diff.is_empty() {
+        return Err(StatsError::BadParams);
+    }
+    let n = diff.len();
+    let abs_diff: Vec<f64> = diff.iter().map(|d| d.abs()).collect();
+    let ranks = ranks(&abs_diff)?;
+    let statistic: f64 = ranks
+        .iter()
+        .zip(&diff)
+        .filter(|(_, &d)| d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let ties = tie_sum(&ranks);
+
+    let p_value = if ties == 0.0 && n < RANK_SUM_EXACT_MAX {
+        exact_p_value(&signed_rank_pmf(n), statistic as usize, alternative)
+    } else {
+        let n = n as f64;
+        let var = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+        let mean = n * (n + 1.0) / 4.0;
+        normal_p_value(statistic, mean, var.sqrt(), correction, alternative)
+    };
+    Ok(RankTestResult { statistic, p_value })
+}
+
+/// Performs the Wilcoxon signed-rank test of the null hypothesis that the
+/// differences `x_i - y_i` of paired observations are symmetric about zero.
+/// See `wilcoxon_signed_rank_test` for details.
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` have different lengths, contain `NaN`, or
+/// no difference is non-zero
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{wilcoxon_paired_test, Alternative};
+///
+/// let before = [125.0, 115.0, 130.0, 140.0, 140.0, 115.0, 140.0, 125.0, 140.0, 135.0];
+/// let after = [110.0, 122.0, 125.0, 120.0, 140.0, 124.0, 123.0, 137.0, 135.0, 145.0];
+/// let t = wilcoxon_paired_test(&before, &after, Alternative::TwoSided, true).unwrap();
+/// assert_eq!(t.statistic, 27.0);
+/// assert!(t.p_value > 0.5);
+/// ```
+pub fn wilcoxon_paired_test(
+    x: &[f64],
+    y: &[f64],
+    alternative: Alternative,
+    correction: bool,
+) -> Result<RankTestResult> {
+    if x.len() != y.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let diff: Vec<f64> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+    wilcoxon_signed_rank_test(&diff, 0.0, alternative, correction)
+}
+
+/// Returns the null probability mass function of the Wilcoxon signed-rank
+/// statistic `W+` for `n` observations without ties on
+/// `0..=n * (n + 1) / 2`
+fn signed_rank_pmf(n: usize) -> Vec<f64> {
+    // rank i contributes to W+ with probability one half
+    let mut pmf = vec![0.0; n * (n + 1) / 2 + 1];
+    pmf[0] = 1.0;
+    for i in 1..=n {
+        let max = i * (i + 1) / 2;
+        for w in (0..=max).rev() {
+            let with = if w >= i { pmf[w - i] } else { 0.0 };
+            pmf[w] = (pmf[w] + with) / 2.0;
+        }
+    }
+    pmf
+}
+
+/// Performs the Kruskal-Wallis H test of the null hypothesis that all
+/// `groups` are drawn from the same distribution
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two groups, a group is empty,
+/// the data contains `NaN`, or all observations are equal
+///
+/// # Remarks
+///
+/// The p-value is computed from the exact permutation distribution of `H`
+/// (conditional on the ties) if there are at most `10^6` distinct
+/// assignments of the observations to the groups, and from the chi-squared
+/// distribution with `k - 1` degrees of freedom otherwise.
+///
+/// # Formula
+///
+/// ```ignore
+/// H = (12 / (N * (N + 1)) * Σ R_i^2 / n_i - 3 * (N + 1)) / (1 - Σ (t^3 - t) / (N^3 - N))
+/// ```
+///
+/// where `R_i` and `n_i` are the rank sum and size of group `i`, `N` the
+/// total number of observations, `k` the number of groups and `t` the sizes
+/// of the groups of ties
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::kruskal_wallis_test;
+///
+/// let a = [2.9, 3.0, 2.5, 2.6, 3.2];
+/// let b = [3.8, 2.7, 4.0, 2.4];
+/// let c = [2.8, 3.4, 3.7, 2.2, 2.0];
+/// let t = kruskal_wallis_test(&[&a, &b, &c]).unwrap();
+/// assert_eq!(t.df, 2.0);
+/// assert!((t.p_value - 89647.0 / 126126.0).abs() < 1e-12);
+/// ```
+pub fn kruskal_wallis_test(groups: &[&[f64]]) -> Result<ChiSquareTestResult> {
+    if groups.len() < 2 || groups.iter().any(|g| g.is_empty()) {
+        return Err(StatsError::BadParams);
+    }
+    let merged: Vec<f64> = groups.iter().flat_map(|g| g.iter().copied()).collect();
+    let ranks = ranks(&merged)?;
+    let total = merged.len() as f64;
+    let correction = 1.0 - tie_sum(&ranks) / (total * total * total - total);
+    if correction <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let sizes: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+    let mut rank_sums = vec![0.0; groups.len()];
+    let mut offset = 0;
+    for (sum, &size) in rank_sums.iter_mut().zip(&sizes) {
+        *sum = ranks[offset..offset + size].iter().sum();
+        offset += size;
+    }
+    let h = |rank_sums: &[f64]| {
+        let s: f64 = rank_sums
+            .iter()
+            .zip(&sizes)
+            .map(|(r, &n)| r * r / n as f64)
+            .sum();
+        (12.0 / (total * (total + 1.0)) * s - 3.0 * (total + 1.0)) / correction
+    };
+    let statistic = h(&rank_sums);
+    let df = (groups.len() - 1) as f64;
+
+    let ln_arrangements = factorial::ln_factorial(merged.len() as u64)
+        - sizes
+            .iter()
+            .map(|&n| factorial::ln_factorial(n as u64))
+            .sum::<f64>();
+    let p_value = if ln_arrangements.exp() <= RANK_PERMUTATION_EXACT_MAX {
+        kruskal_wallis_exact_sf(&ranks, &sizes, statistic, h)
+    } else {
+        ChiSquared::new(df)?.sf(statistic)
+    };
+    Ok(ChiSquareTestResult {
+        statistic,
+        df,
+        p_value,
+    })
+}
+
+/// Computes the probability that `h` of the group rank sums is at least
+/// `statistic` when the `ranks` are assigned to groups of `sizes` uniformly
+/// at random
+fn kruskal_wallis_exact_sf<F: Fn(&[f64]) -> f64>(
+    ranks: &[f64],
+    sizes: &[usize],
+    statistic: f64,
+    h: F,
+) -> f64 {
+    // the state is the number of ranks and the doubled (hence integer) rank
+    // sum of each group after assigning a prefix of the ranks
+    let k = sizes.len();
+    let mut states: HashMap<Vec<u64>, f64> = HashMap::new();
+    states.insert(vec![0; 2 * k], 1.0);
+    for (assigned, &rank) in ranks.iter().enumerate() {
+        let remaining = (ranks.len() - assigned) as f64;
+        let doubled = (2.0 * rank).round() as u64;
+        let mut next: HashMap<Vec<u64>, f64> = HashMap::with_capacity(states.len() * k);
+        for (state, p) in states {
+            for (g, &size) in sizes.iter().enumerate() {
+                let count = state[g] as usize;
+                if count < size {
+                    let mut s = state.clone();
+                    s[g] += 1;
+                    s[k + g] += doubled;
+                    *next.entry(s).or_insert(0.0) += p * (size - count) as f64 / remaining;
+                }
+            }
+        }
+        states = next;
+    }
+    let threshold = statistic - STATISTIC_TOLERANCE * statistic.abs().max(1.0);
+    let sf: f64 = states
+        .iter()
+        .filter(|(state, _)| {
+            let sums: Vec<f64> = state[k..].iter().map(|&s| s as f64 / 2.0).collect();
+            h(&sums) >= threshold
+        })
+        .map(|(_, p)| p)
+        .sum();
+    sf.min(1.0)
+}
+
+/// Performs the Friedman test of the null hypothesis that the treatments
+/// have identical effects. Each of the `blocks` holds one observation per
+/// treatment, in the same order for all blocks, and the observations are
+/// ranked within each block.
+///
+/// # Errors
+///
+/// Returns an error if there are fewer than two blocks or treatments, the
+/// blocks have different lengths, the data contains `NaN`, or all
+/// observations in every block are equal
+///
+/// # Remarks
+///
+/// The p-value is computed from the exact permutation distribution of `Q`
+/// (conditional on the ties) for small designs, namely if
+/// `(b * (k - 1) + 1)^(k - 1) * k!` is at most `10^6`, and from the
+/// chi-squared distribution with `k - 1` degrees of freedom otherwise.
+///
+/// # Formula
+///
+/// ```ignore
+/// Q = (12 / (b * k * (k + 1)) * Σ R_j^2 - 3 * b * (k + 1)) / (1 - Σ (t^3 - t) / (b * (k^3 - k)))
+/// ```
+///
+/// where `R_j` is the rank sum of treatment `j`, `b` the number of blocks,
+/// `k` the number of treatments and `t` the sizes of the groups of ties
+/// within the blocks
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::friedman_test;
+///
+/// let blocks: [&[f64]; 6] = [
+///     &[7.0, 9.0, 8.0],
+///     &[6.0, 5.0, 7.0],
+///     &[9.0, 7.0, 6.0],
+///     &[8.0, 5.0, 6.0],
+///     &[9.5, 8.5, 7.0],
+///     &[7.5, 6.0, 5.5],
+/// ];
+/// let t = friedman_test(&blocks).unwrap();
+/// assert_eq!(t.df, 2.0);
+/// assert!((t.statistic - 7.0 / 3.0).abs() < 1e-12);
+/// assert!((t.p_value - 3341.0 / 7776.0).abs() < 1e-12);
+/// ```
+pub fn friedman_test(blocks: &[&[f64]]) -> Result<ChiSquareTestResult> {
+    let b = blocks.len();
+    let k = blocks.first().map_or(0, |block| block.len());
+    if b < 2 || k < 2 {
+        return Err(StatsError::BadParams);
+    }
+    if blocks.iter().any(|block| block.len() != k) {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let block_ranks = blocks
+        .iter()
+        .map(|block| ranks(block))
+        .collect::<Result<Vec<_>>>()?;
+    let (b_f, k_f) = (b as f64, k as f64);
+    let ties: f64 = block_ranks.iter().map(|r| tie_sum(r)).sum();
+    let correction = 1.0 - ties / (b_f * (k_f * k_f * k_f - k_f));
+    if correction <= 0.0 {
+        return Err(StatsError::BadParams);
+    }
+    let q = |rank_sums: &[f64]| {
+        let s: f64 = rank_sums.iter().map(|r| r * r).sum();
+        (12.0 / (b_f * k_f * (k_f + 1.0)) * s - 3.0 * b_f * (k_f + 1.0)) / correction
+    };
+    let mut rank_sums = vec![0.0; k];
+    for r in &block_ranks {
+        for (sum, rank) in rank_sums.iter_mut().zip(r) {
+            *sum += rank;
+        }
+    }
+    let statistic = q(&rank_sums);
+    let df = k_f - 1.0;
+
+    let permutations = (1..=k).map(|i| i as f64).product::<f64>();
+    let states = (b_f * (k_f - 1.0) + 1.0).powf(k_f - 1.0);
+    let p_value = if states * permutations <= RANK_PERMUTATION_EXACT_MAX {
+        friedman_exact_sf(&block_ranks, statistic, q)
+    } else {
+        ChiSquared::new(df)?.sf(statistic)
+    };
+    Ok(ChiSquareTestResult {
+        statistic,
+        df,
+        p_value,
+    })
+}
+
+/// Computes the probability that `q` of the treatment rank sums is at least
+/// `statistic` when the ranks within each block are permuted uniformly at
+/// random
+fn friedman_exact_sf<F: Fn(&[f64]) -> f64>(block_ranks: &[Vec<f64>], statistic: f64, q: F) -> f64 {
+    let k = block_ranks[0].len();
+    let mut states: HashMap<Vec<u64>, f64> = HashMap::new();
+    states.insert(vec![0; k], 1.0);
+    for ranks in block_ranks {
+        let doubled: Vec<u64> = ranks.iter().map(|r| (2.0 * r).round() as u64).collect();
+        let permutations = permutations(&doubled);
+        let weight = 1.0 / permutations.len() as f64;
+        let mut next: HashMap<Vec<u64>, f64> = HashMap::with_capacity(states.len() * k);
+        for (state, p) in states {
+            for perm in &permutations {
+                let s: Vec<u64> = state.iter().zip(perm).map(|(a, b)| a + b).collect();
+                *next.entry(s).or_insert(0.0) += p * weight;
+            }
+        }
+        states = next;
+    }
+    let threshold = statistic - STATISTIC_TOLERANCE * statistic.abs().max(1.0);
+    let sf: f64 = states
+        .iter()
+        .filter(|(state, _)| {
+            let sums: Vec<f64> = state.iter().map(|&s| s as f64 / 2.0).collect();
+            q(&sums) >= threshold
+        })
+        .map(|(_, p)| p)
+        .sum();
+    sf.min(1.0)
+}
+
+/// Returns all `k!` permutations of `values` (including repetitions if
+/// `values` has equal entries), generated by Heap's algorithm
+fn permutations(values: &[u64]) -> Vec<Vec<u64>> {
+    let mut values = values.to_vec();
+    let k = values.len();
+    let mut result = vec![values.clone()];
+    let mut c = vec![0; k];
+    let mut i = 0;
+    while i < k {
+        if c[i] < i {
+            if i % 2 == 0 {
+                values.swap(0, i);
+            } else {
+                values.swap(c[i], i);
+            }
+            result.push(values.clone());
+            c[i] += 1;
+            i = 0;
+        } else {
+            c[i] = 0;
+            i += 1;
+        }
+    }
+    result
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: [f64; 9] = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73];
+    const Y: [f64; 8] = [1.2, 1.5, 3.4, 1.05, 2.2, 1.8, 6.5, 1.3];
+    const X_TIES: [f64; 10] = [1.0, 2.0, 2.0, 3.0, 4.0, 5.0, 5.0, 6.0, 7.0, 8.0];
+    const Y_TIES: [f64; 11] = [2.0, 3.0, 3.0, 5.0, 6.0, 6.0, 7.0, 9.0, 9.0, 10.0, 11.0];
+    const DIFF: [f64; 12] = [-1.2, 0.3, 2.7, -0.4, 0.9, 5.1, -3.3, 0.1, 1.6, -0.8, 2.2, 0.6];
+    const DIFF_TIES: [f64; 12] = [1.0, -2.0, 2.0, 3.0, -3.0, 3.0, 4.0, 5.0, -1.0, 6.0, 0.0, 7.0];
+
+    #[test]
+    fn test_mann_whitney_u_exact() {
+        let t = mann_whitney_u_test(&X, &Y, Alternative::TwoSided, true).unwrap();
+        assert_eq!(t.statistic, 26.0);
+        assert_almost_eq!(t.p_value, 4502.0 / 12155.0, 1e-14);
+        let t = mann_whitney_u_test(&X, &Y, Alternative::Less, true).unwrap();
+        assert_almost_eq!(t.p_value, 2251.0 / 12155.0, 1e-14);
+        let t = mann_whitney_u_test(&X, &Y, Alternative::Greater, true).unwrap();
+        assert_almost_eq!(t.p_value, 371.0 / 442.0, 1e-14);
+    }
+
+    #[test]
+    fn test_mann_whitney_u_ties() {
+        let t = mann_whitney_u_test(&X_TIES, &Y_TIES, Alternative::TwoSided, true).unwrap();
+        assert_eq!(t.statistic, 31.5);
+        assert_almost_eq!(t.p_value, 0.1032790953179926, 1e-10);
+        let t = mann_whitney_u_test(&X_TIES, &Y_TIES, Alternative::Less, false).unwrap();
+        assert_almost_eq!(t.p_value, 0.047998608887608626, 1e-10);
+        let t = mann_whitney_u_test(&X_TIES, &Y_TIES, Alternative::Greater, false).unwrap();
+        assert_almost_eq!(t.p_value, 0.95200139111239137, 1e-10);
+    }
+
+    #[test]
+    fn test_mann_whitney_u_symmetry() {
+        let xy = mann_whitney_u_test(&X, &Y, Alternative::Less, true).unwrap();
+        let yx = mann_whitney_u_test(&Y, &X, Alternative::Greater, true).unwrap();
+        assert_eq!(xy.statistic + yx.statistic, 72.0);
+        assert_almost_eq!(xy.p_value, yx.p_value, 1e-14);
+    }
+
+    #[test]
+    fn test_mann_whitney_u_bad_params() {
+        assert!(mann_whitney_u_test(&[], &Y, Alternative::TwoSided, true).is_err());
+        assert!(mann_whitney_u_test(&[1.0, f64::NAN], &Y, Alternative::TwoSided, true).is_err());
+        assert!(mann_whitney_u_test(&[1.0; 60], &[1.0; 60], Alternative::TwoSided, true).is_err());
+    }
+
+    #[test]
+    fn test_mann_whitney_pmf() {
+        let pmf = mann_whitney_pmf(3, 2);
+        let expected = [1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 1.0];
+        for (p, e) in pmf.iter().zip(&expected) {
+            assert_almost_eq!(*p, e / 10.0, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank_exact() {
+        let t = wilcoxon_signed_rank_test(&DIFF, 0.0, Alternative::TwoSided, true).unwrap();
+        assert_eq!(t.statistic, 52.0);
+        assert_almost_eq!(t.p_value, 695.0 / 2048.0, 1e-14);
+        let t = wilcoxon_signed_rank_test(&DIFF, 0.0, Alternative::Less, true).unwrap();
+        assert_almost_eq!(t.p_value, 3479.0 / 4096.0, 1e-14);
+        let t = wilcoxon_signed_rank_test(&DIFF, 0.0, Alternative::Greater, true).unwrap();
+        assert_almost_eq!(t.p_value, 695.0 / 4096.0, 1e-14);
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank_ties() {
+        let t = wilcoxon_signed_rank_test(&DIFF_TIES, 0.0, Alternative::TwoSided, true).unwrap();
+        assert_eq!(t.statistic, 55.0);
+        assert_almost_eq!(t.p_value, 0.055202974168669977, 1e-10);
+        let t = wilcoxon_signed_rank_test(&DIFF_TIES, 0.0, Alternative::Greater, false).unwrap();
+        assert_almost_eq!(t.p_value, 0.024889216341108081, 1e-10);
+        let t = wilcoxon_signed_rank_test(&DIFF_TIES, 0.0, Alternative::Less, false).unwrap();
+        assert_almost_eq!(t.p_value, 0.97511078365889192, 1e-10);
+    }
+
+    #[test]
+    fn test_wilcoxon_paired() {
+        let y = [0.0; 12];
+        let paired = wilcoxon_paired_test(&DIFF, &y, Alternative::TwoSided, true).unwrap();
+        let one = wilcoxon_signed_rank_test(&DIFF, 0.0, Alternative::TwoSided, true).unwrap();
+        assert_eq!(paired, one);
+        assert!(wilcoxon_paired_test(&DIFF, &[0.0; 11], Alternative::TwoSided, true).is_err());
+    }
+
+    #[test]
+    fn test_wilcoxon_signed_rank_bad_params() {
+        assert!(wilcoxon_signed_rank_test(&[], 0.0, Alternative::TwoSided, true).is_err());
+        assert!(wilcoxon_signed_rank_test(&[1.0, 1.0], 1.0, Alternative::TwoSided, true).is_err());
+        assert!(wilcoxon_signed_rank_test(&[1.0, f64::NAN], 0.0, Alternative::TwoSided, true).is_err());
+    }
+
+    #[test]
+    fn test_signed_rank_pmf() {
+        let pmf = signed_rank_pmf(3);
+        let expected = [1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0];
+        for (p, e) in pmf.iter().zip(&expected) {
+            assert_almost_eq!(*p, e / 8.0, 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_kruskal_wallis_exact_ties() {
+        let a = [1.0, 2.0, 2.0, 4.0];
+        let b = [3.0, 5.0, 5.0, 6.0];
+        let c = [2.0, 7.0, 8.0, 8.0];
+        let t = kruskal_wallis_test(&[&a, &b, &c]).unwrap();
+        assert_almost_eq!(t.statistic, 5.460714285714285, 1e-12);
+        assert_eq!(t.df, 2.0);
+        assert_almost_eq!(t.p_value, 109.0 / 1925.0, 1e-12);
+    }
+
+    #[test]
+    fn test_kruskal_wallis_asymptotic() {
+        let a: Vec<f64> = (0..12).map(|i| 0.5 * i as f64 + (i % 3) as f64).collect();
+        let b: Vec<f64> = (0..12).map(|i| 0.5 * i as f64 + 1.3).collect();
+        let c: Vec<f64> = (0..12).map(|i| 0.4 * i as f64 + 2.1 + (i % 2) as f64).collect();
+        let t = kruskal_wallis_test(&[&a, &b, &c]).unwrap();
+        assert_almost_eq!(t.statistic, 1.6838643105894492, 1e-12);
+        assert_almost_eq!(t.p_value, 0.43087719697275978, 1e-12);
+    }
+
+    #[test]
+    fn test_kruskal_wallis_bad_params() {
+        assert!(kruskal_wallis_test(&[&[1.0, 2.0]]).is_err());
+        assert!(kruskal_wallis_test(&[&[1.0, 2.0], &[]]).is_err());
+        assert!(kruskal_wallis_test(&[&[1.0, f64::NAN], &[2.0]]).is_err());
+        assert!(kruskal_wallis_test(&[&[1.0, 1.0], &[1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_friedman_exact_ties() {
+        let blocks: [&[f64]; 5] = [
+            &[1.0, 2.0, 2.0, 3.0],
+            &[2.0, 1.0, 3.0, 4.0],
+            &[1.0, 1.0, 2.0, 3.0],
+            &[4.0, 3.0, 2.0, 1.0],
+            &[1.0, 3.0, 2.0, 4.0],
+        ];
+        let t = friedman_test(&blocks).unwrap();
+        assert_eq!(t.df, 3.0);
+        assert_almost_eq!(t.statistic, 63.0 / 16.0, 1e-12);
+        assert_almost_eq!(t.p_value, 3937.0 / 13824.0, 1e-12);
+    }
+
+    #[test]
+    fn test_friedman_asymptotic() {
+        let blocks: Vec<Vec<f64>> = (0..12)
+            .map(|i| (0..5).map(|j| ((i * 7 + j * 3) % 11) as f64 + 0.5 * j as f64).collect())
+            .collect();
+        let blocks: Vec<&[f64]> = blocks.iter().map(|b| b.as_slice()).collect();
+        let t = friedman_test(&blocks).unwrap();
+        assert_eq!(t.df, 4.0);
+        assert_almost_eq!(t.statistic, 5.0 / 3.0, 1e-12);
+        assert_almost_eq!(t.p_value, 0.79676338226297674, 1e-12);
+    }
+
+    #[test]
+    fn test_friedman_bad_params() {
+        assert!(friedman_test(&[&[1.0, 2.0]]).is_err());
+        assert!(friedman_test(&[&[1.0], &[2.0]]).is_err());
+        assert!(friedman_test(&[&[1.0, 2.0], &[1.0, 2.0, 3.0]]).is_err());
+        assert!(friedman_test(&[&[1.0, f64::NAN], &[1.0, 2.0]]).is_err());
+        assert!(friedman_test(&[&[1.0, 1.0], &[2.0, 2.0]]).is_err());
+    }
+
+    #[test]
+    fn test_permutations() {
+        let mut p = permutations(&[1, 2, 3]);
+        p.sort();
+        assert_eq!(p, vec![vec![1, 2, 3], vec![1, 3, 2], vec![2, 1, 3], vec![2, 3, 1], vec![3, 1, 2], vec![3, 2, 1]]);
+    }
+}
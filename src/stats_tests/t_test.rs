@@ -0,0 +1,369 @@
+use crate::distribution::{ContinuousCDF, StudentsT};
+use crate::statistics::Statistics;
+use crate::stats_tests::{check_level, p_value, Alternative, ConfidenceInterval};
+use crate::{Result, StatsError};
+use std::f64;
+
+/// The result of a Student t-test
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TTestResult {
+    /// The t statistic
+    pub statistic: f64,
+    /// The degrees of freedom of the t distribution of the statistic under
+    /// the null hypothesis
+    pub df: f64,
+    /// The p-value of the test
+    pub p_value: f64,
+    /// The estimated mean (difference)
+    pub estimate: f64,
+    /// The value of the mean (difference) under the null hypothesis
+    pub null_value: f64,
+    /// The standard error of the estimate, i.e. the denominator of the
+    /// statistic
+    pub std_err: f64,
+    /// The effect size, Cohen's d
+    pub effect_size: f64,
+    /// The alternative hypothesis of the test
+    pub alternative: Alternative,
+}
+
+impl TTestResult {
+    fn new(
+        estimate: f64,
+        null_value: f64,
+        std_err: f64,
+        df: f64,
+        sd: f64,
+        alternative: Alternative,
+    ) -> Result<Self> {
+        if std_err.is_nan() || std_err <= 0.0 {
+            return Err(StatsError::BadParams);
+        }
+        let statistic = (estimate - null_value) / std_err;
+        let t = StudentsT::new(0.0, 1.0, df)?;
+        Ok(TTestResult {
+            statistic,
+            df,
+            p_value: p_value(t.cdf(statistic), t.sf(statistic), alternative),
+            estimate,
+            null_value,
+            std_err,
+            effect_size: (estimate - null_value) / sd,
+            alternative,
+        })
+    }
+
+    /// Returns the confidence interval for the mean (difference) at the
+    /// confidence `level`. The interval is one-sided for the `Less` and `Greater` alternatives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `level` is not in `(0, 1)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use statrs::stats_tests::{one_sample_t_test, Alternative};
+    ///
+    /// let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+    /// let t = one_sample_t_test(&data, 5.0, Alternative::TwoSided).unwrap();
+    /// let ci = t.confidence_interval(0.95).unwrap();
+    /// assert!(ci.lower > 5.0 && ci.contains(t.estimate));
+    ///
+    /// let t = one_sample_t_test(&data, 5.0, Alternative::Greater).unwrap();
+    /// assert_eq!(t.confidence_interval(0.95).unwrap().upper, f64::INFINITY);
+    /// ```
+    pub fn confidence_interval(&self, level: f64) -> Result<ConfidenceInterval> {
+        check_level(level)?;
+        let t = StudentsT::new(0.0, 1.0, self.df)?;
+        let (lower, upper) = match self.alternative {
+            Alternative::TwoSided => {
+                let half_width = t.inverse_cdf((1.0 + level) / 2.0) * self.std_err;
+                (self.estimate - half_width, self.estimate + half_width)
+            }
+            Alternative::Less => (
+                f64::NEG_INFINITY,
+                self.estimate + t.inverse_cdf(level) * self.std_err,
+            ),
+            Alternative::Greater => (
+                self.estimate - t.inverse_cdf(level) * self.std_err,
+                f64::INFINITY,
+            ),
+        };
+        Ok(ConfidenceInterval {
+            lower,
+            upper,
+            level,
+        })
+    }
+}
+
+/// Performs a one-sample t-test of the null hypothesis that `data` comes
+/// from a normal distribution with mean `mu`
+///
+/// # Errors
+///
+/// Returns an error if `data` has fewer than two observations or zero
+/// variance
+///
+/// # Formula
+///
+/// ```ignore
+/// t = (x̄ - μ) / (s / sqrt(n))
+/// d = (x̄ - μ) / s
+/// ```
+///
+/// where `x̄` and `s` are the sample mean and standard deviation and `n` the
+/// number of observations. The statistic has `n - 1` degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{one_sample_t_test, Alternative};
+/// use statrs::prec;
+///
+/// let data = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let t = one_sample_t_test(&data, 5.0, Alternative::TwoSided).unwrap();
+/// assert_eq!(t.df, 5.0);
+/// assert!(prec::almost_eq(t.statistic, 2.7351263, 1e-7));
+/// assert!(prec::almost_eq(t.p_value, 0.0410292, 1e-7));
+/// ```
+pub fn one_sample_t_test(data: &[f64], mu: f64, alternative: Alternative) -> Result<TTestResult> {
+    let n = data.len() as f64;
+    if data.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let sd = data.std_dev();
+    TTestResult::new(data.mean(), mu, sd / n.sqrt(), n - 1.0, sd, alternative)
+}
+
+/// Performs a two-sample t-test of the null hypothesis that `x` and `y`
+/// come from normal distributions with equal means, assuming they also have
+/// equal variances
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` has fewer than two observations or if both
+/// have zero variance
+///
+/// # Formula
+///
+/// ```ignore
+/// s_p^2 = ((n_x - 1) * s_x^2 + (n_y - 1) * s_y^2) / (n_x + n_y - 2)
+/// t = (x̄ - ȳ) / (s_p * sqrt(1 / n_x + 1 / n_y))
+/// d = (x̄ - ȳ) / s_p
+/// ```
+///
+/// where `x̄`, `s_x` and `n_x` are the sample mean, standard deviation and
+/// number of observations of `x` and similarly for `y`. The statistic has
+/// `n_x + n_y - 2` degrees of freedom.
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{two_sample_t_test, Alternative};
+///
+/// let x = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let y = [4.8, 5.0, 4.6, 5.1, 4.7];
+/// let t = two_sample_t_test(&x, &y, Alternative::Greater).unwrap();
+/// assert_eq!(t.df, 9.0);
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn two_sample_t_test(x: &[f64], y: &[f64], alternative: Alternative) -> Result<TTestResult> {
+    if x.len() < 2 || y.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let (n_x, n_y) = (x.len() as f64, y.len() as f64);
+    let df = n_x + n_y - 2.0;
+    let pooled_sd = (((n_x - 1.0) * x.variance() + (n_y - 1.0) * y.variance()) / df).sqrt();
+    let std_err = pooled_sd * (1.0 / n_x + 1.0 / n_y).sqrt();
+    TTestResult::new(
+        x.mean() - y.mean(),
+        0.0,
+        std_err,
+        df,
+        pooled_sd,
+        alternative,
+    )
+}
+
+/// Performs Welch's t-test of the null hypothesis that `x` and `y` come from
+/// normal distributions with equal means, without assuming equal variances
+///
+/// # Errors
+///
+/// Returns an error if `x` or `y` has fewer than two observations or if both
+/// have zero variance
+///
+/// # Remarks
+///
+/// The degrees of freedom are approximated by the Welch-Satterthwaite
+/// equation and are in general not an integer. The effect size is
+/// standardized by the root mean square of the two standard deviations.
+///
+/// # Formula
+///
+/// ```ignore
+/// t = (x̄ - ȳ) / sqrt(s_x^2 / n_x + s_y^2 / n_y)
+/// ν = (s_x^2 / n_x + s_y^2 / n_y)^2 / ((s_x^2 / n_x)^2 / (n_x - 1) + (s_y^2 / n_y)^2 / (n_y - 1))
+/// d = (x̄ - ȳ) / sqrt((s_x^2 + s_y^2) / 2)
+/// ```
+///
+/// where `x̄`, `s_x` and `n_x` are the sample mean, standard deviation and
+/// number of observations of `x` and similarly for `y`, and `ν` is the
+/// degrees of freedom of the statistic
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{welch_t_test, Alternative};
+///
+/// let x = [5.1, 4.9, 5.6, 5.8, 6.0, 5.4];
+/// let y = [4.8, 5.0, 4.6, 5.1, 4.7];
+/// let t = welch_t_test(&x, &y, Alternative::TwoSided).unwrap();
+/// assert!(t.df > 7.0 && t.df < 9.0);
+/// ```
+pub fn welch_t_test(x: &[f64], y: &[f64], alternative: Alternative) -> Result<TTestResult> {
+    if x.len() < 2 || y.len() < 2 {
+        return Err(StatsError::BadParams);
+    }
+    let (n_x, n_y) = (x.len() as f64, y.len() as f64);
+    let (var_x, var_y) = (x.variance(), y.variance());
+    let (a, b) = (var_x / n_x, var_y / n_y);
+    let df = (a + b) * (a + b) / (a * a / (n_x - 1.0) + b * b / (n_y - 1.0));
+    let sd = ((var_x + var_y) / 2.0).sqrt();
+    TTestResult::new(
+        x.mean() - y.mean(),
+        0.0,
+        (a + b).sqrt(),
+        df,
+        sd,
+        alternative,
+    )
+}
+
+/// Performs a paired t-test of the null hypothesis that the differences
+/// `x_i - y_i` come from a normal distribution with mean zero
+///
+/// # Errors
+///
+/// Returns an error if `x` and `y` have different lengths, fewer than two
+/// observations, or if the differences have zero variance
+///
+/// # Remarks
+///
+/// This is the one-sample t-test of the differences, so the effect size is
+/// standardized by the standard deviation of the differences (Cohen's `d_z`)
+///
+/// # Examples
+///
+/// ```
+/// use statrs::stats_tests::{paired_t_test, Alternative};
+///
+/// let before = [72.0, 80.0, 65.0, 90.0, 77.0];
+/// let after = [70.0, 76.0, 64.0, 85.0, 75.0];
+/// let t = paired_t_test(&before, &after, Alternative::Greater).unwrap();
+/// assert_eq!(t.df, 4.0);
+/// assert!(t.p_value < 0.01);
+/// ```
+pub fn paired_t_test(x: &[f64], y: &[f64], alternative: Alternative) -> Result<TTestResult> {
+    if x.len() != y.len() {
+        return Err(StatsError::ContainersMustBeSameLength);
+    }
+    let diff: Vec<f64> = x.iter().zip(y).map(|(x, y)| x - y).collect();
+    one_sample_t_test(&diff, 0.0, alternative)
+}
+
+#[rustfmt::skip]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats_tests::mean_interval;
+
+    const X: [f64; 10] = [0.64, 1.38, 2.05, 0.31, 3.72, 1.14, 0.87, 2.46, 1.73, 0.52];
+    const Y: [f64; 8] = [1.2, 1.5, 3.4, 1.05, 2.2, 1.8, 6.5, 1.3];
+
+    fn check(t: TTestResult, statistic: f64, df: f64, p_value: f64, effect_size: f64) {
+        assert_almost_eq!(t.statistic, statistic, 1e-12);
+        assert_almost_eq!(t.df, df, 1e-12);
+        assert_almost_eq!(t.p_value, p_value, 1e-12);
+        assert_almost_eq!(t.effect_size, effect_size, 1e-12);
+    }
+
+    #[test]
+    fn test_one_sample_t_test() {
+        let t = one_sample_t_test(&X, 1.0, Alternative::TwoSided).unwrap();
+        check(t, 1.456291570353248, 9.0, 0.17929409746294841, 0.46051982996196039);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.73327647878628473, 1e-12);
+        assert_almost_eq!(ci.upper, 2.2307235212137153, 1e-12);
+        let interval = mean_interval(&X, 0.95).unwrap();
+        assert_almost_eq!(interval.lower, ci.lower, 1e-12);
+        assert_almost_eq!(interval.upper, ci.upper, 1e-12);
+
+        let t = one_sample_t_test(&X, 1.0, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.9103529512685258, 1e-12);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_eq!(ci.lower, f64::NEG_INFINITY);
+        assert_almost_eq!(ci.upper, 2.0887194588828002, 1e-12);
+
+        let t = one_sample_t_test(&X, 1.0, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.089647048731474204, 1e-12);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, 0.87528054111719976, 1e-12);
+        assert_eq!(ci.upper, f64::INFINITY);
+        assert!(t.confidence_interval(1.0).is_err());
+        assert!(t.confidence_interval(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_one_sample_t_test_bad_data() {
+        assert!(one_sample_t_test(&[], 0.0, Alternative::TwoSided).is_err());
+        assert!(one_sample_t_test(&[1.0], 0.0, Alternative::TwoSided).is_err());
+        assert!(one_sample_t_test(&[1.0, 1.0], 0.0, Alternative::TwoSided).is_err());
+        assert!(one_sample_t_test(&[1.0, f64::NAN], 0.0, Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_two_sample_t_test() {
+        let t = two_sample_t_test(&X, &Y, Alternative::TwoSided).unwrap();
+        check(t, -1.2949770563474769, 16.0, 0.21370364495203785, -0.61426155235773523);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, -2.3383788260631852, 1e-12);
+        assert_almost_eq!(ci.upper, 0.56487882606318521, 1e-12);
+
+        let t = two_sample_t_test(&X, &Y, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.10685182247601893, 1e-12);
+        let t = two_sample_t_test(&X, &Y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.89314817752398107, 1e-12);
+        assert!(two_sample_t_test(&X, &[1.0], Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_welch_t_test() {
+        let t = welch_t_test(&X, &Y, Alternative::TwoSided).unwrap();
+        check(t, -1.2192927507885423, 10.573522988510419, 0.24923458235580296, -0.59445066026284709);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, -2.4953638728101051, 1e-11);
+        assert_almost_eq!(ci.upper, 0.72186387281010508, 1e-11);
+
+        let t = welch_t_test(&X, &Y, Alternative::Less).unwrap();
+        assert_almost_eq!(t.p_value, 0.12461729117790148, 1e-12);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.upper, 0.42417322803645075, 1e-11);
+        assert!(welch_t_test(&[1.0, 1.0], &[2.0, 2.0], Alternative::TwoSided).is_err());
+    }
+
+    #[test]
+    fn test_paired_t_test() {
+        let t = paired_t_test(&X[..8], &Y, Alternative::TwoSided).unwrap();
+        check(t, -1.0326684271423547, 7.0, 0.33611192436819963, -0.36510342377480262);
+        let ci = t.confidence_interval(0.95).unwrap();
+        assert_almost_eq!(ci.lower, -2.6236310127043203, 1e-12);
+        assert_almost_eq!(ci.upper, 1.0286310127043203, 1e-12);
+
+        let t = paired_t_test(&X[..8], &Y, Alternative::Greater).unwrap();
+        assert_almost_eq!(t.p_value, 0.83194403781590019, 1e-12);
+        assert!(paired_t_test(&X, &Y, Alternative::TwoSided).is_err());
+    }
+}